@@ -0,0 +1,1421 @@
+use core::time::Duration;
+use std::collections::HashMap;
+use std::io::{
+    Read,
+    Write,
+};
+use std::net::TcpStream;
+use std::time::{
+    Instant,
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+};
+use codec::{
+    Uuid,
+    VarInt,
+};
+use data::model::configuration::ResourcePackStatus;
+use data::model::{
+    configuration,
+    handshake,
+    login,
+    play,
+};
+use data::packet::Packet;
+use data::protocol::{
+    ConfigurationClientbound,
+    Direction,
+    LoginClientbound,
+    State,
+};
+use data::text::{
+    json_to_nbt,
+    plain_text,
+};
+use data::version::ProtocolVersion;
+use log::{
+    debug,
+    warn,
+};
+use rsa::pkcs8::DecodePublicKey as _;
+use rsa::{
+    Pkcs1v15Encrypt,
+    RsaPublicKey,
+};
+
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::event::{
+    Entity,
+    Event,
+    Position,
+};
+
+/// Chunks per tick the client asks for after each batch, vanilla's upper
+/// bound.
+const CHUNKS_PER_TICK: f32 = 64.0;
+
+/// Answers to a resource pack push. Packs are not downloaded, but servers
+/// requiring one would kick a client declining it.
+const PACK_STATUSES: [ResourcePackStatus; 2] = [
+    ResourcePackStatus::Accepted,
+    ResourcePackStatus::SuccessfullyLoaded,
+];
+
+/// Settings sent to the server during configuration.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    pub name: String,
+    pub locale: String,
+    pub view_distance: i8,
+}
+
+impl ClientOptions {
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            locale: "en_us".to_owned(),
+            view_distance: 2,
+        }
+    }
+}
+
+/// A client logged into a server in offline mode, speaking the latest
+/// protocol version.
+///
+/// Keep-alives, pings, teleport confirmations, chunk batches and
+/// reconfiguration are answered while waiting for the next [`Event`].
+pub struct Client<S = TcpStream> {
+    connection: Connection<S>,
+    options: ClientOptions,
+    uuid: Uuid,
+    entity_id: Option<i32>,
+    position: Option<Position>,
+    yaw: f32,
+    pitch: f32,
+    entities: HashMap<i32, Entity>,
+    /// Event raised during configuration, returned by the next call to
+    /// [`Client::next_event`].
+    pending: Option<Event>,
+}
+
+impl Client {
+    /// Connects to `host:port` and logs in.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the connection fails or the server refuses the
+    /// login.
+    pub fn connect(
+        host: &str,
+        port: u16,
+        options: ClientOptions,
+    ) -> Result<Self, Error> {
+        let stream = TcpStream::connect((host, port))?;
+        stream.set_nodelay(true)?;
+        Self::login(stream, host, port, options)
+    }
+
+    /// Like [`Client::next_event`], but gives up after `timeout`, returning
+    /// `None`, so that the caller can act in between events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] like [`Client::next_event`].
+    pub fn next_event_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<Event>, Error> {
+        if let Some(event) = self.pending.take() {
+            return Ok(Some(event));
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !self.connection.wait_readable(remaining)? {
+                return Ok(None);
+            }
+            if let Some(event) = self.receive()? {
+                return Ok(Some(event));
+            }
+        }
+    }
+}
+
+impl<S: Read + Write> Client<S> {
+    /// Logs in over an open stream, then completes the configuration.
+    /// `host` and `port` are only sent in the handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the stream fails or the server refuses the
+    /// login.
+    pub fn login(
+        stream: S,
+        host: &str,
+        port: u16,
+        options: ClientOptions,
+    ) -> Result<Self, Error> {
+        let mut connection = Connection::new(stream);
+        connection.send(&handshake::Handshake {
+            protocol_version: ProtocolVersion::LATEST.protocol(),
+            server_address: host.to_owned(),
+            server_port: port,
+            intent: handshake::Intent::Login,
+        })?;
+        connection.send(&login::Hello {
+            name: options.name.clone(),
+            // offline servers derive the uuid from the name
+            uuid: Uuid::null(),
+        })?;
+
+        let uuid = loop {
+            let packet = connection.receive()?;
+            match LoginClientbound::from_packet(&packet)? {
+                LoginClientbound::LoginDisconnect(packet) => {
+                    return Err(Error::Disconnected(plain_text(&json_to_nbt(
+                        &packet.reason,
+                    ))));
+                }
+                LoginClientbound::EncryptionRequest(request) => {
+                    encrypt(&mut connection, &request)?;
+                }
+                LoginClientbound::LoginCompression(packet) => {
+                    connection.set_compression(usize::try_from(packet.size).ok());
+                }
+                LoginClientbound::CustomQuery(query) => {
+                    connection.send(&login::CustomQueryAnswer {
+                        transaction_id: query.transaction_id,
+                        // no payload: the query is not understood
+                        payload: Box::new([0]),
+                    })?;
+                }
+                LoginClientbound::CookieRequest(request) => {
+                    connection.send(&login::CookieResponse {
+                        key: request.key,
+                        payload: None,
+                    })?;
+                }
+                LoginClientbound::LoginFinished(finished) => {
+                    connection.send(&login::LoginAcknowledged {})?;
+                    break finished.uuid;
+                }
+            }
+        };
+
+        let mut client = Self {
+            connection,
+            options,
+            uuid,
+            entity_id: None,
+            position: None,
+            yaw: 0.0,
+            pitch: 0.0,
+            entities: HashMap::new(),
+            pending: None,
+        };
+        client.configure()?;
+        Ok(client)
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str { &self.options.name }
+
+    /// The uuid the server gave the player.
+    #[must_use]
+    pub const fn uuid(&self) -> &Uuid { &self.uuid }
+
+    /// Entity id of the player, once joined.
+    #[must_use]
+    pub const fn entity_id(&self) -> Option<i32> { self.entity_id }
+
+    /// Position of the player, once the server placed it.
+    #[must_use]
+    pub const fn position(&self) -> Option<Position> { self.position }
+
+    /// Packets received since the connection opened.
+    #[must_use]
+    pub const fn packets_received(&self) -> u64 { self.connection.received() }
+
+    /// Packets sent since the connection opened.
+    #[must_use]
+    pub const fn packets_sent(&self) -> u64 { self.connection.sent() }
+
+    #[must_use]
+    pub fn entity(
+        &self,
+        id: i32,
+    ) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> { self.entities.values() }
+
+    /// Sends an unsigned chat message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be sent.
+    pub fn chat(
+        &mut self,
+        message: &str,
+    ) -> Result<(), Error> {
+        self.connection.send(&play::Chat {
+            message: message.to_owned(),
+            timestamp: now_millis(),
+            salt: 0,
+            signature: None,
+            offset: 0,
+            acknowledged: [0; 3],
+            // 0 skips the check of the acknowledged messages
+            checksum: 0,
+        })
+    }
+
+    /// Runs a command, given without the leading slash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be sent.
+    pub fn command(
+        &mut self,
+        command: &str,
+    ) -> Result<(), Error> {
+        self.connection.send(&play::ChatCommand {
+            command: command.to_owned(),
+        })
+    }
+
+    /// Moves the player, on the ground. The server only accepts short moves,
+    /// a few blocks per tick at most.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be sent.
+    pub fn move_to(
+        &mut self,
+        position: Position,
+    ) -> Result<(), Error> {
+        self.connection.send(&play::MovePlayerPos {
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            flags: play::movement::ON_GROUND,
+        })?;
+        self.position = Some(position);
+        Ok(())
+    }
+
+    /// Sends a raw play packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be sent.
+    pub fn send(
+        &mut self,
+        packet: &Packet,
+    ) -> Result<(), Error> {
+        self.connection.send_raw(packet)
+    }
+
+    /// Waits for the next event, answering the packets that need it on the
+    /// way.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Disconnected`] once the server kicks the player, or
+    /// another [`Error`] if the stream fails or a packet is malformed.
+    pub fn next_event(&mut self) -> Result<Event, Error> {
+        if let Some(event) = self.pending.take() {
+            return Ok(event);
+        }
+        loop {
+            if let Some(event) = self.receive()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Receives and handles one play packet.
+    fn receive(&mut self) -> Result<Option<Event>, Error> {
+        let packet = self.connection.receive()?;
+        let name =
+            ProtocolVersion::LATEST.packet_name(State::Play, Direction::Clientbound, packet.id);
+        let Some(name) = name else {
+            return Err(DecodeError::UnknownPacketId(packet.id).into());
+        };
+        self.handle(name, packet)
+    }
+
+    /// Handles a play packet, returning the event it raises if any.
+    fn handle(
+        &mut self,
+        name: &str,
+        packet: Packet,
+    ) -> Result<Option<Event>, Error> {
+        let data = &mut packet.data.as_ref();
+        let event = match name {
+            "login" => {
+                let login = play::Login::decode(data)?;
+                self.entity_id = Some(login.entity_id);
+                self.entities.clear();
+                Event::Joined {
+                    entity_id: login.entity_id,
+                }
+            }
+            "respawn" => {
+                self.entities.clear();
+                Event::Packet(packet)
+            }
+            "keep_alive" => {
+                let keep_alive = play::KeepAlive::decode(data)?;
+                self.connection.send(&play::KeepAliveResponse {
+                    id: keep_alive.id,
+                })?;
+                return Ok(None);
+            }
+            "ping" => {
+                let ping = play::Ping::decode(data)?;
+                self.connection.send(&play::Pong {
+                    id: ping.id,
+                })?;
+                return Ok(None);
+            }
+            "chunk_batch_finished" => {
+                self.connection.send(&play::ChunkBatchReceived {
+                    desired_chunks_per_tick: CHUNKS_PER_TICK,
+                })?;
+                return Ok(None);
+            }
+            "start_configuration" => {
+                self.connection.send(&play::ConfigurationAcknowledged {})?;
+                self.configure()?;
+                return Ok(self.pending.take());
+            }
+            "disconnect" => {
+                let disconnect = play::Disconnect::decode(data)?;
+                return Err(Error::Disconnected(plain_text(&disconnect.reason)));
+            }
+            "player_position" => self.teleport(&play::PlayerPosition::decode(data)?)?,
+            "cookie_request" => {
+                let request = play::CookieRequest::decode(data)?;
+                self.connection.send(&play::CookieResponse {
+                    key: request.key,
+                    payload: None,
+                })?;
+                return Ok(None);
+            }
+            "resource_pack_push" => {
+                let push = play::ResourcePackPush::decode(data)?;
+                for status in PACK_STATUSES {
+                    self.connection.send(&play::ResourcePackResponse {
+                        id: push.id.clone(),
+                        status,
+                    })?;
+                }
+                return Ok(None);
+            }
+            "transfer" => {
+                let transfer = play::Transfer::decode(data)?;
+                Event::Transfer {
+                    host: transfer.host,
+                    port: transfer.port,
+                }
+            }
+            "system_chat" => {
+                let chat = play::SystemChat::decode(data)?;
+                if chat.overlay {
+                    return Ok(None);
+                }
+                Event::Chat {
+                    sender: None,
+                    message: plain_text(&chat.content),
+                }
+            }
+            "player_chat" => {
+                let chat = play::PlayerChat::decode(data)?;
+                Event::Chat {
+                    sender: Some(chat.sender),
+                    message: chat
+                        .unsigned_content
+                        .as_ref()
+                        .map_or(chat.message, plain_text),
+                }
+            }
+            "disguised_chat" => {
+                let chat = play::DisguisedChat::decode(data)?;
+                Event::Chat {
+                    sender: None,
+                    message: plain_text(&chat.message),
+                }
+            }
+            _ => return self.track_entities(name, packet),
+        };
+        Ok(Some(event))
+    }
+
+    /// Confirms a teleport. The first one also tells the server the world
+    /// around the player has loaded.
+    fn teleport(
+        &mut self,
+        packet: &play::PlayerPosition,
+    ) -> Result<Event, Error> {
+        let first = self.position.is_none();
+        let mut position = self.position.unwrap_or_default();
+        position.teleport(
+            Position {
+                x: packet.x,
+                y: packet.y,
+                z: packet.z,
+            },
+            packet.relatives,
+        );
+        #[allow(
+            clippy::float_arithmetic,
+            reason = "relative rotations are offsets in degrees"
+        )]
+        let rotate = |current: f32, target: f32, flag: i32| {
+            if packet.relatives & flag == 0 {
+                target
+            } else {
+                current + target
+            }
+        };
+        self.yaw = rotate(self.yaw, packet.yaw, play::relative::YAW);
+        self.pitch = rotate(self.pitch, packet.pitch, play::relative::PITCH);
+        self.position = Some(position);
+
+        self.connection.send(&play::AcceptTeleportation {
+            teleport_id: packet.teleport_id,
+        })?;
+        self.connection.send(&play::MovePlayerPosRot {
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            yaw: self.yaw,
+            pitch: self.pitch,
+            flags: 0,
+        })?;
+        if first {
+            self.connection.send(&play::PlayerLoaded {})?;
+        }
+        Ok(Event::Teleported {
+            position,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        })
+    }
+
+    /// Keeps the tracked entities up to date. Other packets are returned as
+    /// [`Event::Packet`].
+    fn track_entities(
+        &mut self,
+        name: &str,
+        packet: Packet,
+    ) -> Result<Option<Event>, Error> {
+        let data = &mut packet.data.as_ref();
+        let (id, position) = match name {
+            "add_entity" => {
+                let added = play::AddEntity::decode(data)?;
+                let entity = Entity {
+                    id: added.id,
+                    uuid: added.uuid,
+                    kind: added.kind,
+                    position: Position {
+                        x: added.x,
+                        y: added.y,
+                        z: added.z,
+                    },
+                };
+                self.entities.insert(entity.id, entity.clone());
+                return Ok(Some(Event::EntityAdded(entity)));
+            }
+            "remove_entities" => {
+                let removed = play::RemoveEntities::decode(data)?;
+                let ids: Vec<i32> = removed.ids.iter().map(VarInt::value).collect();
+                for id in &ids {
+                    self.entities.remove(id);
+                }
+                return Ok(Some(Event::EntitiesRemoved(ids)));
+            }
+            "move_entity_pos" => {
+                let moved = play::MoveEntityPos::decode(data)?;
+                (moved.id, (moved.delta_x, moved.delta_y, moved.delta_z))
+            }
+            "move_entity_pos_rot" => {
+                let moved = play::MoveEntityPosRot::decode(data)?;
+                (moved.id, (moved.delta_x, moved.delta_y, moved.delta_z))
+            }
+            "entity_position_sync" => {
+                let synced = play::EntityPositionSync::decode(data)?;
+                return Ok(self.set_entity_position(synced.id, synced.x, synced.y, synced.z, 0));
+            }
+            "teleport_entity" => {
+                let teleported = play::TeleportEntity::decode(data)?;
+                return Ok(self.set_entity_position(
+                    teleported.id,
+                    teleported.x,
+                    teleported.y,
+                    teleported.z,
+                    teleported.relatives,
+                ));
+            }
+            _ => return Ok(Some(Event::Packet(packet))),
+        };
+        let Some(entity) = self.entities.get_mut(&id) else {
+            return Ok(None);
+        };
+        entity.position.offset(position.0, position.1, position.2);
+        Ok(Some(Event::EntityMoved {
+            id,
+            position: entity.position,
+        }))
+    }
+
+    fn set_entity_position(
+        &mut self,
+        id: i32,
+        x: f64,
+        y: f64,
+        z: f64,
+        relatives: i32,
+    ) -> Option<Event> {
+        let entity = self.entities.get_mut(&id)?;
+        entity.position.teleport(
+            Position {
+                x,
+                y,
+                z,
+            },
+            relatives,
+        );
+        Some(Event::EntityMoved {
+            id,
+            position: entity.position,
+        })
+    }
+
+    /// Runs the configuration state until the server finishes it, or
+    /// transfers the client which is then left for
+    /// [`Client::next_event`]. Registry data and tags are not kept.
+    fn configure(&mut self) -> Result<(), Error> {
+        self.connection.send(&configuration::ClientInformation {
+            locale: self.options.locale.clone(),
+            view_distance: self.options.view_distance,
+            chat_mode: 0,
+            chat_colors: true,
+            displayed_skin_parts: 0x7F,
+            main_hand: 1,
+            text_filtering: false,
+            allow_server_listings: true,
+            particle_status: 2,
+        })?;
+
+        loop {
+            let packet = self.connection.receive()?;
+            let packet = match ConfigurationClientbound::from_packet(&packet) {
+                Ok(packet) => packet,
+                Err(DecodeError::UnknownPacketId(id)) => {
+                    debug!("Ignoring configuration packet {id:#04X}");
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            match packet {
+                ConfigurationClientbound::Disconnect(packet) => {
+                    return Err(Error::Disconnected(plain_text(&packet.reason)));
+                }
+                ConfigurationClientbound::FinishConfiguration(_) => {
+                    self.connection
+                        .send(&configuration::AcknowledgeFinishConfiguration {})?;
+                    return Ok(());
+                }
+                ConfigurationClientbound::KeepAlive(keep_alive) => {
+                    self.connection.send(&configuration::KeepAliveResponse {
+                        id: keep_alive.id,
+                    })?;
+                }
+                ConfigurationClientbound::Ping(ping) => {
+                    self.connection.send(&configuration::Pong {
+                        id: ping.id,
+                    })?;
+                }
+                ConfigurationClientbound::CookieRequest(request) => {
+                    // cookies are not stored
+                    self.connection.send(&configuration::CookieResponse {
+                        key: request.key,
+                        payload: None,
+                    })?;
+                }
+                ConfigurationClientbound::RegistryData(_)
+                | ConfigurationClientbound::ResourcePackPop(_)
+                | ConfigurationClientbound::StoreCookie(_) => {}
+                ConfigurationClientbound::ResourcePackPush(push) => {
+                    for status in PACK_STATUSES {
+                        self.connection.send(&configuration::ResourcePackResponse {
+                            id: push.id.clone(),
+                            status,
+                        })?;
+                    }
+                }
+                ConfigurationClientbound::Transfer(transfer) => {
+                    self.pending = Some(Event::Transfer {
+                        host: transfer.host,
+                        port: transfer.port,
+                    });
+                    return Ok(());
+                }
+                ConfigurationClientbound::SelectKnownPacks(packet) => {
+                    // claiming the server's packs spares sending their
+                    // registry entries, which are not kept anyway
+                    self.connection.send(&configuration::KnownPacks {
+                        packs: packet.packs,
+                    })?;
+                }
+            }
+        }
+    }
+}
+
+/// Answers an encryption request, then turns encryption on.
+fn encrypt<S: Read + Write>(
+    connection: &mut Connection<S>,
+    request: &login::EncryptionRequest,
+) -> Result<(), Error> {
+    if request.should_authenticate {
+        warn!("The server requires authentication, which offline clients cannot do");
+    }
+    let key = RsaPublicKey::from_public_key_der(&request.public_key)
+        .map_err(|err| rsa::Error::Pkcs8(rsa::pkcs8::Error::PublicKey(err)))?;
+    let secret: [u8; 16] = rand::random();
+    let mut rng = rand::thread_rng();
+    connection.send(&login::Key {
+        shared_secret: key.encrypt(&mut rng, Pkcs1v15Encrypt, &secret)?,
+        verify_token: key.encrypt(&mut rng, Pkcs1v15Encrypt, &request.verify_token)?,
+    })?;
+    connection.enable_encryption(&secret);
+    Ok(())
+}
+
+/// Milliseconds since the Unix epoch.
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX)
+        })
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+
+    use codec::nbt::Nbt;
+    use codec::{
+        PrefixedOption,
+        VarInt,
+    };
+    use data::protocol::ProtocolPacket;
+    use rsa::RsaPrivateKey;
+    use rsa::pkcs8::EncodePublicKey as _;
+
+    use super::*;
+
+    fn expect<P: ProtocolPacket>(connection: &mut Connection<TcpStream>) -> P {
+        P::from_packet(&connection.receive().unwrap()).unwrap()
+    }
+
+    fn spawn_info() -> play::SpawnInfo {
+        play::SpawnInfo {
+            dimension_type: 0,
+            dimension: "minecraft:overworld".parse().unwrap(),
+            hashed_seed: 0,
+            game_mode: 0,
+            previous_game_mode: -1,
+            debug: false,
+            flat: false,
+            death_location: PrefixedOption::None,
+            portal_cooldown: 0,
+            sea_level: 63,
+        }
+    }
+
+    /// Logs a client in with encryption and compression, then plays a few
+    /// packets, checking every answer.
+    fn serve(stream: TcpStream) {
+        let mut server = Connection::new(stream);
+        serve_login(&mut server);
+        serve_configuration(&mut server);
+        serve_play(&mut server);
+    }
+
+    fn serve_login(server: &mut Connection<TcpStream>) {
+        let handshake: handshake::Handshake = expect(server);
+        assert_eq!(
+            handshake.protocol_version,
+            ProtocolVersion::LATEST.protocol()
+        );
+        let hello: login::Hello = expect(server);
+        assert_eq!(hello.name, "bot", "the client should log in as its name");
+
+        let key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+        let verify_token = vec![1, 2, 3, 4];
+        server
+            .send(&login::EncryptionRequest {
+                server_id: String::new(),
+                public_key: key.to_public_key().to_public_key_der().unwrap().into_vec(),
+                verify_token: verify_token.clone(),
+                should_authenticate: false,
+            })
+            .unwrap();
+        let reply: login::Key = expect(server);
+        let token = key.decrypt(Pkcs1v15Encrypt, &reply.verify_token).unwrap();
+        assert_eq!(token, verify_token, "the verify token should be echoed");
+        let secret = key.decrypt(Pkcs1v15Encrypt, &reply.shared_secret).unwrap();
+        server.enable_encryption(&secret.try_into().unwrap());
+
+        server
+            .send(&login::LoginCompression {
+                size: 16,
+            })
+            .unwrap();
+        server.set_compression(Some(16));
+        server
+            .send(&login::LoginFinished {
+                uuid: Uuid::null(),
+                username: hello.name,
+                properties: Vec::new(),
+            })
+            .unwrap();
+        let _: login::LoginAcknowledged = expect(server);
+    }
+
+    fn serve_configuration(server: &mut Connection<TcpStream>) {
+        let information: configuration::ClientInformation = expect(server);
+        assert_eq!(information.locale, "en_us");
+        let packs = vec![configuration::KnownPack {
+            namespace: "minecraft".to_owned(),
+            id: "core".to_owned(),
+            version: "1.21.8".to_owned(),
+        }];
+        server
+            .send(&configuration::SelectKnownPacks {
+                packs: packs.clone(),
+            })
+            .unwrap();
+        let known: configuration::KnownPacks = expect(server);
+        assert_eq!(known.packs, packs, "the server's packs should be claimed");
+        server
+            .send(&configuration::KeepAlive {
+                id: 7,
+            })
+            .unwrap();
+        let keep_alive: configuration::KeepAliveResponse = expect(server);
+        assert_eq!(keep_alive.id, 7);
+        server.send(&configuration::FinishConfiguration {}).unwrap();
+        let _: configuration::AcknowledgeFinishConfiguration = expect(server);
+    }
+
+    fn serve_play(server: &mut Connection<TcpStream>) {
+        server
+            .send(&play::Login {
+                entity_id: 42,
+                hardcore: false,
+                dimensions: Vec::new(),
+                max_players: 20,
+                view_distance: 2,
+                simulation_distance: 2,
+                reduced_debug_info: false,
+                show_death_screen: true,
+                do_limited_crafting: false,
+                spawn: spawn_info(),
+                enforces_secure_chat: false,
+            })
+            .unwrap();
+        server
+            .send(&play::PlayerPosition {
+                teleport_id: 5,
+                x: 0.5,
+                y: 64.0,
+                z: -3.5,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                velocity_z: 0.0,
+                yaw: 90.0,
+                pitch: 0.0,
+                relatives: 0,
+            })
+            .unwrap();
+        let accepted: play::AcceptTeleportation = expect(server);
+        assert_eq!(accepted.teleport_id, 5);
+        let moved: play::MovePlayerPosRot = expect(server);
+        assert!(moved.y > 63.9, "the client should confirm the position");
+        let _: play::PlayerLoaded = expect(server);
+
+        server
+            .send(&play::KeepAlive {
+                id: 9,
+            })
+            .unwrap();
+        let keep_alive: play::KeepAliveResponse = expect(server);
+        assert_eq!(keep_alive.id, 9);
+
+        let mut content = Nbt::compound();
+        content.insert("text", Nbt::String("welcome".to_owned()));
+        server
+            .send(&play::SystemChat {
+                content,
+                overlay: false,
+            })
+            .unwrap();
+        let chat: play::Chat = expect(server);
+        assert_eq!(chat.message, "hi");
+
+        server
+            .send(&play::AddEntity {
+                id: 3,
+                uuid: Uuid::null(),
+                kind: 0,
+                x: 1.0,
+                y: 64.0,
+                z: 1.0,
+                pitch: 0,
+                yaw: 0,
+                head_yaw: 0,
+                data: 0,
+                velocity_x: 0,
+                velocity_y: 0,
+                velocity_z: 0,
+            })
+            .unwrap();
+        server
+            .send(&play::MoveEntityPos {
+                id: 3,
+                delta_x: 4096,
+                delta_y: 0,
+                delta_z: -2048,
+                on_ground: true,
+            })
+            .unwrap();
+        server
+            .send(&play::RemoveEntities {
+                ids: vec![VarInt::new(3)],
+            })
+            .unwrap();
+        server
+            .send(&play::Disconnect {
+                reason: Nbt::String("bye".to_owned()),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn login_and_play() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || serve(listener.accept().unwrap().0));
+
+        let mut client = Client::connect("127.0.0.1", port, ClientOptions::new("bot")).unwrap();
+        assert!(matches!(client.next_event().unwrap(), Event::Joined {
+            entity_id: 42
+        }));
+        let Event::Teleported {
+            position,
+            yaw,
+            ..
+        } = client.next_event().unwrap()
+        else {
+            panic!("the teleport should be reported");
+        };
+        assert_eq!(position.z.to_bits(), (-3.5_f64).to_bits());
+        assert_eq!(yaw.to_bits(), 90.0_f32.to_bits());
+
+        let Event::Chat {
+            sender: None,
+            message,
+        } = client.next_event().unwrap()
+        else {
+            panic!("the system message should be reported");
+        };
+        assert_eq!(message, "welcome");
+        // the server waits for the chat message before sending more
+        assert!(
+            client
+                .next_event_timeout(Duration::from_millis(50))
+                .unwrap()
+                .is_none(),
+            "nothing should arrive before the chat message"
+        );
+        client.chat("hi").unwrap();
+
+        assert!(matches!(
+            client.next_event_timeout(Duration::from_secs(5)).unwrap(),
+            Some(Event::EntityAdded(_))
+        ));
+        let Event::EntityMoved {
+            id: 3,
+            position,
+        } = client.next_event().unwrap()
+        else {
+            panic!("the move should be reported");
+        };
+        assert_eq!(position.x.to_bits(), 2.0_f64.to_bits());
+        assert_eq!(position.z.to_bits(), 0.5_f64.to_bits());
+        assert_eq!(client.entities().count(), 1, "the entity should be tracked");
+        assert!(matches!(
+            client.next_event().unwrap(),
+            Event::EntitiesRemoved(ids) if ids == [3]
+        ));
+        assert!(matches!(
+            client.next_event(),
+            Err(Error::Disconnected(reason)) if reason == "bye"
+        ));
+        assert_eq!(client.packets_sent(), 13, "every answer should be counted");
+        server.join().unwrap();
+    }
+
+    /// Connects a client to a server thread running `serve`.
+    fn accept(
+        serve: impl FnOnce(&mut Connection<TcpStream>) + Send + 'static
+    ) -> (Client, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let mut server = Connection::new(listener.accept().unwrap().0);
+            serve(&mut server);
+        });
+        let client = Client::connect("127.0.0.1", port, ClientOptions::new("bot")).unwrap();
+        (client, server)
+    }
+
+    /// Logs the client in without encryption, compressing from `threshold`
+    /// bytes, none if negative.
+    fn serve_offline_login(
+        server: &mut Connection<TcpStream>,
+        threshold: i32,
+    ) {
+        let _: handshake::Handshake = expect(server);
+        let hello: login::Hello = expect(server);
+        server
+            .send(&login::LoginCompression {
+                size: threshold,
+            })
+            .unwrap();
+        server.set_compression(usize::try_from(threshold).ok());
+        server
+            .send(&login::LoginFinished {
+                uuid: Uuid::from_u128(1),
+                username: hello.name,
+                properties: Vec::new(),
+            })
+            .unwrap();
+        let _: login::LoginAcknowledged = expect(server);
+    }
+
+    fn serve_finish_configuration(server: &mut Connection<TcpStream>) {
+        let _: configuration::ClientInformation = expect(server);
+        server.send(&configuration::FinishConfiguration {}).unwrap();
+        let _: configuration::AcknowledgeFinishConfiguration = expect(server);
+    }
+
+    fn serve_resource_pack(
+        server: &mut Connection<TcpStream>,
+        push: &configuration::ResourcePackPush,
+    ) {
+        server.send(push).unwrap();
+        let statuses: Vec<_> = PACK_STATUSES
+            .iter()
+            .map(|_| {
+                let response: configuration::ResourcePackResponse = expect(server);
+                assert_eq!(response.id, push.id);
+                response.status
+            })
+            .collect();
+        assert_eq!(
+            statuses, PACK_STATUSES,
+            "the pack should be accepted then loaded"
+        );
+    }
+
+    fn serve_configuration_answers(server: &mut Connection<TcpStream>) {
+        // a negative threshold leaves compression off
+        serve_offline_login(server, -1);
+        let _: configuration::ClientInformation = expect(server);
+        server
+            .send(&configuration::Ping {
+                id: 3,
+            })
+            .unwrap();
+        let pong: configuration::Pong = expect(server);
+        assert_eq!(pong.id, 3);
+        server
+            .send(&configuration::CookieRequest {
+                key: "proxy:origin".to_owned(),
+            })
+            .unwrap();
+        let cookie: configuration::CookieResponse = expect(server);
+        assert_eq!(
+            (cookie.key.as_str(), cookie.payload),
+            ("proxy:origin", None),
+            "cookies are not stored"
+        );
+        let push = configuration::ResourcePackPush {
+            id: Uuid::from_u128(5),
+            url: "http://localhost/pack.zip".to_owned(),
+            hash: String::new(),
+            forced: true,
+            prompt: PrefixedOption::None,
+        };
+        serve_resource_pack(server, &push);
+        // packets the client has no model for are skipped
+        server.send_raw(&Packet::new(0x7F, &[])).unwrap();
+        server
+            .send(&configuration::SelectKnownPacks {
+                packs: Vec::new(),
+            })
+            .unwrap();
+        let known: configuration::KnownPacks = expect(server);
+        assert!(known.packs.is_empty(), "no pack should be claimed");
+        server.send(&configuration::FinishConfiguration {}).unwrap();
+        let _: configuration::AcknowledgeFinishConfiguration = expect(server);
+
+        server.send(&play::StartConfiguration {}).unwrap();
+        let _: play::ConfigurationAcknowledged = expect(server);
+        let _: configuration::ClientInformation = expect(server);
+        server
+            .send(&configuration::Transfer {
+                host: "elsewhere".to_owned(),
+                port: 25566,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn configuration_answers() {
+        let (mut client, server) = accept(serve_configuration_answers);
+        assert_eq!(client.uuid(), &Uuid::from_u128(1));
+        assert!(
+            matches!(
+                client.next_event().unwrap(),
+                Event::Transfer { host, port: 25566 } if host == "elsewhere"
+            ),
+            "a transfer during reconfiguration should be reported"
+        );
+        server.join().unwrap();
+    }
+
+    fn teleport(
+        teleport_id: i32,
+        x: f64,
+        relatives: i32,
+    ) -> play::PlayerPosition {
+        play::PlayerPosition {
+            teleport_id,
+            x,
+            y: 64.0,
+            z: 0.0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            velocity_z: 0.0,
+            yaw: 10.0,
+            pitch: 0.0,
+            relatives,
+        }
+    }
+
+    fn serve_keep_alive_and_teleports(server: &mut Connection<TcpStream>) {
+        serve_offline_login(server, 16);
+        serve_finish_configuration(server);
+        server
+            .send(&play::KeepAlive {
+                id: 1 << 40,
+            })
+            .unwrap();
+        let keep_alive: play::KeepAliveResponse = expect(server);
+        assert_eq!(keep_alive.id, 1 << 40, "the keep-alive id should be echoed");
+        server
+            .send(&play::Ping {
+                id: 9,
+            })
+            .unwrap();
+        let pong: play::Pong = expect(server);
+        assert_eq!(pong.id, 9);
+        server
+            .send(&play::ChunkBatchFinished {
+                batch_size: 4,
+            })
+            .unwrap();
+        let received: play::ChunkBatchReceived = expect(server);
+        assert_eq!(
+            received.desired_chunks_per_tick.to_bits(),
+            CHUNKS_PER_TICK.to_bits()
+        );
+
+        server.send(&teleport(1, 0.5, 0)).unwrap();
+        let accepted: play::AcceptTeleportation = expect(server);
+        assert_eq!(accepted.teleport_id, 1);
+        let _: play::MovePlayerPosRot = expect(server);
+        let _: play::PlayerLoaded = expect(server);
+        let relative = play::relative::X | play::relative::YAW;
+        server.send(&teleport(2, 1.0, relative)).unwrap();
+        let accepted: play::AcceptTeleportation = expect(server);
+        assert_eq!(accepted.teleport_id, 2);
+        let moved: play::MovePlayerPosRot = expect(server);
+        assert_eq!(moved.x.to_bits(), 1.5_f64.to_bits(), "x is an offset");
+        assert_eq!(moved.yaw.to_bits(), 20.0_f32.to_bits(), "yaw is an offset");
+
+        let id = Uuid::from_u128(6);
+        let push = play::ResourcePackPush {
+            id: id.clone(),
+            url: "http://localhost/pack.zip".to_owned(),
+            hash: String::new(),
+            forced: false,
+            prompt: PrefixedOption::None,
+        };
+        // answered with the play ids, which differ from the configuration ones
+        server.send(&push).unwrap();
+        for status in PACK_STATUSES {
+            let response: play::ResourcePackResponse = expect(server);
+            assert_eq!((&response.id, response.status), (&id, status));
+        }
+        // only the first teleport is followed by player_loaded
+        server
+            .send(&play::KeepAlive {
+                id: 2,
+            })
+            .unwrap();
+        let _: play::KeepAliveResponse = expect(server);
+    }
+
+    #[test]
+    fn keep_alive_and_teleports() {
+        let (mut client, server) = accept(serve_keep_alive_and_teleports);
+        let Event::Teleported {
+            position, ..
+        } = client.next_event().unwrap()
+        else {
+            panic!("keep-alives, pings and chunk batches raise no event");
+        };
+        assert_eq!(position.x.to_bits(), 0.5_f64.to_bits());
+        let Event::Teleported {
+            position,
+            yaw,
+            ..
+        } = client.next_event().unwrap()
+        else {
+            panic!("the second teleport should be reported");
+        };
+        assert_eq!(position.x.to_bits(), 1.5_f64.to_bits());
+        assert_eq!(yaw.to_bits(), 20.0_f32.to_bits());
+        assert_eq!(client.position(), Some(position));
+        assert!(
+            matches!(client.next_event(), Err(Error::Io(_) | Error::Decode(_))),
+            "the connection should end once the server is done"
+        );
+        server.join().unwrap();
+    }
+
+    fn player_chat(unsigned_content: Option<Nbt>) -> play::PlayerChat {
+        play::PlayerChat {
+            global_index: 0,
+            sender: Uuid::from_u128(7),
+            index: 0,
+            signature: None,
+            message: "hello".to_owned(),
+            timestamp: 0,
+            salt: 0,
+            previous_messages: Vec::new(),
+            unsigned_content,
+            filter: play::FilterMask::PassThrough,
+            chat_type: 1,
+            sender_name: Nbt::String("Alex".to_owned()),
+            target_name: PrefixedOption::None,
+        }
+    }
+
+    fn serve_chat_events(server: &mut Connection<TcpStream>) {
+        server.send(&player_chat(None)).unwrap();
+        server
+            .send(&player_chat(Some(Nbt::String("<Alex> hello!".to_owned()))))
+            .unwrap();
+        server
+            .send(&play::SystemChat {
+                content: Nbt::String("action bar".to_owned()),
+                overlay: true,
+            })
+            .unwrap();
+        server
+            .send(&play::DisguisedChat {
+                message: Nbt::String("from the console".to_owned()),
+                chat_type: 1,
+                sender_name: Nbt::String("Server".to_owned()),
+                target_name: PrefixedOption::None,
+            })
+            .unwrap();
+    }
+
+    fn serve_entity_events(server: &mut Connection<TcpStream>) {
+        server
+            .send(&play::AddEntity {
+                id: 3,
+                uuid: Uuid::from_u128(8),
+                kind: 0,
+                x: 1.0,
+                y: 64.0,
+                z: 1.0,
+                pitch: 0,
+                yaw: 0,
+                head_yaw: 0,
+                data: 0,
+                velocity_x: 0,
+                velocity_y: 0,
+                velocity_z: 0,
+            })
+            .unwrap();
+        // moves of entities the client does not track raise no event
+        server
+            .send(&play::MoveEntityPos {
+                id: 4,
+                delta_x: 4096,
+                delta_y: 0,
+                delta_z: 0,
+                on_ground: true,
+            })
+            .unwrap();
+        server
+            .send(&play::EntityPositionSync {
+                id: 3,
+                x: 10.0,
+                y: 70.0,
+                z: 10.0,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                velocity_z: 0.0,
+                yaw: 0.0,
+                pitch: 0.0,
+                on_ground: true,
+            })
+            .unwrap();
+        server
+            .send(&play::TeleportEntity {
+                id: 3,
+                x: 2.0,
+                y: 0.0,
+                z: 0.0,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                velocity_z: 0.0,
+                yaw: 0.0,
+                pitch: 0.0,
+                relatives: play::relative::X | play::relative::Y | play::relative::Z,
+                on_ground: true,
+            })
+            .unwrap();
+        server
+            .send(&play::Respawn {
+                spawn: spawn_info(),
+                data_kept: 0,
+            })
+            .unwrap();
+    }
+
+    fn serve_events(server: &mut Connection<TcpStream>) {
+        serve_offline_login(server, 256);
+        serve_finish_configuration(server);
+        serve_chat_events(server);
+        serve_entity_events(server);
+        server
+            .send(&play::SetChunkCacheCenter {
+                x: 1,
+                z: 2,
+            })
+            .unwrap();
+        server
+            .send(&play::Transfer {
+                host: "elsewhere".to_owned(),
+                port: 25566,
+            })
+            .unwrap();
+    }
+
+    fn next_chat(client: &mut Client) -> (Option<Uuid>, String) {
+        let Event::Chat {
+            sender,
+            message,
+        } = client.next_event().unwrap()
+        else {
+            panic!("a chat message should be reported");
+        };
+        (sender, message)
+    }
+
+    fn next_move(client: &mut Client) -> Position {
+        let Event::EntityMoved {
+            id: 3,
+            position,
+        } = client.next_event().unwrap()
+        else {
+            panic!("the entity should move");
+        };
+        position
+    }
+
+    #[test]
+    fn events() {
+        let (mut client, server) = accept(serve_events);
+        let alex = Some(Uuid::from_u128(7));
+        assert_eq!(next_chat(&mut client), (alex.clone(), "hello".to_owned()));
+        assert_eq!(
+            next_chat(&mut client),
+            (alex, "<Alex> hello!".to_owned()),
+            "the decorated content should be preferred"
+        );
+        assert_eq!(
+            next_chat(&mut client),
+            (None, "from the console".to_owned()),
+            "action bar messages raise no event"
+        );
+
+        assert!(matches!(
+            client.next_event().unwrap(),
+            Event::EntityAdded(Entity {
+                id: 3,
+                ..
+            })
+        ));
+        let position = next_move(&mut client);
+        assert_eq!(
+            (position.x.to_bits(), position.y.to_bits()),
+            (10.0_f64.to_bits(), 70.0_f64.to_bits()),
+            "synced positions are absolute"
+        );
+        let position = next_move(&mut client);
+        assert_eq!(position.x.to_bits(), 12.0_f64.to_bits(), "x is an offset");
+        assert_eq!(client.entity(3).unwrap().position, position);
+
+        assert!(matches!(client.next_event().unwrap(), Event::Packet(_)));
+        assert_eq!(
+            client.entities().count(),
+            0,
+            "respawning forgets the entities"
+        );
+        let Event::Packet(packet) = client.next_event().unwrap() else {
+            panic!("packets without an event should be handed over");
+        };
+        let center = play::SetChunkCacheCenter::from_packet(&packet).unwrap();
+        assert_eq!((center.x, center.z), (1, 2));
+        assert!(matches!(
+            client.next_event().unwrap(),
+            Event::Transfer { host, port: 25566 } if host == "elsewhere"
+        ));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,284 @@
+//! Packet framing over a stream, with the compression and encryption set up
+//! during login.
+
+use core::time::Duration;
+use std::io::{
+    self,
+    BufRead as _,
+    BufReader,
+    Read,
+    Write,
+};
+use std::net::TcpStream;
+
+use aes::Aes128;
+use aes::cipher::inout::InOutBuf;
+use aes::cipher::{
+    BlockDecryptMut as _,
+    BlockEncryptMut as _,
+    KeyIvInit as _,
+};
+use data::packet::{
+    Packet,
+    ReadPacket as _,
+    WritePacket as _,
+};
+use data::protocol::ProtocolPacket;
+
+use crate::error::Error;
+
+type Encryptor = cfb8::Encryptor<Aes128>;
+type Decryptor = cfb8::Decryptor<Aes128>;
+
+/// Either end of a connection: packets are read and written the same way on
+/// both sides.
+pub struct Connection<S> {
+    stream: BufReader<S>,
+    /// Size from which packets are compressed, once enabled.
+    compression: Option<usize>,
+    encryption: Option<(Encryptor, Decryptor)>,
+    /// Packets received so far.
+    received: u64,
+    /// Packets sent so far.
+    sent: u64,
+}
+
+impl<S: Read + Write> Connection<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+            compression: None,
+            encryption: None,
+            received: 0,
+            sent: 0,
+        }
+    }
+
+    pub const fn received(&self) -> u64 { self.received }
+
+    pub const fn sent(&self) -> u64 { self.sent }
+
+    pub const fn set_compression(
+        &mut self,
+        compression: Option<usize>,
+    ) {
+        self.compression = compression;
+    }
+
+    /// Encrypts everything sent and received from now on with AES/CFB8,
+    /// whose key and IV are both the shared secret.
+    pub fn enable_encryption(
+        &mut self,
+        secret: &[u8; 16],
+    ) {
+        self.encryption = Some((
+            Encryptor::new(secret.into(), secret.into()),
+            Decryptor::new(secret.into(), secret.into()),
+        ));
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the stream fails or the packet is malformed.
+    pub fn receive(&mut self) -> Result<Packet, Error> {
+        let compressed = self.compression.is_some();
+        let mut reader = Decrypting {
+            stream: &mut self.stream,
+            decryptor: self.encryption.as_mut().map(|(_, decryptor)| decryptor),
+        };
+        let packet = reader.read_packet(compressed)?;
+        self.received += 1;
+        Ok(packet)
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be encoded or the stream
+    /// fails.
+    pub fn send_raw(
+        &mut self,
+        packet: &Packet,
+    ) -> Result<(), Error> {
+        // framed in memory first, so the packet goes out in one write
+        let mut buffer = Vec::new();
+        buffer.write_packet(packet, self.compression)?;
+        if let Some((encryptor, _)) = &mut self.encryption {
+            let (blocks, _) = InOutBuf::from(buffer.as_mut_slice()).into_chunks();
+            encryptor.encrypt_blocks_inout_mut(blocks);
+        }
+        let stream = self.stream.get_mut();
+        stream.write_all(&buffer)?;
+        stream.flush()?;
+        self.sent += 1;
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be encoded or the stream
+    /// fails.
+    pub fn send<P: ProtocolPacket>(
+        &mut self,
+        packet: &P,
+    ) -> Result<(), Error> {
+        self.send_raw(&packet.to_packet()?)
+    }
+}
+
+impl Connection<TcpStream> {
+    /// Waits up to `timeout` for data to arrive, returning whether some did.
+    /// Only the wait is bounded: a packet that started arriving is then read
+    /// whole by [`Connection::receive`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the stream fails.
+    pub fn wait_readable(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<bool, Error> {
+        if !self.stream.buffer().is_empty() {
+            return Ok(true);
+        }
+        if timeout.is_zero() {
+            return Ok(false);
+        }
+        self.stream.get_ref().set_read_timeout(Some(timeout))?;
+        let result = self.stream.fill_buf().map(|_| ());
+        self.stream.get_ref().set_read_timeout(None)?;
+        match result {
+            // an empty buffer means the stream ended, left for receive to
+            // report
+            Ok(()) => Ok(true),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Reads from the stream, decrypting what was read.
+struct Decrypting<'stream, S> {
+    stream: &'stream mut BufReader<S>,
+    decryptor: Option<&'stream mut Decryptor>,
+}
+
+impl<S: Read> Read for Decrypting<'_, S> {
+    fn read(
+        &mut self,
+        buf: &mut [u8],
+    ) -> io::Result<usize> {
+        let read = self.stream.read(buf)?;
+        if let Some(decryptor) = &mut self.decryptor {
+            let (blocks, _) = InOutBuf::from(&mut buf[..read]).into_chunks();
+            decryptor.decrypt_blocks_inout_mut(blocks);
+        }
+        Ok(read)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use alloc::collections::VecDeque;
+
+    use super::*;
+
+    /// A stream reading back what was written to it.
+    #[derive(Default)]
+    struct Loopback(VecDeque<u8>);
+
+    impl Read for Loopback {
+        fn read(
+            &mut self,
+            buf: &mut [u8],
+        ) -> io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Write for Loopback {
+        fn write(
+            &mut self,
+            buf: &[u8],
+        ) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    /// Sends `packet`, returning the frame written, then reads it back.
+    fn round_trip(
+        connection: &mut Connection<Loopback>,
+        packet: &Packet,
+    ) -> Vec<u8> {
+        connection.send_raw(packet).unwrap();
+        let frame = connection.stream.get_ref().0.iter().copied().collect();
+        let received = connection.receive().unwrap();
+        assert_eq!(
+            (received.id, received.data),
+            (packet.id, packet.data.clone()),
+            "the packet should read back"
+        );
+        frame
+    }
+
+    #[test]
+    fn compression_threshold() {
+        let mut connection = Connection::new(Loopback::default());
+        let small = Packet::new(0x01, &[7; 10]);
+        assert_eq!(
+            round_trip(&mut connection, &small)[..2],
+            [11, 0x01],
+            "packets are framed without a data length before compression"
+        );
+
+        connection.set_compression(Some(64));
+        let frame = round_trip(&mut connection, &small);
+        assert_eq!(
+            frame[..3],
+            [12, 0, 0x01],
+            "packets under the threshold are sent with a data length of 0"
+        );
+        // the id and 63 bytes reach the threshold
+        let threshold = Packet::new(0x01, &[7; 63]);
+        let frame = round_trip(&mut connection, &threshold);
+        assert_eq!(
+            frame[1], 64,
+            "packets at the threshold are compressed, after their length"
+        );
+        let large = Packet::new(0x01, &[7; 1000]);
+        let frame = round_trip(&mut connection, &large);
+        assert_eq!(frame[1..3], [0xE9, 0x07], "the data length is 1001");
+        assert!(frame.len() < 100, "the payload should be compressed");
+
+        connection.set_compression(None);
+        assert_eq!(
+            round_trip(&mut connection, &large)[..3],
+            [0xE9, 0x07, 0x01],
+            "compression can be turned off again"
+        );
+        assert_eq!(connection.sent(), 5);
+        assert_eq!(connection.received(), 5);
+    }
+
+    #[test]
+    fn encrypted_round_trip() {
+        let mut connection = Connection::new(Loopback::default());
+        connection.enable_encryption(&[3; 16]);
+        connection.set_compression(Some(16));
+        let packet = Packet::new(0x05, b"hello, encrypted world");
+        let frame = round_trip(&mut connection, &packet);
+        assert!(
+            !frame.windows(5).any(|window| window == b"hello"),
+            "the frame should be encrypted"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use core::{
+    error,
+    fmt,
+};
+use std::io;
+
+use codec::dec::DecodeError;
+use codec::enc::EncodeError;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Decode(DecodeError),
+    Encode(EncodeError),
+    /// The server's public key could not be used.
+    Encryption(rsa::Error),
+    /// The server closed the connection, with the plain text of its reason.
+    Disconnected(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "IO error: {err}"),
+            Self::Decode(err) => write!(f, "Decode error: {err}"),
+            Self::Encode(err) => write!(f, "Encode error: {err}"),
+            Self::Encryption(err) => write!(f, "Encryption error: {err}"),
+            Self::Disconnected(reason) => write!(f, "Disconnected: {reason}"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self { Self::Decode(err) }
+}
+
+impl From<EncodeError> for Error {
+    fn from(err: EncodeError) -> Self { Self::Encode(err) }
+}
+
+impl From<rsa::Error> for Error {
+    fn from(err: rsa::Error) -> Self { Self::Encryption(err) }
+}
@@ -0,0 +1,106 @@
+use codec::Uuid;
+use data::model::play;
+use data::packet::Packet;
+use data::registry::{
+    EntityType,
+    Registry as _,
+};
+
+/// Something that happened in the world, as returned by
+/// [`Client::next_event`](crate::Client::next_event).
+#[derive(Debug)]
+pub enum Event {
+    /// The player joined the world, as the entity `entity_id`.
+    Joined {
+        entity_id: i32,
+    },
+    /// The server moved the player, which the client already confirmed.
+    Teleported {
+        position: Position,
+        yaw: f32,
+        pitch: f32,
+    },
+    /// A chat message, from a player when `sender` is set or from the server
+    /// otherwise.
+    Chat {
+        sender: Option<Uuid>,
+        message: String,
+    },
+    EntityAdded(Entity),
+    EntityMoved {
+        id: i32,
+        position: Position,
+    },
+    EntitiesRemoved(Vec<i32>),
+    /// The server sends the player elsewhere. The client does not follow:
+    /// connect a new one there.
+    Transfer {
+        host: String,
+        port: i32,
+    },
+    /// Any other play packet, left to the caller.
+    Packet(Packet),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Position {
+    /// Moves by a relative offset in 4096ths of a block.
+    #[allow(
+        clippy::float_arithmetic,
+        reason = "entity positions are doubles on the wire"
+    )]
+    pub(crate) fn offset(
+        &mut self,
+        delta_x: i16,
+        delta_y: i16,
+        delta_z: i16,
+    ) {
+        self.x += f64::from(delta_x) / 4096.0;
+        self.y += f64::from(delta_y) / 4096.0;
+        self.z += f64::from(delta_z) / 4096.0;
+    }
+
+    /// Applies a teleport, where each coordinate flagged in `relatives` is an
+    /// offset.
+    #[allow(
+        clippy::float_arithmetic,
+        reason = "entity positions are doubles on the wire"
+    )]
+    pub(crate) fn teleport(
+        &mut self,
+        target: Self,
+        relatives: i32,
+    ) {
+        let apply = |current: f64, target: f64, flag: i32| {
+            if relatives & flag == 0 {
+                target
+            } else {
+                current + target
+            }
+        };
+        self.x = apply(self.x, target.x, play::relative::X);
+        self.y = apply(self.y, target.y, play::relative::Y);
+        self.z = apply(self.z, target.z, play::relative::Z);
+    }
+}
+
+/// An entity tracked by the client.
+#[derive(Debug, Clone)]
+pub struct Entity {
+    pub id: i32,
+    pub uuid: Uuid,
+    /// Id in the `minecraft:entity_type` registry.
+    pub kind: i32,
+    pub position: Position,
+}
+
+impl Entity {
+    #[must_use]
+    pub fn entity_type(&self) -> Option<EntityType> { EntityType::from_id(self.kind) }
+}
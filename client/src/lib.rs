@@ -0,0 +1,30 @@
+//! A headless client for bots, load tests and integration tests against a
+//! local server.
+//!
+//! [`Client`] logs in in offline mode, with compression and encryption when
+//! the server asks for them, and goes through the configuration state. Once
+//! in play, [`Client::next_event`] returns chat, position and entity
+//! updates while answering keep-alives and teleports.
+
+#![allow(
+    clippy::multiple_crate_versions,
+    reason = "the workspace lockfile holds the syn of the proxy dependencies too"
+)]
+
+extern crate alloc;
+
+mod client;
+mod connection;
+mod error;
+mod event;
+
+pub use client::{
+    Client,
+    ClientOptions,
+};
+pub use error::Error;
+pub use event::{
+    Entity,
+    Event,
+    Position,
+};
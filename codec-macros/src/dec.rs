@@ -5,8 +5,10 @@ use syn::spanned::Spanned as _;
 use crate::{
     EnumKind,
     FieldKind,
+    LengthPrefix,
+    condition_bindings,
     enum_kind,
-    field_kind,
+    field_attrs,
 };
 
 pub fn derive_struct(
@@ -34,36 +36,38 @@ pub fn derive_struct(
             .ok_or_else(|| syn::Error::new(f.span(), "expected named field"))?;
         let ty: &syn::Type = &f.ty;
 
-        let kind = field_kind(&f.attrs)?;
+        let attrs = field_attrs(&f.attrs)?;
         let ctx = format!("Failed to decode {ident}");
+        let value = decode_value(&attrs.kind, &ctx);
 
-        let stmt = match kind {
-            FieldKind::Normal => {
-                quote! {
-                    let #ident = <#ty as ::codec::dec::Decode>::decode(reader)
-                        .err_context(#ctx)?;
-                }
-            }
-            FieldKind::VarInt => {
-                quote! {
-                    let #ident = ::codec::VarInt::decode(reader)
-                        .err_context(#ctx)?
-                        .value();
-                }
+        let stmt = if attrs.skip {
+            let default = attrs
+                .default
+                .unwrap_or_else(|| quote! { ::core::default::Default::default() });
+            quote! {
+                let #ident: #ty = #default;
             }
-            FieldKind::VarLong => {
-                quote! {
-                    let #ident = ::codec::VarLong::decode(reader)
-                        .err_context(#ctx)?
-                        .value();
-                }
+        } else if let Some(condition) = &attrs.condition {
+            let bindings = condition_bindings(&names, |ident| quote! { #ident });
+            let absent = attrs
+                .default
+                .clone()
+                .unwrap_or_else(|| quote! { ::core::option::Option::None });
+            let present = if attrs.default.is_some() {
+                value
+            } else {
+                quote! { ::core::option::Option::Some(#value) }
+            };
+            quote! {
+                let #ident: #ty = if { #bindings #condition } {
+                    #present
+                } else {
+                    #absent
+                };
             }
-            FieldKind::PrefixedOption => {
-                quote! {
-                    let #ident: #ty = ::codec::PrefixedOption::decode(reader)
-                        .err_context(#ctx)?
-                        .into();
-                }
+        } else {
+            quote! {
+                let #ident: #ty = #value;
             }
         };
 
@@ -85,6 +89,87 @@ pub fn derive_struct(
     .into())
 }
 
+/// Expression decoding one value of the given kind from `reader`.
+fn decode_value(
+    kind: &FieldKind,
+    ctx: &str,
+) -> proc_macro2::TokenStream {
+    match kind {
+        FieldKind::Normal => {
+            quote! {
+                ::codec::dec::Decode::decode(reader)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::VarInt => {
+            quote! {
+                <::codec::VarInt as ::codec::dec::Decode>::decode(reader)
+                    .err_context(#ctx)?
+                    .value()
+            }
+        }
+        FieldKind::VarLong => {
+            quote! {
+                <::codec::VarLong as ::codec::dec::Decode>::decode(reader)
+                    .err_context(#ctx)?
+                    .value()
+            }
+        }
+        FieldKind::PrefixedOption => {
+            quote! {
+                <::codec::PrefixedOption<_> as ::codec::dec::Decode>::decode(reader)
+                    .err_context(#ctx)?
+                    .into()
+            }
+        }
+        FieldKind::Json => {
+            quote! {
+                ::codec::field::decode_json(reader)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::Nbt => {
+            quote! {
+                ::codec::field::decode_nbt(reader)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::Rest => {
+            quote! {
+                ::codec::field::decode_rest(reader)
+                    .err_context(#ctx)?
+                    .into()
+            }
+        }
+        FieldKind::LengthPrefix(LengthPrefix::None) => {
+            quote! {
+                ::codec::field::decode_unprefixed(reader)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::LengthPrefix(prefix) => {
+            let prefix = match prefix {
+                LengthPrefix::U8 => quote! { U8 },
+                LengthPrefix::U16 => quote! { U16 },
+                LengthPrefix::VarInt | LengthPrefix::None => quote! { VarInt },
+            };
+            quote! {
+                ::codec::field::decode_prefixed(
+                    reader,
+                    ::codec::field::LengthPrefix::#prefix,
+                )
+                .err_context(#ctx)?
+            }
+        }
+        FieldKind::With(path) => {
+            quote! {
+                #path::decode(reader)
+                    .err_context(#ctx)?
+            }
+        }
+    }
+}
+
 pub fn derive_enum(
     input: &syn::DeriveInput,
     variants: Vec<&syn::Variant>,
@@ -5,8 +5,10 @@ use syn::spanned::Spanned as _;
 use crate::{
     EnumKind,
     FieldKind,
+    LengthPrefix,
+    condition_bindings,
     enum_kind,
-    field_kind,
+    field_attrs,
 };
 
 pub fn derive_struct(
@@ -25,6 +27,7 @@ pub fn derive_struct(
     };
 
     let mut lets = Vec::new();
+    let mut names = Vec::new();
 
     for f in fields {
         let ident = f
@@ -32,41 +35,48 @@ pub fn derive_struct(
             .as_ref()
             .ok_or_else(|| syn::Error::new(f.span(), "expected named field"))?;
 
-        let kind = field_kind(&f.attrs)?;
+        let attrs = field_attrs(&f.attrs)?;
         let ctx = format!("Failed to encode {ident}");
 
-        let stmt = match kind {
-            FieldKind::Normal => {
+        if attrs.skip {
+            names.push(ident);
+            continue;
+        }
+
+        let value = encode_value(&attrs.kind, &ctx);
+
+        let stmt = if let Some(condition) = &attrs.condition {
+            let bindings = condition_bindings(&names, |ident| quote! { self.#ident });
+            let field_name = ident.to_string();
+            if attrs.default.is_some() {
                 quote! {
-                    self.#ident
-                        .encode(writer)
-                        .err_context(#ctx)?;
+                    if { #bindings #condition } {
+                        let value = &self.#ident;
+                        #value
+                    } else {
+                        0
+                    };
                 }
-            }
-            FieldKind::VarInt => {
+            } else {
                 quote! {
-                    ::codec::VarInt::new(self.#ident)
-                        .encode(writer)
-                        .err_context(#ctx)?;
+                    match ({ #bindings #condition }, &self.#ident) {
+                        (true, ::core::option::Option::Some(value)) => #value,
+                        (false, ::core::option::Option::None) => 0,
+                        _ => return Err(::codec::field::conditional_mismatch(#field_name)),
+                    };
                 }
             }
-            FieldKind::VarLong => {
-                quote! {
-                    ::codec::VarLong::new(self.#ident)
-                        .encode(writer)
-                        .err_context(#ctx)?;
-                }
-            }
-            FieldKind::PrefixedOption => {
-                quote! {
-                    ::codec::PrefixedOption::from(self.#ident.as_ref())
-                        .encode(writer)
-                        .err_context(#ctx)?;
-                }
+        } else {
+            quote! {
+                {
+                    let value = &self.#ident;
+                    #value
+                };
             }
         };
 
         lets.push(stmt);
+        names.push(ident);
     }
 
     Ok(quote! {
@@ -85,6 +95,87 @@ pub fn derive_struct(
     .into())
 }
 
+/// Expression encoding the reference `value` of the given kind to `writer`.
+fn encode_value(
+    kind: &FieldKind,
+    ctx: &str,
+) -> proc_macro2::TokenStream {
+    match kind {
+        FieldKind::Normal => {
+            quote! {
+                ::codec::enc::Encode::encode(value, writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::VarInt => {
+            quote! {
+                ::codec::enc::Encode::encode(&::codec::VarInt::new(*value), writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::VarLong => {
+            quote! {
+                ::codec::enc::Encode::encode(&::codec::VarLong::new(*value), writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::PrefixedOption => {
+            quote! {
+                ::codec::enc::Encode::encode(
+                    &::codec::PrefixedOption::from(value.as_ref()),
+                    writer,
+                )
+                .err_context(#ctx)?
+            }
+        }
+        FieldKind::Json => {
+            quote! {
+                ::codec::field::encode_json(value, writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::Nbt => {
+            quote! {
+                ::codec::field::encode_nbt(value, writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::Rest => {
+            quote! {
+                ::codec::field::encode_rest(value, writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::LengthPrefix(LengthPrefix::None) => {
+            quote! {
+                ::codec::field::encode_unprefixed(value, writer)
+                    .err_context(#ctx)?
+            }
+        }
+        FieldKind::LengthPrefix(prefix) => {
+            let prefix = match prefix {
+                LengthPrefix::U8 => quote! { U8 },
+                LengthPrefix::U16 => quote! { U16 },
+                LengthPrefix::VarInt | LengthPrefix::None => quote! { VarInt },
+            };
+            quote! {
+                ::codec::field::encode_prefixed(
+                    value,
+                    ::codec::field::LengthPrefix::#prefix,
+                    writer,
+                )
+                .err_context(#ctx)?
+            }
+        }
+        FieldKind::With(path) => {
+            quote! {
+                #path::encode(value, writer)
+                    .err_context(#ctx)?
+            }
+        }
+    }
+}
+
 pub fn derive_enum(
     input: &syn::DeriveInput,
     variants: Vec<&syn::Variant>,
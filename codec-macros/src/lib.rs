@@ -39,11 +39,36 @@ pub fn derive_encode(input: TokenStream) -> TokenStream {
     }
 }
 
+/// How a field is represented on the wire.
 enum FieldKind {
     Normal,
     VarInt,
     VarLong,
     PrefixedOption,
+    Json,
+    Nbt,
+    Rest,
+    LengthPrefix(LengthPrefix),
+    With(syn::Path),
+}
+
+enum LengthPrefix {
+    VarInt,
+    U8,
+    U16,
+    None,
+}
+
+struct FieldAttrs {
+    kind: FieldKind,
+    /// `skip`: the field is not on the wire.
+    skip: bool,
+    /// `default = expr`: value of a skipped field or of a conditional field
+    /// whose condition is false. A bare `default` means `Default::default()`.
+    default: Option<proc_macro2::TokenStream>,
+    /// `if = expr`: the field is only on the wire when `expr` holds. Earlier
+    /// fields are in scope as references.
+    condition: Option<syn::Expr>,
 }
 
 enum EnumKind {
@@ -51,8 +76,13 @@ enum EnumKind {
     VarLong,
 }
 
-fn field_kind(attrs: &[syn::Attribute]) -> syn::Result<FieldKind> {
-    let mut kind = FieldKind::Normal;
+fn field_attrs(attrs: &[syn::Attribute]) -> syn::Result<FieldAttrs> {
+    let mut field = FieldAttrs {
+        kind: FieldKind::Normal,
+        skip: false,
+        default: None,
+        condition: None,
+    };
 
     for a in attrs {
         if !a.path().is_ident("codec") {
@@ -60,25 +90,105 @@ fn field_kind(attrs: &[syn::Attribute]) -> syn::Result<FieldKind> {
         }
         a.parse_nested_meta(|meta| {
             if meta.path.is_ident("varint") {
-                kind = FieldKind::VarInt;
+                field.kind = FieldKind::VarInt;
                 return Ok(());
             }
             if meta.path.is_ident("varlong") {
-                kind = FieldKind::VarLong;
+                field.kind = FieldKind::VarLong;
                 return Ok(());
             }
             if meta.path.is_ident("prefixed_option") {
-                kind = FieldKind::PrefixedOption;
+                field.kind = FieldKind::PrefixedOption;
+                return Ok(());
+            }
+            if meta.path.is_ident("json") {
+                field.kind = FieldKind::Json;
+                return Ok(());
+            }
+            if meta.path.is_ident("nbt") {
+                field.kind = FieldKind::Nbt;
+                return Ok(());
+            }
+            if meta.path.is_ident("rest") {
+                field.kind = FieldKind::Rest;
+                return Ok(());
+            }
+            if meta.path.is_ident("length_prefix") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                let prefix = match lit.value().as_str() {
+                    "varint" => LengthPrefix::VarInt,
+                    "u8" => LengthPrefix::U8,
+                    "u16" => LengthPrefix::U16,
+                    "none" => LengthPrefix::None,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expected `\"varint\"`, `\"u8\"`, `\"u16\"` or `\"none\"`",
+                        ));
+                    }
+                };
+                field.kind = FieldKind::LengthPrefix(prefix);
+                return Ok(());
+            }
+            if meta.path.is_ident("with") {
+                field.kind = FieldKind::With(meta.value()?.parse()?);
+                return Ok(());
+            }
+            if meta.path.is_ident("skip") {
+                field.skip = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("default") {
+                field.default = Some(if meta.input.peek(syn::Token![=]) {
+                    let expr: syn::Expr = meta.value()?.parse()?;
+                    quote::quote! { #expr }
+                } else {
+                    quote::quote! { ::core::default::Default::default() }
+                });
+                return Ok(());
+            }
+            if meta.path.is_ident("if") {
+                field.condition = Some(meta.value()?.parse()?);
                 return Ok(());
             }
             Err(meta.error(
-                "unsupported #[codec(...)] argument; expected `varint`, `varlong` or \
-                 `prefixed_option`",
+                "unsupported #[codec(...)] argument; expected `varint`, `varlong`, \
+                 `prefixed_option`, `json`, `nbt`, `rest`, `length_prefix = \"...\"`, `with = \
+                 path`, `skip`, `default` or `if = expr`",
             ))
         })?;
+
+        if field.skip && field.condition.is_some() {
+            return Err(syn::Error::new(
+                a.span(),
+                "`skip` and `if` cannot be combined",
+            ));
+        }
+        if field.default.is_some() && !field.skip && field.condition.is_none() {
+            return Err(syn::Error::new(
+                a.span(),
+                "`default` only applies to `skip` or `if = expr` fields",
+            ));
+        }
     }
 
-    Ok(kind)
+    Ok(field)
+}
+
+/// Rebinds the given fields as references so that `if = expr` conditions
+/// see the same types when decoding and encoding.
+fn condition_bindings(
+    fields: &[&syn::Ident],
+    source: impl Fn(&syn::Ident) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let bindings = fields.iter().map(|ident| {
+        let value = source(ident);
+        quote::quote! {
+            #[allow(unused_variables, reason = "not every condition uses every field")]
+            let #ident = &#value;
+        }
+    });
+    quote::quote! { #(#bindings)* }
 }
 
 fn enum_kind(attrs: &[syn::Attribute]) -> syn::Result<EnumKind> {
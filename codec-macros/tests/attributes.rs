@@ -0,0 +1,215 @@
+#![allow(clippy::unwrap_used, reason = "tests")]
+
+use std::io;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+    FromJson,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+    ToJson,
+};
+use codec::nbt::Nbt;
+
+fn roundtrip<T: Decode + Encode>(bytes: &[u8]) -> T {
+    let value = T::decode(&mut &bytes[..]).unwrap();
+    let mut encoded = Vec::new();
+    let written_bytes = value.encode(&mut encoded).unwrap();
+    assert_eq!(encoded, bytes, "re-encoding should give back the input");
+    assert_eq!(
+        written_bytes,
+        bytes.len(),
+        "written byte count should match"
+    );
+    value
+}
+
+#[derive(Debug, Decode, Encode)]
+struct Prefixes {
+    #[codec(length_prefix = "u8")]
+    small: Vec<u16>,
+    #[codec(length_prefix = "u16")]
+    medium: Vec<u8>,
+    #[codec(length_prefix = "varint")]
+    large: Vec<u8>,
+    #[codec(length_prefix = "none")]
+    tail: Vec<u16>,
+}
+
+#[test]
+fn length_prefixes() {
+    let value: Prefixes = roundtrip(&[
+        0x01, 0x00, 0x07, // small
+        0x00, 0x01, 0x08, // medium
+        0x02, 0x09, 0x0A, // large
+        0x00, 0x01, 0x00, 0x02, // tail
+    ]);
+    assert_eq!(value.small, vec![7]);
+    assert_eq!(value.medium, vec![8]);
+    assert_eq!(value.large, vec![9, 10]);
+    assert_eq!(value.tail, vec![1, 2]);
+}
+
+#[derive(Debug, Decode, Encode)]
+struct Rest {
+    #[codec(varint)]
+    channel: i32,
+    #[codec(rest)]
+    payload: Box<[u8]>,
+}
+
+#[test]
+fn rest() {
+    let value: Rest = roundtrip(&[0x05, 0x01, 0x02, 0x03]);
+    assert_eq!(value.channel, 5);
+    assert_eq!(value.payload.as_ref(), &[0x01, 0x02, 0x03]);
+}
+
+mod angle {
+    use std::io;
+
+    use codec::dec::{
+        Decode as _,
+        DecodeError,
+    };
+    use codec::enc::{
+        Encode as _,
+        EncodeError,
+    };
+
+    pub fn decode<R: io::Read>(reader: &mut R) -> Result<u16, DecodeError> {
+        Ok(u16::from(u8::decode(reader)?) * 360 / 256)
+    }
+
+    #[allow(
+        clippy::trivially_copy_pass_by_ref,
+        reason = "`with` functions take the field by reference"
+    )]
+    pub fn encode<W: io::Write>(
+        value: &u16,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        u8::try_from(value * 256 / 360)
+            .expect("angle should be below 360")
+            .encode(writer)
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+struct With {
+    #[codec(with = angle)]
+    yaw: u16,
+}
+
+#[test]
+fn with() {
+    let value: With = roundtrip(&[0x40]);
+    assert_eq!(value.yaw, 90);
+}
+
+#[derive(Debug, Decode, Encode)]
+struct Conditional {
+    has_extra: bool,
+    #[codec(if = *has_extra)]
+    extra: Option<u8>,
+    #[codec(varint)]
+    kind: i32,
+    #[codec(varint, if = *kind == 2, default = -1)]
+    data: i32,
+    #[codec(skip)]
+    cache: Vec<u8>,
+    #[codec(skip, default = 42)]
+    answer: u8,
+}
+
+#[test]
+fn conditional() {
+    let value: Conditional = roundtrip(&[0x01, 0x07, 0x02, 0x03]);
+    assert_eq!(value.extra, Some(7));
+    assert_eq!(value.data, 3);
+    assert!(value.cache.is_empty(), "skipped fields use their default");
+    assert_eq!(value.answer, 42);
+
+    let value: Conditional = roundtrip(&[0x00, 0x01]);
+    assert_eq!(value.extra, None);
+    assert_eq!(value.data, -1);
+
+    let invalid = Conditional {
+        has_extra: false,
+        extra: Some(1),
+        kind: 0,
+        data: 0,
+        cache: Vec::new(),
+        answer: 0,
+    };
+    assert!(
+        invalid.encode(&mut Vec::new()).is_err(),
+        "a conditional field present against its condition should fail"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct Motd(String);
+
+impl FromJson for Motd {
+    fn from_json(json: json::JsonValue) -> Result<Self, DecodeError> {
+        json["text"]
+            .as_str()
+            .map(|text| Self(text.to_owned()))
+            .ok_or(DecodeError::Custom {
+                message: "missing text".to_owned(),
+            })
+    }
+}
+
+impl ToJson for Motd {
+    fn to_json(&self) -> json::JsonValue {
+        json::object! { text: self.0.as_str() }
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+struct JsonAndNbt {
+    #[codec(json)]
+    motd: Motd,
+    #[codec(nbt)]
+    tag: Nbt,
+}
+
+#[test]
+fn json_and_nbt() {
+    let mut bytes = Vec::new();
+    r#"{"text":"hi"}"#.encode(&mut bytes).unwrap();
+    bytes.extend([0x08, 0x00, 0x02, b'o', b'k']);
+
+    let value: JsonAndNbt = roundtrip(&bytes);
+    assert_eq!(value.motd, Motd("hi".to_owned()));
+    assert_eq!(value.tag, Nbt::String("ok".to_owned()));
+}
+
+#[test]
+fn unprefixed_rejects_trailing_garbage() {
+    let result = Prefixes::decode(&mut io::Cursor::new([0x00, 0x00, 0x00, 0x00, 0x01]));
+    assert!(
+        matches!(result, Err(DecodeError::Context { .. })),
+        "an odd trailing byte cannot be a u16 element"
+    );
+}
+
+#[test]
+fn encode_error_context() {
+    let value = Prefixes {
+        small: vec![0; 256],
+        medium: Vec::new(),
+        large: Vec::new(),
+        tail: Vec::new(),
+    };
+    let err = value.encode(&mut Vec::new()).unwrap_err();
+    assert!(
+        matches!(err, EncodeError::Context { ref context, .. } if context == "Failed to encode small"),
+        "unexpected error: {err}"
+    );
+}
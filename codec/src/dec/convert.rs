@@ -0,0 +1,31 @@
+use super::error::DecodeError;
+use crate::nbt::Nbt;
+
+/// Build a value from a parsed JSON document, used by `#[codec(json)]`
+/// fields.
+pub trait FromJson: Sized {
+    /// Convert a JSON value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the JSON does not describe a valid value.
+    fn from_json(json: json::JsonValue) -> Result<Self, DecodeError>;
+}
+
+/// Build a value from a decoded NBT tag, used by `#[codec(nbt)]` fields.
+pub trait FromNbt: Sized {
+    /// Convert an NBT tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the tag does not describe a valid value.
+    fn from_nbt(nbt: Nbt) -> Result<Self, DecodeError>;
+}
+
+impl FromJson for json::JsonValue {
+    fn from_json(json: json::JsonValue) -> Result<Self, DecodeError> { Ok(json) }
+}
+
+impl FromNbt for Nbt {
+    fn from_nbt(nbt: Nbt) -> Result<Self, DecodeError> { Ok(nbt) }
+}
@@ -57,6 +57,30 @@ impl Decode for u64 {
     }
 }
 
+impl Decode for i8 {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        u8::decode(reader).map(u8::cast_signed)
+    }
+}
+
+impl Decode for i16 {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        u16::decode(reader).map(u16::cast_signed)
+    }
+}
+
+impl Decode for i32 {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        u32::decode(reader).map(u32::cast_signed)
+    }
+}
+
+impl Decode for i64 {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        u64::decode(reader).map(u64::cast_signed)
+    }
+}
+
 impl Decode for f32 {
     fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
         Ok(f32::from_bits(u32::decode(reader)?))
@@ -103,7 +127,8 @@ where
             len.cast_unsigned() as usize
         };
 
-        let mut vec = Vec::with_capacity(len);
+        // the length comes from the wire, the elements must prove it
+        let mut vec = Vec::with_capacity(len.min(1 << 16));
 
         for _ in 0..len {
             let elem = T::decode(reader).err_context("Failed to decode vec element")?;
@@ -126,10 +151,17 @@ impl Decode for String {
             len.cast_unsigned() as usize
         };
 
-        let mut bytes = vec![0; len];
-        reader.read_exact(&mut bytes)?;
+        Ok(String::from_utf8(read_bytes(reader, len)?)?)
+    }
+}
 
-        Ok(String::from_utf8(bytes)?)
+/// Fixed-size byte arrays, such as message signatures, are sent without a
+/// length prefix.
+impl<const N: usize> Decode for [u8; N] {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let mut bytes = [0; N];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
     }
 }
 
@@ -171,8 +203,7 @@ impl Decode for json::JsonValue {
             len.cast_unsigned() as usize
         };
 
-        let mut bytes = vec![0; len];
-        reader.read_exact(&mut bytes)?;
+        let bytes = read_bytes(reader, len)?;
 
         let raw_json = str::from_utf8(&bytes)?;
         let json = json::parse(raw_json)?;
@@ -181,6 +212,20 @@ impl Decode for json::JsonValue {
     }
 }
 
+/// Reads `len` bytes without trusting `len` for the allocation.
+fn read_bytes<R: io::Read>(
+    reader: &mut R,
+    len: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = Vec::with_capacity(len.min(1 << 16));
+    let limit = u64::try_from(len).unwrap_or(u64::MAX);
+    io::Read::read_to_end(&mut io::Read::take(reader, limit), &mut bytes)?;
+    if bytes.len() < len {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    Ok(bytes)
+}
+
 #[allow(clippy::unwrap_used, reason = "tests")]
 #[cfg(test)]
 mod tests {
@@ -214,6 +259,14 @@ mod tests {
         assert_eq!(value, 72_623_859_790_382_856);
     }
 
+    #[test]
+    fn decode_signed() {
+        let mut buffer = [0xFF, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0x80].as_slice();
+        assert_eq!(i8::decode(&mut buffer).unwrap(), -1);
+        assert_eq!(i16::decode(&mut buffer).unwrap(), -2);
+        assert_eq!(i32::decode(&mut buffer).unwrap(), -128);
+    }
+
     #[test]
     fn decode_option() {
         let mut buffer = [0x00].as_slice();
@@ -232,6 +285,28 @@ mod tests {
         assert_eq!(value, vec![0x01, 0x02, 0x03, 0x04, 0x05]);
     }
 
+    #[test]
+    fn decode_array() {
+        let mut buffer = [0x01, 0x02, 0x03].as_slice();
+        let value: [u8; 2] = Decode::decode(&mut buffer).unwrap();
+        assert_eq!(value, [0x01, 0x02]);
+        assert_eq!(buffer, [0x03], "only the array should be read");
+    }
+
+    #[test]
+    fn decode_huge_lengths() {
+        // a length prefix alone must not reserve memory for the elements
+        let mut buffer = [0xFF, 0xFF, 0xFF, 0xFF, 0x07].as_slice();
+        let result: Result<Vec<u64>, _> = Vec::decode(&mut buffer);
+        assert!(result.is_err(), "the elements are missing");
+
+        let mut buffer = [0xFF, 0xFF, 0xFF, 0xFF, 0x07, b'a'].as_slice();
+        assert!(
+            matches!(String::decode(&mut buffer), Err(DecodeError::UnexpectedEnd)),
+            "the bytes are missing"
+        );
+    }
+
     #[test]
     fn decode_string() {
         let mut buffer = [0x05, b'H', b'e', b'l', b'l', b'o'].as_slice();
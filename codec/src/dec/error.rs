@@ -19,7 +19,9 @@ pub enum DecodeError {
     InvalidUtf8(str::Utf8Error),
     InvalidVarInt,
     InvalidVarLong,
+    UnknownPacketId(i32),
     Json(json::Error),
+    Io(io::Error),
 }
 
 impl DecodeError {
@@ -52,7 +54,9 @@ impl fmt::Display for DecodeError {
             DecodeError::InvalidUtf8(err) => write!(f, "Invalid UTF-8 sequence: {err}"),
             DecodeError::InvalidVarInt => write!(f, "Invalid VarInt"),
             DecodeError::InvalidVarLong => write!(f, "Invalid VarLong"),
+            DecodeError::UnknownPacketId(id) => write!(f, "Unknown packet ID: {id:#04X}"),
             DecodeError::Json(err) => write!(f, "JSON error: {err}"),
+            DecodeError::Io(err) => write!(f, "IO error: {err}"),
         }
     }
 }
@@ -63,7 +67,7 @@ impl From<io::Error> for DecodeError {
     fn from(err: io::Error) -> Self {
         match err.kind() {
             io::ErrorKind::UnexpectedEof => DecodeError::UnexpectedEnd,
-            _ => unreachable!("Unexpected error kind: {:?}", err.kind()),
+            _ => DecodeError::Io(err),
         }
     }
 }
@@ -1,7 +1,12 @@
+mod convert;
 mod decode;
 mod error;
 
 pub use codec_macros::Decode;
+pub use convert::{
+    FromJson,
+    FromNbt,
+};
 pub use decode::Decode;
 pub use error::{
     DecodeError,
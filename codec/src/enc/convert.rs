@@ -0,0 +1,19 @@
+use crate::nbt::Nbt;
+
+/// Turn a value into a JSON document, used by `#[codec(json)]` fields.
+pub trait ToJson {
+    fn to_json(&self) -> json::JsonValue;
+}
+
+/// Turn a value into an NBT tag, used by `#[codec(nbt)]` fields.
+pub trait ToNbt {
+    fn to_nbt(&self) -> Nbt;
+}
+
+impl ToJson for json::JsonValue {
+    fn to_json(&self) -> json::JsonValue { self.clone() }
+}
+
+impl ToNbt for Nbt {
+    fn to_nbt(&self) -> Nbt { self.clone() }
+}
@@ -99,6 +99,42 @@ impl Encode for u64 {
     }
 }
 
+impl Encode for i8 {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        self.cast_unsigned().encode(writer)
+    }
+}
+
+impl Encode for i16 {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        self.cast_unsigned().encode(writer)
+    }
+}
+
+impl Encode for i32 {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        self.cast_unsigned().encode(writer)
+    }
+}
+
+impl Encode for i64 {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        self.cast_unsigned().encode(writer)
+    }
+}
+
 impl Encode for f32 {
     fn encode<W: io::Write>(
         &self,
@@ -206,6 +242,17 @@ impl Encode for String {
     }
 }
 
+/// Fixed-size byte arrays are written without a length prefix.
+impl<const N: usize> Encode for [u8; N] {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        writer.write_all(self)?;
+        Ok(N)
+    }
+}
+
 macro_rules! impl_tuple_encode {
     ($($T:tt),+ $(,)?) => {
         impl<$($T),*> Encode for ($($T),*,)
@@ -290,6 +337,15 @@ mod tests {
         assert_eq!(buffer, vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
     }
 
+    #[test]
+    fn encode_signed() {
+        let mut buffer = Vec::new();
+        (-1_i8).encode(&mut buffer).unwrap();
+        (-2_i16).encode(&mut buffer).unwrap();
+        (-128_i32).encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0xFF, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0x80]);
+    }
+
     #[test]
     fn encode_option() {
         let mut buffer = Vec::new();
@@ -311,6 +367,13 @@ mod tests {
         assert_eq!(buffer, vec![0x05, 0x01, 0x02, 0x03, 0x04, 0x05]);
     }
 
+    #[test]
+    fn encode_array() {
+        let mut buffer = Vec::new();
+        [0x01_u8, 0x02].encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x01, 0x02]);
+    }
+
     #[test]
     fn encode_string() {
         let mut buffer = Vec::new();
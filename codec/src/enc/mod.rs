@@ -1,7 +1,12 @@
+mod convert;
 mod encode;
 mod error;
 
 pub use codec_macros::Encode;
+pub use convert::{
+    ToJson,
+    ToNbt,
+};
 pub use encode::Encode;
 pub use error::{
     EncodeError,
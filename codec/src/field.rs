@@ -0,0 +1,199 @@
+//! Support code for the `#[codec(...)]` field attributes of the derive
+//! macros. Not meant to be called directly.
+
+use std::io;
+
+use crate::VarInt;
+use crate::dec::{
+    Decode,
+    DecodeError,
+    FromJson,
+    FromNbt,
+};
+use crate::enc::{
+    Encode,
+    EncodeError,
+    ToJson,
+    ToNbt,
+};
+use crate::nbt::Nbt;
+
+/// Length prefix of a `#[codec(length_prefix = "...")]` collection.
+#[derive(Debug, Clone, Copy)]
+pub enum LengthPrefix {
+    VarInt,
+    U8,
+    U16,
+}
+
+impl LengthPrefix {
+    fn decode<R: io::Read>(
+        self,
+        reader: &mut R,
+    ) -> Result<usize, DecodeError> {
+        match self {
+            Self::VarInt => {
+                let len = VarInt::decode(reader)?.value();
+                usize::try_from(len).map_err(|_| DecodeError::InvalidVarInt)
+            }
+            Self::U8 => Ok(usize::from(u8::decode(reader)?)),
+            Self::U16 => Ok(usize::from(u16::decode(reader)?)),
+        }
+    }
+
+    fn encode<W: io::Write>(
+        self,
+        len: usize,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let too_long = || EncodeError::Custom {
+            message: format!("Collection length {len} does not fit a {self:?} prefix"),
+        };
+        match self {
+            Self::VarInt => VarInt::new(i32::try_from(len).map_err(|_| too_long())?).encode(writer),
+            Self::U8 => u8::try_from(len).map_err(|_| too_long())?.encode(writer),
+            Self::U16 => u16::try_from(len).map_err(|_| too_long())?.encode(writer),
+        }
+    }
+}
+
+/// Decodes a collection whose length is given by `prefix`.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the length or any element fails to decode.
+pub fn decode_prefixed<T: Decode, R: io::Read>(
+    reader: &mut R,
+    prefix: LengthPrefix,
+) -> Result<Vec<T>, DecodeError> {
+    let len = prefix.decode(reader)?;
+    let mut items = Vec::with_capacity(len.min(1 << 16));
+    for _ in 0..len {
+        items.push(T::decode(reader)?);
+    }
+    Ok(items)
+}
+
+/// Encodes a collection preceded by its length as `prefix`.
+///
+/// # Errors
+///
+/// Returns [`EncodeError`] if the length does not fit the prefix or writing
+/// fails.
+pub fn encode_prefixed<T: Encode, W: io::Write>(
+    items: &[T],
+    prefix: LengthPrefix,
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    let mut written_bytes = prefix.encode(items.len(), writer)?;
+    for item in items {
+        written_bytes += item.encode(writer)?;
+    }
+    Ok(written_bytes)
+}
+
+/// Decodes elements until the reader is exhausted.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if an element fails to decode.
+pub fn decode_unprefixed<T: Decode, R: io::Read>(reader: &mut R) -> Result<Vec<T>, DecodeError> {
+    let rest = decode_rest(reader)?;
+    let mut rest = rest.as_slice();
+    let mut items = Vec::new();
+    while !rest.is_empty() {
+        items.push(T::decode(&mut rest)?);
+    }
+    Ok(items)
+}
+
+/// Encodes elements back to back without a length.
+///
+/// # Errors
+///
+/// Returns [`EncodeError`] if writing fails.
+pub fn encode_unprefixed<T: Encode, W: io::Write>(
+    items: &[T],
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    let mut written_bytes = 0;
+    for item in items {
+        written_bytes += item.encode(writer)?;
+    }
+    Ok(written_bytes)
+}
+
+/// Reads every remaining byte.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if reading fails.
+pub fn decode_rest<R: io::Read>(reader: &mut R) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Writes raw bytes without a length.
+///
+/// # Errors
+///
+/// Returns [`EncodeError`] if writing fails.
+pub fn encode_rest<W: io::Write>(
+    bytes: &[u8],
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    writer.write_all(bytes)?;
+    Ok(bytes.len())
+}
+
+/// Decodes a string holding a JSON document.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the string is not valid JSON for `T`.
+pub fn decode_json<T: FromJson, R: io::Read>(reader: &mut R) -> Result<T, DecodeError> {
+    T::from_json(json::JsonValue::decode(reader)?)
+}
+
+/// Encodes a value as a string holding a JSON document.
+///
+/// # Errors
+///
+/// Returns [`EncodeError`] if writing fails.
+pub fn encode_json<T: ToJson, W: io::Write>(
+    value: &T,
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    value.to_json().encode(writer)
+}
+
+/// Decodes a network NBT tag.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the tag is malformed or invalid for `T`.
+pub fn decode_nbt<T: FromNbt, R: io::Read>(reader: &mut R) -> Result<T, DecodeError> {
+    T::from_nbt(Nbt::decode(reader)?)
+}
+
+/// Encodes a value as a network NBT tag.
+///
+/// # Errors
+///
+/// Returns [`EncodeError`] if writing fails.
+pub fn encode_nbt<T: ToNbt, W: io::Write>(
+    value: &T,
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    value.to_nbt().encode(writer)
+}
+
+/// Error for a conditional field whose presence disagrees with its
+/// condition.
+#[must_use]
+pub fn conditional_mismatch(field: &str) -> EncodeError {
+    EncodeError::Custom {
+        message: format!("Field {field} must be present exactly when its condition holds"),
+    }
+}
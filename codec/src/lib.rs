@@ -2,6 +2,9 @@ extern crate alloc;
 
 pub mod dec;
 pub mod enc;
+#[doc(hidden)]
+pub mod field;
+pub mod nbt;
 
 const SEGMENT_MASK: u8 = 0b0111_1111;
 const CONTINUE_MASK: u8 = 0b1000_0000;
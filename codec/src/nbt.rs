@@ -0,0 +1,495 @@
+use std::io;
+
+use crate::dec::{
+    Decode,
+    DecodeError,
+    DecodeErrorContext as _,
+};
+use crate::enc::{
+    Encode,
+    EncodeError,
+};
+
+/// Maximum nesting depth accepted while decoding, matching the vanilla limit.
+const MAX_DEPTH: usize = 512;
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+/// A Named Binary Tag value.
+///
+/// Compounds keep their entries in wire order so that a decoded value
+/// re-encodes to the exact same bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Nbt {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List {
+        element_type: u8,
+        elements: Vec<Nbt>,
+    },
+    Compound(Vec<(String, Nbt)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Nbt {
+    /// Creates an empty compound.
+    #[must_use]
+    pub const fn compound() -> Self { Self::Compound(Vec::new()) }
+
+    /// Creates a list, deriving the element type from the first element.
+    #[must_use]
+    pub fn list(elements: Vec<Nbt>) -> Self {
+        Self::List {
+            element_type: elements.first().map_or(TAG_END, Nbt::tag_type),
+            elements,
+        }
+    }
+
+    /// Returns the tag type id of this value.
+    #[must_use]
+    pub const fn tag_type(&self) -> u8 {
+        match self {
+            Self::Byte(_) => TAG_BYTE,
+            Self::Short(_) => TAG_SHORT,
+            Self::Int(_) => TAG_INT,
+            Self::Long(_) => TAG_LONG,
+            Self::Float(_) => TAG_FLOAT,
+            Self::Double(_) => TAG_DOUBLE,
+            Self::ByteArray(_) => TAG_BYTE_ARRAY,
+            Self::String(_) => TAG_STRING,
+            Self::List {
+                ..
+            } => TAG_LIST,
+            Self::Compound(_) => TAG_COMPOUND,
+            Self::IntArray(_) => TAG_INT_ARRAY,
+            Self::LongArray(_) => TAG_LONG_ARRAY,
+        }
+    }
+
+    /// Looks up an entry of a compound.
+    #[must_use]
+    pub fn get(
+        &self,
+        key: &str,
+    ) -> Option<&Nbt> {
+        match self {
+            Self::Compound(entries) => entries
+                .iter()
+                .find_map(|(name, value)| (name == key).then_some(value)),
+            _ => None,
+        }
+    }
+
+    /// Inserts or replaces an entry of a compound.
+    ///
+    /// Does nothing if the value is not a compound.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: Nbt,
+    ) {
+        if let Self::Compound(entries) = self {
+            let key = key.into();
+            if let Some((_, existing)) = entries.iter_mut().find(|(name, _)| *name == key) {
+                *existing = value;
+            } else {
+                entries.push((key, value));
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of any integral tag widened to an `i64`.
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Byte(value) => Some(i64::from(value)),
+            Self::Short(value) => Some(i64::from(value)),
+            Self::Int(value) => Some(i64::from(value)),
+            Self::Long(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_list(&self) -> Option<&[Nbt]> {
+        match self {
+            Self::List {
+                elements, ..
+            } => Some(elements),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_compound(&self) -> Option<&[(String, Nbt)]> {
+        match self {
+            Self::Compound(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_long_array(&self) -> Option<&[i64]> {
+        match self {
+            Self::LongArray(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Decodes a root tag in the disk format: type, name, then payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the tag is malformed or the root is
+    /// `TAG_End`.
+    pub fn decode_named<R: io::Read>(reader: &mut R) -> Result<(String, Self), DecodeError> {
+        let tag_type = u8::decode(reader).err_context("Failed to decode NBT tag type")?;
+        if tag_type == TAG_END {
+            return Err(DecodeError::Custom {
+                message: "NBT root tag cannot be TAG_End".to_owned(),
+            });
+        }
+        let name = decode_string(reader).err_context("Failed to decode NBT root name")?;
+        let value = Self::decode_payload(reader, tag_type, 0)?;
+        Ok((name, value))
+    }
+
+    /// Encodes a root tag in the disk format: type, name, then payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncodeError`] if writing fails.
+    pub fn encode_named<W: io::Write>(
+        &self,
+        name: &str,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut written_bytes = self.tag_type().encode(writer)?;
+        written_bytes += encode_string(name, writer)?;
+        written_bytes += self.encode_payload(writer)?;
+        Ok(written_bytes)
+    }
+
+    fn decode_payload<R: io::Read>(
+        reader: &mut R,
+        tag_type: u8,
+        depth: usize,
+    ) -> Result<Self, DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::Custom {
+                message: format!("NBT exceeds maximum depth of {MAX_DEPTH}"),
+            });
+        }
+
+        let value = match tag_type {
+            TAG_BYTE => Self::Byte(i8::decode(reader)?),
+            TAG_SHORT => Self::Short(i16::decode(reader)?),
+            TAG_INT => Self::Int(i32::decode(reader)?),
+            TAG_LONG => Self::Long(i64::decode(reader)?),
+            TAG_FLOAT => Self::Float(f32::decode(reader)?),
+            TAG_DOUBLE => Self::Double(f64::decode(reader)?),
+            TAG_BYTE_ARRAY => Self::ByteArray(decode_array(reader)?),
+            TAG_STRING => Self::String(decode_string(reader)?),
+            TAG_LIST => {
+                let element_type = u8::decode(reader)?;
+                let len = decode_len(reader)?;
+                let mut elements = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    elements.push(
+                        Self::decode_payload(reader, element_type, depth + 1)
+                            .err_context("Failed to decode NBT list element")?,
+                    );
+                }
+                Self::List {
+                    element_type,
+                    elements,
+                }
+            }
+            TAG_COMPOUND => {
+                let mut entries = Vec::new();
+                loop {
+                    let entry_type = u8::decode(reader)?;
+                    if entry_type == TAG_END {
+                        break;
+                    }
+                    let name = decode_string(reader)?;
+                    let value = Self::decode_payload(reader, entry_type, depth + 1)
+                        .err_context(format!("Failed to decode NBT compound entry {name}"))?;
+                    entries.push((name, value));
+                }
+                Self::Compound(entries)
+            }
+            TAG_INT_ARRAY => Self::IntArray(decode_array(reader)?),
+            TAG_LONG_ARRAY => Self::LongArray(decode_array(reader)?),
+            _ => {
+                return Err(DecodeError::Custom {
+                    message: format!("Invalid NBT tag type: {tag_type}"),
+                });
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn encode_payload<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        match self {
+            Self::Byte(value) => value.encode(writer),
+            Self::Short(value) => value.encode(writer),
+            Self::Int(value) => value.encode(writer),
+            Self::Long(value) => value.encode(writer),
+            Self::Float(value) => value.encode(writer),
+            Self::Double(value) => value.encode(writer),
+            Self::ByteArray(values) => encode_array(values, writer),
+            Self::String(value) => encode_string(value, writer),
+            Self::List {
+                element_type,
+                elements,
+            } => {
+                let mut written_bytes = element_type.encode(writer)?;
+                written_bytes += encode_len(elements.len(), writer)?;
+                for element in elements {
+                    written_bytes += element.encode_payload(writer)?;
+                }
+                Ok(written_bytes)
+            }
+            Self::Compound(entries) => {
+                let mut written_bytes = 0;
+                for (name, value) in entries {
+                    written_bytes += value.tag_type().encode(writer)?;
+                    written_bytes += encode_string(name, writer)?;
+                    written_bytes += value.encode_payload(writer)?;
+                }
+                written_bytes += TAG_END.encode(writer)?;
+                Ok(written_bytes)
+            }
+            Self::IntArray(values) => encode_array(values, writer),
+            Self::LongArray(values) => encode_array(values, writer),
+        }
+    }
+}
+
+/// Decodes a root tag in the network format (no root name), as sent since
+/// protocol 764. A `TAG_End` root is an error; use `Option<Nbt>` for
+/// optional tags.
+impl Decode for Nbt {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let tag_type = u8::decode(reader).err_context("Failed to decode NBT tag type")?;
+        if tag_type == TAG_END {
+            return Err(DecodeError::Custom {
+                message: "NBT root tag cannot be TAG_End".to_owned(),
+            });
+        }
+        Self::decode_payload(reader, tag_type, 0)
+    }
+}
+
+impl Encode for Nbt {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let written_bytes = self.tag_type().encode(writer)?;
+        Ok(written_bytes + self.encode_payload(writer)?)
+    }
+}
+
+fn decode_len<R: io::Read>(reader: &mut R) -> Result<usize, DecodeError> {
+    let len = i32::decode(reader)?;
+    usize::try_from(len).map_err(|_| DecodeError::Custom {
+        message: format!("Invalid NBT length: {len}"),
+    })
+}
+
+fn encode_len<W: io::Write>(
+    len: usize,
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    let len = i32::try_from(len).map_err(|_| EncodeError::Custom {
+        message: format!("NBT length {len} exceeds i32::MAX"),
+    })?;
+    len.encode(writer)
+}
+
+fn decode_array<T: Decode, R: io::Read>(reader: &mut R) -> Result<Vec<T>, DecodeError> {
+    let len = decode_len(reader)?;
+    let mut values = Vec::with_capacity(len.min(1 << 16));
+    for _ in 0..len {
+        values.push(T::decode(reader)?);
+    }
+    Ok(values)
+}
+
+fn encode_array<T: Encode, W: io::Write>(
+    values: &[T],
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    let mut written_bytes = encode_len(values.len(), writer)?;
+    for value in values {
+        written_bytes += value.encode(writer)?;
+    }
+    Ok(written_bytes)
+}
+
+/// Decodes a length-prefixed Java "modified UTF-8" string.
+fn decode_string<R: io::Read>(reader: &mut R) -> Result<String, DecodeError> {
+    let len = usize::from(u16::decode(reader)?);
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+
+    // fast path: plain UTF-8 without NUL or surrogate pairs is identical
+    if let Ok(value) = String::from_utf8(bytes.clone())
+        && !value.contains('\0')
+    {
+        return Ok(value);
+    }
+
+    let mut units = Vec::with_capacity(len);
+    let mut bytes = bytes.iter().copied();
+    while let Some(first) = bytes.next() {
+        let mut next = || {
+            bytes
+                .next()
+                .filter(|byte| byte & 0xC0 == 0x80)
+                .map(|byte| u16::from(byte & 0x3F))
+                .ok_or(DecodeError::Custom {
+                    message: "Invalid modified UTF-8 sequence".to_owned(),
+                })
+        };
+        let unit = match first {
+            0x00..=0x7F => u16::from(first),
+            0xC0..=0xDF => (u16::from(first & 0x1F) << 6) | next()?,
+            0xE0..=0xEF => (u16::from(first & 0x0F) << 12) | (next()? << 6) | next()?,
+            _ => {
+                return Err(DecodeError::Custom {
+                    message: "Invalid modified UTF-8 sequence".to_owned(),
+                });
+            }
+        };
+        units.push(unit);
+    }
+
+    String::from_utf16(&units).map_err(|_| DecodeError::Custom {
+        message: "Invalid UTF-16 in modified UTF-8 string".to_owned(),
+    })
+}
+
+/// Encodes a length-prefixed Java "modified UTF-8" string.
+fn encode_string<W: io::Write>(
+    value: &str,
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    let mut bytes = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        #[allow(
+            clippy::cast_possible_truncation,
+            reason = "each branch masks the unit to fit into a byte"
+        )]
+        match unit {
+            0x0001..=0x007F => bytes.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                bytes.push(0xC0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                bytes.push(0xE0 | (unit >> 12) as u8);
+                bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+
+    let len = u16::try_from(bytes.len()).map_err(|_| EncodeError::Custom {
+        message: format!("NBT string length {} exceeds u16::MAX", bytes.len()),
+    })?;
+    let written_bytes = len.encode(writer)?;
+    writer.write_all(&bytes)?;
+    Ok(written_bytes + bytes.len())
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_network_compound() {
+        #[rustfmt::skip]
+        let mut buffer = [
+            0x0A,
+            0x08, 0x00, 0x04, b't', b'e', b'x', b't', 0x00, 0x02, b'h', b'i',
+            0x01, 0x00, 0x04, b'b', b'o', b'l', b'd', 0x01,
+            0x00,
+        ]
+        .as_slice();
+        let value = Nbt::decode(&mut buffer).unwrap();
+        assert_eq!(value.get("text").and_then(Nbt::as_str), Some("hi"));
+        assert_eq!(value.get("bold").and_then(Nbt::as_i64), Some(1));
+        assert!(buffer.is_empty(), "the whole compound should be consumed");
+    }
+
+    #[test]
+    fn optional_nbt_end() {
+        let mut buffer = [0x00].as_slice();
+        let value = Option::<Nbt>::decode(&mut buffer).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn roundtrip_named() {
+        let mut value = Nbt::compound();
+        value.insert("list", Nbt::list(vec![Nbt::Int(1), Nbt::Int(2)]));
+        value.insert("empty", Nbt::List {
+            element_type: TAG_COMPOUND,
+            elements: Vec::new(),
+        });
+        value.insert("longs", Nbt::LongArray(vec![-1, 2]));
+        value.insert("nul", Nbt::String("a\0b\u{1F980}".to_owned()));
+
+        let mut buffer = Vec::new();
+        value.encode_named("root", &mut buffer).unwrap();
+        let (name, decoded) = Nbt::decode_named(&mut buffer.as_slice()).unwrap();
+        assert_eq!(name, "root");
+        assert_eq!(decoded, value);
+
+        let mut reencoded = Vec::new();
+        decoded.encode_named("root", &mut reencoded).unwrap();
+        assert_eq!(reencoded, buffer);
+    }
+
+    #[test]
+    fn modified_utf8_nul() {
+        let mut buffer = Vec::new();
+        encode_string("\0", &mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x00, 0x02, 0xC0, 0x80]);
+    }
+}
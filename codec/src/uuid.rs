@@ -16,6 +16,12 @@ pub struct Uuid(u128);
 impl Uuid {
     #[must_use]
     pub const fn null() -> Self { Self(0) }
+
+    #[must_use]
+    pub const fn from_u128(value: u128) -> Self { Self(value) }
+
+    #[must_use]
+    pub const fn as_u128(&self) -> u128 { self.0 }
 }
 
 impl fmt::Debug for Uuid {
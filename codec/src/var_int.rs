@@ -18,8 +18,11 @@ pub struct VarInt(Box<[u8]>);
 
 impl VarInt {
     #[must_use]
-    pub fn new(mut value: i32) -> Self {
+    pub fn new(value: i32) -> Self {
         let mut bytes = Vec::new();
+        // shifted as unsigned, so that negative values end too
+        #[allow(clippy::cast_sign_loss, reason = "the bits are kept as is")]
+        let mut value = value as u32;
 
         loop {
             #[allow(
@@ -133,4 +136,15 @@ mod tests {
             Err(DecodeError::InvalidVarInt)
         ));
     }
+
+    #[test]
+    fn encode_var_int() {
+        assert_eq!(VarInt::new(0x80).as_slice(), [0x80, 0x01]);
+        assert_eq!(
+            VarInt::new(-1).as_slice(),
+            [0xFF, 0xFF, 0xFF, 0xFF, 0x0F],
+            "negative values take five bytes"
+        );
+        assert_eq!(VarInt::new(i32::MIN).value(), i32::MIN);
+    }
 }
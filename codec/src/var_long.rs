@@ -18,8 +18,11 @@ pub struct VarLong(Box<[u8]>);
 
 impl VarLong {
     #[must_use]
-    pub fn new(mut value: i64) -> Self {
+    pub fn new(value: i64) -> Self {
         let mut bytes = Vec::new();
+        // shifted as unsigned, so that negative values end too
+        #[allow(clippy::cast_sign_loss, reason = "the bits are kept as is")]
+        let mut value = value as u64;
 
         loop {
             #[allow(
@@ -142,4 +145,14 @@ mod tests {
             Err(DecodeError::InvalidVarLong)
         ));
     }
+
+    #[test]
+    fn encode_var_long() {
+        assert_eq!(
+            VarLong::new(-1).as_slice(),
+            [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+            "negative values take ten bytes"
+        );
+        assert_eq!(VarLong::new(i64::MIN).value(), i64::MIN);
+    }
 }
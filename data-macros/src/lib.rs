@@ -0,0 +1,208 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::spanned::Spanned as _;
+
+/// Implements `data::protocol::ProtocolPacket` from a
+/// `#[packet(id = 0x03, state = Login, direction = Clientbound)]` attribute.
+///
+/// The packet name defaults to the snake case type name and can be
+/// overridden with `name = "..."`.
+#[proc_macro_derive(Packet, attributes(packet))]
+pub fn derive_packet(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match derive(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Implements `data::json::ToJson`, in the shape serde gives by default.
+///
+/// Structs become objects of their fields, newtypes their content and
+/// other tuple structs arrays. Unit variants become their name, other
+/// variants an object holding their content under their name.
+#[proc_macro_derive(ToJson)]
+pub fn derive_to_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match derive_json(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn derive_json(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let body = match &input.data {
+        syn::Data::Struct(data) => {
+            let (pattern, json) = fields_json(&data.fields);
+            quote! {
+                let Self #pattern = self;
+                #json
+            }
+        }
+        syn::Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let key = ident.to_string();
+                let (pattern, json) = fields_json(&variant.fields);
+                if matches!(variant.fields, syn::Fields::Unit) {
+                    quote! { Self::#ident => ::json::JsonValue::from(#key), }
+                } else {
+                    quote! {
+                        Self::#ident #pattern => {
+                            let mut object = ::json::object::Object::with_capacity(1);
+                            object.insert(#key, { #json });
+                            ::json::JsonValue::Object(object)
+                        }
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.span(),
+                "#[derive(ToJson)] is not supported on unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl ::data::json::ToJson for #name {
+            fn to_json(&self) -> ::json::JsonValue {
+                #body
+            }
+        }
+    })
+}
+
+/// The pattern binding `fields` and the expression turning them into JSON.
+fn fields_json(fields: &syn::Fields) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match fields {
+        syn::Fields::Named(named) => {
+            let idents: Vec<_> = named
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .collect();
+            let keys = idents
+                .iter()
+                .map(|ident| syn::ext::IdentExt::unraw(*ident).to_string());
+            let len = idents.len();
+            (quote! { { #(#idents),* } }, quote! {
+                let mut object = ::json::object::Object::with_capacity(#len);
+                #(object.insert(#keys, ::data::json::ToJson::to_json(#idents));)*
+                ::json::JsonValue::Object(object)
+            })
+        }
+        syn::Fields::Unnamed(unnamed) => {
+            let idents: Vec<_> = (0..unnamed.unnamed.len())
+                .map(|i| quote::format_ident!("field{i}"))
+                .collect();
+            let json = if let [ident] = idents.as_slice() {
+                quote! { ::data::json::ToJson::to_json(#ident) }
+            } else {
+                quote! {
+                    ::json::JsonValue::Array(vec![
+                        #(::data::json::ToJson::to_json(#idents)),*
+                    ])
+                }
+            };
+            (quote! { ( #(#idents),* ) }, json)
+        }
+        syn::Fields::Unit => (quote! {}, quote! { ::json::JsonValue::new_object() }),
+    }
+}
+
+fn derive(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+
+    let mut id: Option<syn::LitInt> = None;
+    let mut state: Option<syn::Ident> = None;
+    let mut direction: Option<syn::Ident> = None;
+    let mut packet_name: Option<syn::LitStr> = None;
+
+    for a in &input.attrs {
+        if !a.path().is_ident("packet") {
+            continue;
+        }
+        a.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                id = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            if meta.path.is_ident("state") {
+                let ident: syn::Ident = meta.value()?.parse()?;
+                if !["Handshake", "Status", "Login", "Configuration", "Play"]
+                    .contains(&ident.to_string().as_str())
+                {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "expected `Handshake`, `Status`, `Login`, `Configuration` or `Play`",
+                    ));
+                }
+                state = Some(ident);
+                return Ok(());
+            }
+            if meta.path.is_ident("direction") {
+                let ident: syn::Ident = meta.value()?.parse()?;
+                if ident != "Serverbound" && ident != "Clientbound" {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "expected `Serverbound` or `Clientbound`",
+                    ));
+                }
+                direction = Some(ident);
+                return Ok(());
+            }
+            if meta.path.is_ident("name") {
+                packet_name = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            Err(meta.error(
+                "unsupported #[packet(...)] argument; expected `id`, `state`, `direction` or \
+                 `name`",
+            ))
+        })?;
+    }
+
+    let missing = |what: &str| {
+        syn::Error::new(
+            input.span(),
+            format!("missing `{what}` in #[packet(...)] attribute"),
+        )
+    };
+    let id = id.ok_or_else(|| missing("id"))?;
+    let state = state.ok_or_else(|| missing("state"))?;
+    let direction = direction.ok_or_else(|| missing("direction"))?;
+    let packet_name = packet_name.map_or_else(|| snake_case(&name.to_string()), |lit| lit.value());
+
+    Ok(quote! {
+        impl ::data::protocol::ProtocolPacket for #name {
+            const ID: i32 = #id;
+            const STATE: ::data::protocol::State = ::data::protocol::State::#state;
+            const DIRECTION: ::data::protocol::Direction = ::data::protocol::Direction::#direction;
+            const NAME: &'static str = #packet_name;
+        }
+    })
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
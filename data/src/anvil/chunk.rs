@@ -0,0 +1,817 @@
+//! Conversion between the chunk NBT of region files and the section model
+//! of network chunks.
+
+use codec::nbt::Nbt;
+
+use super::AnvilError;
+use crate::identifier::Identifier;
+use crate::registry::{
+    Biome,
+    Block,
+    BlockEntityType,
+    BlockState,
+    Registry as _,
+};
+use crate::world::{
+    BlockEntity,
+    BlockPos,
+    ChunkData,
+    ChunkPos,
+    ContainerKind,
+    Heightmap,
+    HeightmapKind,
+    LightData,
+    PalettedContainer,
+    Section,
+};
+
+/// Data version of 1.21.8, the last release of protocol 772.
+pub const DATA_VERSION: i32 = 4440;
+
+/// Status of a fully generated chunk.
+pub const FULL: &str = "minecraft:full";
+
+const HEIGHTMAPS: [(HeightmapKind, &str); 6] = [
+    (HeightmapKind::WorldSurfaceWg, "WORLD_SURFACE_WG"),
+    (HeightmapKind::WorldSurface, "WORLD_SURFACE"),
+    (HeightmapKind::OceanFloorWg, "OCEAN_FLOOR_WG"),
+    (HeightmapKind::OceanFloor, "OCEAN_FLOOR"),
+    (HeightmapKind::MotionBlocking, "MOTION_BLOCKING"),
+    (
+        HeightmapKind::MotionBlockingNoLeaves,
+        "MOTION_BLOCKING_NO_LEAVES",
+    ),
+];
+
+/// A chunk column as saved in a region file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskChunk {
+    pub pos: ChunkPos,
+    /// Section y of `sections[0]`.
+    pub min_section: i32,
+    pub data_version: i32,
+    /// Generation status, [`FULL`] once the chunk is complete.
+    pub status: String,
+    /// Game time of the last save, in ticks.
+    pub last_update: i64,
+    /// Ticks players spent in the chunk, which raises its local difficulty.
+    pub inhabited_time: i64,
+    /// Sections from the bottom of the world up.
+    pub sections: Vec<Section>,
+    pub heightmaps: Vec<Heightmap>,
+    /// Block entities as saved, with their `id` and absolute position.
+    pub block_entities: Vec<Nbt>,
+    /// Light, whose sections start one below `min_section`.
+    pub light: LightData,
+}
+
+impl DiskChunk {
+    /// A full chunk holding everything a network chunk carries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a block entity has an unknown type.
+    pub fn from_network(
+        pos: ChunkPos,
+        min_section: i32,
+        chunk: &ChunkData,
+        light: &LightData,
+    ) -> Result<Self, AnvilError> {
+        let block_entities = chunk
+            .block_entities
+            .iter()
+            .map(|entity| {
+                let position = BlockPos::new(
+                    pos.x * 16 + i32::from(entity.x()),
+                    i32::from(entity.y),
+                    pos.z * 16 + i32::from(entity.z()),
+                );
+                block_entity_to_nbt(entity.kind, position, entity.data.as_ref())
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            pos,
+            min_section,
+            data_version: DATA_VERSION,
+            status: FULL.to_owned(),
+            last_update: 0,
+            inhabited_time: 0,
+            sections: chunk.sections.clone(),
+            heightmaps: chunk.heightmaps.clone(),
+            block_entities,
+            light: light.clone(),
+        })
+    }
+
+    /// The chunk as sent in `level_chunk_with_light`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a block entity has an unknown id or no
+    /// position.
+    pub fn chunk_data(&self) -> Result<ChunkData, AnvilError> {
+        Ok(ChunkData {
+            heightmaps: self.heightmaps.clone(),
+            sections: self.sections.clone(),
+            block_entities: self
+                .block_entities
+                .iter()
+                .map(block_entity_from_nbt)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Sets the block state at an absolute position. Positions outside the
+    /// chunk are ignored.
+    pub fn set_block_state(
+        &mut self,
+        position: BlockPos,
+        block_state: i32,
+    ) {
+        let (x, y, z) = position.in_section();
+        if position.chunk() == self.pos
+            && let Some(section) = usize::try_from((position.y >> 4) - self.min_section)
+                .ok()
+                .and_then(|index| self.sections.get_mut(index))
+        {
+            section.set_block_state(x, y, z, block_state);
+        }
+    }
+
+    /// Updates a block entity as sent in `block_entity_data`. Without data,
+    /// a block entity already there is kept as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if `kind` is not a known block entity type.
+    pub fn set_block_entity(
+        &mut self,
+        position: BlockPos,
+        kind: i32,
+        data: Option<&Nbt>,
+    ) -> Result<(), AnvilError> {
+        let nbt = block_entity_to_nbt(kind, position, data)?;
+        let existing = self
+            .block_entities
+            .iter()
+            .position(|entity| block_entity_position(entity) == Some(position));
+        match existing {
+            Some(_) if data.is_none() => {}
+            Some(index) => self.block_entities[index] = nbt,
+            None => self.block_entities.push(nbt),
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a required tag is missing, or a palette
+    /// holds an unknown block state or biome.
+    pub fn from_nbt(nbt: &Nbt) -> Result<Self, AnvilError> {
+        let int = |key: &str| {
+            nbt.get(key)
+                .and_then(Nbt::as_i64)
+                .and_then(|value| i32::try_from(value).ok())
+        };
+        let (Some(x), Some(z)) = (int("xPos"), int("zPos")) else {
+            return Err(invalid("missing xPos or zPos"));
+        };
+        let min_section = int("yPos").unwrap_or(-4);
+
+        let (sections, light) = read_sections(
+            min_section,
+            nbt.get("sections")
+                .and_then(Nbt::as_list)
+                .unwrap_or_default(),
+        )?;
+        let heightmaps = nbt.get("Heightmaps").map_or_else(Vec::new, |heightmaps| {
+            HEIGHTMAPS
+                .iter()
+                .filter_map(|(kind, name)| {
+                    Some(Heightmap {
+                        kind: *kind,
+                        data: longs_from_nbt(heightmaps.get(name)?.as_long_array()?),
+                    })
+                })
+                .collect()
+        });
+
+        Ok(Self {
+            pos: ChunkPos {
+                x,
+                z,
+            },
+            min_section,
+            data_version: int("DataVersion").unwrap_or_default(),
+            status: nbt
+                .get("Status")
+                .and_then(Nbt::as_str)
+                .unwrap_or("minecraft:empty")
+                .to_owned(),
+            last_update: nbt
+                .get("LastUpdate")
+                .and_then(Nbt::as_i64)
+                .unwrap_or_default(),
+            inhabited_time: nbt
+                .get("InhabitedTime")
+                .and_then(Nbt::as_i64)
+                .unwrap_or_default(),
+            sections,
+            heightmaps,
+            block_entities: nbt
+                .get("block_entities")
+                .and_then(Nbt::as_list)
+                .unwrap_or_default()
+                .to_vec(),
+            light,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a container holds an id that is not a known
+    /// block state or biome.
+    pub fn to_nbt(&self) -> Result<Nbt, AnvilError> {
+        self.to_nbt_with_biomes(|id| Biome::from_id(id).map(|biome| biome.identifier().to_owned()))
+    }
+
+    /// Like [`DiskChunk::to_nbt`], naming the biome ids with `biome`, for
+    /// servers whose biome registry is not the vanilla one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a container holds an id that is not a known
+    /// block state, or a biome id `biome` does not name.
+    pub fn to_nbt_with_biomes(
+        &self,
+        biome: impl Fn(i32) -> Option<String>,
+    ) -> Result<Nbt, AnvilError> {
+        let mut sections = Vec::new();
+        // light sections reach one section past the blocks at both ends
+        for (index, y) in (self.min_section - 1..)
+            .take(self.sections.len() + 2)
+            .enumerate()
+        {
+            let mut section = Nbt::compound();
+            section.insert("Y", Nbt::Byte(i8::try_from(y).unwrap_or_default()));
+            if let Some(blocks) = index
+                .checked_sub(1)
+                .and_then(|index| self.sections.get(index))
+            {
+                section.insert("block_states", block_states_to_nbt(&blocks.block_states)?);
+                section.insert("biomes", biomes_to_nbt(&blocks.biomes, &biome)?);
+            }
+            if let Some(light) = self.light.block_light(index) {
+                section.insert("BlockLight", nibbles_to_nbt(light));
+            }
+            if let Some(light) = self.light.sky_light(index) {
+                section.insert("SkyLight", nibbles_to_nbt(light));
+            }
+            if section
+                .as_compound()
+                .is_some_and(|entries| entries.len() > 1)
+            {
+                sections.push(section);
+            }
+        }
+
+        let mut heightmaps = Nbt::compound();
+        for heightmap in &self.heightmaps {
+            if let Some((_, name)) = HEIGHTMAPS.iter().find(|(kind, _)| *kind == heightmap.kind) {
+                heightmaps.insert(*name, longs_to_nbt(&heightmap.data));
+            }
+        }
+
+        let mut nbt = Nbt::compound();
+        nbt.insert("DataVersion", Nbt::Int(self.data_version));
+        nbt.insert("xPos", Nbt::Int(self.pos.x));
+        nbt.insert("yPos", Nbt::Int(self.min_section));
+        nbt.insert("zPos", Nbt::Int(self.pos.z));
+        nbt.insert("Status", Nbt::String(self.status.clone()));
+        nbt.insert("LastUpdate", Nbt::Long(self.last_update));
+        nbt.insert("InhabitedTime", Nbt::Long(self.inhabited_time));
+        nbt.insert("isLightOn", Nbt::Byte(1));
+        nbt.insert("sections", Nbt::List {
+            element_type: Nbt::compound().tag_type(),
+            elements: sections,
+        });
+        nbt.insert("Heightmaps", heightmaps);
+        nbt.insert("block_entities", Nbt::List {
+            element_type: Nbt::compound().tag_type(),
+            elements: self.block_entities.clone(),
+        });
+        Ok(nbt)
+    }
+}
+
+fn invalid(message: impl Into<String>) -> AnvilError { AnvilError::InvalidChunk(message.into()) }
+
+/// A block entity as saved: its id, absolute position, then its data.
+fn block_entity_to_nbt(
+    kind: i32,
+    position: BlockPos,
+    data: Option<&Nbt>,
+) -> Result<Nbt, AnvilError> {
+    let kind = BlockEntityType::from_id(kind)
+        .ok_or_else(|| invalid(format!("unknown block entity type {kind}")))?;
+    let mut nbt = Nbt::compound();
+    nbt.insert("id", Nbt::String(kind.identifier().to_owned()));
+    nbt.insert("x", Nbt::Int(position.x));
+    nbt.insert("y", Nbt::Int(position.y));
+    nbt.insert("z", Nbt::Int(position.z));
+    for (key, value) in data.and_then(Nbt::as_compound).unwrap_or_default() {
+        nbt.insert(key.clone(), value.clone());
+    }
+    Ok(nbt)
+}
+
+fn block_entity_from_nbt(nbt: &Nbt) -> Result<BlockEntity, AnvilError> {
+    let position =
+        block_entity_position(nbt).ok_or_else(|| invalid("block entity without a position"))?;
+    let id = nbt
+        .get("id")
+        .and_then(Nbt::as_str)
+        .ok_or_else(|| invalid("block entity without an id"))?;
+    let kind = id
+        .parse::<Identifier>()
+        .ok()
+        .and_then(|identifier| BlockEntityType::from_identifier(&identifier))
+        .ok_or_else(|| invalid(format!("unknown block entity {id}")))?;
+
+    let data: Vec<_> = nbt
+        .as_compound()
+        .unwrap_or_default()
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "id" | "x" | "y" | "z" | "keepPacked"))
+        .cloned()
+        .collect();
+    let (x, _, z) = position.in_section();
+    Ok(BlockEntity {
+        packed_xz: u8::try_from(x << 4 | z).unwrap_or_default(),
+        y: i16::try_from(position.y)
+            .map_err(|_| invalid(format!("block entity at y {}", position.y)))?,
+        kind: kind.id(),
+        data: (!data.is_empty()).then_some(Nbt::Compound(data)),
+    })
+}
+
+fn block_entity_position(nbt: &Nbt) -> Option<BlockPos> {
+    let int = |key: &str| {
+        nbt.get(key)
+            .and_then(Nbt::as_i64)
+            .and_then(|value| i32::try_from(value).ok())
+    };
+    Some(BlockPos::new(int("x")?, int("y")?, int("z")?))
+}
+
+/// Block sections from `min_section` up to the highest one saved, and the
+/// light of every section.
+fn read_sections(
+    min_section: i32,
+    entries: &[Nbt],
+) -> Result<(Vec<Section>, LightData), AnvilError> {
+    let mut sections = Vec::new();
+    let mut sky = Vec::new();
+    let mut block = Vec::new();
+    for entry in entries {
+        let y = entry
+            .get("Y")
+            .and_then(Nbt::as_i64)
+            .ok_or_else(|| invalid("section without Y"))?;
+        let index = usize::try_from(y - i64::from(min_section) + 1)
+            .map_err(|_| invalid(format!("section {y} is below the world")))?;
+        if let Some(light) = entry.get("SkyLight").and_then(nibbles_from_nbt) {
+            sky.push((index, light));
+        }
+        if let Some(light) = entry.get("BlockLight").and_then(nibbles_from_nbt) {
+            block.push((index, light));
+        }
+        if let (Some(index), Some(block_states)) = (index.checked_sub(1), entry.get("block_states"))
+        {
+            let section = read_section(block_states, entry.get("biomes"))
+                .map_err(|err| err.context(format!("Failed to read section {y}")))?;
+            sections.push((index, section));
+        }
+    }
+
+    sections.sort_by_key(|(index, _)| *index);
+    let count = sections.last().map_or(0, |(index, _)| index + 1);
+    let mut filled = vec![None; count];
+    for (index, section) in sections {
+        filled[index] = Some(section);
+    }
+    let sections = filled
+        .into_iter()
+        .map(|section| {
+            section.unwrap_or_else(|| Section::filled(BlockState::AIR, Biome::Plains.id()))
+        })
+        .collect();
+
+    let (sky_y_mask, sky_updates) = light_mask(sky);
+    let (block_y_mask, block_updates) = light_mask(block);
+    Ok((sections, LightData {
+        sky_y_mask,
+        block_y_mask,
+        empty_sky_y_mask: Vec::new(),
+        empty_block_y_mask: Vec::new(),
+        sky_updates,
+        block_updates,
+    }))
+}
+
+fn read_section(
+    block_states: &Nbt,
+    biomes: Option<&Nbt>,
+) -> Result<Section, AnvilError> {
+    let states = read_container(block_states, ContainerKind::BlockStates, |entry| {
+        block_state_from_nbt(entry).map(BlockState::id)
+    })?;
+    let block_count = states
+        .iter()
+        .filter(|id| !BlockState::from_id(**id).is_some_and(BlockState::is_air))
+        .count();
+    let biomes = match biomes {
+        Some(biomes) => read_container(biomes, ContainerKind::Biomes, |entry| {
+            let name = entry
+                .as_str()
+                .ok_or_else(|| invalid("biome is not a string"))?;
+            name.parse::<Identifier>()
+                .ok()
+                .and_then(|identifier| Biome::from_identifier(&identifier))
+                .map(Biome::id)
+                .ok_or_else(|| invalid(format!("unknown biome {name}")))
+        })?,
+        None => vec![Biome::Plains.id(); ContainerKind::Biomes.size()],
+    };
+
+    Ok(Section {
+        block_count: i16::try_from(block_count).unwrap_or(i16::MAX),
+        block_states: PalettedContainer::from_values(ContainerKind::BlockStates, &states),
+        biomes: PalettedContainer::from_values(ContainerKind::Biomes, &biomes),
+    })
+}
+
+/// Ids of every entry of a `{palette, data}` compound.
+fn read_container(
+    nbt: &Nbt,
+    kind: ContainerKind,
+    id: impl Fn(&Nbt) -> Result<i32, AnvilError>,
+) -> Result<Vec<i32>, AnvilError> {
+    let palette = nbt
+        .get("palette")
+        .and_then(Nbt::as_list)
+        .filter(|palette| !palette.is_empty())
+        .ok_or_else(|| invalid("missing palette"))?
+        .iter()
+        .map(id)
+        .collect::<Result<Vec<_>, _>>()?;
+    if let [id] = palette[..] {
+        return Ok(vec![id; kind.size()]);
+    }
+
+    let bits = disk_bits(kind, palette.len());
+    let per_long = 64 / bits;
+    let data = nbt
+        .get("data")
+        .and_then(Nbt::as_long_array)
+        .filter(|data| data.len() == kind.size().div_ceil(per_long))
+        .ok_or_else(|| invalid(format!("data does not hold {bits}-bit entries")))?;
+    (0..kind.size())
+        .map(|index| {
+            let long = data[index / per_long].cast_unsigned();
+            let entry = long >> (index % per_long * bits) & ((1 << bits) - 1);
+            usize::try_from(entry)
+                .ok()
+                .and_then(|entry| palette.get(entry))
+                .copied()
+                .ok_or_else(|| invalid(format!("entry {entry} is past the palette")))
+        })
+        .collect()
+}
+
+/// Bits per entry on disk, where palettes are never replaced by raw ids.
+fn disk_bits(
+    kind: ContainerKind,
+    palette_len: usize,
+) -> usize {
+    let bits = (usize::BITS - (palette_len - 1).leading_zeros()) as usize;
+    match kind {
+        ContainerKind::BlockStates => bits.max(4),
+        ContainerKind::Biomes => bits,
+    }
+}
+
+/// A `{palette, data}` compound of the ids of a container.
+fn write_container(
+    container: &PalettedContainer,
+    entry: impl Fn(i32) -> Result<Nbt, AnvilError>,
+) -> Result<Nbt, AnvilError> {
+    let mut palette = Vec::new();
+    let mut indices = Vec::with_capacity(container.kind().size());
+    for id in container.iter() {
+        let index = palette
+            .iter()
+            .position(|other| *other == id)
+            .unwrap_or_else(|| {
+                palette.push(id);
+                palette.len() - 1
+            });
+        indices.push(index as u64);
+    }
+
+    let mut nbt = Nbt::compound();
+    nbt.insert(
+        "palette",
+        Nbt::list(
+            palette
+                .iter()
+                .map(|id| entry(*id))
+                .collect::<Result<_, _>>()?,
+        ),
+    );
+    if palette.len() > 1 {
+        let bits = disk_bits(container.kind(), palette.len());
+        let per_long = 64 / bits;
+        let mut data = vec![0_i64; indices.len().div_ceil(per_long)];
+        for (index, entry) in indices.into_iter().enumerate() {
+            data[index / per_long] |= (entry << (index % per_long * bits)).cast_signed();
+        }
+        nbt.insert("data", Nbt::LongArray(data));
+    }
+    Ok(nbt)
+}
+
+fn block_states_to_nbt(container: &PalettedContainer) -> Result<Nbt, AnvilError> {
+    write_container(container, |id| {
+        let state =
+            BlockState::from_id(id).ok_or_else(|| invalid(format!("unknown block state {id}")))?;
+        let mut nbt = Nbt::compound();
+        nbt.insert("Name", Nbt::String(state.block().identifier().to_owned()));
+        let mut properties = Nbt::compound();
+        for (name, value) in state.properties() {
+            properties.insert(name, Nbt::String(value.to_owned()));
+        }
+        if properties
+            .as_compound()
+            .is_some_and(|entries| !entries.is_empty())
+        {
+            nbt.insert("Properties", properties);
+        }
+        Ok(nbt)
+    })
+}
+
+fn biomes_to_nbt(
+    container: &PalettedContainer,
+    biome: impl Fn(i32) -> Option<String>,
+) -> Result<Nbt, AnvilError> {
+    write_container(container, |id| {
+        biome(id)
+            .map(Nbt::String)
+            .ok_or_else(|| invalid(format!("unknown biome {id}")))
+    })
+}
+
+fn block_state_from_nbt(nbt: &Nbt) -> Result<BlockState, AnvilError> {
+    let name = nbt
+        .get("Name")
+        .and_then(Nbt::as_str)
+        .ok_or_else(|| invalid("block state without a name"))?;
+    let block = name
+        .parse::<Identifier>()
+        .ok()
+        .and_then(|identifier| Block::from_identifier(&identifier))
+        .ok_or_else(|| invalid(format!("unknown block {name}")))?;
+
+    let mut state = block.default_state();
+    let properties = nbt.get("Properties").and_then(Nbt::as_compound);
+    for (property, value) in properties.unwrap_or_default() {
+        state = value
+            .as_str()
+            .and_then(|value| state.with(property, value))
+            .ok_or_else(|| invalid(format!("invalid property {property} of {name}")))?;
+    }
+    Ok(state)
+}
+
+/// Light masks and nibble arrays from `(light section, nibbles)` pairs.
+fn light_mask(mut sections: Vec<(usize, Vec<u8>)>) -> (Vec<u64>, Vec<Vec<u8>>) {
+    sections.sort_by_key(|(index, _)| *index);
+    let mut mask = Vec::new();
+    let mut updates = Vec::new();
+    for (index, nibbles) in sections {
+        if mask.len() <= index / 64 {
+            mask.resize(index / 64 + 1, 0);
+        }
+        mask[index / 64] |= 1 << (index % 64);
+        updates.push(nibbles);
+    }
+    (mask, updates)
+}
+
+fn nibbles_from_nbt(nbt: &Nbt) -> Option<Vec<u8>> {
+    match nbt {
+        Nbt::ByteArray(bytes) if bytes.len() == 2048 => {
+            Some(bytes.iter().map(|byte| byte.cast_unsigned()).collect())
+        }
+        _ => None,
+    }
+}
+
+fn nibbles_to_nbt(nibbles: &[u8]) -> Nbt {
+    Nbt::ByteArray(nibbles.iter().map(|byte| byte.cast_signed()).collect())
+}
+
+fn longs_from_nbt(longs: &[i64]) -> Vec<u64> {
+    longs.iter().map(|long| long.cast_unsigned()).collect()
+}
+
+fn longs_to_nbt(longs: &[u64]) -> Nbt {
+    Nbt::LongArray(longs.iter().map(|long| long.cast_signed()).collect())
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network_chunk() -> (ChunkData, LightData) {
+        let mut ground = Section::filled(Block::Stone.default_state(), Biome::Plains.id());
+        let log = Block::OakLog.default_state().with("axis", "x").unwrap();
+        ground.set_block_state(1, 2, 3, log.id());
+        ground.set_block_state(4, 5, 6, BlockState::AIR.id());
+        ground.block_count = 4095;
+        for index in 0..64 {
+            ground
+                .biomes
+                .set(index, [Biome::Plains, Biome::Forest][index % 2].id());
+        }
+        let sky = LightData {
+            sky_y_mask: vec![0b1001],
+            block_y_mask: vec![0b10],
+            empty_sky_y_mask: Vec::new(),
+            empty_block_y_mask: Vec::new(),
+            sky_updates: vec![vec![0xFF; 2048], vec![0x12; 2048]],
+            block_updates: vec![vec![0x34; 2048]],
+        };
+        let network = ChunkData {
+            heightmaps: vec![Heightmap {
+                kind: HeightmapKind::MotionBlocking,
+                data: vec![u64::MAX; 37],
+            }],
+            sections: vec![ground, Section::filled(BlockState::AIR, Biome::Plains.id())],
+            block_entities: vec![BlockEntity {
+                packed_xz: 0x2F,
+                y: -60,
+                kind: BlockEntityType::Sign.id(),
+                data: Some(sign_text("hello")),
+            }],
+        };
+        (network, sky)
+    }
+
+    fn sign_text(text: &str) -> Nbt {
+        let mut data = Nbt::compound();
+        data.insert("text", Nbt::String(text.to_owned()));
+        data
+    }
+
+    fn chunk() -> DiskChunk {
+        let (network, light) = network_chunk();
+        DiskChunk::from_network(
+            ChunkPos {
+                x: 3,
+                z: -7,
+            },
+            -4,
+            &network,
+            &light,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn nbt_roundtrip() {
+        let chunk = chunk();
+        let nbt = chunk.to_nbt().unwrap();
+        assert_eq!(nbt.get("yPos").and_then(Nbt::as_i64), Some(-4));
+
+        let sections = nbt.get("sections").and_then(Nbt::as_list).unwrap();
+        // light below the world, two block sections, light above them
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[0].get("Y").and_then(Nbt::as_i64), Some(-5));
+        assert!(
+            sections[0].get("block_states").is_none(),
+            "the section below the world only holds light"
+        );
+        let palette = sections[1]
+            .get("block_states")
+            .and_then(|states| states.get("palette"))
+            .and_then(Nbt::as_list)
+            .unwrap();
+        assert_eq!(palette.len(), 3);
+        assert_eq!(
+            palette[1]
+                .get("Properties")
+                .and_then(|properties| properties.get("axis")),
+            Some(&Nbt::String("x".to_owned()))
+        );
+
+        let decoded = DiskChunk::from_nbt(&nbt).unwrap();
+        assert_eq!(
+            decoded.sections[0].block_state(1, 2, 3),
+            chunk.sections[0].block_state(1, 2, 3)
+        );
+        assert_eq!(decoded.sections[0].biome(1, 0, 0), Biome::Forest.id());
+        assert_eq!(decoded, chunk);
+        assert_eq!(decoded.to_nbt().unwrap(), nbt);
+    }
+
+    #[test]
+    fn custom_biomes() {
+        let chunk = chunk();
+        let names = |id| (id == Biome::Plains.id()).then(|| "lobby:void".to_owned());
+        assert!(
+            chunk.to_nbt_with_biomes(names).is_err(),
+            "every biome id should be named"
+        );
+
+        let nbt = chunk
+            .to_nbt_with_biomes(|id| Some(format!("lobby:biome_{id}")))
+            .unwrap();
+        let sections = nbt.get("sections").and_then(Nbt::as_list).unwrap();
+        let palette = sections[2]
+            .get("biomes")
+            .and_then(|biomes| biomes.get("palette"))
+            .and_then(Nbt::as_list)
+            .unwrap();
+        assert_eq!(palette, [Nbt::String(format!(
+            "lobby:biome_{}",
+            Biome::Plains.id()
+        ))]);
+    }
+
+    #[test]
+    fn unknown_block() {
+        let mut nbt = chunk().to_nbt().unwrap();
+        let mut state = Nbt::compound();
+        state.insert("Name", Nbt::String("minecraft:not_a_block".to_owned()));
+        let mut block_states = Nbt::compound();
+        block_states.insert("palette", Nbt::list(vec![state]));
+        let mut section = Nbt::compound();
+        section.insert("Y", Nbt::Byte(0));
+        section.insert("block_states", block_states);
+        nbt.insert("sections", Nbt::list(vec![section]));
+        assert!(
+            DiskChunk::from_nbt(&nbt).is_err(),
+            "unknown blocks should be rejected"
+        );
+    }
+
+    #[test]
+    fn block_entities() {
+        let mut chunk = chunk();
+        let sign = &chunk.block_entities[0];
+        assert_eq!(sign.get("id").and_then(Nbt::as_str), Some("minecraft:sign"));
+        assert_eq!(sign.get("x").and_then(Nbt::as_i64), Some(50));
+        assert_eq!(sign.get("z").and_then(Nbt::as_i64), Some(-97));
+        assert_eq!(chunk.chunk_data().unwrap(), network_chunk().0);
+
+        let position = BlockPos::new(50, -60, -97);
+        chunk
+            .set_block_entity(position, BlockEntityType::Sign.id(), None)
+            .unwrap();
+        assert_eq!(
+            chunk.chunk_data().unwrap(),
+            network_chunk().0,
+            "no data keeps the sign"
+        );
+        chunk
+            .set_block_entity(
+                position,
+                BlockEntityType::Sign.id(),
+                Some(&sign_text("bye")),
+            )
+            .unwrap();
+        chunk
+            .set_block_entity(BlockPos::new(48, 0, -112), BlockEntityType::Bell.id(), None)
+            .unwrap();
+        assert_eq!(chunk.block_entities.len(), 2);
+        assert_eq!(
+            chunk.block_entities[0].get("text"),
+            Some(&Nbt::String("bye".to_owned()))
+        );
+        assert!(
+            chunk.set_block_entity(position, 1000, None).is_err(),
+            "unknown block entity types should be rejected"
+        );
+
+        chunk.set_block_state(position, BlockState::AIR.id());
+        assert_eq!(
+            chunk.sections[0].block_state(2, 4, 15),
+            BlockState::AIR.id()
+        );
+        // outside the chunk
+        chunk.set_block_state(BlockPos::new(0, -60, 0), BlockState::AIR.id());
+    }
+}
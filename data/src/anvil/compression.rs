@@ -0,0 +1,244 @@
+//! Compression schemes of chunk payloads.
+
+use std::io::{
+    self,
+    Read as _,
+    Write as _,
+};
+
+use flate2::read::{
+    GzDecoder,
+    ZlibDecoder,
+};
+use flate2::write::{
+    GzEncoder,
+    ZlibEncoder,
+};
+use twox_hash::XxHash32;
+
+/// Compression of a chunk payload, stored in the byte before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    Gzip = 1,
+    /// What vanilla writes unless configured otherwise.
+    #[default]
+    Zlib = 2,
+    None = 3,
+    /// lz4-java block streams, available since 1.20.5.
+    Lz4 = 4,
+}
+
+impl Compression {
+    #[must_use]
+    pub const fn id(self) -> u8 { self as u8 }
+
+    #[must_use]
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Gzip),
+            2 => Some(Self::Zlib),
+            3 => Some(Self::None),
+            4 => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if `data` is not validly compressed.
+    pub fn decompress(
+        self,
+        data: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        let mut output = Vec::new();
+        match self {
+            Self::Gzip => {
+                GzDecoder::new(data).read_to_end(&mut output)?;
+            }
+            Self::Zlib => {
+                ZlibDecoder::new(data).read_to_end(&mut output)?;
+            }
+            Self::None => output.extend_from_slice(data),
+            Self::Lz4 => output = lz4_decompress(data)?,
+        }
+        Ok(output)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the encoder fails.
+    pub fn compress(
+        self,
+        data: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::None => Ok(data.to_vec()),
+            Self::Lz4 => Ok(lz4_compress(data)),
+        }
+    }
+}
+
+const LZ4_MAGIC: &[u8; 8] = b"LZ4Block";
+const LZ4_HEADER_LEN: usize = 21;
+const LZ4_RAW: u8 = 0x10;
+const LZ4_COMPRESSED: u8 = 0x20;
+/// The 64 KiB blocks of lz4-java, whose level is `log2(size) - 10`.
+const LZ4_BLOCK_SIZE: usize = 1 << 16;
+const LZ4_LEVEL: u8 = 6;
+const LZ4_SEED: u32 = 0x9747_B28C;
+
+/// xxHash32 of a block, truncated to 28 bits like lz4-java does.
+fn lz4_checksum(block: &[u8]) -> u32 { XxHash32::oneshot(LZ4_SEED, block) & 0x0FFF_FFFF }
+
+fn invalid(message: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, message) }
+
+fn le_u32(
+    bytes: &[u8],
+    at: usize,
+) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+/// Reads blocks until the empty end block or the end of `data`.
+fn lz4_decompress(mut data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    while !data.is_empty() {
+        let (header, rest) = data
+            .split_at_checked(LZ4_HEADER_LEN)
+            .ok_or_else(|| invalid("truncated LZ4 block header"))?;
+        if &header[..8] != LZ4_MAGIC {
+            return Err(invalid("invalid LZ4 block magic"));
+        }
+        let method = header[8] & 0xF0;
+        let compressed_len = le_u32(header, 9) as usize;
+        let original_len = le_u32(header, 13) as usize;
+        let checksum = le_u32(header, 17);
+        if original_len == 0 {
+            break;
+        }
+
+        let (block, rest) = rest
+            .split_at_checked(compressed_len)
+            .ok_or_else(|| invalid("truncated LZ4 block"))?;
+        let block = match method {
+            LZ4_RAW => block.to_vec(),
+            LZ4_COMPRESSED => lz4_flex::block::decompress(block, original_len)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            _ => return Err(invalid("unknown LZ4 block method")),
+        };
+        if block.len() != original_len {
+            return Err(invalid("LZ4 block length mismatch"));
+        }
+        if lz4_checksum(&block) != checksum {
+            return Err(invalid("LZ4 block checksum mismatch"));
+        }
+        output.extend_from_slice(&block);
+        data = rest;
+    }
+    Ok(output)
+}
+
+fn lz4_compress(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    for block in data.chunks(LZ4_BLOCK_SIZE) {
+        let compressed = lz4_flex::block::compress(block);
+        let (method, payload) = if compressed.len() < block.len() {
+            (LZ4_COMPRESSED, compressed.as_slice())
+        } else {
+            (LZ4_RAW, block)
+        };
+        lz4_header(&mut output, method, payload.len(), block);
+        output.extend_from_slice(payload);
+    }
+    lz4_header(&mut output, LZ4_RAW, 0, &[]);
+    output
+}
+
+fn lz4_header(
+    output: &mut Vec<u8>,
+    method: u8,
+    compressed_len: usize,
+    block: &[u8],
+) {
+    #[allow(
+        clippy::cast_possible_truncation,
+        reason = "blocks and their payloads are at most 64 KiB"
+    )]
+    let (compressed_len, original_len) = (compressed_len as u32, block.len() as u32);
+    let checksum = if block.is_empty() {
+        0
+    } else {
+        lz4_checksum(block)
+    };
+    output.extend_from_slice(LZ4_MAGIC);
+    output.push(method | LZ4_LEVEL);
+    output.extend_from_slice(&compressed_len.to_le_bytes());
+    output.extend_from_slice(&original_len.to_le_bytes());
+    output.extend_from_slice(&checksum.to_le_bytes());
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<u8> {
+        // compressible, and long enough for several LZ4 blocks
+        (0..200_000_u32)
+            .flat_map(|i| (i / 7).to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let data = sample();
+        for compression in [
+            Compression::Gzip,
+            Compression::Zlib,
+            Compression::None,
+            Compression::Lz4,
+        ] {
+            let compressed = compression.compress(&data).unwrap();
+            assert_eq!(
+                compression.decompress(&compressed).unwrap(),
+                data,
+                "{compression:?} should roundtrip"
+            );
+            assert_eq!(Compression::from_id(compression.id()), Some(compression));
+        }
+    }
+
+    #[test]
+    fn lz4_blocks() {
+        let compressed = lz4_compress(&sample());
+        assert_eq!(&compressed[..8], LZ4_MAGIC);
+        assert_eq!(compressed[8], LZ4_COMPRESSED | LZ4_LEVEL);
+        assert_eq!(le_u32(&compressed, 13) as usize, LZ4_BLOCK_SIZE);
+        // the stream ends with an empty raw block
+        let end = &compressed[compressed.len() - LZ4_HEADER_LEN..];
+        assert_eq!(end[8], LZ4_RAW | LZ4_LEVEL);
+        assert_eq!(&end[9..], &[0; 12]);
+
+        // incompressible data is stored raw
+        let compressed = lz4_compress(&[1, 2, 3]);
+        assert_eq!(compressed[8], LZ4_RAW | LZ4_LEVEL);
+        assert_eq!(&compressed[LZ4_HEADER_LEN..LZ4_HEADER_LEN + 3], &[1, 2, 3]);
+
+        let mut corrupted = compressed.clone();
+        corrupted[LZ4_HEADER_LEN] = 4;
+        assert!(
+            lz4_decompress(&corrupted).is_err(),
+            "a checksum mismatch should be rejected"
+        );
+    }
+}
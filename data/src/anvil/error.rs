@@ -0,0 +1,71 @@
+use core::{
+    error,
+    fmt,
+};
+use std::io;
+
+use codec::dec::DecodeError;
+use codec::enc::EncodeError;
+
+#[derive(Debug)]
+pub enum AnvilError {
+    Context {
+        context: String,
+        error: Box<AnvilError>,
+    },
+    Io(io::Error),
+    Decode(DecodeError),
+    Encode(EncodeError),
+    /// The region header or a chunk header points outside the file.
+    InvalidRegion(String),
+    UnknownCompression(u8),
+    /// The chunk NBT does not describe a valid chunk.
+    InvalidChunk(String),
+}
+
+impl AnvilError {
+    #[must_use]
+    pub fn context(
+        self,
+        context: impl Into<String>,
+    ) -> Self {
+        Self::Context {
+            context: context.into(),
+            error: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for AnvilError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Context {
+                context,
+                error,
+            } => write!(f, "{context}: {error}"),
+            Self::Io(err) => write!(f, "IO error: {err}"),
+            Self::Decode(err) => write!(f, "Decode error: {err}"),
+            Self::Encode(err) => write!(f, "Encode error: {err}"),
+            Self::InvalidRegion(message) => write!(f, "Invalid region file: {message}"),
+            Self::UnknownCompression(id) => write!(f, "Unknown chunk compression: {id}"),
+            Self::InvalidChunk(message) => write!(f, "Invalid chunk: {message}"),
+        }
+    }
+}
+
+impl error::Error for AnvilError {}
+
+impl From<io::Error> for AnvilError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl From<DecodeError> for AnvilError {
+    fn from(err: DecodeError) -> Self { Self::Decode(err) }
+}
+
+impl From<EncodeError> for AnvilError {
+    fn from(err: EncodeError) -> Self { Self::Encode(err) }
+}
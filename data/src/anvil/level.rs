@@ -0,0 +1,159 @@
+//! `level.dat`, the world settings saved next to the region directories.
+
+use std::fs;
+use std::path::Path;
+
+use codec::nbt::Nbt;
+
+use super::{
+    AnvilError,
+    Compression,
+    DATA_VERSION,
+};
+use crate::world::BlockPos;
+
+/// Version of the `level.dat` layout, unchanged since Anvil was introduced.
+const ANVIL_VERSION: i32 = 19133;
+
+/// Vanilla dimensions, with the biome their void generator fills them with.
+const DIMENSIONS: [(&str, &str); 3] = [
+    ("minecraft:overworld", "minecraft:plains"),
+    ("minecraft:the_nether", "minecraft:nether_wastes"),
+    ("minecraft:the_end", "minecraft:the_end"),
+];
+
+/// Settings of a saved world.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelData {
+    pub name: String,
+    pub game_mode: u8,
+    pub hardcore: bool,
+    pub spawn: BlockPos,
+    pub seed: i64,
+    /// Last time the world was played, in milliseconds since the Unix epoch.
+    pub last_played: i64,
+}
+
+impl LevelData {
+    /// The root compound. Every vanilla dimension gets a void generator, so
+    /// the world does not grow past what was saved.
+    #[must_use]
+    pub fn to_nbt(&self) -> Nbt {
+        let mut version = Nbt::compound();
+        version.insert("Id", Nbt::Int(DATA_VERSION));
+        version.insert("Name", Nbt::String("1.21.8".to_owned()));
+        version.insert("Series", Nbt::String("main".to_owned()));
+        version.insert("Snapshot", Nbt::Byte(0));
+
+        let mut dimensions = Nbt::compound();
+        for (dimension, biome) in DIMENSIONS {
+            dimensions.insert(dimension, void_dimension(dimension, biome));
+        }
+        let mut world_gen = Nbt::compound();
+        world_gen.insert("seed", Nbt::Long(self.seed));
+        world_gen.insert("generate_features", Nbt::Byte(0));
+        world_gen.insert("bonus_chest", Nbt::Byte(0));
+        world_gen.insert("dimensions", dimensions);
+
+        let mut data_packs = Nbt::compound();
+        data_packs.insert(
+            "Enabled",
+            Nbt::list(vec![Nbt::String("vanilla".to_owned())]),
+        );
+        data_packs.insert("Disabled", Nbt::List {
+            element_type: Nbt::String(String::new()).tag_type(),
+            elements: Vec::new(),
+        });
+
+        let mut data = Nbt::compound();
+        data.insert("DataVersion", Nbt::Int(DATA_VERSION));
+        data.insert("version", Nbt::Int(ANVIL_VERSION));
+        data.insert("Version", version);
+        data.insert("LevelName", Nbt::String(self.name.clone()));
+        data.insert("GameType", Nbt::Int(i32::from(self.game_mode)));
+        data.insert("hardcore", Nbt::Byte(i8::from(self.hardcore)));
+        data.insert("allowCommands", Nbt::Byte(1));
+        data.insert("initialized", Nbt::Byte(1));
+        data.insert("LastPlayed", Nbt::Long(self.last_played));
+        data.insert("SpawnX", Nbt::Int(self.spawn.x));
+        data.insert("SpawnY", Nbt::Int(self.spawn.y));
+        data.insert("SpawnZ", Nbt::Int(self.spawn.z));
+        data.insert("WorldGenSettings", world_gen);
+        data.insert("DataPacks", data_packs);
+
+        let mut root = Nbt::compound();
+        root.insert("Data", data);
+        root
+    }
+
+    /// Writes `level.dat` into a world directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if the file cannot be written.
+    pub fn write(
+        &self,
+        dir: &Path,
+    ) -> Result<(), AnvilError> {
+        let mut nbt = Vec::new();
+        self.to_nbt().encode_named("", &mut nbt)?;
+        fs::write(dir.join("level.dat"), Compression::Gzip.compress(&nbt)?)?;
+        Ok(())
+    }
+}
+
+/// A flat generator without layers.
+fn void_dimension(
+    dimension: &str,
+    biome: &str,
+) -> Nbt {
+    let mut settings = Nbt::compound();
+    settings.insert("biome", Nbt::String(biome.to_owned()));
+    settings.insert("layers", Nbt::List {
+        element_type: Nbt::compound().tag_type(),
+        elements: Vec::new(),
+    });
+    settings.insert("features", Nbt::Byte(0));
+    settings.insert("lakes", Nbt::Byte(0));
+
+    let mut generator = Nbt::compound();
+    generator.insert("type", Nbt::String("minecraft:flat".to_owned()));
+    generator.insert("settings", settings);
+
+    let mut nbt = Nbt::compound();
+    nbt.insert("type", Nbt::String(dimension.to_owned()));
+    nbt.insert("generator", generator);
+    nbt
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_nbt() {
+        let level = LevelData {
+            name: "Archive".to_owned(),
+            game_mode: 1,
+            hardcore: false,
+            spawn: BlockPos::new(10, 64, -3),
+            seed: 42,
+            last_played: 0,
+        };
+        let nbt = level.to_nbt();
+        let data = nbt.get("Data").unwrap();
+        assert_eq!(data.get("LevelName").and_then(Nbt::as_str), Some("Archive"));
+        assert_eq!(data.get("SpawnY").and_then(Nbt::as_i64), Some(64));
+        let generator = data
+            .get("WorldGenSettings")
+            .and_then(|settings| settings.get("dimensions"))
+            .and_then(|dimensions| dimensions.get("minecraft:the_nether"))
+            .and_then(|nether| nether.get("generator"))
+            .unwrap();
+        assert_eq!(
+            generator.get("type").and_then(Nbt::as_str),
+            Some("minecraft:flat")
+        );
+    }
+}
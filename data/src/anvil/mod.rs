@@ -0,0 +1,23 @@
+//! Saved worlds in the Anvil format: region files of compressed chunk NBT,
+//! the conversion of that NBT to the section model of network chunks, and
+//! `level.dat`.
+
+mod chunk;
+mod compression;
+mod error;
+mod level;
+mod region;
+
+pub use chunk::{
+    DATA_VERSION,
+    DiskChunk,
+    FULL,
+};
+pub use compression::Compression;
+pub use error::AnvilError;
+pub use level::LevelData;
+pub use region::{
+    Region,
+    RegionChunk,
+    RegionFiles,
+};
@@ -0,0 +1,410 @@
+//! Region files: 32×32 chunk columns, each compressed into 4 KiB sectors,
+//! after a table of locations and one of timestamps.
+
+use std::path::Path;
+use std::{
+    fs,
+    io,
+};
+
+use codec::nbt::Nbt;
+
+use super::{
+    AnvilError,
+    Compression,
+};
+use crate::world::ChunkPos;
+
+const SECTOR: usize = 4096;
+const CHUNKS: usize = 1024;
+const HEADER_LEN: usize = 2 * SECTOR;
+/// Length and compression bytes in front of each payload.
+const CHUNK_HEADER_LEN: usize = 5;
+/// Bit of the compression byte marking a payload stored in a `.mcc` file.
+const EXTERNAL: u8 = 0x80;
+/// A location stores the sector count in a byte, so larger chunks go to a
+/// `.mcc` file instead.
+const MAX_SECTORS: usize = 255;
+
+/// A saved chunk column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionChunk {
+    /// Time of the last save, in seconds since the Unix epoch.
+    pub timestamp: u32,
+    pub compression: Compression,
+    /// Root compound, saved with an empty name.
+    pub nbt: Nbt,
+}
+
+/// The files an encoded region is made of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionFiles {
+    /// Contents of `r.<x>.<z>.mca`.
+    pub region: Vec<u8>,
+    /// Name and contents of a `c.<x>.<z>.mcc` file per oversized chunk.
+    pub external: Vec<(String, Vec<u8>)>,
+}
+
+/// The chunks of a region file, indexed by absolute chunk position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    x: i32,
+    z: i32,
+    chunks: Vec<Option<RegionChunk>>,
+}
+
+impl Region {
+    #[must_use]
+    pub fn new(
+        x: i32,
+        z: i32,
+    ) -> Self {
+        Self {
+            x,
+            z,
+            chunks: vec![None; CHUNKS],
+        }
+    }
+
+    #[must_use]
+    pub const fn x(&self) -> i32 { self.x }
+
+    #[must_use]
+    pub const fn z(&self) -> i32 { self.z }
+
+    /// Name of the file of the region at `x`, `z`.
+    #[must_use]
+    pub fn file_name(
+        x: i32,
+        z: i32,
+    ) -> String {
+        format!("r.{x}.{z}.mca")
+    }
+
+    /// Name of the file of an oversized chunk.
+    #[must_use]
+    pub fn external_file_name(pos: ChunkPos) -> String { format!("c.{}.{}.mcc", pos.x, pos.z) }
+
+    /// Reads `r.<x>.<z>.mca` and the `.mcc` files it refers to from a
+    /// `region` directory. A missing file reads as an empty region.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a file cannot be read or a chunk is invalid.
+    pub fn read(
+        dir: &Path,
+        x: i32,
+        z: i32,
+    ) -> Result<Self, AnvilError> {
+        let bytes = match fs::read(dir.join(Self::file_name(x, z))) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Self::from_bytes(x, z, &bytes, |name| fs::read(dir.join(name)))
+    }
+
+    /// Decodes a region file, calling `external` with the name of each
+    /// `.mcc` file it refers to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if the file or a chunk is invalid.
+    pub fn from_bytes(
+        x: i32,
+        z: i32,
+        bytes: &[u8],
+        mut external: impl FnMut(&str) -> io::Result<Vec<u8>>,
+    ) -> Result<Self, AnvilError> {
+        let mut region = Self::new(x, z);
+        // vanilla leaves empty files behind for regions it never saved to
+        if bytes.is_empty() {
+            return Ok(region);
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(AnvilError::InvalidRegion(format!(
+                "header is {} bytes long",
+                bytes.len()
+            )));
+        }
+
+        for index in 0..CHUNKS {
+            let location = be_u32(bytes, index * 4);
+            if location == 0 {
+                continue;
+            }
+            let pos = region.pos(index);
+            let chunk = read_chunk(bytes, location, be_u32(bytes, SECTOR + index * 4), || {
+                external(&Self::external_file_name(pos))
+            })
+            .map_err(|err| err.context(format!("Failed to read chunk {}, {}", pos.x, pos.z)))?;
+            region.chunks[index] = Some(chunk);
+        }
+        Ok(region)
+    }
+
+    /// Writes `r.<x>.<z>.mca` and the `.mcc` files of oversized chunks to a
+    /// `region` directory, removing `.mcc` files of chunks that now fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a chunk cannot be encoded or a file cannot
+    /// be written.
+    pub fn write(
+        &self,
+        dir: &Path,
+    ) -> Result<(), AnvilError> {
+        let files = self.to_bytes()?;
+        fs::write(dir.join(Self::file_name(self.x, self.z)), files.region)?;
+        for (pos, _) in self.chunks() {
+            let name = Self::external_file_name(pos);
+            if files.external.iter().all(|(external, _)| *external != name) {
+                match fs::remove_file(dir.join(name)) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                    _ => {}
+                }
+            }
+        }
+        for (name, bytes) in files.external {
+            fs::write(dir.join(name), bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Encodes the region, laying chunks out in index order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnvilError`] if a chunk cannot be encoded.
+    pub fn to_bytes(&self) -> Result<RegionFiles, AnvilError> {
+        let mut region = vec![0; HEADER_LEN];
+        let mut external = Vec::new();
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            let Some(chunk) = chunk else {
+                continue;
+            };
+            let pos = self.pos(index);
+            let mut nbt = Vec::new();
+            chunk.nbt.encode_named("", &mut nbt)?;
+            let payload = chunk.compression.compress(&nbt)?;
+
+            let start = region.len();
+            if (CHUNK_HEADER_LEN + payload.len()).div_ceil(SECTOR) > MAX_SECTORS {
+                region.extend_from_slice(&1_u32.to_be_bytes());
+                region.push(chunk.compression.id() | EXTERNAL);
+                external.push((Self::external_file_name(pos), payload));
+            } else {
+                region.extend_from_slice(&len_u32(payload.len() + 1).to_be_bytes());
+                region.push(chunk.compression.id());
+                region.extend_from_slice(&payload);
+            }
+            region.resize(region.len().next_multiple_of(SECTOR), 0);
+
+            let location = len_u32(start / SECTOR) << 8 | len_u32((region.len() - start) / SECTOR);
+            region[index * 4..index * 4 + 4].copy_from_slice(&location.to_be_bytes());
+            region[SECTOR + index * 4..SECTOR + index * 4 + 4]
+                .copy_from_slice(&chunk.timestamp.to_be_bytes());
+        }
+        Ok(RegionFiles {
+            region,
+            external,
+        })
+    }
+
+    /// The chunk at an absolute position, if saved in this region.
+    #[must_use]
+    pub fn get(
+        &self,
+        pos: ChunkPos,
+    ) -> Option<&RegionChunk> {
+        self.index(pos)
+            .and_then(|index| self.chunks[index].as_ref())
+    }
+
+    /// Saves a chunk, returning the one it replaces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is outside the region.
+    pub fn insert(
+        &mut self,
+        pos: ChunkPos,
+        chunk: RegionChunk,
+    ) -> Option<RegionChunk> {
+        let index = self
+            .index(pos)
+            .expect("chunk position should be inside the region");
+        self.chunks[index].replace(chunk)
+    }
+
+    pub fn remove(
+        &mut self,
+        pos: ChunkPos,
+    ) -> Option<RegionChunk> {
+        self.index(pos).and_then(|index| self.chunks[index].take())
+    }
+
+    /// Saved chunks, in index order.
+    pub fn chunks(&self) -> impl Iterator<Item = (ChunkPos, &RegionChunk)> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, chunk)| Some((self.pos(index), chunk.as_ref()?)))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.chunks.iter().all(Option::is_none) }
+
+    fn index(
+        &self,
+        pos: ChunkPos,
+    ) -> Option<usize> {
+        (pos.region() == (self.x, self.z))
+            .then(|| usize::try_from((pos.x & 31) + (pos.z & 31) * 32).unwrap_or_default())
+    }
+
+    fn pos(
+        &self,
+        index: usize,
+    ) -> ChunkPos {
+        let index = i32::try_from(index).unwrap_or_default();
+        ChunkPos {
+            x: self.x * 32 + index % 32,
+            z: self.z * 32 + index / 32,
+        }
+    }
+}
+
+fn read_chunk(
+    bytes: &[u8],
+    location: u32,
+    timestamp: u32,
+    external: impl FnOnce() -> io::Result<Vec<u8>>,
+) -> Result<RegionChunk, AnvilError> {
+    let start = (location >> 8) as usize * SECTOR;
+    let sectors = (location & 0xFF) as usize;
+    if start < HEADER_LEN || start + CHUNK_HEADER_LEN > bytes.len() {
+        return Err(AnvilError::InvalidRegion(format!(
+            "chunk starts at {start}, outside the file"
+        )));
+    }
+    let len = be_u32(bytes, start) as usize;
+    if len == 0 || start + 4 + len > bytes.len() || 4 + len > sectors * SECTOR {
+        return Err(AnvilError::InvalidRegion(format!(
+            "chunk of {len} bytes does not fit its {sectors} sectors"
+        )));
+    }
+
+    let compression_id = bytes[start + 4];
+    let compression = Compression::from_id(compression_id & !EXTERNAL)
+        .ok_or(AnvilError::UnknownCompression(compression_id))?;
+    let payload = if compression_id & EXTERNAL == 0 {
+        compression.decompress(&bytes[start + CHUNK_HEADER_LEN..start + 4 + len])?
+    } else {
+        compression.decompress(&external()?)?
+    };
+    let (_, nbt) = Nbt::decode_named(&mut payload.as_slice())?;
+    Ok(RegionChunk {
+        timestamp,
+        compression,
+        nbt,
+    })
+}
+
+fn be_u32(
+    bytes: &[u8],
+    at: usize,
+) -> u32 {
+    u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "payloads past 255 sectors are stored externally"
+)]
+const fn len_u32(len: usize) -> u32 { len as u32 }
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(
+        pos: ChunkPos,
+        compression: Compression,
+        filler: usize,
+    ) -> RegionChunk {
+        let mut nbt = Nbt::compound();
+        nbt.insert("xPos", Nbt::Int(pos.x));
+        nbt.insert("zPos", Nbt::Int(pos.z));
+        nbt.insert("filler", Nbt::ByteArray(vec![7; filler]));
+        RegionChunk {
+            timestamp: 1_700_000_000,
+            compression,
+            nbt,
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut region = Region::new(-1, 2);
+        let first = ChunkPos {
+            x: -32,
+            z: 64,
+        };
+        let last = ChunkPos {
+            x: -1,
+            z: 95,
+        };
+        let oversized = ChunkPos {
+            x: -20,
+            z: 70,
+        };
+        region.insert(first, chunk(first, Compression::Zlib, 10));
+        region.insert(last, chunk(last, Compression::Lz4, 10_000));
+        region.insert(oversized, chunk(oversized, Compression::None, 1 << 20));
+        assert_eq!(
+            region.get(ChunkPos {
+                x: 0,
+                z: 64
+            }),
+            None
+        );
+
+        let files = region.to_bytes().unwrap();
+        assert_eq!(files.region.len() % SECTOR, 0);
+        // the first chunk takes the first sector after the header
+        assert_eq!(be_u32(&files.region, 0), 2 << 8 | 1);
+        assert_eq!(be_u32(&files.region, SECTOR), 1_700_000_000);
+        assert_eq!(files.external.len(), 1);
+        assert_eq!(files.external[0].0, "c.-20.70.mcc");
+
+        let decoded = Region::from_bytes(-1, 2, &files.region, |name| {
+            assert_eq!(name, "c.-20.70.mcc");
+            Ok(files.external[0].1.clone())
+        })
+        .unwrap();
+        assert_eq!(decoded, region);
+        assert_eq!(
+            decoded.chunks().map(|(pos, _)| pos).collect::<Vec<_>>(),
+            vec![first, oversized, last]
+        );
+    }
+
+    #[test]
+    fn empty_and_invalid() {
+        let region = Region::from_bytes(0, 0, &[], |_| unreachable!()).unwrap();
+        assert!(region.is_empty(), "an empty file is an empty region");
+        assert!(
+            Region::from_bytes(0, 0, &[0; 100], |_| unreachable!()).is_err(),
+            "a truncated header should be rejected"
+        );
+
+        let mut bytes = vec![0; HEADER_LEN];
+        bytes[..4].copy_from_slice(&(5_u32 << 8 | 1).to_be_bytes());
+        assert!(
+            Region::from_bytes(0, 0, &bytes, |_| unreachable!()).is_err(),
+            "a location past the end should be rejected"
+        );
+    }
+}
@@ -0,0 +1,911 @@
+//! The Brigadier command graph sent in the `commands` packet, from which
+//! the client parses and tab completes commands.
+
+use alloc::collections::BTreeMap;
+use std::io;
+
+use codec::VarInt;
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+
+use crate::identifier::Identifier;
+use crate::json::ToJson;
+
+mod flags {
+    pub const KIND: u8 = 0x03;
+    pub const EXECUTABLE: u8 = 0x04;
+    pub const REDIRECT: u8 = 0x08;
+    pub const SUGGESTIONS: u8 = 0x10;
+    pub const RESTRICTED: u8 = 0x20;
+}
+
+/// The `minecraft:command_argument_type` registry of protocol 772, by id.
+const PARSERS: [&str; 57] = [
+    "brigadier:bool",
+    "brigadier:float",
+    "brigadier:double",
+    "brigadier:integer",
+    "brigadier:long",
+    "brigadier:string",
+    "minecraft:entity",
+    "minecraft:game_profile",
+    "minecraft:block_pos",
+    "minecraft:column_pos",
+    "minecraft:vec3",
+    "minecraft:vec2",
+    "minecraft:block_state",
+    "minecraft:block_predicate",
+    "minecraft:item_stack",
+    "minecraft:item_predicate",
+    "minecraft:color",
+    "minecraft:hex_color",
+    "minecraft:component",
+    "minecraft:style",
+    "minecraft:message",
+    "minecraft:nbt_compound_tag",
+    "minecraft:nbt_tag",
+    "minecraft:nbt_path",
+    "minecraft:objective",
+    "minecraft:objective_criteria",
+    "minecraft:operation",
+    "minecraft:particle",
+    "minecraft:angle",
+    "minecraft:rotation",
+    "minecraft:scoreboard_slot",
+    "minecraft:score_holder",
+    "minecraft:swizzle",
+    "minecraft:team",
+    "minecraft:item_slot",
+    "minecraft:item_slots",
+    "minecraft:resource_location",
+    "minecraft:function",
+    "minecraft:entity_anchor",
+    "minecraft:int_range",
+    "minecraft:float_range",
+    "minecraft:dimension",
+    "minecraft:gamemode",
+    "minecraft:time",
+    "minecraft:resource_or_tag",
+    "minecraft:resource_or_tag_key",
+    "minecraft:resource",
+    "minecraft:resource_key",
+    "minecraft:resource_selector",
+    "minecraft:template_mirror",
+    "minecraft:template_rotation",
+    "minecraft:heightmap",
+    "minecraft:loot_table",
+    "minecraft:loot_predicate",
+    "minecraft:loot_modifier",
+    "minecraft:dialog",
+    "minecraft:uuid",
+];
+
+/// The commands of a client, as nodes referring to each other by index.
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub struct CommandGraph {
+    pub nodes: Vec<CommandNode>,
+    /// Index of the root node.
+    pub root: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub struct CommandNode {
+    pub kind: NodeKind,
+    /// Whether a command ending at this node runs.
+    pub executable: bool,
+    /// Whether running the command from a click event needs confirmation.
+    pub restricted: bool,
+    /// Indices of the nodes which may follow.
+    pub children: Vec<i32>,
+    /// Index of the node parsing continues at, e.g. for `/execute run`.
+    pub redirect: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub enum NodeKind {
+    Root,
+    /// A word typed as is.
+    Literal {
+        name: String,
+    },
+    /// A value read by a parser.
+    Argument {
+        name: String,
+        parser: Parser,
+        /// Where suggestions come from, e.g. `minecraft:ask_server`.
+        suggestions: Option<Identifier>,
+    },
+}
+
+/// How an argument is read, with the properties of its parser.
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub enum Parser {
+    Float {
+        min: Option<f32>,
+        max: Option<f32>,
+    },
+    Double {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Integer {
+        min: Option<i32>,
+        max: Option<i32>,
+    },
+    Long {
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    String(StringKind),
+    Entity {
+        /// Whether a single entity may be selected.
+        single: bool,
+        players_only: bool,
+    },
+    ScoreHolder {
+        multiple: bool,
+    },
+    Time {
+        /// Fewest ticks allowed.
+        min: i32,
+    },
+    ResourceOrTag {
+        registry: Identifier,
+    },
+    ResourceOrTagKey {
+        registry: Identifier,
+    },
+    Resource {
+        registry: Identifier,
+    },
+    ResourceKey {
+        registry: Identifier,
+    },
+    ResourceSelector {
+        registry: Identifier,
+    },
+    /// A parser without properties, by id. The ids of the parsers above
+    /// must not be used.
+    Plain(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, ToJson)]
+#[codec(varint)]
+pub enum StringKind {
+    SingleWord = 0,
+    /// A word, or any text between quotes.
+    QuotablePhrase = 1,
+    /// The rest of the command.
+    GreedyPhrase = 2,
+}
+
+impl CommandGraph {
+    /// A graph with only a root, to add commands to.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![CommandNode::new(NodeKind::Root)],
+            root: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn node(
+        &self,
+        index: i32,
+    ) -> Option<&CommandNode> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.nodes.get(index))
+    }
+
+    pub fn node_mut(
+        &mut self,
+        index: i32,
+    ) -> Option<&mut CommandNode> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.nodes.get_mut(index))
+    }
+
+    /// Adds `node` to the graph, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph already has `i32::MAX` nodes.
+    pub fn push(
+        &mut self,
+        node: CommandNode,
+    ) -> i32 {
+        let index = i32::try_from(self.nodes.len()).expect("Too many command nodes");
+        self.nodes.push(node);
+        index
+    }
+
+    /// The children of the node at `index`, skipping invalid indices.
+    pub fn children(
+        &self,
+        index: i32,
+    ) -> impl Iterator<Item = (i32, &CommandNode)> {
+        self.node(index)
+            .map(|node| node.children.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|&child| Some((child, self.node(child)?)))
+    }
+
+    /// The child named `name` of the node at `index`.
+    #[must_use]
+    pub fn child(
+        &self,
+        index: i32,
+        name: &str,
+    ) -> Option<i32> {
+        self.children(index)
+            .find(|(_, node)| node.name() == Some(name))
+            .map(|(child, _)| child)
+    }
+
+    /// The node reached from the root through the nodes named `path`,
+    /// following redirects, e.g. `["execute", "run", "say"]`.
+    #[must_use]
+    pub fn find(
+        &self,
+        path: &[&str],
+    ) -> Option<i32> {
+        path.iter().try_fold(self.root, |index, name| {
+            let from = self.node(index)?.redirect.unwrap_or(index);
+            self.child(from, name)
+        })
+    }
+
+    /// Calls `visit` with the names leading to each node reachable from the
+    /// root without following redirects, depth first, once per node.
+    pub fn walk(
+        &self,
+        mut visit: impl FnMut(&[&str], i32, &CommandNode),
+    ) {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![(self.root, Vec::new())];
+        while let Some((index, path)) = stack.pop() {
+            let Some(node) = self.node(index) else {
+                continue;
+            };
+            let Some(seen) = usize::try_from(index).ok().and_then(|i| seen.get_mut(i)) else {
+                continue;
+            };
+            if core::mem::replace(seen, true) {
+                continue;
+            }
+            visit(&path, index, node);
+            for &child in node.children.iter().rev() {
+                let mut path = path.clone();
+                path.extend(self.node(child).and_then(CommandNode::name));
+                stack.push((child, path));
+            }
+        }
+    }
+
+    /// Detaches the command `name` from the root, returning its index. Its
+    /// nodes stay in the graph.
+    pub fn remove(
+        &mut self,
+        name: &str,
+    ) -> Option<i32> {
+        let index = self.child(self.root, name)?;
+        let root = self.node_mut(self.root)?;
+        root.children.retain(|&child| child != index);
+        Some(index)
+    }
+
+    /// Adds the commands of `other` to the graph. Nodes of the same kind at
+    /// the same place are merged, as Brigadier does, so that `other` only
+    /// adds to existing commands.
+    pub fn merge(
+        &mut self,
+        other: &Self,
+    ) {
+        let mut copied = BTreeMap::from([(other.root, self.root)]);
+        self.merge_children(self.root, other, other.root, &mut copied);
+    }
+
+    /// Merges the children of `other`'s node at `from` into those of the
+    /// node at `into`. `copied` maps the indices of `other` to those of
+    /// the nodes they became.
+    fn merge_children(
+        &mut self,
+        into: i32,
+        other: &Self,
+        from: i32,
+        copied: &mut BTreeMap<i32, i32>,
+    ) {
+        for (child, node) in other.children(from) {
+            let existing = self
+                .children(into)
+                .find(|(_, existing)| existing.kind == node.kind)
+                .map(|(index, _)| index);
+            if let Some(existing) = existing {
+                copied.insert(child, existing);
+                if let Some(merged) = self.node_mut(existing) {
+                    merged.executable |= node.executable;
+                }
+                self.merge_children(existing, other, child, copied);
+            } else if let Some(copy) = self.copy(other, child, copied)
+                && let Some(into) = self.node_mut(into)
+            {
+                into.children.push(copy);
+            }
+        }
+    }
+
+    /// Copies the node of `other` at `index` and those it refers to, unless
+    /// copied already.
+    fn copy(
+        &mut self,
+        other: &Self,
+        index: i32,
+        copied: &mut BTreeMap<i32, i32>,
+    ) -> Option<i32> {
+        if let Some(&copy) = copied.get(&index) {
+            return Some(copy);
+        }
+        let node = other.node(index)?;
+        let copy = self.push(CommandNode {
+            children: Vec::new(),
+            redirect: None,
+            ..node.clone()
+        });
+        copied.insert(index, copy);
+        let children = node
+            .children
+            .iter()
+            .filter_map(|&child| self.copy(other, child, copied))
+            .collect();
+        let redirect = node
+            .redirect
+            .and_then(|redirect| self.copy(other, redirect, copied));
+        let node = self.node_mut(copy)?;
+        node.children = children;
+        node.redirect = redirect;
+        Some(copy)
+    }
+}
+
+impl Default for CommandGraph {
+    fn default() -> Self { Self::new() }
+}
+
+impl CommandNode {
+    /// A node of `kind` without children, not executable.
+    #[must_use]
+    pub const fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            executable: false,
+            restricted: false,
+            children: Vec::new(),
+            redirect: None,
+        }
+    }
+
+    /// A word typed as is.
+    #[must_use]
+    pub const fn literal(name: String) -> Self {
+        Self::new(NodeKind::Literal {
+            name,
+        })
+    }
+
+    /// An argument read by `parser`, suggested by the client.
+    #[must_use]
+    pub const fn argument(
+        name: String,
+        parser: Parser,
+    ) -> Self {
+        Self::new(NodeKind::Argument {
+            name,
+            parser,
+            suggestions: None,
+        })
+    }
+
+    /// An argument taking the rest of the command, spaces included.
+    #[must_use]
+    pub const fn greedy_string(name: String) -> Self {
+        Self::argument(name, Parser::String(StringKind::GreedyPhrase))
+    }
+
+    /// Name of the node, unless it is the root.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match &self.kind {
+            NodeKind::Root => None,
+            NodeKind::Literal {
+                name,
+            }
+            | NodeKind::Argument {
+                name, ..
+            } => Some(name),
+        }
+    }
+}
+
+impl Parser {
+    /// Id in the latest `minecraft:command_argument_type` registry.
+    #[must_use]
+    pub const fn id(&self) -> i32 {
+        match self {
+            Self::Float {
+                ..
+            } => 1,
+            Self::Double {
+                ..
+            } => 2,
+            Self::Integer {
+                ..
+            } => 3,
+            Self::Long {
+                ..
+            } => 4,
+            Self::String(_) => 5,
+            Self::Entity {
+                ..
+            } => 6,
+            Self::ScoreHolder {
+                ..
+            } => 31,
+            Self::Time {
+                ..
+            } => 43,
+            Self::ResourceOrTag {
+                ..
+            } => 44,
+            Self::ResourceOrTagKey {
+                ..
+            } => 45,
+            Self::Resource {
+                ..
+            } => 46,
+            Self::ResourceKey {
+                ..
+            } => 47,
+            Self::ResourceSelector {
+                ..
+            } => 48,
+            Self::Plain(id) => *id,
+        }
+    }
+
+    /// Name of the parser, e.g. `brigadier:integer`, if the id is known.
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        usize::try_from(self.id())
+            .ok()
+            .and_then(|id| PARSERS.get(id))
+            .copied()
+    }
+}
+
+/// Reads the bounds of a numeric parser, each present if flagged.
+fn decode_bounds<T: Decode, R: io::Read>(
+    reader: &mut R
+) -> Result<(Option<T>, Option<T>), DecodeError> {
+    let flags = u8::decode(reader)?;
+    let min = (flags & 0x01 != 0).then(|| T::decode(reader)).transpose()?;
+    let max = (flags & 0x02 != 0).then(|| T::decode(reader)).transpose()?;
+    Ok((min, max))
+}
+
+fn encode_bounds<T: Encode, W: io::Write>(
+    (min, max): (&Option<T>, &Option<T>),
+    writer: &mut W,
+) -> Result<usize, EncodeError> {
+    let flags = u8::from(min.is_some()) | u8::from(max.is_some()) << 1;
+    let mut written = flags.encode(writer)?;
+    for bound in [min, max].into_iter().flatten() {
+        written += bound.encode(writer)?;
+    }
+    Ok(written)
+}
+
+impl Decode for Parser {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let id = VarInt::decode(reader)?.value();
+        Ok(match id {
+            1 => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Float {
+                    min,
+                    max,
+                }
+            }
+            2 => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Double {
+                    min,
+                    max,
+                }
+            }
+            3 => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Integer {
+                    min,
+                    max,
+                }
+            }
+            4 => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Long {
+                    min,
+                    max,
+                }
+            }
+            5 => Self::String(StringKind::decode(reader)?),
+            6 => {
+                let flags = u8::decode(reader)?;
+                Self::Entity {
+                    single: flags & 0x01 != 0,
+                    players_only: flags & 0x02 != 0,
+                }
+            }
+            31 => Self::ScoreHolder {
+                multiple: u8::decode(reader)? & 0x01 != 0,
+            },
+            43 => Self::Time {
+                min: i32::decode(reader)?,
+            },
+            44 => Self::ResourceOrTag {
+                registry: Identifier::decode(reader)?,
+            },
+            45 => Self::ResourceOrTagKey {
+                registry: Identifier::decode(reader)?,
+            },
+            46 => Self::Resource {
+                registry: Identifier::decode(reader)?,
+            },
+            47 => Self::ResourceKey {
+                registry: Identifier::decode(reader)?,
+            },
+            48 => Self::ResourceSelector {
+                registry: Identifier::decode(reader)?,
+            },
+            // without properties, anything else could not be skipped
+            id if usize::try_from(id).is_ok_and(|id| id < PARSERS.len()) => Self::Plain(id),
+            id => {
+                return Err(DecodeError::Custom {
+                    message: format!("Unknown argument parser {id}"),
+                });
+            }
+        })
+    }
+}
+
+impl Encode for Parser {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let written = VarInt::new(self.id()).encode(writer)?;
+        Ok(written
+            + match self {
+                Self::Float {
+                    min,
+                    max,
+                } => encode_bounds((min, max), writer)?,
+                Self::Double {
+                    min,
+                    max,
+                } => encode_bounds((min, max), writer)?,
+                Self::Integer {
+                    min,
+                    max,
+                } => encode_bounds((min, max), writer)?,
+                Self::Long {
+                    min,
+                    max,
+                } => encode_bounds((min, max), writer)?,
+                Self::String(kind) => kind.encode(writer)?,
+                Self::Entity {
+                    single,
+                    players_only,
+                } => (u8::from(*single) | u8::from(*players_only) << 1).encode(writer)?,
+                Self::ScoreHolder {
+                    multiple,
+                } => u8::from(*multiple).encode(writer)?,
+                Self::Time {
+                    min,
+                } => min.encode(writer)?,
+                Self::ResourceOrTag {
+                    registry,
+                }
+                | Self::ResourceOrTagKey {
+                    registry,
+                }
+                | Self::Resource {
+                    registry,
+                }
+                | Self::ResourceKey {
+                    registry,
+                }
+                | Self::ResourceSelector {
+                    registry,
+                } => registry.encode(writer)?,
+                Self::Plain(_) => 0,
+            })
+    }
+}
+
+impl Decode for CommandGraph {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(Self {
+            nodes: Vec::decode(reader)?,
+            root: VarInt::decode(reader)?.value(),
+        })
+    }
+}
+
+impl Encode for CommandGraph {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        Ok(self.nodes.encode(writer)? + VarInt::new(self.root).encode(writer)?)
+    }
+}
+
+impl Decode for CommandNode {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let flags = u8::decode(reader)?;
+        let children = Vec::<VarInt>::decode(reader)?
+            .iter()
+            .map(VarInt::value)
+            .collect();
+        let redirect = (flags & flags::REDIRECT != 0)
+            .then(|| VarInt::decode(reader).map(|index| index.value()))
+            .transpose()?;
+        let kind = match flags & flags::KIND {
+            0 => NodeKind::Root,
+            1 => NodeKind::Literal {
+                name: String::decode(reader)?,
+            },
+            2 => NodeKind::Argument {
+                name: String::decode(reader)?,
+                parser: Parser::decode(reader)?,
+                suggestions: (flags & flags::SUGGESTIONS != 0)
+                    .then(|| Identifier::decode(reader))
+                    .transpose()?,
+            },
+            kind => {
+                return Err(DecodeError::Custom {
+                    message: format!("Unknown command node type {kind}"),
+                });
+            }
+        };
+        Ok(Self {
+            kind,
+            executable: flags & flags::EXECUTABLE != 0,
+            restricted: flags & flags::RESTRICTED != 0,
+            children,
+            redirect,
+        })
+    }
+}
+
+impl Encode for CommandNode {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut flags = match &self.kind {
+            NodeKind::Root => 0,
+            NodeKind::Literal {
+                ..
+            } => 1,
+            NodeKind::Argument {
+                suggestions, ..
+            } => {
+                2 | if suggestions.is_some() {
+                    flags::SUGGESTIONS
+                } else {
+                    0
+                }
+            }
+        };
+        if self.executable {
+            flags |= flags::EXECUTABLE;
+        }
+        if self.redirect.is_some() {
+            flags |= flags::REDIRECT;
+        }
+        if self.restricted {
+            flags |= flags::RESTRICTED;
+        }
+
+        let mut written = flags.encode(writer)?;
+        written +=
+            VarInt::new(i32::try_from(self.children.len()).unwrap_or(i32::MAX)).encode(writer)?;
+        for child in &self.children {
+            written += VarInt::new(*child).encode(writer)?;
+        }
+        if let Some(redirect) = self.redirect {
+            written += VarInt::new(redirect).encode(writer)?;
+        }
+        match &self.kind {
+            NodeKind::Root => {}
+            NodeKind::Literal {
+                name,
+            } => written += name.encode(writer)?,
+            NodeKind::Argument {
+                name,
+                parser,
+                suggestions,
+            } => {
+                written += name.encode(writer)?;
+                written += parser.encode(writer)?;
+                if let Some(suggestions) = suggestions {
+                    written += suggestions.encode(writer)?;
+                }
+            }
+        }
+        Ok(written)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `/give <targets> <count>`, `/say <message>` and `/execute run ...`.
+    fn graph() -> CommandGraph {
+        let mut graph = CommandGraph::new();
+        let count = graph.push(CommandNode {
+            executable: true,
+            ..CommandNode::argument("count".to_owned(), Parser::Integer {
+                min: Some(1),
+                max: None,
+            })
+        });
+        let targets = graph.push(CommandNode {
+            children: vec![count],
+            ..CommandNode::new(NodeKind::Argument {
+                name: "targets".to_owned(),
+                parser: Parser::Entity {
+                    single: false,
+                    players_only: true,
+                },
+                suggestions: Some("minecraft:ask_server".parse().unwrap()),
+            })
+        });
+        let give = graph.push(CommandNode {
+            children: vec![targets],
+            restricted: true,
+            ..CommandNode::literal("give".to_owned())
+        });
+        let message = graph.push(CommandNode {
+            executable: true,
+            ..CommandNode::greedy_string("message".to_owned())
+        });
+        let say = graph.push(CommandNode {
+            children: vec![message],
+            ..CommandNode::literal("say".to_owned())
+        });
+        let run = graph.push(CommandNode {
+            redirect: Some(0),
+            ..CommandNode::literal("run".to_owned())
+        });
+        let execute = graph.push(CommandNode {
+            children: vec![run],
+            ..CommandNode::literal("execute".to_owned())
+        });
+        graph.nodes[0].children = vec![give, say, execute];
+        graph
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut graph = graph();
+        let time = graph.push(CommandNode::argument("time".to_owned(), Parser::Time {
+            min: 0,
+        }));
+        let block = graph.push(CommandNode::argument("block".to_owned(), Parser::Plain(12)));
+        graph.nodes[0].children.extend([time, block]);
+
+        let mut data = Vec::new();
+        let written = graph.encode(&mut data).unwrap();
+        assert_eq!(written, data.len(), "the written size should be exact");
+        let decoded = CommandGraph::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded, graph);
+        let NodeKind::Argument {
+            parser, ..
+        } = &decoded.node(block).unwrap().kind
+        else {
+            panic!("block should be an argument");
+        };
+        assert_eq!(parser.name(), Some("minecraft:block_state"));
+    }
+
+    #[test]
+    fn reject_unknown_parsers() {
+        let mut data = Vec::new();
+        VarInt::new(1000).encode(&mut data).unwrap();
+        assert!(
+            Parser::decode(&mut data.as_slice()).is_err(),
+            "the properties of unknown parsers cannot be skipped"
+        );
+    }
+
+    #[test]
+    fn traverse() {
+        let graph = graph();
+        let count = graph.find(&["give", "targets", "count"]).unwrap();
+        assert!(graph.node(count).unwrap().executable, "count ends /give");
+        assert_eq!(
+            graph.find(&["execute", "run", "say", "message"]),
+            graph.find(&["say", "message"]),
+            "redirects should be followed"
+        );
+        assert_eq!(graph.find(&["give", "message"]), None);
+
+        let mut paths = Vec::new();
+        graph.walk(|path, _, _| paths.push(path.join(" ")));
+        assert_eq!(paths, [
+            "",
+            "give",
+            "give targets",
+            "give targets count",
+            "say",
+            "say message",
+            "execute",
+            "execute run",
+        ]);
+    }
+
+    #[test]
+    fn merge() {
+        let mut graph = graph();
+        let mut other = CommandGraph::new();
+        let item = other.push(CommandNode {
+            executable: true,
+            ..CommandNode::greedy_string("item".to_owned())
+        });
+        let targets = other.push(CommandNode {
+            children: vec![item],
+            ..graph
+                .node(graph.find(&["give", "targets"]).unwrap())
+                .unwrap()
+                .clone()
+        });
+        let give = other.push(CommandNode {
+            children: vec![targets],
+            ..CommandNode::literal("give".to_owned())
+        });
+        let back = other.push(CommandNode {
+            redirect: Some(give),
+            executable: true,
+            ..CommandNode::literal("back".to_owned())
+        });
+        other.nodes[0].children = vec![give, back];
+
+        let nodes = graph.nodes.len();
+        graph.merge(&other);
+        assert_eq!(graph.nodes.len(), nodes + 2, "only item and back are new");
+        assert!(graph.find(&["give", "targets", "count"]).is_some());
+        assert!(graph.find(&["give", "targets", "item"]).is_some());
+        assert_eq!(
+            graph.find(&["back", "targets", "item"]),
+            graph.find(&["give", "targets", "item"]),
+            "redirects should point into the merged graph"
+        );
+
+        assert!(graph.remove("give").is_some());
+        assert_eq!(graph.find(&["give"]), None);
+        assert!(graph.remove("give").is_none(), "give is gone");
+    }
+}
@@ -0,0 +1,150 @@
+//! Generated by `datagen` from the protocol 772 `blocks.json` report. Do not edit by hand.
+
+use crate::registry::block::{BlockStates, Property};
+
+const SNOWY: Property = Property {
+    name: "snowy",
+    values: &["true", "false"],
+};
+
+const AXIS: Property = Property {
+    name: "axis",
+    values: &["x", "y", "z"],
+};
+
+const STAGE: Property = Property {
+    name: "stage",
+    values: &["0", "1"],
+};
+
+const AGE: Property = Property {
+    name: "age",
+    values: &["0", "1", "2", "3", "4"],
+};
+
+const HANGING: Property = Property {
+    name: "hanging",
+    values: &["true", "false"],
+};
+
+const WATERLOGGED: Property = Property {
+    name: "waterlogged",
+    values: &["true", "false"],
+};
+
+const LEVEL: Property = Property {
+    name: "level",
+    values: &["0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15"],
+};
+
+const DUSTED: Property = Property {
+    name: "dusted",
+    values: &["0", "1", "2", "3"],
+};
+
+/// Number of block states.
+pub const STATE_COUNT: u32 = 139;
+
+/// State layout of every block, indexed by block id.
+pub static BLOCKS: [BlockStates; 50] = [
+    // minecraft:air
+    BlockStates { first_state: 0, default_state: 0, properties: &[] },
+    // minecraft:stone
+    BlockStates { first_state: 1, default_state: 1, properties: &[] },
+    // minecraft:granite
+    BlockStates { first_state: 2, default_state: 2, properties: &[] },
+    // minecraft:polished_granite
+    BlockStates { first_state: 3, default_state: 3, properties: &[] },
+    // minecraft:diorite
+    BlockStates { first_state: 4, default_state: 4, properties: &[] },
+    // minecraft:polished_diorite
+    BlockStates { first_state: 5, default_state: 5, properties: &[] },
+    // minecraft:andesite
+    BlockStates { first_state: 6, default_state: 6, properties: &[] },
+    // minecraft:polished_andesite
+    BlockStates { first_state: 7, default_state: 7, properties: &[] },
+    // minecraft:grass_block
+    BlockStates { first_state: 8, default_state: 9, properties: &[SNOWY] },
+    // minecraft:dirt
+    BlockStates { first_state: 10, default_state: 10, properties: &[] },
+    // minecraft:coarse_dirt
+    BlockStates { first_state: 11, default_state: 11, properties: &[] },
+    // minecraft:podzol
+    BlockStates { first_state: 12, default_state: 13, properties: &[SNOWY] },
+    // minecraft:cobblestone
+    BlockStates { first_state: 14, default_state: 14, properties: &[] },
+    // minecraft:oak_planks
+    BlockStates { first_state: 15, default_state: 15, properties: &[] },
+    // minecraft:spruce_planks
+    BlockStates { first_state: 16, default_state: 16, properties: &[] },
+    // minecraft:birch_planks
+    BlockStates { first_state: 17, default_state: 17, properties: &[] },
+    // minecraft:jungle_planks
+    BlockStates { first_state: 18, default_state: 18, properties: &[] },
+    // minecraft:acacia_planks
+    BlockStates { first_state: 19, default_state: 19, properties: &[] },
+    // minecraft:cherry_planks
+    BlockStates { first_state: 20, default_state: 20, properties: &[] },
+    // minecraft:dark_oak_planks
+    BlockStates { first_state: 21, default_state: 21, properties: &[] },
+    // minecraft:pale_oak_wood
+    BlockStates { first_state: 22, default_state: 23, properties: &[AXIS] },
+    // minecraft:pale_oak_planks
+    BlockStates { first_state: 25, default_state: 25, properties: &[] },
+    // minecraft:mangrove_planks
+    BlockStates { first_state: 26, default_state: 26, properties: &[] },
+    // minecraft:bamboo_planks
+    BlockStates { first_state: 27, default_state: 27, properties: &[] },
+    // minecraft:bamboo_mosaic
+    BlockStates { first_state: 28, default_state: 28, properties: &[] },
+    // minecraft:oak_sapling
+    BlockStates { first_state: 29, default_state: 29, properties: &[STAGE] },
+    // minecraft:spruce_sapling
+    BlockStates { first_state: 31, default_state: 31, properties: &[STAGE] },
+    // minecraft:birch_sapling
+    BlockStates { first_state: 33, default_state: 33, properties: &[STAGE] },
+    // minecraft:jungle_sapling
+    BlockStates { first_state: 35, default_state: 35, properties: &[STAGE] },
+    // minecraft:acacia_sapling
+    BlockStates { first_state: 37, default_state: 37, properties: &[STAGE] },
+    // minecraft:cherry_sapling
+    BlockStates { first_state: 39, default_state: 39, properties: &[STAGE] },
+    // minecraft:dark_oak_sapling
+    BlockStates { first_state: 41, default_state: 41, properties: &[STAGE] },
+    // minecraft:pale_oak_sapling
+    BlockStates { first_state: 43, default_state: 43, properties: &[STAGE] },
+    // minecraft:mangrove_propagule
+    BlockStates { first_state: 45, default_state: 50, properties: &[AGE, HANGING, STAGE, WATERLOGGED] },
+    // minecraft:bedrock
+    BlockStates { first_state: 85, default_state: 85, properties: &[] },
+    // minecraft:water
+    BlockStates { first_state: 86, default_state: 86, properties: &[LEVEL] },
+    // minecraft:lava
+    BlockStates { first_state: 102, default_state: 102, properties: &[LEVEL] },
+    // minecraft:sand
+    BlockStates { first_state: 118, default_state: 118, properties: &[] },
+    // minecraft:suspicious_sand
+    BlockStates { first_state: 119, default_state: 119, properties: &[DUSTED] },
+    // minecraft:red_sand
+    BlockStates { first_state: 123, default_state: 123, properties: &[] },
+    // minecraft:gravel
+    BlockStates { first_state: 124, default_state: 124, properties: &[] },
+    // minecraft:suspicious_gravel
+    BlockStates { first_state: 125, default_state: 125, properties: &[DUSTED] },
+    // minecraft:gold_ore
+    BlockStates { first_state: 129, default_state: 129, properties: &[] },
+    // minecraft:deepslate_gold_ore
+    BlockStates { first_state: 130, default_state: 130, properties: &[] },
+    // minecraft:iron_ore
+    BlockStates { first_state: 131, default_state: 131, properties: &[] },
+    // minecraft:deepslate_iron_ore
+    BlockStates { first_state: 132, default_state: 132, properties: &[] },
+    // minecraft:coal_ore
+    BlockStates { first_state: 133, default_state: 133, properties: &[] },
+    // minecraft:deepslate_coal_ore
+    BlockStates { first_state: 134, default_state: 134, properties: &[] },
+    // minecraft:nether_gold_ore
+    BlockStates { first_state: 135, default_state: 135, properties: &[] },
+    // minecraft:oak_log
+    BlockStates { first_state: 136, default_state: 137, properties: &[AXIS] },
+];
@@ -0,0 +1,204 @@
+//! Modules generated by `datagen` from the vanilla reports checked in under
+//! `datagen/reports`. Regenerate them instead of editing by hand.
+
+#[rustfmt::skip]
+pub(crate) mod blocks;
+#[rustfmt::skip]
+pub mod packets;
+#[rustfmt::skip]
+pub mod registries;
+/// Raw-payload structs for every packet, to copy into [`crate::model`] when
+/// a packet gets a real layout.
+#[cfg(feature = "skeletons")]
+#[rustfmt::skip]
+pub mod skeletons;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{
+        Direction,
+        State,
+    };
+    use crate::version::ProtocolVersion;
+
+    #[test]
+    fn packet_ids_match_version_tables() {
+        let tables = [
+            (
+                State::Handshake,
+                Direction::Serverbound,
+                packets::handshake::serverbound::NAMES,
+            ),
+            (
+                State::Status,
+                Direction::Serverbound,
+                packets::status::serverbound::NAMES,
+            ),
+            (
+                State::Status,
+                Direction::Clientbound,
+                packets::status::clientbound::NAMES,
+            ),
+            (
+                State::Login,
+                Direction::Serverbound,
+                packets::login::serverbound::NAMES,
+            ),
+            (
+                State::Login,
+                Direction::Clientbound,
+                packets::login::clientbound::NAMES,
+            ),
+            (
+                State::Configuration,
+                Direction::Serverbound,
+                packets::configuration::serverbound::NAMES,
+            ),
+            (
+                State::Configuration,
+                Direction::Clientbound,
+                packets::configuration::clientbound::NAMES,
+            ),
+            (
+                State::Play,
+                Direction::Serverbound,
+                packets::play::serverbound::NAMES,
+            ),
+            (
+                State::Play,
+                Direction::Clientbound,
+                packets::play::clientbound::NAMES,
+            ),
+        ];
+        for (state, direction, names) in tables {
+            for (id, name) in (0..).zip(names) {
+                assert_eq!(
+                    ProtocolVersion::V1_21_7.packet_name(state, direction, id),
+                    Some(*name),
+                    "{state:?} {direction:?} {id:#04X}"
+                );
+            }
+        }
+        assert_eq!(packets::play::clientbound::SYSTEM_CHAT, 0x72);
+    }
+
+    #[test]
+    fn play_models_match_generated_ids() {
+        use packets::play::{
+            clientbound,
+            serverbound,
+        };
+
+        use crate::model::play;
+        use crate::protocol::ProtocolPacket as _;
+
+        assert_eq!(play::AddEntity::ID, clientbound::ADD_ENTITY);
+        assert_eq!(
+            play::ChunkBatchFinished::ID,
+            clientbound::CHUNK_BATCH_FINISHED
+        );
+        assert_eq!(play::Disconnect::ID, clientbound::DISCONNECT);
+        assert_eq!(play::DisguisedChat::ID, clientbound::DISGUISED_CHAT);
+        assert_eq!(play::GameEvent::ID, clientbound::GAME_EVENT);
+        assert_eq!(play::KeepAlive::ID, clientbound::KEEP_ALIVE);
+        assert_eq!(play::Ping::ID, clientbound::PING);
+        assert_eq!(play::PlayerChat::ID, clientbound::PLAYER_CHAT);
+        assert_eq!(
+            play::StartConfiguration::ID,
+            clientbound::START_CONFIGURATION
+        );
+        assert_eq!(
+            play::SetChunkCacheCenter::ID,
+            clientbound::SET_CHUNK_CACHE_CENTER
+        );
+        assert_eq!(play::SystemChat::ID, clientbound::SYSTEM_CHAT);
+        assert_eq!(play::Transfer::ID, clientbound::TRANSFER);
+        assert_eq!(play::BlockEntityData::ID, clientbound::BLOCK_ENTITY_DATA);
+        assert_eq!(play::BlockUpdate::ID, clientbound::BLOCK_UPDATE);
+        assert_eq!(
+            play::EntityPositionSync::ID,
+            clientbound::ENTITY_POSITION_SYNC
+        );
+        assert_eq!(play::ForgetLevelChunk::ID, clientbound::FORGET_LEVEL_CHUNK);
+        assert_eq!(
+            play::LevelChunkWithLight::ID,
+            clientbound::LEVEL_CHUNK_WITH_LIGHT
+        );
+        assert_eq!(play::Login::ID, clientbound::LOGIN);
+        assert_eq!(play::MoveEntityPos::ID, clientbound::MOVE_ENTITY_POS);
+        assert_eq!(play::MoveEntityPosRot::ID, clientbound::MOVE_ENTITY_POS_ROT);
+        assert_eq!(play::MoveEntityRot::ID, clientbound::MOVE_ENTITY_ROT);
+        assert_eq!(play::PlayerPosition::ID, clientbound::PLAYER_POSITION);
+        assert_eq!(play::RemoveEntities::ID, clientbound::REMOVE_ENTITIES);
+        assert_eq!(play::Respawn::ID, clientbound::RESPAWN);
+        assert_eq!(
+            play::SectionBlocksUpdate::ID,
+            clientbound::SECTION_BLOCKS_UPDATE
+        );
+        assert_eq!(play::SetEntityData::ID, clientbound::SET_ENTITY_DATA);
+        assert_eq!(play::TeleportEntity::ID, clientbound::TELEPORT_ENTITY);
+        assert_eq!(play::MovePlayerPos::ID, serverbound::MOVE_PLAYER_POS);
+        assert_eq!(play::MovePlayerPosRot::ID, serverbound::MOVE_PLAYER_POS_ROT);
+        assert_eq!(play::MovePlayerRot::ID, serverbound::MOVE_PLAYER_ROT);
+        assert_eq!(
+            play::AcceptTeleportation::ID,
+            serverbound::ACCEPT_TELEPORTATION
+        );
+        assert_eq!(play::ChatCommand::ID, serverbound::CHAT_COMMAND);
+        assert_eq!(play::Chat::ID, serverbound::CHAT);
+        assert_eq!(
+            play::ChunkBatchReceived::ID,
+            serverbound::CHUNK_BATCH_RECEIVED
+        );
+        assert_eq!(
+            play::ConfigurationAcknowledged::ID,
+            serverbound::CONFIGURATION_ACKNOWLEDGED
+        );
+        assert_eq!(play::KeepAliveResponse::ID, serverbound::KEEP_ALIVE);
+        assert_eq!(play::PlayerLoaded::ID, serverbound::PLAYER_LOADED);
+        assert_eq!(play::Pong::ID, serverbound::PONG);
+    }
+
+    #[test]
+    fn configuration_models_match_generated_ids() {
+        use packets::configuration::{
+            clientbound,
+            serverbound,
+        };
+
+        use crate::model::configuration;
+        use crate::protocol::ProtocolPacket as _;
+
+        assert_eq!(configuration::Disconnect::ID, clientbound::DISCONNECT);
+        assert_eq!(
+            configuration::FinishConfiguration::ID,
+            clientbound::FINISH_CONFIGURATION
+        );
+        assert_eq!(configuration::KeepAlive::ID, clientbound::KEEP_ALIVE);
+        assert_eq!(configuration::Ping::ID, clientbound::PING);
+        assert_eq!(configuration::RegistryData::ID, clientbound::REGISTRY_DATA);
+        assert_eq!(configuration::Transfer::ID, clientbound::TRANSFER);
+        assert_eq!(
+            configuration::SelectKnownPacks::ID,
+            clientbound::SELECT_KNOWN_PACKS
+        );
+        assert_eq!(
+            configuration::ClientInformation::ID,
+            serverbound::CLIENT_INFORMATION
+        );
+        assert_eq!(
+            configuration::AcknowledgeFinishConfiguration::ID,
+            serverbound::FINISH_CONFIGURATION
+        );
+        assert_eq!(
+            configuration::KeepAliveResponse::ID,
+            serverbound::KEEP_ALIVE
+        );
+        assert_eq!(configuration::Pong::ID, serverbound::PONG);
+        assert_eq!(
+            configuration::KnownPacks::ID,
+            serverbound::SELECT_KNOWN_PACKS
+        );
+    }
+}
@@ -0,0 +1,562 @@
+//! Generated by `datagen` from the protocol 772 `packets.json` report. Do not edit by hand.
+
+pub mod handshake {
+    pub mod serverbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "intention",
+        ];
+
+        pub const INTENTION: i32 = 0x00;
+    }
+}
+
+pub mod status {
+    pub mod serverbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "status_request",
+            "ping_request",
+        ];
+
+        pub const STATUS_REQUEST: i32 = 0x00;
+        pub const PING_REQUEST: i32 = 0x01;
+    }
+
+    pub mod clientbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "status_response",
+            "pong_response",
+        ];
+
+        pub const STATUS_RESPONSE: i32 = 0x00;
+        pub const PONG_RESPONSE: i32 = 0x01;
+    }
+}
+
+pub mod login {
+    pub mod serverbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "hello",
+            "key",
+            "custom_query_answer",
+            "login_acknowledged",
+            "cookie_response",
+        ];
+
+        pub const HELLO: i32 = 0x00;
+        pub const KEY: i32 = 0x01;
+        pub const CUSTOM_QUERY_ANSWER: i32 = 0x02;
+        pub const LOGIN_ACKNOWLEDGED: i32 = 0x03;
+        pub const COOKIE_RESPONSE: i32 = 0x04;
+    }
+
+    pub mod clientbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "login_disconnect",
+            "hello",
+            "login_finished",
+            "login_compression",
+            "custom_query",
+            "cookie_request",
+        ];
+
+        pub const LOGIN_DISCONNECT: i32 = 0x00;
+        pub const HELLO: i32 = 0x01;
+        pub const LOGIN_FINISHED: i32 = 0x02;
+        pub const LOGIN_COMPRESSION: i32 = 0x03;
+        pub const CUSTOM_QUERY: i32 = 0x04;
+        pub const COOKIE_REQUEST: i32 = 0x05;
+    }
+}
+
+pub mod configuration {
+    pub mod serverbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "client_information",
+            "cookie_response",
+            "custom_payload",
+            "finish_configuration",
+            "keep_alive",
+            "pong",
+            "resource_pack",
+            "select_known_packs",
+            "custom_click_action",
+        ];
+
+        pub const CLIENT_INFORMATION: i32 = 0x00;
+        pub const COOKIE_RESPONSE: i32 = 0x01;
+        pub const CUSTOM_PAYLOAD: i32 = 0x02;
+        pub const FINISH_CONFIGURATION: i32 = 0x03;
+        pub const KEEP_ALIVE: i32 = 0x04;
+        pub const PONG: i32 = 0x05;
+        pub const RESOURCE_PACK: i32 = 0x06;
+        pub const SELECT_KNOWN_PACKS: i32 = 0x07;
+        pub const CUSTOM_CLICK_ACTION: i32 = 0x08;
+    }
+
+    pub mod clientbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "cookie_request",
+            "custom_payload",
+            "disconnect",
+            "finish_configuration",
+            "keep_alive",
+            "ping",
+            "reset_chat",
+            "registry_data",
+            "resource_pack_pop",
+            "resource_pack_push",
+            "store_cookie",
+            "transfer",
+            "update_enabled_features",
+            "update_tags",
+            "select_known_packs",
+            "custom_report_details",
+            "server_links",
+            "clear_dialog",
+            "show_dialog",
+        ];
+
+        pub const COOKIE_REQUEST: i32 = 0x00;
+        pub const CUSTOM_PAYLOAD: i32 = 0x01;
+        pub const DISCONNECT: i32 = 0x02;
+        pub const FINISH_CONFIGURATION: i32 = 0x03;
+        pub const KEEP_ALIVE: i32 = 0x04;
+        pub const PING: i32 = 0x05;
+        pub const RESET_CHAT: i32 = 0x06;
+        pub const REGISTRY_DATA: i32 = 0x07;
+        pub const RESOURCE_PACK_POP: i32 = 0x08;
+        pub const RESOURCE_PACK_PUSH: i32 = 0x09;
+        pub const STORE_COOKIE: i32 = 0x0A;
+        pub const TRANSFER: i32 = 0x0B;
+        pub const UPDATE_ENABLED_FEATURES: i32 = 0x0C;
+        pub const UPDATE_TAGS: i32 = 0x0D;
+        pub const SELECT_KNOWN_PACKS: i32 = 0x0E;
+        pub const CUSTOM_REPORT_DETAILS: i32 = 0x0F;
+        pub const SERVER_LINKS: i32 = 0x10;
+        pub const CLEAR_DIALOG: i32 = 0x11;
+        pub const SHOW_DIALOG: i32 = 0x12;
+    }
+}
+
+pub mod play {
+    pub mod serverbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "accept_teleportation",
+            "block_entity_tag_query",
+            "bundle_item_selected",
+            "change_difficulty",
+            "change_game_mode",
+            "chat_ack",
+            "chat_command",
+            "chat_command_signed",
+            "chat",
+            "chat_session_update",
+            "chunk_batch_received",
+            "client_command",
+            "client_tick_end",
+            "client_information",
+            "command_suggestion",
+            "configuration_acknowledged",
+            "container_button_click",
+            "container_click",
+            "container_close",
+            "container_slot_state_changed",
+            "cookie_response",
+            "custom_payload",
+            "debug_sample_subscription",
+            "edit_book",
+            "entity_tag_query",
+            "interact",
+            "jigsaw_generate",
+            "keep_alive",
+            "lock_difficulty",
+            "move_player_pos",
+            "move_player_pos_rot",
+            "move_player_rot",
+            "move_player_status_only",
+            "move_vehicle",
+            "paddle_boat",
+            "pick_item_from_block",
+            "pick_item_from_entity",
+            "ping_request",
+            "place_recipe",
+            "player_abilities",
+            "player_action",
+            "player_command",
+            "player_input",
+            "player_loaded",
+            "pong",
+            "recipe_book_change_settings",
+            "recipe_book_seen_recipe",
+            "rename_item",
+            "resource_pack",
+            "seen_advancements",
+            "select_trade",
+            "set_beacon",
+            "set_carried_item",
+            "set_command_block",
+            "set_command_minecart",
+            "set_creative_mode_slot",
+            "set_jigsaw_block",
+            "set_structure_block",
+            "set_test_block",
+            "sign_update",
+            "swing",
+            "teleport_to_entity",
+            "test_instance_block_action",
+            "use_item_on",
+            "use_item",
+            "custom_click_action",
+        ];
+
+        pub const ACCEPT_TELEPORTATION: i32 = 0x00;
+        pub const BLOCK_ENTITY_TAG_QUERY: i32 = 0x01;
+        pub const BUNDLE_ITEM_SELECTED: i32 = 0x02;
+        pub const CHANGE_DIFFICULTY: i32 = 0x03;
+        pub const CHANGE_GAME_MODE: i32 = 0x04;
+        pub const CHAT_ACK: i32 = 0x05;
+        pub const CHAT_COMMAND: i32 = 0x06;
+        pub const CHAT_COMMAND_SIGNED: i32 = 0x07;
+        pub const CHAT: i32 = 0x08;
+        pub const CHAT_SESSION_UPDATE: i32 = 0x09;
+        pub const CHUNK_BATCH_RECEIVED: i32 = 0x0A;
+        pub const CLIENT_COMMAND: i32 = 0x0B;
+        pub const CLIENT_TICK_END: i32 = 0x0C;
+        pub const CLIENT_INFORMATION: i32 = 0x0D;
+        pub const COMMAND_SUGGESTION: i32 = 0x0E;
+        pub const CONFIGURATION_ACKNOWLEDGED: i32 = 0x0F;
+        pub const CONTAINER_BUTTON_CLICK: i32 = 0x10;
+        pub const CONTAINER_CLICK: i32 = 0x11;
+        pub const CONTAINER_CLOSE: i32 = 0x12;
+        pub const CONTAINER_SLOT_STATE_CHANGED: i32 = 0x13;
+        pub const COOKIE_RESPONSE: i32 = 0x14;
+        pub const CUSTOM_PAYLOAD: i32 = 0x15;
+        pub const DEBUG_SAMPLE_SUBSCRIPTION: i32 = 0x16;
+        pub const EDIT_BOOK: i32 = 0x17;
+        pub const ENTITY_TAG_QUERY: i32 = 0x18;
+        pub const INTERACT: i32 = 0x19;
+        pub const JIGSAW_GENERATE: i32 = 0x1A;
+        pub const KEEP_ALIVE: i32 = 0x1B;
+        pub const LOCK_DIFFICULTY: i32 = 0x1C;
+        pub const MOVE_PLAYER_POS: i32 = 0x1D;
+        pub const MOVE_PLAYER_POS_ROT: i32 = 0x1E;
+        pub const MOVE_PLAYER_ROT: i32 = 0x1F;
+        pub const MOVE_PLAYER_STATUS_ONLY: i32 = 0x20;
+        pub const MOVE_VEHICLE: i32 = 0x21;
+        pub const PADDLE_BOAT: i32 = 0x22;
+        pub const PICK_ITEM_FROM_BLOCK: i32 = 0x23;
+        pub const PICK_ITEM_FROM_ENTITY: i32 = 0x24;
+        pub const PING_REQUEST: i32 = 0x25;
+        pub const PLACE_RECIPE: i32 = 0x26;
+        pub const PLAYER_ABILITIES: i32 = 0x27;
+        pub const PLAYER_ACTION: i32 = 0x28;
+        pub const PLAYER_COMMAND: i32 = 0x29;
+        pub const PLAYER_INPUT: i32 = 0x2A;
+        pub const PLAYER_LOADED: i32 = 0x2B;
+        pub const PONG: i32 = 0x2C;
+        pub const RECIPE_BOOK_CHANGE_SETTINGS: i32 = 0x2D;
+        pub const RECIPE_BOOK_SEEN_RECIPE: i32 = 0x2E;
+        pub const RENAME_ITEM: i32 = 0x2F;
+        pub const RESOURCE_PACK: i32 = 0x30;
+        pub const SEEN_ADVANCEMENTS: i32 = 0x31;
+        pub const SELECT_TRADE: i32 = 0x32;
+        pub const SET_BEACON: i32 = 0x33;
+        pub const SET_CARRIED_ITEM: i32 = 0x34;
+        pub const SET_COMMAND_BLOCK: i32 = 0x35;
+        pub const SET_COMMAND_MINECART: i32 = 0x36;
+        pub const SET_CREATIVE_MODE_SLOT: i32 = 0x37;
+        pub const SET_JIGSAW_BLOCK: i32 = 0x38;
+        pub const SET_STRUCTURE_BLOCK: i32 = 0x39;
+        pub const SET_TEST_BLOCK: i32 = 0x3A;
+        pub const SIGN_UPDATE: i32 = 0x3B;
+        pub const SWING: i32 = 0x3C;
+        pub const TELEPORT_TO_ENTITY: i32 = 0x3D;
+        pub const TEST_INSTANCE_BLOCK_ACTION: i32 = 0x3E;
+        pub const USE_ITEM_ON: i32 = 0x3F;
+        pub const USE_ITEM: i32 = 0x40;
+        pub const CUSTOM_CLICK_ACTION: i32 = 0x41;
+    }
+
+    pub mod clientbound {
+        /// Packet names in id order.
+        pub const NAMES: &[&str] = &[
+            "bundle_delimiter",
+            "add_entity",
+            "animate",
+            "award_stats",
+            "block_changed_ack",
+            "block_destruction",
+            "block_entity_data",
+            "block_event",
+            "block_update",
+            "boss_event",
+            "change_difficulty",
+            "chunk_batch_finished",
+            "chunk_batch_start",
+            "chunks_biomes",
+            "clear_titles",
+            "command_suggestions",
+            "commands",
+            "container_close",
+            "container_set_content",
+            "container_set_data",
+            "container_set_slot",
+            "cookie_request",
+            "cooldown",
+            "custom_chat_completions",
+            "custom_payload",
+            "damage_event",
+            "debug_sample",
+            "delete_chat",
+            "disconnect",
+            "disguised_chat",
+            "entity_event",
+            "entity_position_sync",
+            "explode",
+            "forget_level_chunk",
+            "game_event",
+            "horse_screen_open",
+            "hurt_animation",
+            "initialize_border",
+            "keep_alive",
+            "level_chunk_with_light",
+            "level_event",
+            "level_particles",
+            "light_update",
+            "login",
+            "map_item_data",
+            "merchant_offers",
+            "move_entity_pos",
+            "move_entity_pos_rot",
+            "move_minecart_along_track",
+            "move_entity_rot",
+            "move_vehicle",
+            "open_book",
+            "open_screen",
+            "open_sign_editor",
+            "ping",
+            "pong_response",
+            "place_ghost_recipe",
+            "player_abilities",
+            "player_chat",
+            "player_combat_end",
+            "player_combat_enter",
+            "player_combat_kill",
+            "player_info_remove",
+            "player_info_update",
+            "player_look_at",
+            "player_position",
+            "player_rotation",
+            "recipe_book_add",
+            "recipe_book_remove",
+            "recipe_book_settings",
+            "remove_entities",
+            "remove_mob_effect",
+            "reset_score",
+            "resource_pack_pop",
+            "resource_pack_push",
+            "respawn",
+            "rotate_head",
+            "section_blocks_update",
+            "select_advancements_tab",
+            "server_data",
+            "set_action_bar_text",
+            "set_border_center",
+            "set_border_lerp_size",
+            "set_border_size",
+            "set_border_warning_delay",
+            "set_border_warning_distance",
+            "set_camera",
+            "set_chunk_cache_center",
+            "set_chunk_cache_radius",
+            "set_cursor_item",
+            "set_default_spawn_position",
+            "set_display_objective",
+            "set_entity_data",
+            "set_entity_link",
+            "set_entity_motion",
+            "set_equipment",
+            "set_experience",
+            "set_health",
+            "set_held_slot",
+            "set_objective",
+            "set_passengers",
+            "set_player_inventory",
+            "set_player_team",
+            "set_score",
+            "set_simulation_distance",
+            "set_subtitle_text",
+            "set_time",
+            "set_title_text",
+            "set_titles_animation",
+            "sound_entity",
+            "sound",
+            "start_configuration",
+            "stop_sound",
+            "store_cookie",
+            "system_chat",
+            "tab_list",
+            "tag_query",
+            "take_item_entity",
+            "teleport_entity",
+            "test_instance_block_status",
+            "ticking_state",
+            "ticking_step",
+            "transfer",
+            "update_advancements",
+            "update_attributes",
+            "update_mob_effect",
+            "update_recipes",
+            "update_tags",
+            "projectile_power",
+            "custom_report_details",
+            "server_links",
+            "waypoint",
+            "clear_dialog",
+            "show_dialog",
+        ];
+
+        pub const BUNDLE_DELIMITER: i32 = 0x00;
+        pub const ADD_ENTITY: i32 = 0x01;
+        pub const ANIMATE: i32 = 0x02;
+        pub const AWARD_STATS: i32 = 0x03;
+        pub const BLOCK_CHANGED_ACK: i32 = 0x04;
+        pub const BLOCK_DESTRUCTION: i32 = 0x05;
+        pub const BLOCK_ENTITY_DATA: i32 = 0x06;
+        pub const BLOCK_EVENT: i32 = 0x07;
+        pub const BLOCK_UPDATE: i32 = 0x08;
+        pub const BOSS_EVENT: i32 = 0x09;
+        pub const CHANGE_DIFFICULTY: i32 = 0x0A;
+        pub const CHUNK_BATCH_FINISHED: i32 = 0x0B;
+        pub const CHUNK_BATCH_START: i32 = 0x0C;
+        pub const CHUNKS_BIOMES: i32 = 0x0D;
+        pub const CLEAR_TITLES: i32 = 0x0E;
+        pub const COMMAND_SUGGESTIONS: i32 = 0x0F;
+        pub const COMMANDS: i32 = 0x10;
+        pub const CONTAINER_CLOSE: i32 = 0x11;
+        pub const CONTAINER_SET_CONTENT: i32 = 0x12;
+        pub const CONTAINER_SET_DATA: i32 = 0x13;
+        pub const CONTAINER_SET_SLOT: i32 = 0x14;
+        pub const COOKIE_REQUEST: i32 = 0x15;
+        pub const COOLDOWN: i32 = 0x16;
+        pub const CUSTOM_CHAT_COMPLETIONS: i32 = 0x17;
+        pub const CUSTOM_PAYLOAD: i32 = 0x18;
+        pub const DAMAGE_EVENT: i32 = 0x19;
+        pub const DEBUG_SAMPLE: i32 = 0x1A;
+        pub const DELETE_CHAT: i32 = 0x1B;
+        pub const DISCONNECT: i32 = 0x1C;
+        pub const DISGUISED_CHAT: i32 = 0x1D;
+        pub const ENTITY_EVENT: i32 = 0x1E;
+        pub const ENTITY_POSITION_SYNC: i32 = 0x1F;
+        pub const EXPLODE: i32 = 0x20;
+        pub const FORGET_LEVEL_CHUNK: i32 = 0x21;
+        pub const GAME_EVENT: i32 = 0x22;
+        pub const HORSE_SCREEN_OPEN: i32 = 0x23;
+        pub const HURT_ANIMATION: i32 = 0x24;
+        pub const INITIALIZE_BORDER: i32 = 0x25;
+        pub const KEEP_ALIVE: i32 = 0x26;
+        pub const LEVEL_CHUNK_WITH_LIGHT: i32 = 0x27;
+        pub const LEVEL_EVENT: i32 = 0x28;
+        pub const LEVEL_PARTICLES: i32 = 0x29;
+        pub const LIGHT_UPDATE: i32 = 0x2A;
+        pub const LOGIN: i32 = 0x2B;
+        pub const MAP_ITEM_DATA: i32 = 0x2C;
+        pub const MERCHANT_OFFERS: i32 = 0x2D;
+        pub const MOVE_ENTITY_POS: i32 = 0x2E;
+        pub const MOVE_ENTITY_POS_ROT: i32 = 0x2F;
+        pub const MOVE_MINECART_ALONG_TRACK: i32 = 0x30;
+        pub const MOVE_ENTITY_ROT: i32 = 0x31;
+        pub const MOVE_VEHICLE: i32 = 0x32;
+        pub const OPEN_BOOK: i32 = 0x33;
+        pub const OPEN_SCREEN: i32 = 0x34;
+        pub const OPEN_SIGN_EDITOR: i32 = 0x35;
+        pub const PING: i32 = 0x36;
+        pub const PONG_RESPONSE: i32 = 0x37;
+        pub const PLACE_GHOST_RECIPE: i32 = 0x38;
+        pub const PLAYER_ABILITIES: i32 = 0x39;
+        pub const PLAYER_CHAT: i32 = 0x3A;
+        pub const PLAYER_COMBAT_END: i32 = 0x3B;
+        pub const PLAYER_COMBAT_ENTER: i32 = 0x3C;
+        pub const PLAYER_COMBAT_KILL: i32 = 0x3D;
+        pub const PLAYER_INFO_REMOVE: i32 = 0x3E;
+        pub const PLAYER_INFO_UPDATE: i32 = 0x3F;
+        pub const PLAYER_LOOK_AT: i32 = 0x40;
+        pub const PLAYER_POSITION: i32 = 0x41;
+        pub const PLAYER_ROTATION: i32 = 0x42;
+        pub const RECIPE_BOOK_ADD: i32 = 0x43;
+        pub const RECIPE_BOOK_REMOVE: i32 = 0x44;
+        pub const RECIPE_BOOK_SETTINGS: i32 = 0x45;
+        pub const REMOVE_ENTITIES: i32 = 0x46;
+        pub const REMOVE_MOB_EFFECT: i32 = 0x47;
+        pub const RESET_SCORE: i32 = 0x48;
+        pub const RESOURCE_PACK_POP: i32 = 0x49;
+        pub const RESOURCE_PACK_PUSH: i32 = 0x4A;
+        pub const RESPAWN: i32 = 0x4B;
+        pub const ROTATE_HEAD: i32 = 0x4C;
+        pub const SECTION_BLOCKS_UPDATE: i32 = 0x4D;
+        pub const SELECT_ADVANCEMENTS_TAB: i32 = 0x4E;
+        pub const SERVER_DATA: i32 = 0x4F;
+        pub const SET_ACTION_BAR_TEXT: i32 = 0x50;
+        pub const SET_BORDER_CENTER: i32 = 0x51;
+        pub const SET_BORDER_LERP_SIZE: i32 = 0x52;
+        pub const SET_BORDER_SIZE: i32 = 0x53;
+        pub const SET_BORDER_WARNING_DELAY: i32 = 0x54;
+        pub const SET_BORDER_WARNING_DISTANCE: i32 = 0x55;
+        pub const SET_CAMERA: i32 = 0x56;
+        pub const SET_CHUNK_CACHE_CENTER: i32 = 0x57;
+        pub const SET_CHUNK_CACHE_RADIUS: i32 = 0x58;
+        pub const SET_CURSOR_ITEM: i32 = 0x59;
+        pub const SET_DEFAULT_SPAWN_POSITION: i32 = 0x5A;
+        pub const SET_DISPLAY_OBJECTIVE: i32 = 0x5B;
+        pub const SET_ENTITY_DATA: i32 = 0x5C;
+        pub const SET_ENTITY_LINK: i32 = 0x5D;
+        pub const SET_ENTITY_MOTION: i32 = 0x5E;
+        pub const SET_EQUIPMENT: i32 = 0x5F;
+        pub const SET_EXPERIENCE: i32 = 0x60;
+        pub const SET_HEALTH: i32 = 0x61;
+        pub const SET_HELD_SLOT: i32 = 0x62;
+        pub const SET_OBJECTIVE: i32 = 0x63;
+        pub const SET_PASSENGERS: i32 = 0x64;
+        pub const SET_PLAYER_INVENTORY: i32 = 0x65;
+        pub const SET_PLAYER_TEAM: i32 = 0x66;
+        pub const SET_SCORE: i32 = 0x67;
+        pub const SET_SIMULATION_DISTANCE: i32 = 0x68;
+        pub const SET_SUBTITLE_TEXT: i32 = 0x69;
+        pub const SET_TIME: i32 = 0x6A;
+        pub const SET_TITLE_TEXT: i32 = 0x6B;
+        pub const SET_TITLES_ANIMATION: i32 = 0x6C;
+        pub const SOUND_ENTITY: i32 = 0x6D;
+        pub const SOUND: i32 = 0x6E;
+        pub const START_CONFIGURATION: i32 = 0x6F;
+        pub const STOP_SOUND: i32 = 0x70;
+        pub const STORE_COOKIE: i32 = 0x71;
+        pub const SYSTEM_CHAT: i32 = 0x72;
+        pub const TAB_LIST: i32 = 0x73;
+        pub const TAG_QUERY: i32 = 0x74;
+        pub const TAKE_ITEM_ENTITY: i32 = 0x75;
+        pub const TELEPORT_ENTITY: i32 = 0x76;
+        pub const TEST_INSTANCE_BLOCK_STATUS: i32 = 0x77;
+        pub const TICKING_STATE: i32 = 0x78;
+        pub const TICKING_STEP: i32 = 0x79;
+        pub const TRANSFER: i32 = 0x7A;
+        pub const UPDATE_ADVANCEMENTS: i32 = 0x7B;
+        pub const UPDATE_ATTRIBUTES: i32 = 0x7C;
+        pub const UPDATE_MOB_EFFECT: i32 = 0x7D;
+        pub const UPDATE_RECIPES: i32 = 0x7E;
+        pub const UPDATE_TAGS: i32 = 0x7F;
+        pub const PROJECTILE_POWER: i32 = 0x80;
+        pub const CUSTOM_REPORT_DETAILS: i32 = 0x81;
+        pub const SERVER_LINKS: i32 = 0x82;
+        pub const WAYPOINT: i32 = 0x83;
+        pub const CLEAR_DIALOG: i32 = 0x84;
+        pub const SHOW_DIALOG: i32 = 0x85;
+    }
+}
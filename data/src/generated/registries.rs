@@ -0,0 +1,1398 @@
+//! Generated by `datagen` from the protocol 772 `registries.json` report. Do not edit by hand.
+
+use codec::dec::Decode;
+use codec::enc::Encode;
+
+use crate::registry::Registry;
+
+/// Entries of the `minecraft:block` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum Block {
+    Air = 0,
+    Stone = 1,
+    Granite = 2,
+    PolishedGranite = 3,
+    Diorite = 4,
+    PolishedDiorite = 5,
+    Andesite = 6,
+    PolishedAndesite = 7,
+    GrassBlock = 8,
+    Dirt = 9,
+    CoarseDirt = 10,
+    Podzol = 11,
+    Cobblestone = 12,
+    OakPlanks = 13,
+    SprucePlanks = 14,
+    BirchPlanks = 15,
+    JunglePlanks = 16,
+    AcaciaPlanks = 17,
+    CherryPlanks = 18,
+    DarkOakPlanks = 19,
+    PaleOakWood = 20,
+    PaleOakPlanks = 21,
+    MangrovePlanks = 22,
+    BambooPlanks = 23,
+    BambooMosaic = 24,
+    OakSapling = 25,
+    SpruceSapling = 26,
+    BirchSapling = 27,
+    JungleSapling = 28,
+    AcaciaSapling = 29,
+    CherrySapling = 30,
+    DarkOakSapling = 31,
+    PaleOakSapling = 32,
+    MangrovePropagule = 33,
+    Bedrock = 34,
+    Water = 35,
+    Lava = 36,
+    Sand = 37,
+    SuspiciousSand = 38,
+    RedSand = 39,
+    Gravel = 40,
+    SuspiciousGravel = 41,
+    GoldOre = 42,
+    DeepslateGoldOre = 43,
+    IronOre = 44,
+    DeepslateIronOre = 45,
+    CoalOre = 46,
+    DeepslateCoalOre = 47,
+    NetherGoldOre = 48,
+    OakLog = 49,
+}
+
+impl Block {
+    const IDENTIFIERS: [&'static str; 50] = [
+        "minecraft:air",
+        "minecraft:stone",
+        "minecraft:granite",
+        "minecraft:polished_granite",
+        "minecraft:diorite",
+        "minecraft:polished_diorite",
+        "minecraft:andesite",
+        "minecraft:polished_andesite",
+        "minecraft:grass_block",
+        "minecraft:dirt",
+        "minecraft:coarse_dirt",
+        "minecraft:podzol",
+        "minecraft:cobblestone",
+        "minecraft:oak_planks",
+        "minecraft:spruce_planks",
+        "minecraft:birch_planks",
+        "minecraft:jungle_planks",
+        "minecraft:acacia_planks",
+        "minecraft:cherry_planks",
+        "minecraft:dark_oak_planks",
+        "minecraft:pale_oak_wood",
+        "minecraft:pale_oak_planks",
+        "minecraft:mangrove_planks",
+        "minecraft:bamboo_planks",
+        "minecraft:bamboo_mosaic",
+        "minecraft:oak_sapling",
+        "minecraft:spruce_sapling",
+        "minecraft:birch_sapling",
+        "minecraft:jungle_sapling",
+        "minecraft:acacia_sapling",
+        "minecraft:cherry_sapling",
+        "minecraft:dark_oak_sapling",
+        "minecraft:pale_oak_sapling",
+        "minecraft:mangrove_propagule",
+        "minecraft:bedrock",
+        "minecraft:water",
+        "minecraft:lava",
+        "minecraft:sand",
+        "minecraft:suspicious_sand",
+        "minecraft:red_sand",
+        "minecraft:gravel",
+        "minecraft:suspicious_gravel",
+        "minecraft:gold_ore",
+        "minecraft:deepslate_gold_ore",
+        "minecraft:iron_ore",
+        "minecraft:deepslate_iron_ore",
+        "minecraft:coal_ore",
+        "minecraft:deepslate_coal_ore",
+        "minecraft:nether_gold_ore",
+        "minecraft:oak_log",
+    ];
+}
+
+impl Registry for Block {
+    const REGISTRY: &'static str = "minecraft:block";
+    const ENTRIES: &'static [Self] = &[
+        Self::Air,
+        Self::Stone,
+        Self::Granite,
+        Self::PolishedGranite,
+        Self::Diorite,
+        Self::PolishedDiorite,
+        Self::Andesite,
+        Self::PolishedAndesite,
+        Self::GrassBlock,
+        Self::Dirt,
+        Self::CoarseDirt,
+        Self::Podzol,
+        Self::Cobblestone,
+        Self::OakPlanks,
+        Self::SprucePlanks,
+        Self::BirchPlanks,
+        Self::JunglePlanks,
+        Self::AcaciaPlanks,
+        Self::CherryPlanks,
+        Self::DarkOakPlanks,
+        Self::PaleOakWood,
+        Self::PaleOakPlanks,
+        Self::MangrovePlanks,
+        Self::BambooPlanks,
+        Self::BambooMosaic,
+        Self::OakSapling,
+        Self::SpruceSapling,
+        Self::BirchSapling,
+        Self::JungleSapling,
+        Self::AcaciaSapling,
+        Self::CherrySapling,
+        Self::DarkOakSapling,
+        Self::PaleOakSapling,
+        Self::MangrovePropagule,
+        Self::Bedrock,
+        Self::Water,
+        Self::Lava,
+        Self::Sand,
+        Self::SuspiciousSand,
+        Self::RedSand,
+        Self::Gravel,
+        Self::SuspiciousGravel,
+        Self::GoldOre,
+        Self::DeepslateGoldOre,
+        Self::IronOre,
+        Self::DeepslateIronOre,
+        Self::CoalOre,
+        Self::DeepslateCoalOre,
+        Self::NetherGoldOre,
+        Self::OakLog,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
+
+/// Entries of the `minecraft:block_entity_type` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum BlockEntityType {
+    Furnace = 0,
+    Chest = 1,
+    TrappedChest = 2,
+    EnderChest = 3,
+    Jukebox = 4,
+    Dispenser = 5,
+    Dropper = 6,
+    Sign = 7,
+    HangingSign = 8,
+    MobSpawner = 9,
+    CreakingHeart = 10,
+    Piston = 11,
+    BrewingStand = 12,
+    EnchantingTable = 13,
+    EndPortal = 14,
+    Beacon = 15,
+    Skull = 16,
+    DaylightDetector = 17,
+    Hopper = 18,
+    Comparator = 19,
+    Banner = 20,
+    StructureBlock = 21,
+    EndGateway = 22,
+    CommandBlock = 23,
+    ShulkerBox = 24,
+    Bed = 25,
+    Conduit = 26,
+    Barrel = 27,
+    Smoker = 28,
+    BlastFurnace = 29,
+    Lectern = 30,
+    Bell = 31,
+    Jigsaw = 32,
+    Campfire = 33,
+    Beehive = 34,
+    SculkSensor = 35,
+    CalibratedSculkSensor = 36,
+    SculkCatalyst = 37,
+    SculkShrieker = 38,
+    ChiseledBookshelf = 39,
+    BrushableBlock = 40,
+    DecoratedPot = 41,
+    Crafter = 42,
+    TrialSpawner = 43,
+    Vault = 44,
+    TestBlock = 45,
+    TestInstanceBlock = 46,
+}
+
+impl BlockEntityType {
+    const IDENTIFIERS: [&'static str; 47] = [
+        "minecraft:furnace",
+        "minecraft:chest",
+        "minecraft:trapped_chest",
+        "minecraft:ender_chest",
+        "minecraft:jukebox",
+        "minecraft:dispenser",
+        "minecraft:dropper",
+        "minecraft:sign",
+        "minecraft:hanging_sign",
+        "minecraft:mob_spawner",
+        "minecraft:creaking_heart",
+        "minecraft:piston",
+        "minecraft:brewing_stand",
+        "minecraft:enchanting_table",
+        "minecraft:end_portal",
+        "minecraft:beacon",
+        "minecraft:skull",
+        "minecraft:daylight_detector",
+        "minecraft:hopper",
+        "minecraft:comparator",
+        "minecraft:banner",
+        "minecraft:structure_block",
+        "minecraft:end_gateway",
+        "minecraft:command_block",
+        "minecraft:shulker_box",
+        "minecraft:bed",
+        "minecraft:conduit",
+        "minecraft:barrel",
+        "minecraft:smoker",
+        "minecraft:blast_furnace",
+        "minecraft:lectern",
+        "minecraft:bell",
+        "minecraft:jigsaw",
+        "minecraft:campfire",
+        "minecraft:beehive",
+        "minecraft:sculk_sensor",
+        "minecraft:calibrated_sculk_sensor",
+        "minecraft:sculk_catalyst",
+        "minecraft:sculk_shrieker",
+        "minecraft:chiseled_bookshelf",
+        "minecraft:brushable_block",
+        "minecraft:decorated_pot",
+        "minecraft:crafter",
+        "minecraft:trial_spawner",
+        "minecraft:vault",
+        "minecraft:test_block",
+        "minecraft:test_instance_block",
+    ];
+}
+
+impl Registry for BlockEntityType {
+    const REGISTRY: &'static str = "minecraft:block_entity_type";
+    const ENTRIES: &'static [Self] = &[
+        Self::Furnace,
+        Self::Chest,
+        Self::TrappedChest,
+        Self::EnderChest,
+        Self::Jukebox,
+        Self::Dispenser,
+        Self::Dropper,
+        Self::Sign,
+        Self::HangingSign,
+        Self::MobSpawner,
+        Self::CreakingHeart,
+        Self::Piston,
+        Self::BrewingStand,
+        Self::EnchantingTable,
+        Self::EndPortal,
+        Self::Beacon,
+        Self::Skull,
+        Self::DaylightDetector,
+        Self::Hopper,
+        Self::Comparator,
+        Self::Banner,
+        Self::StructureBlock,
+        Self::EndGateway,
+        Self::CommandBlock,
+        Self::ShulkerBox,
+        Self::Bed,
+        Self::Conduit,
+        Self::Barrel,
+        Self::Smoker,
+        Self::BlastFurnace,
+        Self::Lectern,
+        Self::Bell,
+        Self::Jigsaw,
+        Self::Campfire,
+        Self::Beehive,
+        Self::SculkSensor,
+        Self::CalibratedSculkSensor,
+        Self::SculkCatalyst,
+        Self::SculkShrieker,
+        Self::ChiseledBookshelf,
+        Self::BrushableBlock,
+        Self::DecoratedPot,
+        Self::Crafter,
+        Self::TrialSpawner,
+        Self::Vault,
+        Self::TestBlock,
+        Self::TestInstanceBlock,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
+
+/// Entries of the `minecraft:item` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum Item {
+    Air = 0,
+    Stone = 1,
+    Granite = 2,
+    PolishedGranite = 3,
+    Diorite = 4,
+    PolishedDiorite = 5,
+    Andesite = 6,
+    PolishedAndesite = 7,
+    Deepslate = 8,
+    CobbledDeepslate = 9,
+    PolishedDeepslate = 10,
+    Calcite = 11,
+    Tuff = 12,
+    TuffSlab = 13,
+    TuffStairs = 14,
+    TuffWall = 15,
+    ChiseledTuff = 16,
+    PolishedTuff = 17,
+    PolishedTuffSlab = 18,
+    PolishedTuffStairs = 19,
+    PolishedTuffWall = 20,
+    TuffBricks = 21,
+    TuffBrickSlab = 22,
+    TuffBrickStairs = 23,
+    TuffBrickWall = 24,
+    ChiseledTuffBricks = 25,
+    DripstoneBlock = 26,
+    GrassBlock = 27,
+    Dirt = 28,
+    CoarseDirt = 29,
+    Podzol = 30,
+    RootedDirt = 31,
+    Mud = 32,
+    CrimsonNylium = 33,
+    WarpedNylium = 34,
+    Cobblestone = 35,
+}
+
+impl Item {
+    const IDENTIFIERS: [&'static str; 36] = [
+        "minecraft:air",
+        "minecraft:stone",
+        "minecraft:granite",
+        "minecraft:polished_granite",
+        "minecraft:diorite",
+        "minecraft:polished_diorite",
+        "minecraft:andesite",
+        "minecraft:polished_andesite",
+        "minecraft:deepslate",
+        "minecraft:cobbled_deepslate",
+        "minecraft:polished_deepslate",
+        "minecraft:calcite",
+        "minecraft:tuff",
+        "minecraft:tuff_slab",
+        "minecraft:tuff_stairs",
+        "minecraft:tuff_wall",
+        "minecraft:chiseled_tuff",
+        "minecraft:polished_tuff",
+        "minecraft:polished_tuff_slab",
+        "minecraft:polished_tuff_stairs",
+        "minecraft:polished_tuff_wall",
+        "minecraft:tuff_bricks",
+        "minecraft:tuff_brick_slab",
+        "minecraft:tuff_brick_stairs",
+        "minecraft:tuff_brick_wall",
+        "minecraft:chiseled_tuff_bricks",
+        "minecraft:dripstone_block",
+        "minecraft:grass_block",
+        "minecraft:dirt",
+        "minecraft:coarse_dirt",
+        "minecraft:podzol",
+        "minecraft:rooted_dirt",
+        "minecraft:mud",
+        "minecraft:crimson_nylium",
+        "minecraft:warped_nylium",
+        "minecraft:cobblestone",
+    ];
+}
+
+impl Registry for Item {
+    const REGISTRY: &'static str = "minecraft:item";
+    const ENTRIES: &'static [Self] = &[
+        Self::Air,
+        Self::Stone,
+        Self::Granite,
+        Self::PolishedGranite,
+        Self::Diorite,
+        Self::PolishedDiorite,
+        Self::Andesite,
+        Self::PolishedAndesite,
+        Self::Deepslate,
+        Self::CobbledDeepslate,
+        Self::PolishedDeepslate,
+        Self::Calcite,
+        Self::Tuff,
+        Self::TuffSlab,
+        Self::TuffStairs,
+        Self::TuffWall,
+        Self::ChiseledTuff,
+        Self::PolishedTuff,
+        Self::PolishedTuffSlab,
+        Self::PolishedTuffStairs,
+        Self::PolishedTuffWall,
+        Self::TuffBricks,
+        Self::TuffBrickSlab,
+        Self::TuffBrickStairs,
+        Self::TuffBrickWall,
+        Self::ChiseledTuffBricks,
+        Self::DripstoneBlock,
+        Self::GrassBlock,
+        Self::Dirt,
+        Self::CoarseDirt,
+        Self::Podzol,
+        Self::RootedDirt,
+        Self::Mud,
+        Self::CrimsonNylium,
+        Self::WarpedNylium,
+        Self::Cobblestone,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
+
+/// Entries of the `minecraft:entity_type` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum EntityType {
+    AcaciaBoat = 0,
+    AcaciaChestBoat = 1,
+    Allay = 2,
+    AreaEffectCloud = 3,
+    Armadillo = 4,
+    ArmorStand = 5,
+    Arrow = 6,
+    Axolotl = 7,
+    BambooChestRaft = 8,
+    BambooRaft = 9,
+    Bat = 10,
+    Bee = 11,
+    BirchBoat = 12,
+    BirchChestBoat = 13,
+    Blaze = 14,
+    BlockDisplay = 15,
+    Bogged = 16,
+    Breeze = 17,
+    BreezeWindCharge = 18,
+    Camel = 19,
+    Cat = 20,
+    CaveSpider = 21,
+    CherryBoat = 22,
+    CherryChestBoat = 23,
+    ChestMinecart = 24,
+    Chicken = 25,
+    Cod = 26,
+    CommandBlockMinecart = 27,
+    Cow = 28,
+    Creaking = 29,
+    Creeper = 30,
+}
+
+impl EntityType {
+    const IDENTIFIERS: [&'static str; 31] = [
+        "minecraft:acacia_boat",
+        "minecraft:acacia_chest_boat",
+        "minecraft:allay",
+        "minecraft:area_effect_cloud",
+        "minecraft:armadillo",
+        "minecraft:armor_stand",
+        "minecraft:arrow",
+        "minecraft:axolotl",
+        "minecraft:bamboo_chest_raft",
+        "minecraft:bamboo_raft",
+        "minecraft:bat",
+        "minecraft:bee",
+        "minecraft:birch_boat",
+        "minecraft:birch_chest_boat",
+        "minecraft:blaze",
+        "minecraft:block_display",
+        "minecraft:bogged",
+        "minecraft:breeze",
+        "minecraft:breeze_wind_charge",
+        "minecraft:camel",
+        "minecraft:cat",
+        "minecraft:cave_spider",
+        "minecraft:cherry_boat",
+        "minecraft:cherry_chest_boat",
+        "minecraft:chest_minecart",
+        "minecraft:chicken",
+        "minecraft:cod",
+        "minecraft:command_block_minecart",
+        "minecraft:cow",
+        "minecraft:creaking",
+        "minecraft:creeper",
+    ];
+}
+
+impl Registry for EntityType {
+    const REGISTRY: &'static str = "minecraft:entity_type";
+    const ENTRIES: &'static [Self] = &[
+        Self::AcaciaBoat,
+        Self::AcaciaChestBoat,
+        Self::Allay,
+        Self::AreaEffectCloud,
+        Self::Armadillo,
+        Self::ArmorStand,
+        Self::Arrow,
+        Self::Axolotl,
+        Self::BambooChestRaft,
+        Self::BambooRaft,
+        Self::Bat,
+        Self::Bee,
+        Self::BirchBoat,
+        Self::BirchChestBoat,
+        Self::Blaze,
+        Self::BlockDisplay,
+        Self::Bogged,
+        Self::Breeze,
+        Self::BreezeWindCharge,
+        Self::Camel,
+        Self::Cat,
+        Self::CaveSpider,
+        Self::CherryBoat,
+        Self::CherryChestBoat,
+        Self::ChestMinecart,
+        Self::Chicken,
+        Self::Cod,
+        Self::CommandBlockMinecart,
+        Self::Cow,
+        Self::Creaking,
+        Self::Creeper,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
+
+/// Entries of the `minecraft:particle_type` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum ParticleType {
+    AngryVillager = 0,
+    Block = 1,
+    BlockMarker = 2,
+    Bubble = 3,
+    Cloud = 4,
+    Crit = 5,
+    DamageIndicator = 6,
+    DragonBreath = 7,
+    DrippingLava = 8,
+    FallingLava = 9,
+    LandingLava = 10,
+    DrippingWater = 11,
+    FallingWater = 12,
+    Dust = 13,
+    DustColorTransition = 14,
+    Effect = 15,
+    ElderGuardian = 16,
+    EnchantedHit = 17,
+    Enchant = 18,
+    EndRod = 19,
+    EntityEffect = 20,
+    ExplosionEmitter = 21,
+    Explosion = 22,
+    Gust = 23,
+    GustEmitterLarge = 24,
+    GustEmitterSmall = 25,
+    SonicBoom = 26,
+    FallingDust = 27,
+    Firework = 28,
+    Fishing = 29,
+    Flame = 30,
+    Infested = 31,
+    CherryLeaves = 32,
+    PaleOakLeaves = 33,
+    TintedLeaves = 34,
+    SculkSoul = 35,
+    SculkCharge = 36,
+    SculkChargePop = 37,
+    SoulFireFlame = 38,
+    Soul = 39,
+    Flash = 40,
+    HappyVillager = 41,
+    Composter = 42,
+    Heart = 43,
+    InstantEffect = 44,
+    Item = 45,
+    Vibration = 46,
+    Trail = 47,
+    ItemSlime = 48,
+    ItemCobweb = 49,
+    ItemSnowball = 50,
+    LargeSmoke = 51,
+    Lava = 52,
+    Mycelium = 53,
+    Note = 54,
+    Poof = 55,
+    Portal = 56,
+    Rain = 57,
+    Smoke = 58,
+    WhiteSmoke = 59,
+    Sneeze = 60,
+    Spit = 61,
+    SquidInk = 62,
+    SweepAttack = 63,
+    TotemOfUndying = 64,
+    Underwater = 65,
+    Splash = 66,
+    Witch = 67,
+    BubblePop = 68,
+    CurrentDown = 69,
+    BubbleColumnUp = 70,
+    Nautilus = 71,
+    Dolphin = 72,
+    CampfireCosySmoke = 73,
+    CampfireSignalSmoke = 74,
+    DrippingHoney = 75,
+    FallingHoney = 76,
+    LandingHoney = 77,
+    FallingNectar = 78,
+    FallingSporeBlossom = 79,
+    Ash = 80,
+    CrimsonSpore = 81,
+    WarpedSpore = 82,
+    SporeBlossomAir = 83,
+    DrippingObsidianTear = 84,
+    FallingObsidianTear = 85,
+    LandingObsidianTear = 86,
+    ReversePortal = 87,
+    WhiteAsh = 88,
+    SmallFlame = 89,
+    Snowflake = 90,
+    DrippingDripstoneLava = 91,
+    FallingDripstoneLava = 92,
+    DrippingDripstoneWater = 93,
+    FallingDripstoneWater = 94,
+    GlowSquidInk = 95,
+    Glow = 96,
+    ElectricSpark = 97,
+    WaxOn = 98,
+    WaxOff = 99,
+    Scrape = 100,
+    Shriek = 101,
+    EggCrack = 102,
+    DustPlume = 103,
+    TrialSpawnerDetection = 104,
+    TrialSpawnerDetectionOminous = 105,
+    VaultConnection = 106,
+    DustPillar = 107,
+    OminousSpawning = 108,
+    RaidOmen = 109,
+    TrialOmen = 110,
+    BlockCrumble = 111,
+    Firefly = 112,
+}
+
+impl ParticleType {
+    const IDENTIFIERS: [&'static str; 113] = [
+        "minecraft:angry_villager",
+        "minecraft:block",
+        "minecraft:block_marker",
+        "minecraft:bubble",
+        "minecraft:cloud",
+        "minecraft:crit",
+        "minecraft:damage_indicator",
+        "minecraft:dragon_breath",
+        "minecraft:dripping_lava",
+        "minecraft:falling_lava",
+        "minecraft:landing_lava",
+        "minecraft:dripping_water",
+        "minecraft:falling_water",
+        "minecraft:dust",
+        "minecraft:dust_color_transition",
+        "minecraft:effect",
+        "minecraft:elder_guardian",
+        "minecraft:enchanted_hit",
+        "minecraft:enchant",
+        "minecraft:end_rod",
+        "minecraft:entity_effect",
+        "minecraft:explosion_emitter",
+        "minecraft:explosion",
+        "minecraft:gust",
+        "minecraft:gust_emitter_large",
+        "minecraft:gust_emitter_small",
+        "minecraft:sonic_boom",
+        "minecraft:falling_dust",
+        "minecraft:firework",
+        "minecraft:fishing",
+        "minecraft:flame",
+        "minecraft:infested",
+        "minecraft:cherry_leaves",
+        "minecraft:pale_oak_leaves",
+        "minecraft:tinted_leaves",
+        "minecraft:sculk_soul",
+        "minecraft:sculk_charge",
+        "minecraft:sculk_charge_pop",
+        "minecraft:soul_fire_flame",
+        "minecraft:soul",
+        "minecraft:flash",
+        "minecraft:happy_villager",
+        "minecraft:composter",
+        "minecraft:heart",
+        "minecraft:instant_effect",
+        "minecraft:item",
+        "minecraft:vibration",
+        "minecraft:trail",
+        "minecraft:item_slime",
+        "minecraft:item_cobweb",
+        "minecraft:item_snowball",
+        "minecraft:large_smoke",
+        "minecraft:lava",
+        "minecraft:mycelium",
+        "minecraft:note",
+        "minecraft:poof",
+        "minecraft:portal",
+        "minecraft:rain",
+        "minecraft:smoke",
+        "minecraft:white_smoke",
+        "minecraft:sneeze",
+        "minecraft:spit",
+        "minecraft:squid_ink",
+        "minecraft:sweep_attack",
+        "minecraft:totem_of_undying",
+        "minecraft:underwater",
+        "minecraft:splash",
+        "minecraft:witch",
+        "minecraft:bubble_pop",
+        "minecraft:current_down",
+        "minecraft:bubble_column_up",
+        "minecraft:nautilus",
+        "minecraft:dolphin",
+        "minecraft:campfire_cosy_smoke",
+        "minecraft:campfire_signal_smoke",
+        "minecraft:dripping_honey",
+        "minecraft:falling_honey",
+        "minecraft:landing_honey",
+        "minecraft:falling_nectar",
+        "minecraft:falling_spore_blossom",
+        "minecraft:ash",
+        "minecraft:crimson_spore",
+        "minecraft:warped_spore",
+        "minecraft:spore_blossom_air",
+        "minecraft:dripping_obsidian_tear",
+        "minecraft:falling_obsidian_tear",
+        "minecraft:landing_obsidian_tear",
+        "minecraft:reverse_portal",
+        "minecraft:white_ash",
+        "minecraft:small_flame",
+        "minecraft:snowflake",
+        "minecraft:dripping_dripstone_lava",
+        "minecraft:falling_dripstone_lava",
+        "minecraft:dripping_dripstone_water",
+        "minecraft:falling_dripstone_water",
+        "minecraft:glow_squid_ink",
+        "minecraft:glow",
+        "minecraft:electric_spark",
+        "minecraft:wax_on",
+        "minecraft:wax_off",
+        "minecraft:scrape",
+        "minecraft:shriek",
+        "minecraft:egg_crack",
+        "minecraft:dust_plume",
+        "minecraft:trial_spawner_detection",
+        "minecraft:trial_spawner_detection_ominous",
+        "minecraft:vault_connection",
+        "minecraft:dust_pillar",
+        "minecraft:ominous_spawning",
+        "minecraft:raid_omen",
+        "minecraft:trial_omen",
+        "minecraft:block_crumble",
+        "minecraft:firefly",
+    ];
+}
+
+impl Registry for ParticleType {
+    const REGISTRY: &'static str = "minecraft:particle_type";
+    const ENTRIES: &'static [Self] = &[
+        Self::AngryVillager,
+        Self::Block,
+        Self::BlockMarker,
+        Self::Bubble,
+        Self::Cloud,
+        Self::Crit,
+        Self::DamageIndicator,
+        Self::DragonBreath,
+        Self::DrippingLava,
+        Self::FallingLava,
+        Self::LandingLava,
+        Self::DrippingWater,
+        Self::FallingWater,
+        Self::Dust,
+        Self::DustColorTransition,
+        Self::Effect,
+        Self::ElderGuardian,
+        Self::EnchantedHit,
+        Self::Enchant,
+        Self::EndRod,
+        Self::EntityEffect,
+        Self::ExplosionEmitter,
+        Self::Explosion,
+        Self::Gust,
+        Self::GustEmitterLarge,
+        Self::GustEmitterSmall,
+        Self::SonicBoom,
+        Self::FallingDust,
+        Self::Firework,
+        Self::Fishing,
+        Self::Flame,
+        Self::Infested,
+        Self::CherryLeaves,
+        Self::PaleOakLeaves,
+        Self::TintedLeaves,
+        Self::SculkSoul,
+        Self::SculkCharge,
+        Self::SculkChargePop,
+        Self::SoulFireFlame,
+        Self::Soul,
+        Self::Flash,
+        Self::HappyVillager,
+        Self::Composter,
+        Self::Heart,
+        Self::InstantEffect,
+        Self::Item,
+        Self::Vibration,
+        Self::Trail,
+        Self::ItemSlime,
+        Self::ItemCobweb,
+        Self::ItemSnowball,
+        Self::LargeSmoke,
+        Self::Lava,
+        Self::Mycelium,
+        Self::Note,
+        Self::Poof,
+        Self::Portal,
+        Self::Rain,
+        Self::Smoke,
+        Self::WhiteSmoke,
+        Self::Sneeze,
+        Self::Spit,
+        Self::SquidInk,
+        Self::SweepAttack,
+        Self::TotemOfUndying,
+        Self::Underwater,
+        Self::Splash,
+        Self::Witch,
+        Self::BubblePop,
+        Self::CurrentDown,
+        Self::BubbleColumnUp,
+        Self::Nautilus,
+        Self::Dolphin,
+        Self::CampfireCosySmoke,
+        Self::CampfireSignalSmoke,
+        Self::DrippingHoney,
+        Self::FallingHoney,
+        Self::LandingHoney,
+        Self::FallingNectar,
+        Self::FallingSporeBlossom,
+        Self::Ash,
+        Self::CrimsonSpore,
+        Self::WarpedSpore,
+        Self::SporeBlossomAir,
+        Self::DrippingObsidianTear,
+        Self::FallingObsidianTear,
+        Self::LandingObsidianTear,
+        Self::ReversePortal,
+        Self::WhiteAsh,
+        Self::SmallFlame,
+        Self::Snowflake,
+        Self::DrippingDripstoneLava,
+        Self::FallingDripstoneLava,
+        Self::DrippingDripstoneWater,
+        Self::FallingDripstoneWater,
+        Self::GlowSquidInk,
+        Self::Glow,
+        Self::ElectricSpark,
+        Self::WaxOn,
+        Self::WaxOff,
+        Self::Scrape,
+        Self::Shriek,
+        Self::EggCrack,
+        Self::DustPlume,
+        Self::TrialSpawnerDetection,
+        Self::TrialSpawnerDetectionOminous,
+        Self::VaultConnection,
+        Self::DustPillar,
+        Self::OminousSpawning,
+        Self::RaidOmen,
+        Self::TrialOmen,
+        Self::BlockCrumble,
+        Self::Firefly,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
+
+/// Entries of the `minecraft:data_component_type` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum DataComponentType {
+    CustomData = 0,
+    MaxStackSize = 1,
+    MaxDamage = 2,
+    Damage = 3,
+    Unbreakable = 4,
+    CustomName = 5,
+    ItemName = 6,
+    ItemModel = 7,
+    Lore = 8,
+    Rarity = 9,
+    Enchantments = 10,
+    CanPlaceOn = 11,
+    CanBreak = 12,
+    AttributeModifiers = 13,
+    CustomModelData = 14,
+    TooltipDisplay = 15,
+    RepairCost = 16,
+    CreativeSlotLock = 17,
+    EnchantmentGlintOverride = 18,
+    IntangibleProjectile = 19,
+    Food = 20,
+    Consumable = 21,
+    UseRemainder = 22,
+    UseCooldown = 23,
+    DamageResistant = 24,
+    Tool = 25,
+    Weapon = 26,
+    Enchantable = 27,
+    Equippable = 28,
+    Repairable = 29,
+    Glider = 30,
+    TooltipStyle = 31,
+    DeathProtection = 32,
+    BlocksAttacks = 33,
+    StoredEnchantments = 34,
+    DyedColor = 35,
+    MapColor = 36,
+    MapId = 37,
+    MapDecorations = 38,
+    MapPostProcessing = 39,
+    ChargedProjectiles = 40,
+    BundleContents = 41,
+    PotionContents = 42,
+    PotionDurationScale = 43,
+    SuspiciousStewEffects = 44,
+    WritableBookContent = 45,
+    WrittenBookContent = 46,
+    Trim = 47,
+    DebugStickState = 48,
+    EntityData = 49,
+    BucketEntityData = 50,
+    BlockEntityData = 51,
+    Instrument = 52,
+    ProvidesTrimMaterial = 53,
+    OminousBottleAmplifier = 54,
+    JukeboxPlayable = 55,
+    ProvidesBannerPatterns = 56,
+    Recipes = 57,
+    LodestoneTracker = 58,
+    FireworkExplosion = 59,
+    Fireworks = 60,
+    Profile = 61,
+    NoteBlockSound = 62,
+    BannerPatterns = 63,
+    BaseColor = 64,
+    PotDecorations = 65,
+    Container = 66,
+    BlockState = 67,
+    Bees = 68,
+    Lock = 69,
+    ContainerLoot = 70,
+    BreakSound = 71,
+}
+
+impl DataComponentType {
+    const IDENTIFIERS: [&'static str; 72] = [
+        "minecraft:custom_data",
+        "minecraft:max_stack_size",
+        "minecraft:max_damage",
+        "minecraft:damage",
+        "minecraft:unbreakable",
+        "minecraft:custom_name",
+        "minecraft:item_name",
+        "minecraft:item_model",
+        "minecraft:lore",
+        "minecraft:rarity",
+        "minecraft:enchantments",
+        "minecraft:can_place_on",
+        "minecraft:can_break",
+        "minecraft:attribute_modifiers",
+        "minecraft:custom_model_data",
+        "minecraft:tooltip_display",
+        "minecraft:repair_cost",
+        "minecraft:creative_slot_lock",
+        "minecraft:enchantment_glint_override",
+        "minecraft:intangible_projectile",
+        "minecraft:food",
+        "minecraft:consumable",
+        "minecraft:use_remainder",
+        "minecraft:use_cooldown",
+        "minecraft:damage_resistant",
+        "minecraft:tool",
+        "minecraft:weapon",
+        "minecraft:enchantable",
+        "minecraft:equippable",
+        "minecraft:repairable",
+        "minecraft:glider",
+        "minecraft:tooltip_style",
+        "minecraft:death_protection",
+        "minecraft:blocks_attacks",
+        "minecraft:stored_enchantments",
+        "minecraft:dyed_color",
+        "minecraft:map_color",
+        "minecraft:map_id",
+        "minecraft:map_decorations",
+        "minecraft:map_post_processing",
+        "minecraft:charged_projectiles",
+        "minecraft:bundle_contents",
+        "minecraft:potion_contents",
+        "minecraft:potion_duration_scale",
+        "minecraft:suspicious_stew_effects",
+        "minecraft:writable_book_content",
+        "minecraft:written_book_content",
+        "minecraft:trim",
+        "minecraft:debug_stick_state",
+        "minecraft:entity_data",
+        "minecraft:bucket_entity_data",
+        "minecraft:block_entity_data",
+        "minecraft:instrument",
+        "minecraft:provides_trim_material",
+        "minecraft:ominous_bottle_amplifier",
+        "minecraft:jukebox_playable",
+        "minecraft:provides_banner_patterns",
+        "minecraft:recipes",
+        "minecraft:lodestone_tracker",
+        "minecraft:firework_explosion",
+        "minecraft:fireworks",
+        "minecraft:profile",
+        "minecraft:note_block_sound",
+        "minecraft:banner_patterns",
+        "minecraft:base_color",
+        "minecraft:pot_decorations",
+        "minecraft:container",
+        "minecraft:block_state",
+        "minecraft:bees",
+        "minecraft:lock",
+        "minecraft:container_loot",
+        "minecraft:break_sound",
+    ];
+}
+
+impl Registry for DataComponentType {
+    const REGISTRY: &'static str = "minecraft:data_component_type";
+    const ENTRIES: &'static [Self] = &[
+        Self::CustomData,
+        Self::MaxStackSize,
+        Self::MaxDamage,
+        Self::Damage,
+        Self::Unbreakable,
+        Self::CustomName,
+        Self::ItemName,
+        Self::ItemModel,
+        Self::Lore,
+        Self::Rarity,
+        Self::Enchantments,
+        Self::CanPlaceOn,
+        Self::CanBreak,
+        Self::AttributeModifiers,
+        Self::CustomModelData,
+        Self::TooltipDisplay,
+        Self::RepairCost,
+        Self::CreativeSlotLock,
+        Self::EnchantmentGlintOverride,
+        Self::IntangibleProjectile,
+        Self::Food,
+        Self::Consumable,
+        Self::UseRemainder,
+        Self::UseCooldown,
+        Self::DamageResistant,
+        Self::Tool,
+        Self::Weapon,
+        Self::Enchantable,
+        Self::Equippable,
+        Self::Repairable,
+        Self::Glider,
+        Self::TooltipStyle,
+        Self::DeathProtection,
+        Self::BlocksAttacks,
+        Self::StoredEnchantments,
+        Self::DyedColor,
+        Self::MapColor,
+        Self::MapId,
+        Self::MapDecorations,
+        Self::MapPostProcessing,
+        Self::ChargedProjectiles,
+        Self::BundleContents,
+        Self::PotionContents,
+        Self::PotionDurationScale,
+        Self::SuspiciousStewEffects,
+        Self::WritableBookContent,
+        Self::WrittenBookContent,
+        Self::Trim,
+        Self::DebugStickState,
+        Self::EntityData,
+        Self::BucketEntityData,
+        Self::BlockEntityData,
+        Self::Instrument,
+        Self::ProvidesTrimMaterial,
+        Self::OminousBottleAmplifier,
+        Self::JukeboxPlayable,
+        Self::ProvidesBannerPatterns,
+        Self::Recipes,
+        Self::LodestoneTracker,
+        Self::FireworkExplosion,
+        Self::Fireworks,
+        Self::Profile,
+        Self::NoteBlockSound,
+        Self::BannerPatterns,
+        Self::BaseColor,
+        Self::PotDecorations,
+        Self::Container,
+        Self::BlockState,
+        Self::Bees,
+        Self::Lock,
+        Self::ContainerLoot,
+        Self::BreakSound,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
+
+/// Entries of the `minecraft:worldgen/biome` registry.
+///
+/// Ids are those of the vanilla data pack. Servers with other data packs assign
+/// their own in the registry data they send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]
+#[codec(varint)]
+pub enum Biome {
+    Badlands = 0,
+    BambooJungle = 1,
+    BasaltDeltas = 2,
+    Beach = 3,
+    BirchForest = 4,
+    CherryGrove = 5,
+    ColdOcean = 6,
+    CrimsonForest = 7,
+    DarkForest = 8,
+    DeepColdOcean = 9,
+    DeepDark = 10,
+    DeepFrozenOcean = 11,
+    DeepLukewarmOcean = 12,
+    DeepOcean = 13,
+    Desert = 14,
+    DripstoneCaves = 15,
+    EndBarrens = 16,
+    EndHighlands = 17,
+    EndMidlands = 18,
+    ErodedBadlands = 19,
+    FlowerForest = 20,
+    Forest = 21,
+    FrozenOcean = 22,
+    FrozenPeaks = 23,
+    FrozenRiver = 24,
+    Grove = 25,
+    IceSpikes = 26,
+    JaggedPeaks = 27,
+    Jungle = 28,
+    LukewarmOcean = 29,
+    LushCaves = 30,
+    MangroveSwamp = 31,
+    Meadow = 32,
+    MushroomFields = 33,
+    NetherWastes = 34,
+    Ocean = 35,
+    OldGrowthBirchForest = 36,
+    OldGrowthPineTaiga = 37,
+    OldGrowthSpruceTaiga = 38,
+    PaleGarden = 39,
+    Plains = 40,
+    River = 41,
+    Savanna = 42,
+    SavannaPlateau = 43,
+    SmallEndIslands = 44,
+    SnowyBeach = 45,
+    SnowyPlains = 46,
+    SnowySlopes = 47,
+    SnowyTaiga = 48,
+    SoulSandValley = 49,
+    SparseJungle = 50,
+    StonyPeaks = 51,
+    StonyShore = 52,
+    SunflowerPlains = 53,
+    Swamp = 54,
+    Taiga = 55,
+    TheEnd = 56,
+    TheVoid = 57,
+    WarmOcean = 58,
+    WarpedForest = 59,
+    WindsweptForest = 60,
+    WindsweptGravellyHills = 61,
+    WindsweptHills = 62,
+    WindsweptSavanna = 63,
+    WoodedBadlands = 64,
+}
+
+impl Biome {
+    const IDENTIFIERS: [&'static str; 65] = [
+        "minecraft:badlands",
+        "minecraft:bamboo_jungle",
+        "minecraft:basalt_deltas",
+        "minecraft:beach",
+        "minecraft:birch_forest",
+        "minecraft:cherry_grove",
+        "minecraft:cold_ocean",
+        "minecraft:crimson_forest",
+        "minecraft:dark_forest",
+        "minecraft:deep_cold_ocean",
+        "minecraft:deep_dark",
+        "minecraft:deep_frozen_ocean",
+        "minecraft:deep_lukewarm_ocean",
+        "minecraft:deep_ocean",
+        "minecraft:desert",
+        "minecraft:dripstone_caves",
+        "minecraft:end_barrens",
+        "minecraft:end_highlands",
+        "minecraft:end_midlands",
+        "minecraft:eroded_badlands",
+        "minecraft:flower_forest",
+        "minecraft:forest",
+        "minecraft:frozen_ocean",
+        "minecraft:frozen_peaks",
+        "minecraft:frozen_river",
+        "minecraft:grove",
+        "minecraft:ice_spikes",
+        "minecraft:jagged_peaks",
+        "minecraft:jungle",
+        "minecraft:lukewarm_ocean",
+        "minecraft:lush_caves",
+        "minecraft:mangrove_swamp",
+        "minecraft:meadow",
+        "minecraft:mushroom_fields",
+        "minecraft:nether_wastes",
+        "minecraft:ocean",
+        "minecraft:old_growth_birch_forest",
+        "minecraft:old_growth_pine_taiga",
+        "minecraft:old_growth_spruce_taiga",
+        "minecraft:pale_garden",
+        "minecraft:plains",
+        "minecraft:river",
+        "minecraft:savanna",
+        "minecraft:savanna_plateau",
+        "minecraft:small_end_islands",
+        "minecraft:snowy_beach",
+        "minecraft:snowy_plains",
+        "minecraft:snowy_slopes",
+        "minecraft:snowy_taiga",
+        "minecraft:soul_sand_valley",
+        "minecraft:sparse_jungle",
+        "minecraft:stony_peaks",
+        "minecraft:stony_shore",
+        "minecraft:sunflower_plains",
+        "minecraft:swamp",
+        "minecraft:taiga",
+        "minecraft:the_end",
+        "minecraft:the_void",
+        "minecraft:warm_ocean",
+        "minecraft:warped_forest",
+        "minecraft:windswept_forest",
+        "minecraft:windswept_gravelly_hills",
+        "minecraft:windswept_hills",
+        "minecraft:windswept_savanna",
+        "minecraft:wooded_badlands",
+    ];
+}
+
+impl Registry for Biome {
+    const REGISTRY: &'static str = "minecraft:worldgen/biome";
+    const ENTRIES: &'static [Self] = &[
+        Self::Badlands,
+        Self::BambooJungle,
+        Self::BasaltDeltas,
+        Self::Beach,
+        Self::BirchForest,
+        Self::CherryGrove,
+        Self::ColdOcean,
+        Self::CrimsonForest,
+        Self::DarkForest,
+        Self::DeepColdOcean,
+        Self::DeepDark,
+        Self::DeepFrozenOcean,
+        Self::DeepLukewarmOcean,
+        Self::DeepOcean,
+        Self::Desert,
+        Self::DripstoneCaves,
+        Self::EndBarrens,
+        Self::EndHighlands,
+        Self::EndMidlands,
+        Self::ErodedBadlands,
+        Self::FlowerForest,
+        Self::Forest,
+        Self::FrozenOcean,
+        Self::FrozenPeaks,
+        Self::FrozenRiver,
+        Self::Grove,
+        Self::IceSpikes,
+        Self::JaggedPeaks,
+        Self::Jungle,
+        Self::LukewarmOcean,
+        Self::LushCaves,
+        Self::MangroveSwamp,
+        Self::Meadow,
+        Self::MushroomFields,
+        Self::NetherWastes,
+        Self::Ocean,
+        Self::OldGrowthBirchForest,
+        Self::OldGrowthPineTaiga,
+        Self::OldGrowthSpruceTaiga,
+        Self::PaleGarden,
+        Self::Plains,
+        Self::River,
+        Self::Savanna,
+        Self::SavannaPlateau,
+        Self::SmallEndIslands,
+        Self::SnowyBeach,
+        Self::SnowyPlains,
+        Self::SnowySlopes,
+        Self::SnowyTaiga,
+        Self::SoulSandValley,
+        Self::SparseJungle,
+        Self::StonyPeaks,
+        Self::StonyShore,
+        Self::SunflowerPlains,
+        Self::Swamp,
+        Self::Taiga,
+        Self::TheEnd,
+        Self::TheVoid,
+        Self::WarmOcean,
+        Self::WarpedForest,
+        Self::WindsweptForest,
+        Self::WindsweptGravellyHills,
+        Self::WindsweptHills,
+        Self::WindsweptSavanna,
+        Self::WoodedBadlands,
+    ];
+
+    fn identifier(self) -> &'static str { Self::IDENTIFIERS[self as usize] }
+
+    fn id(self) -> i32 { self as i32 }
+}
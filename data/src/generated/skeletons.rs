@@ -0,0 +1,1714 @@
+//! Generated by `datagen` from the protocol 772 `packets.json` report. Do not edit by hand.
+
+use codec::dec::Decode;
+use codec::enc::Encode;
+
+use crate::protocol::Packet;
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Handshake, direction = Serverbound, name = "intention")]
+pub struct HandshakeServerboundIntention {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Status, direction = Serverbound, name = "status_request")]
+pub struct StatusServerboundStatusRequest {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Status, direction = Serverbound, name = "ping_request")]
+pub struct StatusServerboundPingRequest {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Status, direction = Clientbound, name = "status_response")]
+pub struct StatusClientboundStatusResponse {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Status, direction = Clientbound, name = "pong_response")]
+pub struct StatusClientboundPongResponse {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Login, direction = Serverbound, name = "hello")]
+pub struct LoginServerboundHello {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Login, direction = Serverbound, name = "key")]
+pub struct LoginServerboundKey {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x02, state = Login, direction = Serverbound, name = "custom_query_answer")]
+pub struct LoginServerboundCustomQueryAnswer {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x03, state = Login, direction = Serverbound, name = "login_acknowledged")]
+pub struct LoginServerboundLoginAcknowledged {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x04, state = Login, direction = Serverbound, name = "cookie_response")]
+pub struct LoginServerboundCookieResponse {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Login, direction = Clientbound, name = "login_disconnect")]
+pub struct LoginClientboundLoginDisconnect {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Login, direction = Clientbound, name = "hello")]
+pub struct LoginClientboundHello {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x02, state = Login, direction = Clientbound, name = "login_finished")]
+pub struct LoginClientboundLoginFinished {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x03, state = Login, direction = Clientbound, name = "login_compression")]
+pub struct LoginClientboundLoginCompression {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x04, state = Login, direction = Clientbound, name = "custom_query")]
+pub struct LoginClientboundCustomQuery {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x05, state = Login, direction = Clientbound, name = "cookie_request")]
+pub struct LoginClientboundCookieRequest {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Configuration, direction = Serverbound, name = "client_information")]
+pub struct ConfigurationServerboundClientInformation {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Configuration, direction = Serverbound, name = "cookie_response")]
+pub struct ConfigurationServerboundCookieResponse {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x02, state = Configuration, direction = Serverbound, name = "custom_payload")]
+pub struct ConfigurationServerboundCustomPayload {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x03, state = Configuration, direction = Serverbound, name = "finish_configuration")]
+pub struct ConfigurationServerboundFinishConfiguration {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x04, state = Configuration, direction = Serverbound, name = "keep_alive")]
+pub struct ConfigurationServerboundKeepAlive {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x05, state = Configuration, direction = Serverbound, name = "pong")]
+pub struct ConfigurationServerboundPong {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x06, state = Configuration, direction = Serverbound, name = "resource_pack")]
+pub struct ConfigurationServerboundResourcePack {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x07, state = Configuration, direction = Serverbound, name = "select_known_packs")]
+pub struct ConfigurationServerboundSelectKnownPacks {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x08, state = Configuration, direction = Serverbound, name = "custom_click_action")]
+pub struct ConfigurationServerboundCustomClickAction {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Configuration, direction = Clientbound, name = "cookie_request")]
+pub struct ConfigurationClientboundCookieRequest {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Configuration, direction = Clientbound, name = "custom_payload")]
+pub struct ConfigurationClientboundCustomPayload {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x02, state = Configuration, direction = Clientbound, name = "disconnect")]
+pub struct ConfigurationClientboundDisconnect {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x03, state = Configuration, direction = Clientbound, name = "finish_configuration")]
+pub struct ConfigurationClientboundFinishConfiguration {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x04, state = Configuration, direction = Clientbound, name = "keep_alive")]
+pub struct ConfigurationClientboundKeepAlive {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x05, state = Configuration, direction = Clientbound, name = "ping")]
+pub struct ConfigurationClientboundPing {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x06, state = Configuration, direction = Clientbound, name = "reset_chat")]
+pub struct ConfigurationClientboundResetChat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x07, state = Configuration, direction = Clientbound, name = "registry_data")]
+pub struct ConfigurationClientboundRegistryData {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x08, state = Configuration, direction = Clientbound, name = "resource_pack_pop")]
+pub struct ConfigurationClientboundResourcePackPop {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x09, state = Configuration, direction = Clientbound, name = "resource_pack_push")]
+pub struct ConfigurationClientboundResourcePackPush {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0A, state = Configuration, direction = Clientbound, name = "store_cookie")]
+pub struct ConfigurationClientboundStoreCookie {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0B, state = Configuration, direction = Clientbound, name = "transfer")]
+pub struct ConfigurationClientboundTransfer {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0C, state = Configuration, direction = Clientbound, name = "update_enabled_features")]
+pub struct ConfigurationClientboundUpdateEnabledFeatures {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0D, state = Configuration, direction = Clientbound, name = "update_tags")]
+pub struct ConfigurationClientboundUpdateTags {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0E, state = Configuration, direction = Clientbound, name = "select_known_packs")]
+pub struct ConfigurationClientboundSelectKnownPacks {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0F, state = Configuration, direction = Clientbound, name = "custom_report_details")]
+pub struct ConfigurationClientboundCustomReportDetails {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x10, state = Configuration, direction = Clientbound, name = "server_links")]
+pub struct ConfigurationClientboundServerLinks {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x11, state = Configuration, direction = Clientbound, name = "clear_dialog")]
+pub struct ConfigurationClientboundClearDialog {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x12, state = Configuration, direction = Clientbound, name = "show_dialog")]
+pub struct ConfigurationClientboundShowDialog {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Play, direction = Serverbound, name = "accept_teleportation")]
+pub struct PlayServerboundAcceptTeleportation {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Play, direction = Serverbound, name = "block_entity_tag_query")]
+pub struct PlayServerboundBlockEntityTagQuery {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x02, state = Play, direction = Serverbound, name = "bundle_item_selected")]
+pub struct PlayServerboundBundleItemSelected {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x03, state = Play, direction = Serverbound, name = "change_difficulty")]
+pub struct PlayServerboundChangeDifficulty {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x04, state = Play, direction = Serverbound, name = "change_game_mode")]
+pub struct PlayServerboundChangeGameMode {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x05, state = Play, direction = Serverbound, name = "chat_ack")]
+pub struct PlayServerboundChatAck {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x06, state = Play, direction = Serverbound, name = "chat_command")]
+pub struct PlayServerboundChatCommand {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x07, state = Play, direction = Serverbound, name = "chat_command_signed")]
+pub struct PlayServerboundChatCommandSigned {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x08, state = Play, direction = Serverbound, name = "chat")]
+pub struct PlayServerboundChat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x09, state = Play, direction = Serverbound, name = "chat_session_update")]
+pub struct PlayServerboundChatSessionUpdate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0A, state = Play, direction = Serverbound, name = "chunk_batch_received")]
+pub struct PlayServerboundChunkBatchReceived {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0B, state = Play, direction = Serverbound, name = "client_command")]
+pub struct PlayServerboundClientCommand {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0C, state = Play, direction = Serverbound, name = "client_tick_end")]
+pub struct PlayServerboundClientTickEnd {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0D, state = Play, direction = Serverbound, name = "client_information")]
+pub struct PlayServerboundClientInformation {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0E, state = Play, direction = Serverbound, name = "command_suggestion")]
+pub struct PlayServerboundCommandSuggestion {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0F, state = Play, direction = Serverbound, name = "configuration_acknowledged")]
+pub struct PlayServerboundConfigurationAcknowledged {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x10, state = Play, direction = Serverbound, name = "container_button_click")]
+pub struct PlayServerboundContainerButtonClick {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x11, state = Play, direction = Serverbound, name = "container_click")]
+pub struct PlayServerboundContainerClick {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x12, state = Play, direction = Serverbound, name = "container_close")]
+pub struct PlayServerboundContainerClose {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x13, state = Play, direction = Serverbound, name = "container_slot_state_changed")]
+pub struct PlayServerboundContainerSlotStateChanged {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x14, state = Play, direction = Serverbound, name = "cookie_response")]
+pub struct PlayServerboundCookieResponse {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x15, state = Play, direction = Serverbound, name = "custom_payload")]
+pub struct PlayServerboundCustomPayload {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x16, state = Play, direction = Serverbound, name = "debug_sample_subscription")]
+pub struct PlayServerboundDebugSampleSubscription {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x17, state = Play, direction = Serverbound, name = "edit_book")]
+pub struct PlayServerboundEditBook {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x18, state = Play, direction = Serverbound, name = "entity_tag_query")]
+pub struct PlayServerboundEntityTagQuery {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x19, state = Play, direction = Serverbound, name = "interact")]
+pub struct PlayServerboundInteract {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1A, state = Play, direction = Serverbound, name = "jigsaw_generate")]
+pub struct PlayServerboundJigsawGenerate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1B, state = Play, direction = Serverbound, name = "keep_alive")]
+pub struct PlayServerboundKeepAlive {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1C, state = Play, direction = Serverbound, name = "lock_difficulty")]
+pub struct PlayServerboundLockDifficulty {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1D, state = Play, direction = Serverbound, name = "move_player_pos")]
+pub struct PlayServerboundMovePlayerPos {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1E, state = Play, direction = Serverbound, name = "move_player_pos_rot")]
+pub struct PlayServerboundMovePlayerPosRot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1F, state = Play, direction = Serverbound, name = "move_player_rot")]
+pub struct PlayServerboundMovePlayerRot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x20, state = Play, direction = Serverbound, name = "move_player_status_only")]
+pub struct PlayServerboundMovePlayerStatusOnly {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x21, state = Play, direction = Serverbound, name = "move_vehicle")]
+pub struct PlayServerboundMoveVehicle {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x22, state = Play, direction = Serverbound, name = "paddle_boat")]
+pub struct PlayServerboundPaddleBoat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x23, state = Play, direction = Serverbound, name = "pick_item_from_block")]
+pub struct PlayServerboundPickItemFromBlock {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x24, state = Play, direction = Serverbound, name = "pick_item_from_entity")]
+pub struct PlayServerboundPickItemFromEntity {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x25, state = Play, direction = Serverbound, name = "ping_request")]
+pub struct PlayServerboundPingRequest {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x26, state = Play, direction = Serverbound, name = "place_recipe")]
+pub struct PlayServerboundPlaceRecipe {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x27, state = Play, direction = Serverbound, name = "player_abilities")]
+pub struct PlayServerboundPlayerAbilities {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x28, state = Play, direction = Serverbound, name = "player_action")]
+pub struct PlayServerboundPlayerAction {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x29, state = Play, direction = Serverbound, name = "player_command")]
+pub struct PlayServerboundPlayerCommand {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2A, state = Play, direction = Serverbound, name = "player_input")]
+pub struct PlayServerboundPlayerInput {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2B, state = Play, direction = Serverbound, name = "player_loaded")]
+pub struct PlayServerboundPlayerLoaded {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2C, state = Play, direction = Serverbound, name = "pong")]
+pub struct PlayServerboundPong {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2D, state = Play, direction = Serverbound, name = "recipe_book_change_settings")]
+pub struct PlayServerboundRecipeBookChangeSettings {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2E, state = Play, direction = Serverbound, name = "recipe_book_seen_recipe")]
+pub struct PlayServerboundRecipeBookSeenRecipe {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2F, state = Play, direction = Serverbound, name = "rename_item")]
+pub struct PlayServerboundRenameItem {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x30, state = Play, direction = Serverbound, name = "resource_pack")]
+pub struct PlayServerboundResourcePack {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x31, state = Play, direction = Serverbound, name = "seen_advancements")]
+pub struct PlayServerboundSeenAdvancements {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x32, state = Play, direction = Serverbound, name = "select_trade")]
+pub struct PlayServerboundSelectTrade {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x33, state = Play, direction = Serverbound, name = "set_beacon")]
+pub struct PlayServerboundSetBeacon {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x34, state = Play, direction = Serverbound, name = "set_carried_item")]
+pub struct PlayServerboundSetCarriedItem {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x35, state = Play, direction = Serverbound, name = "set_command_block")]
+pub struct PlayServerboundSetCommandBlock {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x36, state = Play, direction = Serverbound, name = "set_command_minecart")]
+pub struct PlayServerboundSetCommandMinecart {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x37, state = Play, direction = Serverbound, name = "set_creative_mode_slot")]
+pub struct PlayServerboundSetCreativeModeSlot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x38, state = Play, direction = Serverbound, name = "set_jigsaw_block")]
+pub struct PlayServerboundSetJigsawBlock {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x39, state = Play, direction = Serverbound, name = "set_structure_block")]
+pub struct PlayServerboundSetStructureBlock {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3A, state = Play, direction = Serverbound, name = "set_test_block")]
+pub struct PlayServerboundSetTestBlock {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3B, state = Play, direction = Serverbound, name = "sign_update")]
+pub struct PlayServerboundSignUpdate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3C, state = Play, direction = Serverbound, name = "swing")]
+pub struct PlayServerboundSwing {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3D, state = Play, direction = Serverbound, name = "teleport_to_entity")]
+pub struct PlayServerboundTeleportToEntity {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3E, state = Play, direction = Serverbound, name = "test_instance_block_action")]
+pub struct PlayServerboundTestInstanceBlockAction {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3F, state = Play, direction = Serverbound, name = "use_item_on")]
+pub struct PlayServerboundUseItemOn {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x40, state = Play, direction = Serverbound, name = "use_item")]
+pub struct PlayServerboundUseItem {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x41, state = Play, direction = Serverbound, name = "custom_click_action")]
+pub struct PlayServerboundCustomClickAction {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x00, state = Play, direction = Clientbound, name = "bundle_delimiter")]
+pub struct PlayClientboundBundleDelimiter {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x01, state = Play, direction = Clientbound, name = "add_entity")]
+pub struct PlayClientboundAddEntity {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x02, state = Play, direction = Clientbound, name = "animate")]
+pub struct PlayClientboundAnimate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x03, state = Play, direction = Clientbound, name = "award_stats")]
+pub struct PlayClientboundAwardStats {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x04, state = Play, direction = Clientbound, name = "block_changed_ack")]
+pub struct PlayClientboundBlockChangedAck {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x05, state = Play, direction = Clientbound, name = "block_destruction")]
+pub struct PlayClientboundBlockDestruction {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x06, state = Play, direction = Clientbound, name = "block_entity_data")]
+pub struct PlayClientboundBlockEntityData {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x07, state = Play, direction = Clientbound, name = "block_event")]
+pub struct PlayClientboundBlockEvent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x08, state = Play, direction = Clientbound, name = "block_update")]
+pub struct PlayClientboundBlockUpdate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x09, state = Play, direction = Clientbound, name = "boss_event")]
+pub struct PlayClientboundBossEvent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0A, state = Play, direction = Clientbound, name = "change_difficulty")]
+pub struct PlayClientboundChangeDifficulty {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0B, state = Play, direction = Clientbound, name = "chunk_batch_finished")]
+pub struct PlayClientboundChunkBatchFinished {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0C, state = Play, direction = Clientbound, name = "chunk_batch_start")]
+pub struct PlayClientboundChunkBatchStart {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0D, state = Play, direction = Clientbound, name = "chunks_biomes")]
+pub struct PlayClientboundChunksBiomes {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0E, state = Play, direction = Clientbound, name = "clear_titles")]
+pub struct PlayClientboundClearTitles {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x0F, state = Play, direction = Clientbound, name = "command_suggestions")]
+pub struct PlayClientboundCommandSuggestions {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x10, state = Play, direction = Clientbound, name = "commands")]
+pub struct PlayClientboundCommands {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x11, state = Play, direction = Clientbound, name = "container_close")]
+pub struct PlayClientboundContainerClose {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x12, state = Play, direction = Clientbound, name = "container_set_content")]
+pub struct PlayClientboundContainerSetContent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x13, state = Play, direction = Clientbound, name = "container_set_data")]
+pub struct PlayClientboundContainerSetData {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x14, state = Play, direction = Clientbound, name = "container_set_slot")]
+pub struct PlayClientboundContainerSetSlot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x15, state = Play, direction = Clientbound, name = "cookie_request")]
+pub struct PlayClientboundCookieRequest {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x16, state = Play, direction = Clientbound, name = "cooldown")]
+pub struct PlayClientboundCooldown {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x17, state = Play, direction = Clientbound, name = "custom_chat_completions")]
+pub struct PlayClientboundCustomChatCompletions {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x18, state = Play, direction = Clientbound, name = "custom_payload")]
+pub struct PlayClientboundCustomPayload {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x19, state = Play, direction = Clientbound, name = "damage_event")]
+pub struct PlayClientboundDamageEvent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1A, state = Play, direction = Clientbound, name = "debug_sample")]
+pub struct PlayClientboundDebugSample {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1B, state = Play, direction = Clientbound, name = "delete_chat")]
+pub struct PlayClientboundDeleteChat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1C, state = Play, direction = Clientbound, name = "disconnect")]
+pub struct PlayClientboundDisconnect {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1D, state = Play, direction = Clientbound, name = "disguised_chat")]
+pub struct PlayClientboundDisguisedChat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1E, state = Play, direction = Clientbound, name = "entity_event")]
+pub struct PlayClientboundEntityEvent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x1F, state = Play, direction = Clientbound, name = "entity_position_sync")]
+pub struct PlayClientboundEntityPositionSync {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x20, state = Play, direction = Clientbound, name = "explode")]
+pub struct PlayClientboundExplode {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x21, state = Play, direction = Clientbound, name = "forget_level_chunk")]
+pub struct PlayClientboundForgetLevelChunk {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x22, state = Play, direction = Clientbound, name = "game_event")]
+pub struct PlayClientboundGameEvent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x23, state = Play, direction = Clientbound, name = "horse_screen_open")]
+pub struct PlayClientboundHorseScreenOpen {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x24, state = Play, direction = Clientbound, name = "hurt_animation")]
+pub struct PlayClientboundHurtAnimation {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x25, state = Play, direction = Clientbound, name = "initialize_border")]
+pub struct PlayClientboundInitializeBorder {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x26, state = Play, direction = Clientbound, name = "keep_alive")]
+pub struct PlayClientboundKeepAlive {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x27, state = Play, direction = Clientbound, name = "level_chunk_with_light")]
+pub struct PlayClientboundLevelChunkWithLight {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x28, state = Play, direction = Clientbound, name = "level_event")]
+pub struct PlayClientboundLevelEvent {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x29, state = Play, direction = Clientbound, name = "level_particles")]
+pub struct PlayClientboundLevelParticles {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2A, state = Play, direction = Clientbound, name = "light_update")]
+pub struct PlayClientboundLightUpdate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2B, state = Play, direction = Clientbound, name = "login")]
+pub struct PlayClientboundLogin {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2C, state = Play, direction = Clientbound, name = "map_item_data")]
+pub struct PlayClientboundMapItemData {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2D, state = Play, direction = Clientbound, name = "merchant_offers")]
+pub struct PlayClientboundMerchantOffers {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2E, state = Play, direction = Clientbound, name = "move_entity_pos")]
+pub struct PlayClientboundMoveEntityPos {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x2F, state = Play, direction = Clientbound, name = "move_entity_pos_rot")]
+pub struct PlayClientboundMoveEntityPosRot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x30, state = Play, direction = Clientbound, name = "move_minecart_along_track")]
+pub struct PlayClientboundMoveMinecartAlongTrack {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x31, state = Play, direction = Clientbound, name = "move_entity_rot")]
+pub struct PlayClientboundMoveEntityRot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x32, state = Play, direction = Clientbound, name = "move_vehicle")]
+pub struct PlayClientboundMoveVehicle {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x33, state = Play, direction = Clientbound, name = "open_book")]
+pub struct PlayClientboundOpenBook {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x34, state = Play, direction = Clientbound, name = "open_screen")]
+pub struct PlayClientboundOpenScreen {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x35, state = Play, direction = Clientbound, name = "open_sign_editor")]
+pub struct PlayClientboundOpenSignEditor {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x36, state = Play, direction = Clientbound, name = "ping")]
+pub struct PlayClientboundPing {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x37, state = Play, direction = Clientbound, name = "pong_response")]
+pub struct PlayClientboundPongResponse {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x38, state = Play, direction = Clientbound, name = "place_ghost_recipe")]
+pub struct PlayClientboundPlaceGhostRecipe {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x39, state = Play, direction = Clientbound, name = "player_abilities")]
+pub struct PlayClientboundPlayerAbilities {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3A, state = Play, direction = Clientbound, name = "player_chat")]
+pub struct PlayClientboundPlayerChat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3B, state = Play, direction = Clientbound, name = "player_combat_end")]
+pub struct PlayClientboundPlayerCombatEnd {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3C, state = Play, direction = Clientbound, name = "player_combat_enter")]
+pub struct PlayClientboundPlayerCombatEnter {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3D, state = Play, direction = Clientbound, name = "player_combat_kill")]
+pub struct PlayClientboundPlayerCombatKill {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3E, state = Play, direction = Clientbound, name = "player_info_remove")]
+pub struct PlayClientboundPlayerInfoRemove {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x3F, state = Play, direction = Clientbound, name = "player_info_update")]
+pub struct PlayClientboundPlayerInfoUpdate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x40, state = Play, direction = Clientbound, name = "player_look_at")]
+pub struct PlayClientboundPlayerLookAt {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x41, state = Play, direction = Clientbound, name = "player_position")]
+pub struct PlayClientboundPlayerPosition {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x42, state = Play, direction = Clientbound, name = "player_rotation")]
+pub struct PlayClientboundPlayerRotation {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x43, state = Play, direction = Clientbound, name = "recipe_book_add")]
+pub struct PlayClientboundRecipeBookAdd {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x44, state = Play, direction = Clientbound, name = "recipe_book_remove")]
+pub struct PlayClientboundRecipeBookRemove {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x45, state = Play, direction = Clientbound, name = "recipe_book_settings")]
+pub struct PlayClientboundRecipeBookSettings {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x46, state = Play, direction = Clientbound, name = "remove_entities")]
+pub struct PlayClientboundRemoveEntities {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x47, state = Play, direction = Clientbound, name = "remove_mob_effect")]
+pub struct PlayClientboundRemoveMobEffect {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x48, state = Play, direction = Clientbound, name = "reset_score")]
+pub struct PlayClientboundResetScore {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x49, state = Play, direction = Clientbound, name = "resource_pack_pop")]
+pub struct PlayClientboundResourcePackPop {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x4A, state = Play, direction = Clientbound, name = "resource_pack_push")]
+pub struct PlayClientboundResourcePackPush {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x4B, state = Play, direction = Clientbound, name = "respawn")]
+pub struct PlayClientboundRespawn {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x4C, state = Play, direction = Clientbound, name = "rotate_head")]
+pub struct PlayClientboundRotateHead {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x4D, state = Play, direction = Clientbound, name = "section_blocks_update")]
+pub struct PlayClientboundSectionBlocksUpdate {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x4E, state = Play, direction = Clientbound, name = "select_advancements_tab")]
+pub struct PlayClientboundSelectAdvancementsTab {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x4F, state = Play, direction = Clientbound, name = "server_data")]
+pub struct PlayClientboundServerData {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x50, state = Play, direction = Clientbound, name = "set_action_bar_text")]
+pub struct PlayClientboundSetActionBarText {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x51, state = Play, direction = Clientbound, name = "set_border_center")]
+pub struct PlayClientboundSetBorderCenter {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x52, state = Play, direction = Clientbound, name = "set_border_lerp_size")]
+pub struct PlayClientboundSetBorderLerpSize {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x53, state = Play, direction = Clientbound, name = "set_border_size")]
+pub struct PlayClientboundSetBorderSize {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x54, state = Play, direction = Clientbound, name = "set_border_warning_delay")]
+pub struct PlayClientboundSetBorderWarningDelay {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x55, state = Play, direction = Clientbound, name = "set_border_warning_distance")]
+pub struct PlayClientboundSetBorderWarningDistance {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x56, state = Play, direction = Clientbound, name = "set_camera")]
+pub struct PlayClientboundSetCamera {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x57, state = Play, direction = Clientbound, name = "set_chunk_cache_center")]
+pub struct PlayClientboundSetChunkCacheCenter {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x58, state = Play, direction = Clientbound, name = "set_chunk_cache_radius")]
+pub struct PlayClientboundSetChunkCacheRadius {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x59, state = Play, direction = Clientbound, name = "set_cursor_item")]
+pub struct PlayClientboundSetCursorItem {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x5A, state = Play, direction = Clientbound, name = "set_default_spawn_position")]
+pub struct PlayClientboundSetDefaultSpawnPosition {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x5B, state = Play, direction = Clientbound, name = "set_display_objective")]
+pub struct PlayClientboundSetDisplayObjective {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x5C, state = Play, direction = Clientbound, name = "set_entity_data")]
+pub struct PlayClientboundSetEntityData {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x5D, state = Play, direction = Clientbound, name = "set_entity_link")]
+pub struct PlayClientboundSetEntityLink {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x5E, state = Play, direction = Clientbound, name = "set_entity_motion")]
+pub struct PlayClientboundSetEntityMotion {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x5F, state = Play, direction = Clientbound, name = "set_equipment")]
+pub struct PlayClientboundSetEquipment {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x60, state = Play, direction = Clientbound, name = "set_experience")]
+pub struct PlayClientboundSetExperience {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x61, state = Play, direction = Clientbound, name = "set_health")]
+pub struct PlayClientboundSetHealth {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x62, state = Play, direction = Clientbound, name = "set_held_slot")]
+pub struct PlayClientboundSetHeldSlot {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x63, state = Play, direction = Clientbound, name = "set_objective")]
+pub struct PlayClientboundSetObjective {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x64, state = Play, direction = Clientbound, name = "set_passengers")]
+pub struct PlayClientboundSetPassengers {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x65, state = Play, direction = Clientbound, name = "set_player_inventory")]
+pub struct PlayClientboundSetPlayerInventory {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x66, state = Play, direction = Clientbound, name = "set_player_team")]
+pub struct PlayClientboundSetPlayerTeam {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x67, state = Play, direction = Clientbound, name = "set_score")]
+pub struct PlayClientboundSetScore {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x68, state = Play, direction = Clientbound, name = "set_simulation_distance")]
+pub struct PlayClientboundSetSimulationDistance {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x69, state = Play, direction = Clientbound, name = "set_subtitle_text")]
+pub struct PlayClientboundSetSubtitleText {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x6A, state = Play, direction = Clientbound, name = "set_time")]
+pub struct PlayClientboundSetTime {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x6B, state = Play, direction = Clientbound, name = "set_title_text")]
+pub struct PlayClientboundSetTitleText {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x6C, state = Play, direction = Clientbound, name = "set_titles_animation")]
+pub struct PlayClientboundSetTitlesAnimation {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x6D, state = Play, direction = Clientbound, name = "sound_entity")]
+pub struct PlayClientboundSoundEntity {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x6E, state = Play, direction = Clientbound, name = "sound")]
+pub struct PlayClientboundSound {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x6F, state = Play, direction = Clientbound, name = "start_configuration")]
+pub struct PlayClientboundStartConfiguration {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x70, state = Play, direction = Clientbound, name = "stop_sound")]
+pub struct PlayClientboundStopSound {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x71, state = Play, direction = Clientbound, name = "store_cookie")]
+pub struct PlayClientboundStoreCookie {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x72, state = Play, direction = Clientbound, name = "system_chat")]
+pub struct PlayClientboundSystemChat {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x73, state = Play, direction = Clientbound, name = "tab_list")]
+pub struct PlayClientboundTabList {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x74, state = Play, direction = Clientbound, name = "tag_query")]
+pub struct PlayClientboundTagQuery {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x75, state = Play, direction = Clientbound, name = "take_item_entity")]
+pub struct PlayClientboundTakeItemEntity {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x76, state = Play, direction = Clientbound, name = "teleport_entity")]
+pub struct PlayClientboundTeleportEntity {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x77, state = Play, direction = Clientbound, name = "test_instance_block_status")]
+pub struct PlayClientboundTestInstanceBlockStatus {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x78, state = Play, direction = Clientbound, name = "ticking_state")]
+pub struct PlayClientboundTickingState {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x79, state = Play, direction = Clientbound, name = "ticking_step")]
+pub struct PlayClientboundTickingStep {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x7A, state = Play, direction = Clientbound, name = "transfer")]
+pub struct PlayClientboundTransfer {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x7B, state = Play, direction = Clientbound, name = "update_advancements")]
+pub struct PlayClientboundUpdateAdvancements {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x7C, state = Play, direction = Clientbound, name = "update_attributes")]
+pub struct PlayClientboundUpdateAttributes {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x7D, state = Play, direction = Clientbound, name = "update_mob_effect")]
+pub struct PlayClientboundUpdateMobEffect {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x7E, state = Play, direction = Clientbound, name = "update_recipes")]
+pub struct PlayClientboundUpdateRecipes {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x7F, state = Play, direction = Clientbound, name = "update_tags")]
+pub struct PlayClientboundUpdateTags {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x80, state = Play, direction = Clientbound, name = "projectile_power")]
+pub struct PlayClientboundProjectilePower {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x81, state = Play, direction = Clientbound, name = "custom_report_details")]
+pub struct PlayClientboundCustomReportDetails {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x82, state = Play, direction = Clientbound, name = "server_links")]
+pub struct PlayClientboundServerLinks {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x83, state = Play, direction = Clientbound, name = "waypoint")]
+pub struct PlayClientboundWaypoint {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x84, state = Play, direction = Clientbound, name = "clear_dialog")]
+pub struct PlayClientboundClearDialog {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet)]
+#[packet(id = 0x85, state = Play, direction = Clientbound, name = "show_dialog")]
+pub struct PlayClientboundShowDialog {
+    #[codec(rest)]
+    pub data: Box<[u8]>,
+}
@@ -0,0 +1,178 @@
+//! Namespaced identifiers (`minecraft:stone`), the keys of every registry.
+
+use core::{
+    error,
+    fmt,
+    str,
+};
+use std::io;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+
+/// Namespace assumed when an identifier has none.
+pub const DEFAULT_NAMESPACE: &str = "minecraft";
+
+/// A namespaced identifier such as `minecraft:stone`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Identifier {
+    value: String,
+    separator: usize,
+}
+
+impl Identifier {
+    /// # Errors
+    ///
+    /// Returns [`InvalidIdentifier`] if the namespace or path holds
+    /// characters identifiers cannot contain.
+    pub fn new(
+        namespace: &str,
+        path: &str,
+    ) -> Result<Self, InvalidIdentifier> {
+        if namespace.is_empty()
+            || !namespace.bytes().all(is_namespace_byte)
+            || path.is_empty()
+            || !path
+                .bytes()
+                .all(|byte| is_namespace_byte(byte) || byte == b'/')
+        {
+            return Err(InvalidIdentifier(format!("{namespace}:{path}")));
+        }
+        Ok(Self {
+            value: format!("{namespace}:{path}"),
+            separator: namespace.len(),
+        })
+    }
+
+    /// An identifier in the `minecraft` namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidIdentifier`] if the path holds characters identifiers
+    /// cannot contain.
+    pub fn minecraft(path: &str) -> Result<Self, InvalidIdentifier> {
+        Self::new(DEFAULT_NAMESPACE, path)
+    }
+
+    #[must_use]
+    pub fn namespace(&self) -> &str { &self.value[..self.separator] }
+
+    #[must_use]
+    pub fn path(&self) -> &str { &self.value[self.separator + 1..] }
+
+    /// The full `namespace:path` form.
+    #[must_use]
+    pub fn as_str(&self) -> &str { &self.value }
+}
+
+const fn is_namespace_byte(byte: u8) -> bool {
+    matches!(byte, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.')
+}
+
+impl str::FromStr for Identifier {
+    type Err = InvalidIdentifier;
+
+    /// Parses `namespace:path`, or a bare `path` in the `minecraft`
+    /// namespace.
+    fn from_str(identifier: &str) -> Result<Self, Self::Err> {
+        match identifier.split_once(':') {
+            Some((namespace, path)) => Self::new(namespace, path),
+            None => Self::minecraft(identifier),
+        }
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl PartialEq<str> for Identifier {
+    fn eq(
+        &self,
+        other: &str,
+    ) -> bool {
+        self.value == other
+    }
+}
+
+impl Decode for Identifier {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        String::decode(reader)?
+            .parse()
+            .map_err(|err: InvalidIdentifier| DecodeError::Custom {
+                message: err.to_string(),
+            })
+    }
+}
+
+impl Encode for Identifier {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        self.value.encode(writer)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidIdentifier(String);
+
+impl fmt::Display for InvalidIdentifier {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "Invalid identifier: {:?}", self.0)
+    }
+}
+
+impl error::Error for InvalidIdentifier {}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let identifier: Identifier = "stone".parse().unwrap();
+        assert_eq!(identifier.as_str(), "minecraft:stone");
+        assert_eq!(identifier.namespace(), "minecraft");
+        assert_eq!(identifier.path(), "stone");
+
+        let identifier: Identifier = "my_plugin:worldgen/biome".parse().unwrap();
+        assert_eq!(identifier.namespace(), "my_plugin");
+        assert_eq!(identifier.path(), "worldgen/biome");
+
+        assert!(
+            "Stone".parse::<Identifier>().is_err(),
+            "uppercase is not allowed"
+        );
+        assert!(
+            "a/b:c".parse::<Identifier>().is_err(),
+            "namespaces cannot hold slashes"
+        );
+    }
+
+    #[test]
+    fn codec() {
+        let identifier = Identifier::minecraft("stone").unwrap();
+        let mut bytes = Vec::new();
+        identifier.encode(&mut bytes).unwrap();
+        assert_eq!(
+            Identifier::decode(&mut bytes.as_slice()).unwrap(),
+            identifier
+        );
+    }
+}
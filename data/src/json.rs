@@ -0,0 +1,178 @@
+//! Packet models as JSON, for the inspector's NDJSON output and plugins.
+//!
+//! Models implement [`ToJson`] with `#[derive(ToJson)]`, in the shape serde
+//! gives by default: structs become objects, options null or their content,
+//! and enum variants their name, holding their content if they have any.
+//! UUIDs and identifiers become strings and text components their JSON form.
+
+use codec::nbt::Nbt;
+use codec::{
+    PrefixedOption,
+    Uuid,
+    VarInt,
+    VarLong,
+};
+pub use data_macros::ToJson;
+use json::JsonValue;
+
+use crate::identifier::Identifier;
+use crate::text;
+
+/// A value which can be turned into JSON, usually implemented with
+/// `#[derive(ToJson)]`.
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+macro_rules! to_json_from {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ToJson for $ty {
+                fn to_json(&self) -> JsonValue { JsonValue::from(*self) }
+            }
+        )*
+    };
+}
+
+to_json_from!(bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, f32, f64);
+
+impl ToJson for str {
+    fn to_json(&self) -> JsonValue { JsonValue::from(self) }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue { JsonValue::from(self.as_str()) }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> JsonValue { (**self).to_json() }
+}
+
+impl<T: ToJson + ?Sized> ToJson for Box<T> {
+    fn to_json(&self) -> JsonValue { (**self).to_json() }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue { self.as_ref().map_or(JsonValue::Null, ToJson::to_json) }
+}
+
+impl<T: ToJson> ToJson for PrefixedOption<T> {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Self::Some(value) => value.to_json(),
+            Self::None => JsonValue::Null,
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonValue { JsonValue::Array(self.iter().map(ToJson::to_json).collect()) }
+}
+
+impl<T: ToJson, const N: usize> ToJson for [T; N] {
+    fn to_json(&self) -> JsonValue { self.as_slice().to_json() }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonValue { self.as_slice().to_json() }
+}
+
+impl<A: ToJson, B: ToJson> ToJson for (A, B) {
+    fn to_json(&self) -> JsonValue { JsonValue::Array(vec![self.0.to_json(), self.1.to_json()]) }
+}
+
+impl ToJson for VarInt {
+    fn to_json(&self) -> JsonValue { JsonValue::from(self.value()) }
+}
+
+impl ToJson for VarLong {
+    fn to_json(&self) -> JsonValue { JsonValue::from(self.value()) }
+}
+
+impl ToJson for Uuid {
+    fn to_json(&self) -> JsonValue { JsonValue::String(self.to_string()) }
+}
+
+impl ToJson for Identifier {
+    fn to_json(&self) -> JsonValue { JsonValue::String(self.to_string()) }
+}
+
+impl ToJson for Nbt {
+    fn to_json(&self) -> JsonValue { text::nbt_to_json(self) }
+}
+
+impl ToJson for JsonValue {
+    fn to_json(&self) -> JsonValue { self.clone() }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        login,
+        play,
+    };
+    use crate::protocol::{
+        LoginServerbound,
+        PlayServerbound,
+    };
+
+    #[derive(ToJson)]
+    enum Shape {
+        Unit,
+        Pair(i32, f64),
+        Named {
+            label: Option<String>,
+            id: Identifier,
+        },
+    }
+
+    #[derive(ToJson)]
+    struct Empty;
+
+    #[test]
+    fn models_to_json() {
+        let packet = PlayServerbound::KeepAliveResponse(play::KeepAliveResponse {
+            id: 7,
+        });
+        assert_eq!(packet.to_json(), json::object! { id: 7 });
+        let hello = login::Hello {
+            name: "Steve".to_owned(),
+            uuid: Uuid::null(),
+        };
+        assert_eq!(
+            hello.to_json(),
+            json::object! { name: "Steve", uuid: "00000000-0000-0000-0000-000000000000" }
+        );
+        assert_eq!(
+            LoginServerbound::LoginAcknowledged(login::LoginAcknowledged {}).to_json(),
+            json::object! {},
+            "packets without fields should give an empty object"
+        );
+        assert_eq!(
+            Empty.to_json(),
+            json::object! {},
+            "unit structs have no fields"
+        );
+
+        let shapes = [
+            Shape::Unit,
+            Shape::Pair(-3, 1.5),
+            Shape::Named {
+                label: Some("a \"b\"\n".to_owned()),
+                id: "stone".parse().unwrap(),
+            },
+            Shape::Named {
+                label: None,
+                id: "proxy:origin".parse().unwrap(),
+            },
+        ];
+        assert_eq!(shapes.to_json(), json::array![
+            "Unit",
+            { Pair: [-3, 1.5] },
+            { Named: { label: "a \"b\"\n", id: "minecraft:stone" } },
+            { Named: { label: null, id: "proxy:origin" } },
+        ]);
+    }
+}
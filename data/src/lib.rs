@@ -1,4 +1,16 @@
 extern crate alloc;
+// lets `#[derive(Packet)]` refer to this crate as `::data` from inside it
+extern crate self as data;
 
+pub mod anvil;
+pub mod command;
+pub mod generated;
+pub mod identifier;
+pub mod json;
 pub mod model;
 pub mod packet;
+pub mod protocol;
+pub mod registry;
+pub mod text;
+pub mod version;
+pub mod world;
@@ -0,0 +1,221 @@
+use codec::dec::Decode;
+use codec::enc::Encode;
+use codec::nbt::Nbt;
+use codec::{
+    PrefixedOption,
+    Uuid,
+};
+
+use crate::identifier::Identifier;
+use crate::json::ToJson;
+use crate::protocol::Packet;
+
+/// Asks the client for the cookie `key`, see [`StoreCookie`].
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Configuration, direction = Clientbound)]
+pub struct CookieRequest {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x02, state = Configuration, direction = Clientbound)]
+pub struct Disconnect {
+    /// Text component.
+    pub reason: Nbt,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x03, state = Configuration, direction = Clientbound)]
+pub struct FinishConfiguration {}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x04, state = Configuration, direction = Clientbound)]
+pub struct KeepAlive {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x05, state = Configuration, direction = Clientbound)]
+pub struct Ping {
+    pub id: i32,
+}
+
+/// Entries of a registry synchronized with the client, in id order.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x07, state = Configuration, direction = Clientbound)]
+pub struct RegistryData {
+    pub registry: Identifier,
+    pub entries: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, ToJson)]
+pub struct RegistryEntry {
+    pub id: Identifier,
+    /// Left out when the entry comes from a pack both sides know.
+    pub data: PrefixedOption<Nbt>,
+}
+
+/// Removes the resource pack `id`, or every pack pushed by the server.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x08, state = Configuration, direction = Clientbound)]
+pub struct ResourcePackPop {
+    pub id: PrefixedOption<Uuid>,
+}
+
+/// Asks the client to download a resource pack, on top of those it has.
+/// The client reports its progress with [`ResourcePackResponse`].
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x09, state = Configuration, direction = Clientbound)]
+pub struct ResourcePackPush {
+    pub id: Uuid,
+    pub url: String,
+    /// SHA-1 of the pack, as 40 hexadecimal digits. Empty to always
+    /// download it.
+    pub hash: String,
+    /// Whether the client is disconnected if it declines the pack.
+    pub forced: bool,
+    /// Text component shown when asking to download the pack.
+    pub prompt: PrefixedOption<Nbt>,
+}
+
+/// Stores a cookie on the client, kept across transfers until it quits.
+/// Servers read it back with [`CookieRequest`].
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x0A, state = Configuration, direction = Clientbound)]
+pub struct StoreCookie {
+    pub key: String,
+    /// At most 5 KiB.
+    pub payload: Vec<u8>,
+}
+
+/// Sends the client to another server, which it connects to with the
+/// transfer intent.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x0B, state = Configuration, direction = Clientbound)]
+pub struct Transfer {
+    pub host: String,
+    #[codec(varint)]
+    pub port: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x0E, state = Configuration, direction = Clientbound)]
+pub struct SelectKnownPacks {
+    pub packs: Vec<KnownPack>,
+}
+
+/// A data pack both sides may have, whose registry entries then need not be
+/// sent.
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, ToJson)]
+pub struct KnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Configuration, direction = Serverbound)]
+pub struct ClientInformation {
+    pub locale: String,
+    pub view_distance: i8,
+    /// 0 for full chat, 1 for commands only, 2 for hidden.
+    #[codec(varint)]
+    pub chat_mode: i32,
+    pub chat_colors: bool,
+    /// Bit mask of the skin layers shown.
+    pub displayed_skin_parts: u8,
+    /// 0 for left, 1 for right.
+    #[codec(varint)]
+    pub main_hand: i32,
+    pub text_filtering: bool,
+    pub allow_server_listings: bool,
+    /// 0 for all particles, 1 for decreased, 2 for minimal.
+    #[codec(varint)]
+    pub particle_status: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x01, state = Configuration, direction = Serverbound)]
+pub struct CookieResponse {
+    pub key: String,
+    /// `None` if the client has no such cookie.
+    #[codec(prefixed_option)]
+    pub payload: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(
+    id = 0x03,
+    state = Configuration,
+    direction = Serverbound,
+    name = "finish_configuration"
+)]
+pub struct AcknowledgeFinishConfiguration {}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(
+    id = 0x04,
+    state = Configuration,
+    direction = Serverbound,
+    name = "keep_alive"
+)]
+pub struct KeepAliveResponse {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x05, state = Configuration, direction = Serverbound)]
+pub struct Pong {
+    pub id: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(
+    id = 0x06,
+    state = Configuration,
+    direction = Serverbound,
+    name = "resource_pack"
+)]
+pub struct ResourcePackResponse {
+    pub id: Uuid,
+    pub status: ResourcePackStatus,
+}
+
+/// Progress of a pushed resource pack. The client reports
+/// [`Accepted`](Self::Accepted) then [`Downloaded`](Self::Downloaded) before
+/// the final status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, ToJson)]
+#[codec(varint)]
+pub enum ResourcePackStatus {
+    SuccessfullyLoaded = 0,
+    Declined = 1,
+    FailedDownload = 2,
+    Accepted = 3,
+    Downloaded = 4,
+    InvalidUrl = 5,
+    FailedReload = 6,
+    /// Removed before it loaded.
+    Discarded = 7,
+}
+
+impl ResourcePackStatus {
+    /// Whether the pack will not load.
+    #[must_use]
+    pub const fn failed(self) -> bool {
+        !matches!(
+            self,
+            Self::SuccessfullyLoaded | Self::Accepted | Self::Downloaded
+        )
+    }
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(
+    id = 0x07,
+    state = Configuration,
+    direction = Serverbound,
+    name = "select_known_packs"
+)]
+pub struct KnownPacks {
+    pub packs: Vec<KnownPack>,
+}
@@ -1,6 +1,11 @@
 use codec::dec::Decode;
+use codec::enc::Encode;
 
-#[derive(Debug, Decode)]
+use crate::json::ToJson;
+use crate::protocol::Packet;
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Handshake, direction = Serverbound, name = "intention")]
 pub struct Handshake {
     #[codec(varint)]
     pub protocol_version: i32,
@@ -9,7 +14,7 @@ pub struct Handshake {
     pub intent: Intent,
 }
 
-#[derive(Debug, Decode, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Decode, Encode, Clone, Copy, PartialEq, Eq, ToJson)]
 #[codec(varint)]
 pub enum Intent {
     Status = 1,
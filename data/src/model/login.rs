@@ -1,15 +1,96 @@
 use codec::Uuid;
 use codec::dec::Decode;
 use codec::enc::Encode;
+use json::JsonValue;
 
-#[derive(Debug, Decode, Encode)]
+use crate::json::ToJson;
+use crate::protocol::Packet;
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Login, direction = Serverbound)]
 pub struct Hello {
     pub name: String,
     pub uuid: Uuid,
 }
 
-#[derive(Debug, Decode, Encode)]
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x01, state = Login, direction = Serverbound)]
+pub struct Key {
+    pub shared_secret: Vec<u8>,
+    pub verify_token: Vec<u8>,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x02, state = Login, direction = Serverbound)]
+pub struct CustomQueryAnswer {
+    #[codec(varint)]
+    pub transaction_id: i32,
+    #[codec(rest)]
+    pub payload: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x03, state = Login, direction = Serverbound)]
+pub struct LoginAcknowledged {}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x04, state = Login, direction = Serverbound)]
+pub struct CookieResponse {
+    pub key: String,
+    #[codec(prefixed_option)]
+    pub payload: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Login, direction = Clientbound)]
+pub struct LoginDisconnect {
+    pub reason: JsonValue,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x01, state = Login, direction = Clientbound, name = "hello")]
+pub struct EncryptionRequest {
+    pub server_id: String,
+    pub public_key: Vec<u8>,
+    pub verify_token: Vec<u8>,
+    pub should_authenticate: bool,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x02, state = Login, direction = Clientbound)]
+pub struct LoginFinished {
+    pub uuid: Uuid,
+    pub username: String,
+    pub properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, ToJson)]
+pub struct Property {
+    pub name: String,
+    pub value: String,
+    #[codec(prefixed_option)]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x03, state = Login, direction = Clientbound)]
 pub struct LoginCompression {
     #[codec(varint)]
     pub size: i32,
 }
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x04, state = Login, direction = Clientbound)]
+pub struct CustomQuery {
+    #[codec(varint)]
+    pub transaction_id: i32,
+    pub channel: String,
+    #[codec(rest)]
+    pub payload: Box<[u8]>,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x05, state = Login, direction = Clientbound)]
+pub struct CookieRequest {
+    pub key: String,
+}
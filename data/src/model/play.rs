@@ -0,0 +1,940 @@
+use std::io;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+use codec::nbt::Nbt;
+use codec::{
+    PrefixedOption,
+    Uuid,
+    VarInt,
+    VarLong,
+};
+
+use crate::command::CommandGraph;
+use crate::identifier::Identifier;
+use crate::json::ToJson;
+use crate::model::configuration::ResourcePackStatus;
+use crate::model::login::Property;
+use crate::protocol::Packet;
+use crate::world::{
+    BlockPos,
+    ChunkData,
+    EntityMetadata,
+    LightData,
+    SectionPos,
+};
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x01, state = Play, direction = Clientbound)]
+pub struct AddEntity {
+    #[codec(varint)]
+    pub id: i32,
+    pub uuid: Uuid,
+    /// Id in the `minecraft:entity_type` registry.
+    #[codec(varint)]
+    pub kind: i32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Angles in 256ths of a turn.
+    pub pitch: u8,
+    pub yaw: u8,
+    pub head_yaw: u8,
+    #[codec(varint)]
+    pub data: i32,
+    /// Velocity in 8000ths of a block per tick.
+    pub velocity_x: i16,
+    pub velocity_y: i16,
+    pub velocity_z: i16,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x06, state = Play, direction = Clientbound)]
+pub struct BlockEntityData {
+    pub position: BlockPos,
+    /// Id in the `minecraft:block_entity_type` registry.
+    #[codec(varint)]
+    pub kind: i32,
+    pub data: Option<Nbt>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x08, state = Play, direction = Clientbound)]
+pub struct BlockUpdate {
+    pub position: BlockPos,
+    #[codec(varint)]
+    pub block_state: i32,
+}
+
+/// Shows, updates or hides a boss bar.
+#[derive(Debug, Clone, Packet, ToJson)]
+#[packet(id = 0x09, state = Play, direction = Clientbound)]
+pub struct BossEvent {
+    /// Identifies the bar across events.
+    pub id: Uuid,
+    pub action: BossEventAction,
+}
+
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub enum BossEventAction {
+    Add {
+        /// Text component.
+        title: Nbt,
+        /// From 0 to 1.
+        progress: f32,
+        color: BossBarColor,
+        overlay: BossBarOverlay,
+        /// Bits of [`boss_bar`].
+        flags: u8,
+    },
+    Remove,
+    UpdateProgress(f32),
+    /// Text component.
+    UpdateTitle(Nbt),
+    UpdateStyle {
+        color: BossBarColor,
+        overlay: BossBarOverlay,
+    },
+    UpdateFlags(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, ToJson)]
+#[codec(varint)]
+pub enum BossBarColor {
+    Pink = 0,
+    Blue = 1,
+    Red = 2,
+    Green = 3,
+    Yellow = 4,
+    Purple = 5,
+    White = 6,
+}
+
+/// How many segments the bar is split in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, ToJson)]
+#[codec(varint)]
+pub enum BossBarOverlay {
+    Progress = 0,
+    Notched6 = 1,
+    Notched10 = 2,
+    Notched12 = 3,
+    Notched20 = 4,
+}
+
+/// Bits of [`BossEventAction::Add`] and [`BossEventAction::UpdateFlags`].
+pub mod boss_bar {
+    pub const DARKEN_SCREEN: u8 = 1 << 0;
+    pub const PLAY_MUSIC: u8 = 1 << 1;
+    pub const CREATE_FOG: u8 = 1 << 2;
+}
+
+impl Decode for BossEvent {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let id = Uuid::decode(reader)?;
+        let action = match VarInt::decode(reader)?.value() {
+            0 => BossEventAction::Add {
+                title: Nbt::decode(reader)?,
+                progress: f32::decode(reader)?,
+                color: BossBarColor::decode(reader)?,
+                overlay: BossBarOverlay::decode(reader)?,
+                flags: u8::decode(reader)?,
+            },
+            1 => BossEventAction::Remove,
+            2 => BossEventAction::UpdateProgress(f32::decode(reader)?),
+            3 => BossEventAction::UpdateTitle(Nbt::decode(reader)?),
+            4 => BossEventAction::UpdateStyle {
+                color: BossBarColor::decode(reader)?,
+                overlay: BossBarOverlay::decode(reader)?,
+            },
+            5 => BossEventAction::UpdateFlags(u8::decode(reader)?),
+            action => {
+                return Err(DecodeError::Custom {
+                    message: format!("Unknown boss event action {action}"),
+                });
+            }
+        };
+        Ok(Self {
+            id,
+            action,
+        })
+    }
+}
+
+impl Encode for BossEvent {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut written = self.id.encode(writer)?;
+        written += match &self.action {
+            BossEventAction::Add {
+                title,
+                progress,
+                color,
+                overlay,
+                flags,
+            } => {
+                VarInt::new(0).encode(writer)?
+                    + title.encode(writer)?
+                    + progress.encode(writer)?
+                    + color.encode(writer)?
+                    + overlay.encode(writer)?
+                    + flags.encode(writer)?
+            }
+            BossEventAction::Remove => VarInt::new(1).encode(writer)?,
+            BossEventAction::UpdateProgress(progress) => {
+                VarInt::new(2).encode(writer)? + progress.encode(writer)?
+            }
+            BossEventAction::UpdateTitle(title) => {
+                VarInt::new(3).encode(writer)? + title.encode(writer)?
+            }
+            BossEventAction::UpdateStyle {
+                color,
+                overlay,
+            } => VarInt::new(4).encode(writer)? + color.encode(writer)? + overlay.encode(writer)?,
+            BossEventAction::UpdateFlags(flags) => {
+                VarInt::new(5).encode(writer)? + flags.encode(writer)?
+            }
+        };
+        Ok(written)
+    }
+}
+
+/// Ends a batch of chunks, which the client acknowledges with
+/// [`ChunkBatchReceived`].
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x0B, state = Play, direction = Clientbound)]
+pub struct ChunkBatchFinished {
+    #[codec(varint)]
+    pub batch_size: i32,
+}
+
+/// The commands the client may run, replacing those sent before.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x10, state = Play, direction = Clientbound)]
+pub struct Commands {
+    pub graph: CommandGraph,
+}
+
+/// Asks the client for the cookie `key`, see [`StoreCookie`].
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x15, state = Play, direction = Clientbound)]
+pub struct CookieRequest {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1C, state = Play, direction = Clientbound)]
+pub struct Disconnect {
+    /// Text component.
+    pub reason: Nbt,
+}
+
+/// An unsigned chat message, shown as if sent by `sender_name`.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1D, state = Play, direction = Clientbound)]
+pub struct DisguisedChat {
+    /// Text component.
+    pub message: Nbt,
+    /// Id in the `minecraft:chat_type` registry plus one.
+    #[codec(varint)]
+    pub chat_type: i32,
+    pub sender_name: Nbt,
+    pub target_name: PrefixedOption<Nbt>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1F, state = Play, direction = Clientbound)]
+pub struct EntityPositionSync {
+    #[codec(varint)]
+    pub id: i32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub velocity_x: f64,
+    pub velocity_y: f64,
+    pub velocity_z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+/// Sent with the chunk z first, as the halves of a packed chunk position.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x21, state = Play, direction = Clientbound)]
+pub struct ForgetLevelChunk {
+    pub z: i32,
+    pub x: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x22, state = Play, direction = Clientbound)]
+pub struct GameEvent {
+    /// One of [`game_event`].
+    pub event: u8,
+    pub value: f32,
+}
+
+/// Events of [`GameEvent`].
+pub mod game_event {
+    pub const CHANGE_GAME_MODE: u8 = 3;
+    /// Lets the client leave the loading screen once the chunks around the
+    /// player arrived.
+    pub const LEVEL_CHUNKS_LOAD_START: u8 = 13;
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x26, state = Play, direction = Clientbound)]
+pub struct KeepAlive {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x27, state = Play, direction = Clientbound)]
+pub struct LevelChunkWithLight {
+    pub x: i32,
+    pub z: i32,
+    pub chunk: ChunkData,
+    pub light: LightData,
+}
+
+/// Relative move of up to 8 blocks, in 4096ths of a block.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x2B, state = Play, direction = Clientbound)]
+#[allow(clippy::struct_excessive_bools, reason = "mirrors the packet layout")]
+pub struct Login {
+    pub entity_id: i32,
+    pub hardcore: bool,
+    /// Every dimension of the server.
+    pub dimensions: Vec<Identifier>,
+    #[codec(varint)]
+    pub max_players: i32,
+    #[codec(varint)]
+    pub view_distance: i32,
+    #[codec(varint)]
+    pub simulation_distance: i32,
+    pub reduced_debug_info: bool,
+    pub show_death_screen: bool,
+    pub do_limited_crafting: bool,
+    pub spawn: SpawnInfo,
+    pub enforces_secure_chat: bool,
+}
+
+/// The dimension a player spawns in, sent on login and respawn.
+#[derive(Debug, Clone, Decode, Encode, ToJson)]
+pub struct SpawnInfo {
+    /// Id in the `minecraft:dimension_type` registry sent during
+    /// configuration.
+    #[codec(varint)]
+    pub dimension_type: i32,
+    pub dimension: Identifier,
+    /// First 8 bytes of the SHA-256 of the world seed.
+    pub hashed_seed: i64,
+    pub game_mode: u8,
+    /// -1 when there is none.
+    pub previous_game_mode: i8,
+    pub debug: bool,
+    pub flat: bool,
+    pub death_location: PrefixedOption<DeathLocation>,
+    #[codec(varint)]
+    pub portal_cooldown: i32,
+    #[codec(varint)]
+    pub sea_level: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, ToJson)]
+pub struct DeathLocation {
+    pub dimension: Identifier,
+    pub position: BlockPos,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x2E, state = Play, direction = Clientbound)]
+pub struct MoveEntityPos {
+    #[codec(varint)]
+    pub id: i32,
+    pub delta_x: i16,
+    pub delta_y: i16,
+    pub delta_z: i16,
+    pub on_ground: bool,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x2F, state = Play, direction = Clientbound)]
+pub struct MoveEntityPosRot {
+    #[codec(varint)]
+    pub id: i32,
+    pub delta_x: i16,
+    pub delta_y: i16,
+    pub delta_z: i16,
+    pub yaw: u8,
+    pub pitch: u8,
+    pub on_ground: bool,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x31, state = Play, direction = Clientbound)]
+pub struct MoveEntityRot {
+    #[codec(varint)]
+    pub id: i32,
+    pub yaw: u8,
+    pub pitch: u8,
+    pub on_ground: bool,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x36, state = Play, direction = Clientbound)]
+pub struct Ping {
+    pub id: i32,
+}
+
+/// A chat message sent by a player.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x3A, state = Play, direction = Clientbound)]
+pub struct PlayerChat {
+    #[codec(varint)]
+    pub global_index: i32,
+    pub sender: Uuid,
+    /// Index of the message among those of `sender`.
+    #[codec(varint)]
+    pub index: i32,
+    #[codec(prefixed_option)]
+    pub signature: Option<MessageSignature>,
+    pub message: String,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub salt: i64,
+    pub previous_messages: Vec<PreviousMessage>,
+    /// Text component shown instead of `message`, when decorated by the
+    /// server.
+    #[codec(prefixed_option)]
+    pub unsigned_content: Option<Nbt>,
+    pub filter: FilterMask,
+    /// Id in the `minecraft:chat_type` registry plus one. Inline chat types,
+    /// sent as 0, are not supported.
+    #[codec(varint)]
+    pub chat_type: i32,
+    pub sender_name: Nbt,
+    pub target_name: PrefixedOption<Nbt>,
+}
+
+/// Signature of a chat message, sent without a length prefix.
+pub type MessageSignature = [u8; 256];
+
+/// A message acknowledged by a signed message.
+#[derive(Debug, Clone, PartialEq, Eq, ToJson)]
+pub enum PreviousMessage {
+    /// Index in the signature cache of the client.
+    Id(i32),
+    /// A signature the client has not cached.
+    Signature(Box<MessageSignature>),
+}
+
+impl Decode for PreviousMessage {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        match VarInt::decode(reader)?.value() {
+            0 => Ok(Self::Signature(Box::new(MessageSignature::decode(reader)?))),
+            id => Ok(Self::Id(id - 1)),
+        }
+    }
+}
+
+impl Encode for PreviousMessage {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        match self {
+            Self::Id(id) => VarInt::new(id + 1).encode(writer),
+            Self::Signature(signature) => {
+                Ok(VarInt::new(0).encode(writer)? + signature.encode(writer)?)
+            }
+        }
+    }
+}
+
+/// Parts of a chat message hidden by the server's text filter.
+#[derive(Debug, Clone, PartialEq, Eq, ToJson)]
+pub enum FilterMask {
+    PassThrough,
+    FullyFiltered,
+    /// Bit set of the filtered characters.
+    PartiallyFiltered(Vec<i64>),
+}
+
+impl Decode for FilterMask {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        match VarInt::decode(reader)?.value() {
+            0 => Ok(Self::PassThrough),
+            1 => Ok(Self::FullyFiltered),
+            2 => Ok(Self::PartiallyFiltered(Vec::decode(reader)?)),
+            kind => Err(DecodeError::Custom {
+                message: format!("Unknown filter mask type {kind}"),
+            }),
+        }
+    }
+}
+
+impl Encode for FilterMask {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        match self {
+            Self::PassThrough => VarInt::new(0).encode(writer),
+            Self::FullyFiltered => VarInt::new(1).encode(writer),
+            Self::PartiallyFiltered(mask) => {
+                Ok(VarInt::new(2).encode(writer)? + mask.encode(writer)?)
+            }
+        }
+    }
+}
+
+/// Bits of [`PlayerInfoUpdate::actions`], each naming the fields of
+/// [`PlayerInfo`] its entries carry.
+pub mod player_info {
+    /// `name` and `properties`.
+    pub const ADD_PLAYER: u8 = 1 << 0;
+    /// `chat_session`.
+    pub const INITIALIZE_CHAT: u8 = 1 << 1;
+    /// `game_mode`.
+    pub const UPDATE_GAME_MODE: u8 = 1 << 2;
+    /// `listed`.
+    pub const UPDATE_LISTED: u8 = 1 << 3;
+    /// `latency`.
+    pub const UPDATE_LATENCY: u8 = 1 << 4;
+    /// `display_name`.
+    pub const UPDATE_DISPLAY_NAME: u8 = 1 << 5;
+    /// `list_priority`, since 1.21.2.
+    pub const UPDATE_LIST_PRIORITY: u8 = 1 << 6;
+    /// `show_hat`, since 1.21.4.
+    pub const UPDATE_HAT: u8 = 1 << 7;
+}
+
+/// Removes players from the tab list.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x3E, state = Play, direction = Clientbound)]
+pub struct PlayerInfoRemove {
+    pub uuids: Vec<Uuid>,
+}
+
+/// Adds players to the tab list, or updates those added.
+#[derive(Debug, Clone, Packet, ToJson)]
+#[packet(id = 0x3F, state = Play, direction = Clientbound)]
+pub struct PlayerInfoUpdate {
+    /// Bits of [`player_info`], the same for every entry.
+    pub actions: u8,
+    pub entries: Vec<PlayerInfo>,
+}
+
+/// A player of [`PlayerInfoUpdate`]. Only the fields its actions name are
+/// sent, the others keep their default.
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub struct PlayerInfo {
+    pub uuid: Uuid,
+    pub name: String,
+    /// Profile properties, such as the skin.
+    pub properties: Vec<Property>,
+    /// `None` ends the chat session of the player.
+    pub chat_session: Option<ChatSession>,
+    /// Id of the game mode, from 0 for survival.
+    pub game_mode: i32,
+    /// Whether the player shows in the tab list.
+    pub listed: bool,
+    /// Milliseconds.
+    pub latency: i32,
+    /// Text component, shown instead of the name.
+    pub display_name: Option<Nbt>,
+    /// Players of higher priority are listed first.
+    pub list_priority: i32,
+    pub show_hat: bool,
+}
+
+/// The key a player signs chat messages with.
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, ToJson)]
+pub struct ChatSession {
+    pub id: Uuid,
+    /// Milliseconds since the Unix epoch.
+    pub expires_at: i64,
+    pub public_key: Vec<u8>,
+    pub key_signature: Vec<u8>,
+}
+
+impl PlayerInfo {
+    /// An entry of `uuid`, listed, with the other fields empty.
+    #[must_use]
+    pub const fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            name: String::new(),
+            properties: Vec::new(),
+            chat_session: None,
+            game_mode: 0,
+            listed: true,
+            latency: 0,
+            display_name: None,
+            list_priority: 0,
+            show_hat: true,
+        }
+    }
+}
+
+impl Decode for PlayerInfoUpdate {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let actions = u8::decode(reader)?;
+        let len = VarInt::decode(reader)?.value();
+        let mut entries = Vec::new();
+        for _ in 0..len {
+            let mut entry = PlayerInfo::new(Uuid::decode(reader)?);
+            if actions & player_info::ADD_PLAYER != 0 {
+                entry.name = String::decode(reader)?;
+                entry.properties = Vec::decode(reader)?;
+            }
+            if actions & player_info::INITIALIZE_CHAT != 0 {
+                entry.chat_session = PrefixedOption::decode(reader)?.into();
+            }
+            if actions & player_info::UPDATE_GAME_MODE != 0 {
+                entry.game_mode = VarInt::decode(reader)?.value();
+            }
+            if actions & player_info::UPDATE_LISTED != 0 {
+                entry.listed = bool::decode(reader)?;
+            }
+            if actions & player_info::UPDATE_LATENCY != 0 {
+                entry.latency = VarInt::decode(reader)?.value();
+            }
+            if actions & player_info::UPDATE_DISPLAY_NAME != 0 {
+                entry.display_name = PrefixedOption::decode(reader)?.into();
+            }
+            if actions & player_info::UPDATE_LIST_PRIORITY != 0 {
+                entry.list_priority = VarInt::decode(reader)?.value();
+            }
+            if actions & player_info::UPDATE_HAT != 0 {
+                entry.show_hat = bool::decode(reader)?;
+            }
+            entries.push(entry);
+        }
+        Ok(Self {
+            actions,
+            entries,
+        })
+    }
+}
+
+impl Encode for PlayerInfoUpdate {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let actions = self.actions;
+        let len = i32::try_from(self.entries.len()).map_err(|_| EncodeError::Custom {
+            message: "Too many player info entries".to_owned(),
+        })?;
+        let mut written = actions.encode(writer)? + VarInt::new(len).encode(writer)?;
+        for entry in &self.entries {
+            written += entry.uuid.encode(writer)?;
+            if actions & player_info::ADD_PLAYER != 0 {
+                written += entry.name.encode(writer)?;
+                written += entry.properties.encode(writer)?;
+            }
+            if actions & player_info::INITIALIZE_CHAT != 0 {
+                written += PrefixedOption::from(entry.chat_session.as_ref()).encode(writer)?;
+            }
+            if actions & player_info::UPDATE_GAME_MODE != 0 {
+                written += VarInt::new(entry.game_mode).encode(writer)?;
+            }
+            if actions & player_info::UPDATE_LISTED != 0 {
+                written += entry.listed.encode(writer)?;
+            }
+            if actions & player_info::UPDATE_LATENCY != 0 {
+                written += VarInt::new(entry.latency).encode(writer)?;
+            }
+            if actions & player_info::UPDATE_DISPLAY_NAME != 0 {
+                written += PrefixedOption::from(entry.display_name.as_ref()).encode(writer)?;
+            }
+            if actions & player_info::UPDATE_LIST_PRIORITY != 0 {
+                written += VarInt::new(entry.list_priority).encode(writer)?;
+            }
+            if actions & player_info::UPDATE_HAT != 0 {
+                written += entry.show_hat.encode(writer)?;
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// Bits of [`PlayerPosition::relatives`] and [`TeleportEntity::relatives`]
+/// marking coordinates as offsets.
+pub mod relative {
+    pub const X: i32 = 1 << 0;
+    pub const Y: i32 = 1 << 1;
+    pub const Z: i32 = 1 << 2;
+    pub const YAW: i32 = 1 << 3;
+    pub const PITCH: i32 = 1 << 4;
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x41, state = Play, direction = Clientbound)]
+pub struct PlayerPosition {
+    #[codec(varint)]
+    pub teleport_id: i32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub velocity_x: f64,
+    pub velocity_y: f64,
+    pub velocity_z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub relatives: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x46, state = Play, direction = Clientbound)]
+pub struct RemoveEntities {
+    pub ids: Vec<VarInt>,
+}
+
+/// Removes the resource pack `id`, or every pack pushed by the server.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x49, state = Play, direction = Clientbound)]
+pub struct ResourcePackPop {
+    pub id: PrefixedOption<Uuid>,
+}
+
+/// Asks the client to download a resource pack, see
+/// [`configuration::ResourcePackPush`](crate::model::configuration::ResourcePackPush).
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x4A, state = Play, direction = Clientbound)]
+pub struct ResourcePackPush {
+    pub id: Uuid,
+    pub url: String,
+    /// SHA-1 of the pack, as 40 hexadecimal digits.
+    pub hash: String,
+    pub forced: bool,
+    /// Text component.
+    pub prompt: PrefixedOption<Nbt>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x4B, state = Play, direction = Clientbound)]
+pub struct Respawn {
+    pub spawn: SpawnInfo,
+    /// Bit 1 keeps attributes, bit 2 entity metadata.
+    pub data_kept: u8,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x4D, state = Play, direction = Clientbound)]
+pub struct SectionBlocksUpdate {
+    pub section: SectionPos,
+    /// Block state ids shifted left by 12, over x, z and y nibbles.
+    pub blocks: Vec<VarLong>,
+}
+
+impl SectionBlocksUpdate {
+    /// Positions and block state ids of the updated blocks.
+    pub fn blocks(&self) -> impl Iterator<Item = (BlockPos, i32)> + '_ {
+        self.blocks.iter().map(|block| {
+            let block = block.value();
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "block state ids fit in the upper bits of the long"
+            )]
+            let state = (block >> 12) as i32;
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "each coordinate is a nibble"
+            )]
+            let position = self.section.block(
+                (block >> 8 & 15) as i32,
+                (block & 15) as i32,
+                (block >> 4 & 15) as i32,
+            );
+            (position, state)
+        })
+    }
+}
+
+/// Chunk the client's view is centered on.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x57, state = Play, direction = Clientbound)]
+pub struct SetChunkCacheCenter {
+    #[codec(varint)]
+    pub x: i32,
+    #[codec(varint)]
+    pub z: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x5C, state = Play, direction = Clientbound)]
+pub struct SetEntityData {
+    #[codec(varint)]
+    pub id: i32,
+    pub metadata: EntityMetadata,
+}
+
+/// Sends the client back to the configuration state, once it answers with
+/// [`ConfigurationAcknowledged`].
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x6F, state = Play, direction = Clientbound)]
+pub struct StartConfiguration {}
+
+/// Stores a cookie on the client, kept across transfers until it quits.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x71, state = Play, direction = Clientbound)]
+pub struct StoreCookie {
+    pub key: String,
+    /// At most 5 KiB.
+    pub payload: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x72, state = Play, direction = Clientbound)]
+pub struct SystemChat {
+    /// Text component.
+    pub content: Nbt,
+    /// Whether the message is shown above the hotbar instead of in the chat.
+    pub overlay: bool,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x76, state = Play, direction = Clientbound)]
+pub struct TeleportEntity {
+    #[codec(varint)]
+    pub id: i32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub velocity_x: f64,
+    pub velocity_y: f64,
+    pub velocity_z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub relatives: i32,
+    pub on_ground: bool,
+}
+
+/// Sends the client to another server, which it connects to with the
+/// transfer intent.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x7A, state = Play, direction = Clientbound)]
+pub struct Transfer {
+    pub host: String,
+    #[codec(varint)]
+    pub port: i32,
+}
+
+/// Confirms a [`PlayerPosition`] teleport.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Play, direction = Serverbound)]
+pub struct AcceptTeleportation {
+    #[codec(varint)]
+    pub teleport_id: i32,
+}
+
+/// A command typed in the chat, without the leading slash.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x06, state = Play, direction = Serverbound)]
+pub struct ChatCommand {
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x08, state = Play, direction = Serverbound)]
+pub struct Chat {
+    pub message: String,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub salt: i64,
+    #[codec(prefixed_option)]
+    pub signature: Option<MessageSignature>,
+    /// Messages acknowledged since the last chat packet.
+    #[codec(varint)]
+    pub offset: i32,
+    /// Bit set over the last 20 messages seen.
+    pub acknowledged: [u8; 3],
+    pub checksum: u8,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x0A, state = Play, direction = Serverbound)]
+pub struct ChunkBatchReceived {
+    pub desired_chunks_per_tick: f32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x0F, state = Play, direction = Serverbound)]
+pub struct ConfigurationAcknowledged {}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x14, state = Play, direction = Serverbound)]
+pub struct CookieResponse {
+    pub key: String,
+    /// `None` if the client has no such cookie.
+    #[codec(prefixed_option)]
+    pub payload: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1B, state = Play, direction = Serverbound, name = "keep_alive")]
+pub struct KeepAliveResponse {
+    pub id: i64,
+}
+
+/// Flags of the serverbound movement packets.
+pub mod movement {
+    pub const ON_GROUND: u8 = 1 << 0;
+    pub const HORIZONTAL_COLLISION: u8 = 1 << 1;
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1D, state = Play, direction = Serverbound)]
+pub struct MovePlayerPos {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub flags: u8,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1E, state = Play, direction = Serverbound)]
+pub struct MovePlayerPosRot {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub flags: u8,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x1F, state = Play, direction = Serverbound)]
+pub struct MovePlayerRot {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub flags: u8,
+}
+
+/// Tells the server the world around the player has loaded.
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x2B, state = Play, direction = Serverbound)]
+pub struct PlayerLoaded {}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x2C, state = Play, direction = Serverbound)]
+pub struct Pong {
+    pub id: i32,
+}
+
+#[derive(Debug, Clone, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x30, state = Play, direction = Serverbound, name = "resource_pack")]
+pub struct ResourcePackResponse {
+    pub id: Uuid,
+    pub status: ResourcePackStatus,
+}
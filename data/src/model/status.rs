@@ -0,0 +1,28 @@
+use codec::dec::Decode;
+use codec::enc::Encode;
+use json::JsonValue;
+
+use crate::json::ToJson;
+use crate::protocol::Packet;
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Status, direction = Serverbound)]
+pub struct StatusRequest {}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x01, state = Status, direction = Serverbound)]
+pub struct PingRequest {
+    pub time: i64,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x00, state = Status, direction = Clientbound)]
+pub struct StatusResponse {
+    pub status: JsonValue,
+}
+
+#[derive(Debug, Decode, Encode, Packet, ToJson)]
+#[packet(id = 0x01, state = Status, direction = Clientbound)]
+pub struct PongResponse {
+    pub time: i64,
+}
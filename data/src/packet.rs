@@ -3,7 +3,6 @@ use std::io::{
     Read as _,
     Write as _,
 };
-use std::net::TcpStream;
 
 use codec::VarInt;
 use codec::dec::{
@@ -30,7 +29,7 @@ fn parse_len<R: io::Read>(reader: &mut R) -> Result<usize, DecodeError> {
     Ok(len)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Packet {
     pub id: i32,
     pub data: Box<[u8]>,
@@ -48,9 +47,10 @@ impl Packet {
         }
     }
 
-    fn read_packet_uncompressed(from: &mut TcpStream) -> Result<Packet, DecodeError> {
-        let packet_len = parse_len(from)?;
-
+    fn read_packet_uncompressed<R: io::Read>(
+        from: &mut R,
+        packet_len: usize,
+    ) -> Result<Packet, DecodeError> {
         let mut data = vec![0; packet_len];
         from.read_exact(&mut data)?;
         let mut data = data.as_slice();
@@ -61,9 +61,10 @@ impl Packet {
         })
     }
 
-    fn read_packet_compressed(from: &mut TcpStream) -> Result<Packet, DecodeError> {
-        let packet_len = parse_len(from)?;
-
+    fn read_packet_compressed<R: io::Read>(
+        from: &mut R,
+        packet_len: usize,
+    ) -> Result<Packet, DecodeError> {
         let mut packet_buf = vec![0; packet_len];
         from.read_exact(&mut packet_buf)?;
         let mut packet_buf = packet_buf.as_slice();
@@ -89,9 +90,9 @@ impl Packet {
         }
     }
 
-    fn write_packet_uncompressed(
+    fn write_packet_uncompressed<W: io::Write>(
         &self,
-        to: &mut TcpStream,
+        to: &mut W,
     ) -> Result<usize, EncodeError> {
         let id = VarInt::new(self.id);
         let data = self.data.as_ref();
@@ -114,9 +115,9 @@ impl Packet {
         Ok(packet_len_.as_slice().len() + packet_len)
     }
 
-    fn write_packet_compressed(
+    fn write_packet_compressed<W: io::Write>(
         &self,
-        to: &mut TcpStream,
+        to: &mut W,
         min_compression: usize,
     ) -> Result<usize, EncodeError> {
         let id = VarInt::new(self.id);
@@ -192,18 +193,49 @@ pub trait ReadPacket {
         &mut self,
         is_compressed: bool,
     ) -> Result<Packet, DecodeError>;
+
+    /// Reads a packet from the given reader.
+    ///
+    /// # Returns
+    ///
+    /// The decoded packet and the number of bytes it took in the reader,
+    /// compressed if `is_compressed`.
+    ///
+    /// # Errors
+    ///
+    /// If the packet could not be decoded.
+    fn read_packet_sized(
+        &mut self,
+        is_compressed: bool,
+    ) -> Result<(Packet, usize), DecodeError>;
 }
 
-impl ReadPacket for TcpStream {
+impl<R: io::Read> ReadPacket for R {
     fn read_packet(
         &mut self,
         is_compressed: bool,
     ) -> Result<Packet, DecodeError> {
-        if is_compressed {
-            Packet::read_packet_compressed(self)
+        self.read_packet_sized(is_compressed)
+            .map(|(packet, _)| packet)
+    }
+
+    fn read_packet_sized(
+        &mut self,
+        is_compressed: bool,
+    ) -> Result<(Packet, usize), DecodeError> {
+        let packet_len = parse_len(self)?;
+        let packet = if is_compressed {
+            Packet::read_packet_compressed(self, packet_len)?
         } else {
-            Packet::read_packet_uncompressed(self)
-        }
+            Packet::read_packet_uncompressed(self, packet_len)?
+        };
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_possible_wrap,
+            reason = "Packet length are max i32::MAX"
+        )]
+        let prefix_len = VarInt::new(packet_len as i32).as_slice().len();
+        Ok((packet, prefix_len + packet_len))
     }
 }
 
@@ -224,7 +256,7 @@ pub trait WritePacket {
     ) -> Result<usize, EncodeError>;
 }
 
-impl WritePacket for TcpStream {
+impl<W: io::Write> WritePacket for W {
     fn write_packet(
         &mut self,
         packet: &Packet,
@@ -237,3 +269,25 @@ impl WritePacket for TcpStream {
         }
     }
 }
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_size_matches_written_size() {
+        let packet = Packet::new(0x26, &[7; 300]);
+        for min_compression in [None, Some(256), Some(1024)] {
+            let mut buf = Vec::new();
+            let written = buf.write_packet(&packet, min_compression).unwrap();
+            let (read, size) = buf
+                .as_slice()
+                .read_packet_sized(min_compression.is_some())
+                .unwrap();
+            assert_eq!(size, written, "{min_compression:?}");
+            assert_eq!(size, buf.len(), "{min_compression:?}");
+            assert_eq!(read.data, packet.data, "{min_compression:?}");
+        }
+    }
+}
@@ -0,0 +1,518 @@
+mod registry;
+
+use core::fmt::Debug;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+pub use data_macros::Packet;
+pub use registry::{
+    ConfigurationClientbound,
+    ConfigurationServerbound,
+    HandshakeServerbound,
+    LoginClientbound,
+    LoginServerbound,
+    PlayClientbound,
+    PlayServerbound,
+    StatusClientbound,
+    StatusServerbound,
+};
+
+use crate::json::ToJson;
+use crate::packet::Packet;
+use crate::version::{
+    self,
+    ProtocolVersion,
+};
+
+/// Protocol version whose packet ids are given in `#[packet(id = ...)]`.
+pub const PROTOCOL_VERSION: i32 = 772;
+
+/// Connection state a packet belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum State {
+    Handshake,
+    Status,
+    Login,
+    Configuration,
+    Play,
+}
+
+/// Direction a packet travels in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Serverbound,
+    Clientbound,
+}
+
+/// A packet model with a known id, usually implemented with
+/// `#[derive(Packet)]`.
+pub trait ProtocolPacket: Decode + Encode {
+    const ID: i32;
+    const STATE: State;
+    const DIRECTION: Direction;
+    /// Vanilla name of the packet, e.g. `login_compression`.
+    const NAME: &'static str;
+
+    /// Decode this packet from a raw packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError::UnknownPacketId`] if the id does not match, or
+    /// any error raised while decoding the payload.
+    fn from_packet(packet: &Packet) -> Result<Self, DecodeError> {
+        if packet.id != Self::ID {
+            return Err(DecodeError::UnknownPacketId(packet.id));
+        }
+        Self::decode(&mut packet.data.as_ref())
+    }
+
+    /// Encode this packet into a raw packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncodeError`] if the payload could not be encoded.
+    fn to_packet(&self) -> Result<Packet, EncodeError> {
+        let mut data = Vec::new();
+        self.encode(&mut data)?;
+        Ok(Packet {
+            id: Self::ID,
+            data: data.into_boxed_slice(),
+        })
+    }
+
+    /// Decode this packet from a raw packet sent with `version`'s ids and
+    /// layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError::UnknownPacketId`] if the id does not match
+    /// this packet in `version`, or any error raised while decoding the
+    /// payload.
+    fn from_packet_versioned(
+        version: ProtocolVersion,
+        packet: &Packet,
+    ) -> Result<Self, DecodeError> {
+        if version.packet_id(Self::STATE, Self::DIRECTION, Self::NAME) != Some(packet.id) {
+            return Err(DecodeError::UnknownPacketId(packet.id));
+        }
+        let data = version::upgrade(
+            version,
+            Self::STATE,
+            Self::DIRECTION,
+            Self::NAME,
+            &packet.data,
+        )?;
+        Self::decode(&mut data.as_ref())
+    }
+
+    /// Encode this packet into a raw packet with `version`'s ids and layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncodeError`] if the packet does not exist in `version` or
+    /// the payload could not be encoded.
+    fn to_packet_versioned(
+        &self,
+        version: ProtocolVersion,
+    ) -> Result<Packet, EncodeError> {
+        let id = version
+            .packet_id(Self::STATE, Self::DIRECTION, Self::NAME)
+            .ok_or_else(|| EncodeError::Custom {
+                message: format!("Packet {} does not exist in {version}", Self::NAME),
+            })?;
+        let mut data = Vec::new();
+        self.encode(&mut data)?;
+        let data = version::downgrade(version, Self::STATE, Self::DIRECTION, Self::NAME, &data)?;
+        Ok(Packet::new(id, &data))
+    }
+}
+
+/// A decoded packet model, to be printed with [`Debug`] or turned into
+/// JSON.
+pub trait Model: Debug + ToJson {}
+
+impl<T: Debug + ToJson> Model for T {}
+
+/// The packet sent with `version`'s ids decoded into its [`Model`], or
+/// `None` if the packet has no model.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the packet has a model but its payload does
+/// not decode.
+pub fn describe(
+    version: ProtocolVersion,
+    state: State,
+    direction: Direction,
+    packet: &Packet,
+) -> Result<Option<Box<dyn Model>>, DecodeError> {
+    fn boxed(
+        decoded: Result<impl Model + 'static, DecodeError>
+    ) -> Result<Option<Box<dyn Model>>, DecodeError> {
+        match decoded {
+            Ok(decoded) => Ok(Some(Box::new(decoded))),
+            Err(DecodeError::UnknownPacketId(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    match (state, direction) {
+        (State::Handshake, Direction::Serverbound) => {
+            boxed(HandshakeServerbound::from_packet_versioned(version, packet))
+        }
+        (State::Handshake, Direction::Clientbound) => Ok(None),
+        (State::Status, Direction::Serverbound) => {
+            boxed(StatusServerbound::from_packet_versioned(version, packet))
+        }
+        (State::Status, Direction::Clientbound) => {
+            boxed(StatusClientbound::from_packet_versioned(version, packet))
+        }
+        (State::Login, Direction::Serverbound) => {
+            boxed(LoginServerbound::from_packet_versioned(version, packet))
+        }
+        (State::Login, Direction::Clientbound) => {
+            boxed(LoginClientbound::from_packet_versioned(version, packet))
+        }
+        (State::Configuration, Direction::Serverbound) => boxed(
+            ConfigurationServerbound::from_packet_versioned(version, packet),
+        ),
+        (State::Configuration, Direction::Clientbound) => boxed(
+            ConfigurationClientbound::from_packet_versioned(version, packet),
+        ),
+        (State::Play, Direction::Serverbound) => {
+            boxed(PlayServerbound::from_packet_versioned(version, packet))
+        }
+        (State::Play, Direction::Clientbound) => {
+            boxed(PlayClientbound::from_packet_versioned(version, packet))
+        }
+    }
+}
+
+/// Generates an enum over every packet of a state and direction, with
+/// conversions from and to raw packets.
+macro_rules! packets {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident($ty:ty)),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        $vis enum $name {
+            $($variant($ty)),*
+        }
+
+        impl $name {
+            /// Decode a raw packet into the matching model.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`DecodeError::UnknownPacketId`] if no packet of this
+            /// state and direction has the packet's id, or any error raised
+            /// while decoding the payload.
+            pub fn from_packet(
+                packet: &$crate::packet::Packet,
+            ) -> Result<Self, ::codec::dec::DecodeError> {
+                use ::codec::dec::Decode as _;
+                use $crate::protocol::ProtocolPacket as _;
+
+                let mut data = packet.data.as_ref();
+                match packet.id {
+                    $(id if id == <$ty>::ID => Ok(Self::$variant(<$ty>::decode(&mut data)?)),)*
+                    id => Err(::codec::dec::DecodeError::UnknownPacketId(id)),
+                }
+            }
+
+            /// Decode a raw packet sent with `version`'s ids and layout into
+            /// the matching model.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`DecodeError::UnknownPacketId`] if no packet of this
+            /// state and direction has the packet's id in `version`, or any
+            /// error raised while decoding the payload.
+            pub fn from_packet_versioned(
+                version: $crate::version::ProtocolVersion,
+                packet: &$crate::packet::Packet,
+            ) -> Result<Self, ::codec::dec::DecodeError> {
+                use $crate::protocol::ProtocolPacket as _;
+
+                $(
+                    if version.packet_id(<$ty>::STATE, <$ty>::DIRECTION, <$ty>::NAME)
+                        == Some(packet.id)
+                    {
+                        return Ok(Self::$variant(<$ty>::from_packet_versioned(version, packet)?));
+                    }
+                )*
+                Err(::codec::dec::DecodeError::UnknownPacketId(packet.id))
+            }
+
+            /// Encode the model into a raw packet with `version`'s ids and
+            /// layout.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`EncodeError`](::codec::enc::EncodeError) if the
+            /// packet does not exist in `version` or the payload could not be
+            /// encoded.
+            pub fn to_packet_versioned(
+                &self,
+                version: $crate::version::ProtocolVersion,
+            ) -> Result<$crate::packet::Packet, ::codec::enc::EncodeError> {
+                use $crate::protocol::ProtocolPacket as _;
+
+                match self {
+                    $(Self::$variant(packet) => packet.to_packet_versioned(version),)*
+                }
+            }
+
+            /// Encode the model into a raw packet.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`EncodeError`](::codec::enc::EncodeError) if the
+            /// payload could not be encoded.
+            pub fn to_packet(&self) -> Result<$crate::packet::Packet, ::codec::enc::EncodeError> {
+                use $crate::protocol::ProtocolPacket as _;
+
+                match self {
+                    $(Self::$variant(packet) => packet.to_packet(),)*
+                }
+            }
+
+            /// Id of the contained packet.
+            #[must_use]
+            pub const fn id(&self) -> i32 {
+                use $crate::protocol::ProtocolPacket as _;
+
+                match self {
+                    $(Self::$variant(_) => <$ty>::ID,)*
+                }
+            }
+
+            /// Vanilla name of the contained packet.
+            #[must_use]
+            pub const fn name(&self) -> &'static str {
+                use $crate::protocol::ProtocolPacket as _;
+
+                match self {
+                    $(Self::$variant(_) => <$ty>::NAME,)*
+                }
+            }
+
+            /// Vanilla name of the packet with the given id, if known.
+            #[must_use]
+            pub fn name_of(id: i32) -> Option<&'static str> {
+                use $crate::protocol::ProtocolPacket as _;
+
+                match id {
+                    $(id if id == <$ty>::ID => Some(<$ty>::NAME),)*
+                    _ => None,
+                }
+            }
+        }
+
+        /// The fields of the contained packet.
+        impl $crate::json::ToJson for $name {
+            fn to_json(&self) -> ::json::JsonValue {
+                match self {
+                    $(Self::$variant(packet) => $crate::json::ToJson::to_json(packet),)*
+                }
+            }
+        }
+
+        $(
+            impl From<$ty> for $name {
+                fn from(packet: $ty) -> Self { Self::$variant(packet) }
+            }
+        )*
+    };
+}
+
+use packets;
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::login;
+
+    #[test]
+    fn derived_constants() {
+        assert_eq!(login::LoginCompression::ID, 0x03);
+        assert_eq!(login::LoginCompression::STATE, State::Login);
+        assert_eq!(login::LoginCompression::DIRECTION, Direction::Clientbound);
+        assert_eq!(login::LoginCompression::NAME, "login_compression");
+        assert_eq!(login::EncryptionRequest::NAME, "hello");
+    }
+
+    #[test]
+    fn registry_roundtrip() {
+        let packet = Packet::new(0x03, &[0x80, 0x02]);
+        let login_packet = LoginClientbound::from_packet(&packet).unwrap();
+        assert!(
+            matches!(
+                login_packet,
+                LoginClientbound::LoginCompression(login::LoginCompression {
+                    size: 256
+                })
+            ),
+            "unexpected packet: {login_packet:?}"
+        );
+        assert_eq!(login_packet.name(), "login_compression");
+
+        let reencoded = login_packet.to_packet().unwrap();
+        assert_eq!(reencoded.id, packet.id);
+        assert_eq!(reencoded.data, packet.data);
+    }
+
+    #[test]
+    fn registry_versioned() {
+        // 1.20.4 encryption requests have no should_authenticate
+        let packet = Packet::new(0x01, &[0x00, 0x01, 0xAA, 0x01, 0xBB]);
+        let login_packet =
+            LoginClientbound::from_packet_versioned(ProtocolVersion::V1_20_3, &packet).unwrap();
+        let LoginClientbound::EncryptionRequest(request) = &login_packet else {
+            panic!("unexpected packet: {login_packet:?}");
+        };
+        assert!(
+            request.should_authenticate,
+            "old clients always authenticate"
+        );
+
+        let reencoded = login_packet
+            .to_packet_versioned(ProtocolVersion::V1_20_3)
+            .unwrap();
+        assert_eq!(reencoded.data, packet.data);
+        assert_eq!(
+            login_packet
+                .to_packet_versioned(ProtocolVersion::LATEST)
+                .unwrap()
+                .data
+                .len(),
+            packet.data.len() + 1
+        );
+
+        // cookies do not exist before 1.20.5
+        let packet = Packet::new(0x05, &[0x00]);
+        assert!(matches!(
+            LoginClientbound::from_packet_versioned(ProtocolVersion::V1_20_3, &packet),
+            Err(DecodeError::UnknownPacketId(0x05))
+        ));
+    }
+
+    #[test]
+    fn every_registry_id_matches_latest_table() {
+        fn check<P: ProtocolPacket>() {
+            assert_eq!(
+                ProtocolVersion::LATEST.packet_id(P::STATE, P::DIRECTION, P::NAME),
+                Some(P::ID),
+                "{} has a different id in the latest table",
+                P::NAME
+            );
+        }
+
+        check::<crate::model::handshake::Handshake>();
+        check::<crate::model::status::StatusRequest>();
+        check::<crate::model::status::PingRequest>();
+        check::<crate::model::status::StatusResponse>();
+        check::<crate::model::status::PongResponse>();
+        check::<login::Hello>();
+        check::<login::Key>();
+        check::<login::CustomQueryAnswer>();
+        check::<login::LoginAcknowledged>();
+        check::<login::CookieResponse>();
+        check::<login::LoginDisconnect>();
+        check::<login::EncryptionRequest>();
+        check::<login::LoginFinished>();
+        check::<login::LoginCompression>();
+        check::<login::CustomQuery>();
+        check::<login::CookieRequest>();
+    }
+
+    #[test]
+    fn registry_unknown_id() {
+        let packet = Packet::new(0x42, &[]);
+        assert!(matches!(
+            LoginClientbound::from_packet(&packet),
+            Err(DecodeError::UnknownPacketId(0x42))
+        ));
+        assert_eq!(LoginClientbound::name_of(0x05), Some("cookie_request"));
+        assert_eq!(LoginClientbound::name_of(0x42), None);
+    }
+
+    #[test]
+    fn describe_known_packets() {
+        let packet = Packet::new(0x03, &[0x80, 0x02]);
+        let fields = describe(
+            ProtocolVersion::LATEST,
+            State::Login,
+            Direction::Clientbound,
+            &packet,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            format!("{fields:#?}"),
+            "LoginCompression(\n    LoginCompression {\n        size: 256,\n    },\n)"
+        );
+
+        let keep_alive = Packet::new(0x1B, &[0, 0, 0, 0, 0, 0, 0, 7]);
+        let fields = describe(
+            ProtocolVersion::LATEST,
+            State::Play,
+            Direction::Serverbound,
+            &keep_alive,
+        )
+        .unwrap()
+        .unwrap();
+        let fields = format!("{fields:?}");
+        assert!(fields.contains("id: 7"), "unexpected fields: {fields}");
+
+        let unknown = Packet::new(0x7F, &[]);
+        assert!(
+            describe(
+                ProtocolVersion::LATEST,
+                State::Play,
+                Direction::Clientbound,
+                &unknown
+            )
+            .unwrap()
+            .is_none(),
+            "packets without a model should not be described"
+        );
+        let truncated = Packet::new(0x03, &[]);
+        assert!(
+            describe(
+                ProtocolVersion::LATEST,
+                State::Login,
+                Direction::Clientbound,
+                &truncated
+            )
+            .is_err(),
+            "malformed payloads should be reported"
+        );
+    }
+
+    #[test]
+    fn describe_huge_length() {
+        let version = ProtocolVersion::LATEST;
+        let (state, direction) = (State::Configuration, Direction::Serverbound);
+        let id = version
+            .packet_id(state, direction, "select_known_packs")
+            .unwrap();
+        // about 2^31 known packs announced in 5 bytes
+        let packet = Packet::new(id, &[0xFF, 0xFF, 0xFF, 0xFF, 0x07]);
+        assert!(
+            describe(version, state, direction, &packet).is_err(),
+            "the packs are missing"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use super::packets;
+use crate::model::{
+    configuration,
+    handshake,
+    login,
+    play,
+    status,
+};
+
+packets! {
+    /// Packets sent by the client in the handshake state.
+    pub enum HandshakeServerbound {
+        Intention(handshake::Handshake),
+    }
+}
+
+packets! {
+    /// Packets sent by the client in the status state.
+    pub enum StatusServerbound {
+        StatusRequest(status::StatusRequest),
+        PingRequest(status::PingRequest),
+    }
+}
+
+packets! {
+    /// Packets sent by the server in the status state.
+    pub enum StatusClientbound {
+        StatusResponse(status::StatusResponse),
+        PongResponse(status::PongResponse),
+    }
+}
+
+packets! {
+    /// Packets sent by the client in the login state.
+    pub enum LoginServerbound {
+        Hello(login::Hello),
+        Key(login::Key),
+        CustomQueryAnswer(login::CustomQueryAnswer),
+        LoginAcknowledged(login::LoginAcknowledged),
+        CookieResponse(login::CookieResponse),
+    }
+}
+
+packets! {
+    /// Packets sent by the server in the login state.
+    pub enum LoginClientbound {
+        LoginDisconnect(login::LoginDisconnect),
+        EncryptionRequest(login::EncryptionRequest),
+        LoginFinished(login::LoginFinished),
+        LoginCompression(login::LoginCompression),
+        CustomQuery(login::CustomQuery),
+        CookieRequest(login::CookieRequest),
+    }
+}
+
+packets! {
+    /// Packets sent by the client in the configuration state that have a
+    /// model.
+    pub enum ConfigurationServerbound {
+        ClientInformation(configuration::ClientInformation),
+        CookieResponse(configuration::CookieResponse),
+        AcknowledgeFinishConfiguration(configuration::AcknowledgeFinishConfiguration),
+        KeepAliveResponse(configuration::KeepAliveResponse),
+        Pong(configuration::Pong),
+        ResourcePackResponse(configuration::ResourcePackResponse),
+        KnownPacks(configuration::KnownPacks),
+    }
+}
+
+packets! {
+    /// Packets sent by the server in the configuration state that have a
+    /// model.
+    pub enum ConfigurationClientbound {
+        CookieRequest(configuration::CookieRequest),
+        Disconnect(configuration::Disconnect),
+        FinishConfiguration(configuration::FinishConfiguration),
+        KeepAlive(configuration::KeepAlive),
+        Ping(configuration::Ping),
+        RegistryData(configuration::RegistryData),
+        ResourcePackPop(configuration::ResourcePackPop),
+        ResourcePackPush(configuration::ResourcePackPush),
+        StoreCookie(configuration::StoreCookie),
+        Transfer(configuration::Transfer),
+        SelectKnownPacks(configuration::SelectKnownPacks),
+    }
+}
+
+packets! {
+    /// Packets sent by the client in the play state that have a model.
+    #[allow(
+        clippy::large_enum_variant,
+        reason = "decoded one at a time, boxing would only add indirection"
+    )]
+    pub enum PlayServerbound {
+        AcceptTeleportation(play::AcceptTeleportation),
+        ChatCommand(play::ChatCommand),
+        Chat(play::Chat),
+        ChunkBatchReceived(play::ChunkBatchReceived),
+        ConfigurationAcknowledged(play::ConfigurationAcknowledged),
+        CookieResponse(play::CookieResponse),
+        KeepAliveResponse(play::KeepAliveResponse),
+        MovePlayerPos(play::MovePlayerPos),
+        MovePlayerPosRot(play::MovePlayerPosRot),
+        MovePlayerRot(play::MovePlayerRot),
+        PlayerLoaded(play::PlayerLoaded),
+        Pong(play::Pong),
+        ResourcePackResponse(play::ResourcePackResponse),
+    }
+}
+
+packets! {
+    /// Packets sent by the server in the play state that have a model.
+    #[allow(
+        clippy::large_enum_variant,
+        reason = "decoded one at a time, boxing would only add indirection"
+    )]
+    pub enum PlayClientbound {
+        AddEntity(play::AddEntity),
+        BlockEntityData(play::BlockEntityData),
+        BlockUpdate(play::BlockUpdate),
+        BossEvent(play::BossEvent),
+        ChunkBatchFinished(play::ChunkBatchFinished),
+        Commands(play::Commands),
+        CookieRequest(play::CookieRequest),
+        Disconnect(play::Disconnect),
+        DisguisedChat(play::DisguisedChat),
+        EntityPositionSync(play::EntityPositionSync),
+        ForgetLevelChunk(play::ForgetLevelChunk),
+        GameEvent(play::GameEvent),
+        KeepAlive(play::KeepAlive),
+        LevelChunkWithLight(play::LevelChunkWithLight),
+        Login(play::Login),
+        MoveEntityPos(play::MoveEntityPos),
+        MoveEntityPosRot(play::MoveEntityPosRot),
+        MoveEntityRot(play::MoveEntityRot),
+        Ping(play::Ping),
+        PlayerChat(play::PlayerChat),
+        PlayerInfoRemove(play::PlayerInfoRemove),
+        PlayerInfoUpdate(play::PlayerInfoUpdate),
+        PlayerPosition(play::PlayerPosition),
+        RemoveEntities(play::RemoveEntities),
+        ResourcePackPop(play::ResourcePackPop),
+        ResourcePackPush(play::ResourcePackPush),
+        Respawn(play::Respawn),
+        SectionBlocksUpdate(play::SectionBlocksUpdate),
+        SetChunkCacheCenter(play::SetChunkCacheCenter),
+        SetEntityData(play::SetEntityData),
+        StartConfiguration(play::StartConfiguration),
+        StoreCookie(play::StoreCookie),
+        SystemChat(play::SystemChat),
+        TeleportEntity(play::TeleportEntity),
+        Transfer(play::Transfer),
+    }
+}
@@ -0,0 +1,277 @@
+//! Block states: a block plus a value for each of its properties, numbered
+//! consecutively across every block.
+
+use core::fmt;
+use std::io;
+
+use codec::VarInt;
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+
+use super::{
+    Block,
+    Registry as _,
+};
+use crate::generated::blocks::{
+    BLOCKS,
+    STATE_COUNT,
+};
+
+/// A block state property and its possible values, in id order.
+#[derive(Debug)]
+pub struct Property {
+    pub name: &'static str,
+    pub values: &'static [&'static str],
+}
+
+/// State layout of a block, generated from `blocks.json`.
+pub(crate) struct BlockStates {
+    pub(crate) first_state: u32,
+    pub(crate) default_state: u32,
+    /// States enumerate property values with the last property changing
+    /// fastest.
+    pub(crate) properties: &'static [Property],
+}
+
+impl BlockStates {
+    /// Number of consecutive ids a property value spans.
+    fn stride(
+        &self,
+        property: usize,
+    ) -> u32 {
+        self.properties[property + 1..]
+            .iter()
+            .map(property_len)
+            .product()
+    }
+
+    fn state_count(&self) -> u32 { self.properties.iter().map(property_len).product() }
+
+    /// Index of the value a state has for a property.
+    fn value_index(
+        &self,
+        state: u32,
+        property: usize,
+    ) -> u32 {
+        (state - self.first_state) / self.stride(property)
+            % property_len(&self.properties[property])
+    }
+}
+
+fn property_len(property: &Property) -> u32 {
+    u32::try_from(property.values.len()).unwrap_or(u32::MAX)
+}
+
+impl Block {
+    fn states_layout(self) -> &'static BlockStates {
+        // every block id indexes the generated table
+        &BLOCKS[self as usize]
+    }
+
+    #[must_use]
+    pub fn default_state(self) -> BlockState { BlockState(self.states_layout().default_state) }
+
+    /// Every state of the block, in id order.
+    pub fn states(self) -> impl Iterator<Item = BlockState> {
+        let layout = self.states_layout();
+        (layout.first_state..layout.first_state + layout.state_count()).map(BlockState)
+    }
+
+    /// Properties of the block's states.
+    #[must_use]
+    pub fn properties(self) -> &'static [Property] { self.states_layout().properties }
+}
+
+/// A block state id, as found in chunk palettes and block update packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockState(u32);
+
+impl BlockState {
+    pub const AIR: Self = Self(0);
+
+    #[must_use]
+    pub fn from_id(id: i32) -> Option<Self> {
+        u32::try_from(id)
+            .ok()
+            .filter(|id| *id < STATE_COUNT)
+            .map(Self)
+    }
+
+    #[must_use]
+    pub fn id(self) -> i32 { i32::try_from(self.0).unwrap_or(i32::MAX) }
+
+    #[must_use]
+    pub fn block(self) -> Block {
+        let index = BLOCKS.partition_point(|layout| layout.first_state <= self.0) - 1;
+        Block::ENTRIES[index]
+    }
+
+    /// Whether the state is air, cave air or void air.
+    #[must_use]
+    pub fn is_air(self) -> bool {
+        matches!(
+            self.block().identifier(),
+            "minecraft:air" | "minecraft:cave_air" | "minecraft:void_air"
+        )
+    }
+
+    #[must_use]
+    pub fn is_default(self) -> bool { self.block().default_state() == self }
+
+    /// Value of a property, e.g. `get("facing")` gives `Some("north")`.
+    #[must_use]
+    pub fn get(
+        self,
+        name: &str,
+    ) -> Option<&'static str> {
+        let layout = self.block().states_layout();
+        let index = layout
+            .properties
+            .iter()
+            .position(|property| property.name == name)?;
+        Some(self.value_of(layout, index))
+    }
+
+    /// The state of the same block with a property set to another value.
+    #[must_use]
+    pub fn with(
+        self,
+        name: &str,
+        value: &str,
+    ) -> Option<Self> {
+        let layout = self.block().states_layout();
+        let index = layout
+            .properties
+            .iter()
+            .position(|property| property.name == name)?;
+        let new = layout.properties[index]
+            .values
+            .iter()
+            .position(|other| *other == value)?;
+        let new = u32::try_from(new).ok()?;
+        let old = layout.value_index(self.0, index);
+        let stride = layout.stride(index);
+        Some(Self(self.0 - old * stride + new * stride))
+    }
+
+    /// Property names and values of the state.
+    pub fn properties(self) -> impl Iterator<Item = (&'static str, &'static str)> {
+        let layout = self.block().states_layout();
+        (0..layout.properties.len())
+            .map(move |index| (layout.properties[index].name, self.value_of(layout, index)))
+    }
+
+    fn value_of(
+        self,
+        layout: &BlockStates,
+        index: usize,
+    ) -> &'static str {
+        let value = layout.value_index(self.0, index);
+        layout.properties[index].values[value as usize]
+    }
+}
+
+impl fmt::Display for BlockState {
+    /// Formats as `minecraft:oak_log[axis=y]`.
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(self.block().identifier())?;
+        let mut properties = self.properties().peekable();
+        if properties.peek().is_some() {
+            f.write_str("[")?;
+            for (index, (name, value)) in properties.enumerate() {
+                if index > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{name}={value}")?;
+            }
+            f.write_str("]")?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for BlockState {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let id = VarInt::decode(reader)?.value();
+        Self::from_id(id).ok_or_else(|| DecodeError::Custom {
+            message: format!("Unknown block state {id}"),
+        })
+    }
+}
+
+impl Encode for BlockState {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        VarInt::new(self.id()).encode(writer)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn properties() {
+        let log = Block::OakLog.default_state();
+        assert_eq!(log.id(), 137);
+        assert_eq!(log.get("axis"), Some("y"));
+        assert_eq!(log.get("facing"), None);
+        assert_eq!(log.with("axis", "z").map(BlockState::id), Some(138));
+        assert_eq!(log.with("axis", "w"), None);
+        assert_eq!(log.to_string(), "minecraft:oak_log[axis=y]");
+
+        let propagule = Block::MangrovePropagule.default_state();
+        assert_eq!(propagule.properties().collect::<Vec<_>>(), [
+            ("age", "0"),
+            ("hanging", "false"),
+            ("stage", "0"),
+            ("waterlogged", "false")
+        ]);
+        let hanging = propagule.with("hanging", "true").unwrap();
+        assert_eq!(hanging.get("hanging"), Some("true"));
+        assert_eq!(hanging.get("waterlogged"), Some("false"));
+        assert_eq!(hanging.block(), Block::MangrovePropagule);
+        assert!(
+            !hanging.is_default(),
+            "only the generated default is default"
+        );
+        assert_eq!(Block::MangrovePropagule.states().count(), 40);
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(BlockState::AIR.block(), Block::Air);
+        assert_eq!(BlockState::AIR.to_string(), "minecraft:air");
+        assert_eq!(Block::Air.states().collect::<Vec<_>>(), [BlockState::AIR]);
+        assert_eq!(BlockState::from_id(9).unwrap().block(), Block::GrassBlock);
+        assert_eq!(BlockState::from_id(9).unwrap().get("snowy"), Some("false"));
+        assert_eq!(BlockState::from_id(-1), None);
+        assert_eq!(
+            BlockState::from_id(i32::try_from(STATE_COUNT).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn codec() {
+        let mut bytes = Vec::new();
+        Block::OakLog.default_state().encode(&mut bytes).unwrap();
+        assert_eq!(bytes, [0x89, 0x01]);
+        assert_eq!(
+            BlockState::decode(&mut bytes.as_slice()).unwrap(),
+            Block::OakLog.default_state()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Registries of the latest supported protocol version, generated by
+//! `datagen`.
+
+pub mod block;
+
+pub use block::{
+    BlockState,
+    Property,
+};
+
+pub use crate::generated::registries::{
+    Biome,
+    Block,
+    BlockEntityType,
+    DataComponentType,
+    EntityType,
+    Item,
+    ParticleType,
+};
+use crate::identifier::Identifier;
+
+/// A registry whose entries are known at compile time.
+pub trait Registry: Copy + 'static {
+    /// Identifier of the registry, e.g. `minecraft:item`.
+    const REGISTRY: &'static str;
+    /// Every entry, in id order.
+    const ENTRIES: &'static [Self];
+
+    /// Namespaced identifier of the entry, e.g. `minecraft:stone`.
+    fn identifier(self) -> &'static str;
+
+    /// Protocol id of the entry.
+    fn id(self) -> i32;
+
+    #[must_use]
+    fn from_id(id: i32) -> Option<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| Self::ENTRIES.get(id))
+            .copied()
+    }
+
+    #[must_use]
+    fn from_identifier(identifier: &Identifier) -> Option<Self> {
+        Self::ENTRIES
+            .iter()
+            .copied()
+            .find(|entry| *identifier == *entry.identifier())
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        assert_eq!(Item::from_id(1), Some(Item::Stone));
+        assert_eq!(Item::from_id(-1), None);
+        assert_eq!(Item::Stone.identifier(), "minecraft:stone");
+        assert_eq!(
+            Item::from_identifier(&"stone".parse().unwrap()),
+            Some(Item::Stone)
+        );
+        assert_eq!(
+            EntityType::from_identifier(&"minecraft:creeper".parse().unwrap()),
+            Some(EntityType::Creeper)
+        );
+        assert_eq!(Biome::from_id(0), Some(Biome::Badlands));
+        assert_eq!(
+            DataComponentType::from_identifier(&"minecraft:custom_data".parse().unwrap())
+                .map(DataComponentType::id),
+            Some(0)
+        );
+        assert_eq!(Block::REGISTRY, "minecraft:block");
+    }
+}
@@ -0,0 +1,197 @@
+//! Conversions between the JSON and NBT encodings of text components.
+//!
+//! Text components were sent as JSON strings until 1.20.3, and as NBT
+//! since.
+
+use codec::nbt::Nbt;
+use json::JsonValue;
+
+/// Converts a JSON text component to its NBT form.
+#[must_use]
+pub fn json_to_nbt(json: &JsonValue) -> Nbt {
+    match json {
+        JsonValue::Null => Nbt::String(String::new()),
+        JsonValue::Short(value) => Nbt::String(value.as_str().to_owned()),
+        JsonValue::String(value) => Nbt::String(value.clone()),
+        JsonValue::Boolean(value) => Nbt::Byte(i8::from(*value)),
+        JsonValue::Number(_) => json
+            .as_i32()
+            .map_or_else(|| Nbt::Double(json.as_f64().unwrap_or_default()), Nbt::Int),
+        JsonValue::Object(object) => Nbt::Compound(
+            object
+                .iter()
+                .map(|(key, value)| (key.to_owned(), json_to_nbt(value)))
+                .collect(),
+        ),
+        JsonValue::Array(values) => {
+            let elements: Vec<Nbt> = values.iter().map(json_to_nbt).collect();
+            let homogeneous = elements
+                .windows(2)
+                .all(|pair| pair[0].tag_type() == pair[1].tag_type());
+            if homogeneous {
+                Nbt::list(elements)
+            } else {
+                // NBT lists hold a single type; vanilla wraps mixed entries in
+                // compounds with an empty key
+                Nbt::list(
+                    elements
+                        .into_iter()
+                        .map(|element| match element {
+                            Nbt::Compound(_) => element,
+                            _ => Nbt::Compound(vec![(String::new(), element)]),
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Converts an NBT text component to its JSON form.
+#[must_use]
+pub fn nbt_to_json(nbt: &Nbt) -> JsonValue {
+    match nbt {
+        Nbt::Byte(value) => match value {
+            0 => JsonValue::Boolean(false),
+            1 => JsonValue::Boolean(true),
+            _ => JsonValue::from(*value),
+        },
+        Nbt::Short(value) => JsonValue::from(*value),
+        Nbt::Int(value) => JsonValue::from(*value),
+        Nbt::Long(value) => JsonValue::from(*value),
+        Nbt::Float(value) => JsonValue::from(*value),
+        Nbt::Double(value) => JsonValue::from(*value),
+        Nbt::String(value) => JsonValue::from(value.as_str()),
+        Nbt::List {
+            elements, ..
+        } => JsonValue::Array(elements.iter().map(unwrap_list_entry).collect()),
+        Nbt::Compound(entries) => {
+            let mut object = json::object::Object::with_capacity(entries.len());
+            for (key, value) in entries {
+                object.insert(key, nbt_to_json(value));
+            }
+            JsonValue::Object(object)
+        }
+        Nbt::ByteArray(values) => values
+            .iter()
+            .copied()
+            .map(JsonValue::from)
+            .collect::<Vec<_>>()
+            .into(),
+        Nbt::IntArray(values) => values
+            .iter()
+            .copied()
+            .map(JsonValue::from)
+            .collect::<Vec<_>>()
+            .into(),
+        Nbt::LongArray(values) => values
+            .iter()
+            .copied()
+            .map(JsonValue::from)
+            .collect::<Vec<_>>()
+            .into(),
+    }
+}
+
+/// The text of an NBT text component without its styling. Translated
+/// components, whose strings only the client knows, give their key followed
+/// by their arguments in brackets.
+#[must_use]
+pub fn plain_text(nbt: &Nbt) -> String {
+    let mut text = String::new();
+    push_plain_text(nbt, &mut text);
+    text
+}
+
+fn push_plain_text(
+    nbt: &Nbt,
+    text: &mut String,
+) {
+    match nbt {
+        Nbt::String(value) => text.push_str(value),
+        Nbt::List {
+            elements, ..
+        } => {
+            for element in elements {
+                push_plain_text(element, text);
+            }
+        }
+        Nbt::Compound(entries) => {
+            if let [(key, value)] = entries.as_slice()
+                && key.is_empty()
+            {
+                push_plain_text(value, text);
+                return;
+            }
+            if let Some(value) = nbt.get("text").and_then(Nbt::as_str) {
+                text.push_str(value);
+            } else if let Some(key) = nbt.get("translate").and_then(Nbt::as_str) {
+                text.push_str(key);
+                if let Some(Nbt::List {
+                    elements, ..
+                }) = nbt.get("with")
+                {
+                    let arguments: Vec<String> = elements.iter().map(plain_text).collect();
+                    text.push_str(" [");
+                    text.push_str(&arguments.join(", "));
+                    text.push(']');
+                }
+            }
+            if let Some(extra) = nbt.get("extra") {
+                push_plain_text(extra, text);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Undoes the empty-key wrapping of mixed list entries.
+fn unwrap_list_entry(nbt: &Nbt) -> JsonValue {
+    match nbt.as_compound() {
+        Some([(key, value)]) if key.is_empty() => nbt_to_json(value),
+        _ => nbt_to_json(nbt),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_component() {
+        let json = json::object! {
+            text: "hello",
+            bold: true,
+            extra: ["a", { text: "b", color: "red" }],
+        };
+        let nbt = json_to_nbt(&json);
+        assert_eq!(nbt.get("text").and_then(Nbt::as_str), Some("hello"));
+        assert_eq!(nbt.get("bold"), Some(&Nbt::Byte(1)));
+        assert_eq!(nbt_to_json(&nbt), json);
+    }
+
+    #[test]
+    fn plain_text_of_component() {
+        let json = json::object! {
+            text: "hello ",
+            extra: [{ text: "world", color: "red" }, "!"],
+        };
+        assert_eq!(plain_text(&json_to_nbt(&json)), "hello world!");
+
+        let json = json::object! {
+            translate: "multiplayer.player.joined",
+            with: [{ text: "Steve" }],
+        };
+        assert_eq!(
+            plain_text(&json_to_nbt(&json)),
+            "multiplayer.player.joined [Steve]"
+        );
+    }
+
+    #[test]
+    fn plain_string() {
+        let json = JsonValue::from("hi");
+        assert_eq!(json_to_nbt(&json), Nbt::String("hi".to_owned()));
+        assert_eq!(nbt_to_json(&Nbt::String("hi".to_owned())), json);
+    }
+}
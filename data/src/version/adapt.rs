@@ -0,0 +1,178 @@
+//! Payload rewrites for packets whose fields differ between versions.
+//!
+//! Packet models describe the latest layout. [`upgrade`] turns a payload
+//! sent by an older version into that layout and [`downgrade`] does the
+//! opposite, so the models never need to know about versions.
+
+use alloc::borrow::Cow;
+
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+use codec::nbt::Nbt;
+use json::JsonValue;
+
+use super::ProtocolVersion;
+use crate::protocol::{
+    Direction,
+    State,
+};
+use crate::text;
+
+/// Rewrites a payload from `version`'s layout to the latest layout.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the payload is too short for its layout.
+pub fn upgrade<'data>(
+    version: ProtocolVersion,
+    state: State,
+    direction: Direction,
+    name: &str,
+    data: &'data [u8],
+) -> Result<Cow<'data, [u8]>, DecodeError> {
+    let v = version.protocol();
+    match (state, direction, name) {
+        // should_authenticate was added at the end in 1.20.5, older clients
+        // always authenticate
+        (State::Login, Direction::Clientbound, "hello") if v < 766 => {
+            let mut data = data.to_vec();
+            data.push(1);
+            Ok(Cow::Owned(data))
+        }
+        // strict_error_handling only existed from 1.20.5 to 1.21.1, at the end
+        (State::Login, Direction::Clientbound, "login_finished") if (766..768).contains(&v) => {
+            match data.split_last() {
+                Some((_, data)) => Ok(Cow::Borrowed(data)),
+                None => Err(DecodeError::UnexpectedEnd),
+            }
+        }
+        // text components became NBT in 1.20.3
+        (State::Configuration, Direction::Clientbound, "disconnect") if v < 765 => {
+            let reason = JsonValue::decode(&mut &data[..])?;
+            let data =
+                encode_to_vec(&text::json_to_nbt(&reason)).map_err(|err| DecodeError::Custom {
+                    message: err.to_string(),
+                })?;
+            Ok(Cow::Owned(data))
+        }
+        // the checksum of the acknowledged messages came at the end in
+        // 1.21.5, zero skips the check
+        (State::Play, Direction::Serverbound, "chat") if v < 770 => {
+            let mut data = data.to_vec();
+            data.push(0);
+            Ok(Cow::Owned(data))
+        }
+        _ => Ok(Cow::Borrowed(data)),
+    }
+}
+
+/// Rewrites a payload from the latest layout to `version`'s layout.
+///
+/// # Errors
+///
+/// Returns [`EncodeError`] if the payload cannot be expressed in `version`.
+pub fn downgrade<'data>(
+    version: ProtocolVersion,
+    state: State,
+    direction: Direction,
+    name: &str,
+    data: &'data [u8],
+) -> Result<Cow<'data, [u8]>, EncodeError> {
+    let v = version.protocol();
+    match (state, direction, name) {
+        (State::Login, Direction::Clientbound, "hello") if v < 766 => match data.split_last() {
+            Some((1, data)) => Ok(Cow::Borrowed(data)),
+            _ => Err(EncodeError::Custom {
+                message: format!("{version} clients always authenticate"),
+            }),
+        },
+        (State::Login, Direction::Clientbound, "login_finished") if (766..768).contains(&v) => {
+            let mut data = data.to_vec();
+            data.push(0);
+            Ok(Cow::Owned(data))
+        }
+        (State::Configuration, Direction::Clientbound, "disconnect") if v < 765 => {
+            let reason = Nbt::decode(&mut &data[..]).map_err(|err| EncodeError::Custom {
+                message: err.to_string(),
+            })?;
+            Ok(Cow::Owned(encode_to_vec(&text::nbt_to_json(&reason))?))
+        }
+        (State::Play, Direction::Serverbound, "chat") if v < 770 => match data.split_last() {
+            Some((_, data)) => Ok(Cow::Borrowed(data)),
+            None => Err(EncodeError::Custom {
+                message: "Chat message without a checksum".to_owned(),
+            }),
+        },
+        _ => Ok(Cow::Borrowed(data)),
+    }
+}
+
+fn encode_to_vec(value: &impl Encode) -> Result<Vec<u8>, EncodeError> {
+    let mut data = Vec::new();
+    value.encode(&mut data)?;
+    Ok(data)
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuration_disconnect_text() {
+        let mut json = Vec::new();
+        json::object! { text: "bye" }.encode(&mut json).unwrap();
+
+        let version = ProtocolVersion::V1_20_2;
+        let upgraded = upgrade(
+            version,
+            State::Configuration,
+            Direction::Clientbound,
+            "disconnect",
+            &json,
+        )
+        .unwrap();
+        let reason = Nbt::decode(&mut upgraded.as_ref()).unwrap();
+        assert_eq!(reason.get("text").and_then(Nbt::as_str), Some("bye"));
+
+        let downgraded = downgrade(
+            version,
+            State::Configuration,
+            Direction::Clientbound,
+            "disconnect",
+            &upgraded,
+        )
+        .unwrap();
+        assert_eq!(downgraded.as_ref(), json.as_slice());
+    }
+
+    #[test]
+    fn chat_checksum() {
+        // message, timestamp, salt, no signature, offset and acknowledged
+        let mut chat = Vec::new();
+        "hi".encode(&mut chat).unwrap();
+        chat.extend_from_slice(&[0; 16]);
+        chat.extend_from_slice(&[0, 0, 0, 0, 0]);
+
+        let (state, direction) = (State::Play, Direction::Serverbound);
+        let version = ProtocolVersion::V1_21_4;
+        let upgraded = upgrade(version, state, direction, "chat", &chat).unwrap();
+        assert_eq!(upgraded.len(), chat.len() + 1, "a checksum is added");
+        let downgraded = downgrade(version, state, direction, "chat", &upgraded).unwrap();
+        assert_eq!(downgraded.as_ref(), chat.as_slice());
+
+        let latest = ProtocolVersion::V1_21_5;
+        let upgraded = upgrade(latest, state, direction, "chat", &chat).unwrap();
+        assert_eq!(
+            upgraded.as_ref(),
+            chat.as_slice(),
+            "1.21.5 clients send the checksum"
+        );
+    }
+}
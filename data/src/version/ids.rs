@@ -0,0 +1,765 @@
+//! Packet names in id order, per state and direction, for each range of
+//! protocol versions sharing the same layout.
+
+pub(super) const HANDSHAKE_SERVERBOUND: &[&str] = &["intention"];
+
+pub(super) const STATUS_SERVERBOUND: &[&str] = &["status_request", "ping_request"];
+pub(super) const STATUS_CLIENTBOUND: &[&str] = &["status_response", "pong_response"];
+
+/// 1.20.2 to 1.20.4.
+pub(super) const LOGIN_SERVERBOUND_764: &[&str] =
+    &["hello", "key", "custom_query_answer", "login_acknowledged"];
+/// 1.20.5 onwards: cookies.
+pub(super) const LOGIN_SERVERBOUND_766: &[&str] = &[
+    "hello",
+    "key",
+    "custom_query_answer",
+    "login_acknowledged",
+    "cookie_response",
+];
+
+/// 1.20.2 to 1.20.4.
+pub(super) const LOGIN_CLIENTBOUND_764: &[&str] = &[
+    "login_disconnect",
+    "hello",
+    "login_finished",
+    "login_compression",
+    "custom_query",
+];
+/// 1.20.5 onwards: cookies.
+pub(super) const LOGIN_CLIENTBOUND_766: &[&str] = &[
+    "login_disconnect",
+    "hello",
+    "login_finished",
+    "login_compression",
+    "custom_query",
+    "cookie_request",
+];
+
+/// 1.20.2 to 1.20.4.
+pub(super) const CONFIGURATION_SERVERBOUND_764: &[&str] = &[
+    "client_information",
+    "custom_payload",
+    "finish_configuration",
+    "keep_alive",
+    "pong",
+    "resource_pack",
+];
+/// 1.20.5 to 1.21.5: cookies and known packs.
+pub(super) const CONFIGURATION_SERVERBOUND_766: &[&str] = &[
+    "client_information",
+    "cookie_response",
+    "custom_payload",
+    "finish_configuration",
+    "keep_alive",
+    "pong",
+    "resource_pack",
+    "select_known_packs",
+];
+/// 1.21.6 onwards: dialogs.
+pub(super) const CONFIGURATION_SERVERBOUND_771: &[&str] = &[
+    "client_information",
+    "cookie_response",
+    "custom_payload",
+    "finish_configuration",
+    "keep_alive",
+    "pong",
+    "resource_pack",
+    "select_known_packs",
+    "custom_click_action",
+];
+
+/// 1.20.2.
+pub(super) const CONFIGURATION_CLIENTBOUND_764: &[&str] = &[
+    "custom_payload",
+    "disconnect",
+    "finish_configuration",
+    "keep_alive",
+    "ping",
+    "registry_data",
+    "resource_pack_push",
+    "update_enabled_features",
+    "update_tags",
+];
+/// 1.20.3 and 1.20.4: resource pack stacks.
+pub(super) const CONFIGURATION_CLIENTBOUND_765: &[&str] = &[
+    "custom_payload",
+    "disconnect",
+    "finish_configuration",
+    "keep_alive",
+    "ping",
+    "registry_data",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "update_enabled_features",
+    "update_tags",
+];
+/// 1.20.5 and 1.20.6: cookies, transfers and known packs.
+pub(super) const CONFIGURATION_CLIENTBOUND_766: &[&str] = &[
+    "cookie_request",
+    "custom_payload",
+    "disconnect",
+    "finish_configuration",
+    "keep_alive",
+    "ping",
+    "reset_chat",
+    "registry_data",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "store_cookie",
+    "transfer",
+    "update_enabled_features",
+    "update_tags",
+    "select_known_packs",
+];
+/// 1.21 to 1.21.5: report details and server links.
+pub(super) const CONFIGURATION_CLIENTBOUND_767: &[&str] = &[
+    "cookie_request",
+    "custom_payload",
+    "disconnect",
+    "finish_configuration",
+    "keep_alive",
+    "ping",
+    "reset_chat",
+    "registry_data",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "store_cookie",
+    "transfer",
+    "update_enabled_features",
+    "update_tags",
+    "select_known_packs",
+    "custom_report_details",
+    "server_links",
+];
+/// 1.21.6 onwards: dialogs.
+pub(super) const CONFIGURATION_CLIENTBOUND_771: &[&str] = &[
+    "cookie_request",
+    "custom_payload",
+    "disconnect",
+    "finish_configuration",
+    "keep_alive",
+    "ping",
+    "reset_chat",
+    "registry_data",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "store_cookie",
+    "transfer",
+    "update_enabled_features",
+    "update_tags",
+    "select_known_packs",
+    "custom_report_details",
+    "server_links",
+    "clear_dialog",
+    "show_dialog",
+];
+
+/// 1.21.4.
+pub(super) const PLAY_SERVERBOUND_769: &[&str] = &[
+    "accept_teleportation",
+    "block_entity_tag_query",
+    "bundle_item_selected",
+    "change_difficulty",
+    "chat_ack",
+    "chat_command",
+    "chat_command_signed",
+    "chat",
+    "chat_session_update",
+    "chunk_batch_received",
+    "client_command",
+    "client_tick_end",
+    "client_information",
+    "command_suggestion",
+    "configuration_acknowledged",
+    "container_button_click",
+    "container_click",
+    "container_close",
+    "container_slot_state_changed",
+    "cookie_response",
+    "custom_payload",
+    "debug_sample_subscription",
+    "edit_book",
+    "entity_tag_query",
+    "interact",
+    "jigsaw_generate",
+    "keep_alive",
+    "lock_difficulty",
+    "move_player_pos",
+    "move_player_pos_rot",
+    "move_player_rot",
+    "move_player_status_only",
+    "move_vehicle",
+    "paddle_boat",
+    "pick_item_from_block",
+    "pick_item_from_entity",
+    "ping_request",
+    "place_recipe",
+    "player_abilities",
+    "player_action",
+    "player_command",
+    "player_input",
+    "player_loaded",
+    "pong",
+    "recipe_book_change_settings",
+    "recipe_book_seen_recipe",
+    "rename_item",
+    "resource_pack",
+    "seen_advancements",
+    "select_trade",
+    "set_beacon",
+    "set_carried_item",
+    "set_command_block",
+    "set_command_minecart",
+    "set_creative_mode_slot",
+    "set_jigsaw_block",
+    "set_structure_block",
+    "sign_update",
+    "swing",
+    "teleport_to_entity",
+    "use_item_on",
+    "use_item",
+];
+/// 1.21.5: test instance blocks.
+pub(super) const PLAY_SERVERBOUND_770: &[&str] = &[
+    "accept_teleportation",
+    "block_entity_tag_query",
+    "bundle_item_selected",
+    "change_difficulty",
+    "chat_ack",
+    "chat_command",
+    "chat_command_signed",
+    "chat",
+    "chat_session_update",
+    "chunk_batch_received",
+    "client_command",
+    "client_tick_end",
+    "client_information",
+    "command_suggestion",
+    "configuration_acknowledged",
+    "container_button_click",
+    "container_click",
+    "container_close",
+    "container_slot_state_changed",
+    "cookie_response",
+    "custom_payload",
+    "debug_sample_subscription",
+    "edit_book",
+    "entity_tag_query",
+    "interact",
+    "jigsaw_generate",
+    "keep_alive",
+    "lock_difficulty",
+    "move_player_pos",
+    "move_player_pos_rot",
+    "move_player_rot",
+    "move_player_status_only",
+    "move_vehicle",
+    "paddle_boat",
+    "pick_item_from_block",
+    "pick_item_from_entity",
+    "ping_request",
+    "place_recipe",
+    "player_abilities",
+    "player_action",
+    "player_command",
+    "player_input",
+    "player_loaded",
+    "pong",
+    "recipe_book_change_settings",
+    "recipe_book_seen_recipe",
+    "rename_item",
+    "resource_pack",
+    "seen_advancements",
+    "select_trade",
+    "set_beacon",
+    "set_carried_item",
+    "set_command_block",
+    "set_command_minecart",
+    "set_creative_mode_slot",
+    "set_jigsaw_block",
+    "set_structure_block",
+    "set_test_block",
+    "sign_update",
+    "swing",
+    "teleport_to_entity",
+    "test_instance_block_action",
+    "use_item_on",
+    "use_item",
+];
+/// 1.21.6 onwards: game mode switcher and dialogs.
+pub(super) const PLAY_SERVERBOUND_771: &[&str] = &[
+    "accept_teleportation",
+    "block_entity_tag_query",
+    "bundle_item_selected",
+    "change_difficulty",
+    "change_game_mode",
+    "chat_ack",
+    "chat_command",
+    "chat_command_signed",
+    "chat",
+    "chat_session_update",
+    "chunk_batch_received",
+    "client_command",
+    "client_tick_end",
+    "client_information",
+    "command_suggestion",
+    "configuration_acknowledged",
+    "container_button_click",
+    "container_click",
+    "container_close",
+    "container_slot_state_changed",
+    "cookie_response",
+    "custom_payload",
+    "debug_sample_subscription",
+    "edit_book",
+    "entity_tag_query",
+    "interact",
+    "jigsaw_generate",
+    "keep_alive",
+    "lock_difficulty",
+    "move_player_pos",
+    "move_player_pos_rot",
+    "move_player_rot",
+    "move_player_status_only",
+    "move_vehicle",
+    "paddle_boat",
+    "pick_item_from_block",
+    "pick_item_from_entity",
+    "ping_request",
+    "place_recipe",
+    "player_abilities",
+    "player_action",
+    "player_command",
+    "player_input",
+    "player_loaded",
+    "pong",
+    "recipe_book_change_settings",
+    "recipe_book_seen_recipe",
+    "rename_item",
+    "resource_pack",
+    "seen_advancements",
+    "select_trade",
+    "set_beacon",
+    "set_carried_item",
+    "set_command_block",
+    "set_command_minecart",
+    "set_creative_mode_slot",
+    "set_jigsaw_block",
+    "set_structure_block",
+    "set_test_block",
+    "sign_update",
+    "swing",
+    "teleport_to_entity",
+    "test_instance_block_action",
+    "use_item_on",
+    "use_item",
+    "custom_click_action",
+];
+
+/// 1.21.4.
+pub(super) const PLAY_CLIENTBOUND_769: &[&str] = &[
+    "bundle_delimiter",
+    "add_entity",
+    "add_experience_orb",
+    "animate",
+    "award_stats",
+    "block_changed_ack",
+    "block_destruction",
+    "block_entity_data",
+    "block_event",
+    "block_update",
+    "boss_event",
+    "change_difficulty",
+    "chunk_batch_finished",
+    "chunk_batch_start",
+    "chunks_biomes",
+    "clear_titles",
+    "command_suggestions",
+    "commands",
+    "container_close",
+    "container_set_content",
+    "container_set_data",
+    "container_set_slot",
+    "cookie_request",
+    "cooldown",
+    "custom_chat_completions",
+    "custom_payload",
+    "damage_event",
+    "debug_sample",
+    "delete_chat",
+    "disconnect",
+    "disguised_chat",
+    "entity_event",
+    "entity_position_sync",
+    "explode",
+    "forget_level_chunk",
+    "game_event",
+    "horse_screen_open",
+    "hurt_animation",
+    "initialize_border",
+    "keep_alive",
+    "level_chunk_with_light",
+    "level_event",
+    "level_particles",
+    "light_update",
+    "login",
+    "map_item_data",
+    "merchant_offers",
+    "move_entity_pos",
+    "move_entity_pos_rot",
+    "move_minecart_along_track",
+    "move_entity_rot",
+    "move_vehicle",
+    "open_book",
+    "open_screen",
+    "open_sign_editor",
+    "ping",
+    "pong_response",
+    "place_ghost_recipe",
+    "player_abilities",
+    "player_chat",
+    "player_combat_end",
+    "player_combat_enter",
+    "player_combat_kill",
+    "player_info_remove",
+    "player_info_update",
+    "player_look_at",
+    "player_position",
+    "player_rotation",
+    "recipe_book_add",
+    "recipe_book_remove",
+    "recipe_book_settings",
+    "remove_entities",
+    "remove_mob_effect",
+    "reset_score",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "respawn",
+    "rotate_head",
+    "section_blocks_update",
+    "select_advancements_tab",
+    "server_data",
+    "set_action_bar_text",
+    "set_border_center",
+    "set_border_lerp_size",
+    "set_border_size",
+    "set_border_warning_delay",
+    "set_border_warning_distance",
+    "set_camera",
+    "set_chunk_cache_center",
+    "set_chunk_cache_radius",
+    "set_cursor_item",
+    "set_default_spawn_position",
+    "set_display_objective",
+    "set_entity_data",
+    "set_entity_link",
+    "set_entity_motion",
+    "set_equipment",
+    "set_experience",
+    "set_health",
+    "set_held_slot",
+    "set_objective",
+    "set_passengers",
+    "set_player_inventory",
+    "set_player_team",
+    "set_score",
+    "set_simulation_distance",
+    "set_subtitle_text",
+    "set_time",
+    "set_title_text",
+    "set_titles_animation",
+    "sound_entity",
+    "sound",
+    "start_configuration",
+    "stop_sound",
+    "store_cookie",
+    "system_chat",
+    "tab_list",
+    "tag_query",
+    "take_item_entity",
+    "teleport_entity",
+    "ticking_state",
+    "ticking_step",
+    "transfer",
+    "update_advancements",
+    "update_attributes",
+    "update_mob_effect",
+    "update_recipes",
+    "update_tags",
+    "projectile_power",
+    "custom_report_details",
+    "server_links",
+];
+/// 1.21.5: experience orbs use `add_entity`, test instance blocks.
+pub(super) const PLAY_CLIENTBOUND_770: &[&str] = &[
+    "bundle_delimiter",
+    "add_entity",
+    "animate",
+    "award_stats",
+    "block_changed_ack",
+    "block_destruction",
+    "block_entity_data",
+    "block_event",
+    "block_update",
+    "boss_event",
+    "change_difficulty",
+    "chunk_batch_finished",
+    "chunk_batch_start",
+    "chunks_biomes",
+    "clear_titles",
+    "command_suggestions",
+    "commands",
+    "container_close",
+    "container_set_content",
+    "container_set_data",
+    "container_set_slot",
+    "cookie_request",
+    "cooldown",
+    "custom_chat_completions",
+    "custom_payload",
+    "damage_event",
+    "debug_sample",
+    "delete_chat",
+    "disconnect",
+    "disguised_chat",
+    "entity_event",
+    "entity_position_sync",
+    "explode",
+    "forget_level_chunk",
+    "game_event",
+    "horse_screen_open",
+    "hurt_animation",
+    "initialize_border",
+    "keep_alive",
+    "level_chunk_with_light",
+    "level_event",
+    "level_particles",
+    "light_update",
+    "login",
+    "map_item_data",
+    "merchant_offers",
+    "move_entity_pos",
+    "move_entity_pos_rot",
+    "move_minecart_along_track",
+    "move_entity_rot",
+    "move_vehicle",
+    "open_book",
+    "open_screen",
+    "open_sign_editor",
+    "ping",
+    "pong_response",
+    "place_ghost_recipe",
+    "player_abilities",
+    "player_chat",
+    "player_combat_end",
+    "player_combat_enter",
+    "player_combat_kill",
+    "player_info_remove",
+    "player_info_update",
+    "player_look_at",
+    "player_position",
+    "player_rotation",
+    "recipe_book_add",
+    "recipe_book_remove",
+    "recipe_book_settings",
+    "remove_entities",
+    "remove_mob_effect",
+    "reset_score",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "respawn",
+    "rotate_head",
+    "section_blocks_update",
+    "select_advancements_tab",
+    "server_data",
+    "set_action_bar_text",
+    "set_border_center",
+    "set_border_lerp_size",
+    "set_border_size",
+    "set_border_warning_delay",
+    "set_border_warning_distance",
+    "set_camera",
+    "set_chunk_cache_center",
+    "set_chunk_cache_radius",
+    "set_cursor_item",
+    "set_default_spawn_position",
+    "set_display_objective",
+    "set_entity_data",
+    "set_entity_link",
+    "set_entity_motion",
+    "set_equipment",
+    "set_experience",
+    "set_health",
+    "set_held_slot",
+    "set_objective",
+    "set_passengers",
+    "set_player_inventory",
+    "set_player_team",
+    "set_score",
+    "set_simulation_distance",
+    "set_subtitle_text",
+    "set_time",
+    "set_title_text",
+    "set_titles_animation",
+    "sound_entity",
+    "sound",
+    "start_configuration",
+    "stop_sound",
+    "store_cookie",
+    "system_chat",
+    "tab_list",
+    "tag_query",
+    "take_item_entity",
+    "teleport_entity",
+    "test_instance_block_status",
+    "ticking_state",
+    "ticking_step",
+    "transfer",
+    "update_advancements",
+    "update_attributes",
+    "update_mob_effect",
+    "update_recipes",
+    "update_tags",
+    "projectile_power",
+    "custom_report_details",
+    "server_links",
+];
+
+/// 1.21.6 onwards: waypoints and dialogs.
+pub(super) const PLAY_CLIENTBOUND_771: &[&str] = &[
+    "bundle_delimiter",
+    "add_entity",
+    "animate",
+    "award_stats",
+    "block_changed_ack",
+    "block_destruction",
+    "block_entity_data",
+    "block_event",
+    "block_update",
+    "boss_event",
+    "change_difficulty",
+    "chunk_batch_finished",
+    "chunk_batch_start",
+    "chunks_biomes",
+    "clear_titles",
+    "command_suggestions",
+    "commands",
+    "container_close",
+    "container_set_content",
+    "container_set_data",
+    "container_set_slot",
+    "cookie_request",
+    "cooldown",
+    "custom_chat_completions",
+    "custom_payload",
+    "damage_event",
+    "debug_sample",
+    "delete_chat",
+    "disconnect",
+    "disguised_chat",
+    "entity_event",
+    "entity_position_sync",
+    "explode",
+    "forget_level_chunk",
+    "game_event",
+    "horse_screen_open",
+    "hurt_animation",
+    "initialize_border",
+    "keep_alive",
+    "level_chunk_with_light",
+    "level_event",
+    "level_particles",
+    "light_update",
+    "login",
+    "map_item_data",
+    "merchant_offers",
+    "move_entity_pos",
+    "move_entity_pos_rot",
+    "move_minecart_along_track",
+    "move_entity_rot",
+    "move_vehicle",
+    "open_book",
+    "open_screen",
+    "open_sign_editor",
+    "ping",
+    "pong_response",
+    "place_ghost_recipe",
+    "player_abilities",
+    "player_chat",
+    "player_combat_end",
+    "player_combat_enter",
+    "player_combat_kill",
+    "player_info_remove",
+    "player_info_update",
+    "player_look_at",
+    "player_position",
+    "player_rotation",
+    "recipe_book_add",
+    "recipe_book_remove",
+    "recipe_book_settings",
+    "remove_entities",
+    "remove_mob_effect",
+    "reset_score",
+    "resource_pack_pop",
+    "resource_pack_push",
+    "respawn",
+    "rotate_head",
+    "section_blocks_update",
+    "select_advancements_tab",
+    "server_data",
+    "set_action_bar_text",
+    "set_border_center",
+    "set_border_lerp_size",
+    "set_border_size",
+    "set_border_warning_delay",
+    "set_border_warning_distance",
+    "set_camera",
+    "set_chunk_cache_center",
+    "set_chunk_cache_radius",
+    "set_cursor_item",
+    "set_default_spawn_position",
+    "set_display_objective",
+    "set_entity_data",
+    "set_entity_link",
+    "set_entity_motion",
+    "set_equipment",
+    "set_experience",
+    "set_health",
+    "set_held_slot",
+    "set_objective",
+    "set_passengers",
+    "set_player_inventory",
+    "set_player_team",
+    "set_score",
+    "set_simulation_distance",
+    "set_subtitle_text",
+    "set_time",
+    "set_title_text",
+    "set_titles_animation",
+    "sound_entity",
+    "sound",
+    "start_configuration",
+    "stop_sound",
+    "store_cookie",
+    "system_chat",
+    "tab_list",
+    "tag_query",
+    "take_item_entity",
+    "teleport_entity",
+    "test_instance_block_status",
+    "ticking_state",
+    "ticking_step",
+    "transfer",
+    "update_advancements",
+    "update_attributes",
+    "update_mob_effect",
+    "update_recipes",
+    "update_tags",
+    "projectile_power",
+    "custom_report_details",
+    "server_links",
+    "waypoint",
+    "clear_dialog",
+    "show_dialog",
+];
@@ -0,0 +1,229 @@
+mod adapt;
+mod ids;
+
+use core::fmt;
+
+pub use adapt::{
+    downgrade,
+    upgrade,
+};
+
+use crate::protocol::{
+    Direction,
+    State,
+};
+
+/// A protocol version supported by the `data` crate.
+///
+/// The handshake, status, login and configuration packets of every variant
+/// are known, play packets only from 1.21.4, see [`Self::supports_play`].
+/// Variants are named after the first release using the protocol number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    V1_20_2 = 764,
+    V1_20_3 = 765,
+    V1_20_5 = 766,
+    V1_21 = 767,
+    V1_21_2 = 768,
+    V1_21_4 = 769,
+    V1_21_5 = 770,
+    V1_21_6 = 771,
+    V1_21_7 = 772,
+}
+
+impl ProtocolVersion {
+    /// Every supported version, oldest first.
+    pub const ALL: [Self; 9] = [
+        Self::V1_20_2,
+        Self::V1_20_3,
+        Self::V1_20_5,
+        Self::V1_21,
+        Self::V1_21_2,
+        Self::V1_21_4,
+        Self::V1_21_5,
+        Self::V1_21_6,
+        Self::V1_21_7,
+    ];
+    pub const LATEST: Self = Self::V1_21_7;
+    pub const OLDEST: Self = Self::V1_20_2;
+
+    /// Looks up a version from the protocol number sent in the handshake.
+    #[must_use]
+    pub fn from_protocol(protocol: i32) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.protocol() == protocol)
+    }
+
+    /// Protocol number sent in the handshake.
+    #[must_use]
+    pub const fn protocol(self) -> i32 { self as i32 }
+
+    /// Game releases using this protocol.
+    #[must_use]
+    pub const fn releases(self) -> &'static str {
+        match self {
+            Self::V1_20_2 => "1.20.2",
+            Self::V1_20_3 => "1.20.3-1.20.4",
+            Self::V1_20_5 => "1.20.5-1.20.6",
+            Self::V1_21 => "1.21-1.21.1",
+            Self::V1_21_2 => "1.21.2-1.21.3",
+            Self::V1_21_4 => "1.21.4",
+            Self::V1_21_5 => "1.21.5",
+            Self::V1_21_6 => "1.21.6",
+            Self::V1_21_7 => "1.21.7-1.21.8",
+        }
+    }
+
+    /// Whether play packets of this version are known. Older versions can
+    /// still log in, but their play traffic can only be relayed as is.
+    #[must_use]
+    pub const fn supports_play(self) -> bool {
+        self.packet_names(State::Play, Direction::Clientbound)
+            .is_some()
+    }
+
+    /// Name of the packet with the given id, if known.
+    #[must_use]
+    pub fn packet_name(
+        self,
+        state: State,
+        direction: Direction,
+        id: i32,
+    ) -> Option<&'static str> {
+        let names = self.packet_names(state, direction)?;
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| names.get(id))
+            .copied()
+    }
+
+    /// Id of the packet with the given name, if it exists in this version.
+    #[must_use]
+    pub fn packet_id(
+        self,
+        state: State,
+        direction: Direction,
+        name: &str,
+    ) -> Option<i32> {
+        let names = self.packet_names(state, direction)?;
+        names
+            .iter()
+            .position(|candidate| *candidate == name)
+            .and_then(|id| i32::try_from(id).ok())
+    }
+
+    const fn packet_names(
+        self,
+        state: State,
+        direction: Direction,
+    ) -> Option<&'static [&'static str]> {
+        let v = self.protocol();
+        let names = match (state, direction) {
+            (State::Handshake, Direction::Serverbound) => ids::HANDSHAKE_SERVERBOUND,
+            (State::Handshake, Direction::Clientbound) => &[],
+            (State::Status, Direction::Serverbound) => ids::STATUS_SERVERBOUND,
+            (State::Status, Direction::Clientbound) => ids::STATUS_CLIENTBOUND,
+            (State::Login, Direction::Serverbound) if v >= 766 => ids::LOGIN_SERVERBOUND_766,
+            (State::Login, Direction::Serverbound) => ids::LOGIN_SERVERBOUND_764,
+            (State::Login, Direction::Clientbound) if v >= 766 => ids::LOGIN_CLIENTBOUND_766,
+            (State::Login, Direction::Clientbound) => ids::LOGIN_CLIENTBOUND_764,
+            (State::Configuration, Direction::Serverbound) if v >= 771 => {
+                ids::CONFIGURATION_SERVERBOUND_771
+            }
+            (State::Configuration, Direction::Serverbound) if v >= 766 => {
+                ids::CONFIGURATION_SERVERBOUND_766
+            }
+            (State::Configuration, Direction::Serverbound) => ids::CONFIGURATION_SERVERBOUND_764,
+            (State::Configuration, Direction::Clientbound) if v >= 771 => {
+                ids::CONFIGURATION_CLIENTBOUND_771
+            }
+            (State::Configuration, Direction::Clientbound) if v >= 767 => {
+                ids::CONFIGURATION_CLIENTBOUND_767
+            }
+            (State::Configuration, Direction::Clientbound) if v >= 766 => {
+                ids::CONFIGURATION_CLIENTBOUND_766
+            }
+            (State::Configuration, Direction::Clientbound) if v >= 765 => {
+                ids::CONFIGURATION_CLIENTBOUND_765
+            }
+            (State::Configuration, Direction::Clientbound) => ids::CONFIGURATION_CLIENTBOUND_764,
+            (State::Play, Direction::Serverbound) if v >= 771 => ids::PLAY_SERVERBOUND_771,
+            (State::Play, Direction::Serverbound) if v >= 770 => ids::PLAY_SERVERBOUND_770,
+            (State::Play, Direction::Serverbound) if v >= 769 => ids::PLAY_SERVERBOUND_769,
+            (State::Play, Direction::Clientbound) if v >= 771 => ids::PLAY_CLIENTBOUND_771,
+            (State::Play, Direction::Clientbound) if v >= 770 => ids::PLAY_CLIENTBOUND_770,
+            (State::Play, Direction::Clientbound) if v >= 769 => ids::PLAY_CLIENTBOUND_769,
+            (State::Play, _) => return None,
+        };
+        Some(names)
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{} ({})", self.releases(), self.protocol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::PROTOCOL_VERSION;
+
+    #[test]
+    fn latest_matches_derived_ids() {
+        assert_eq!(ProtocolVersion::LATEST.protocol(), PROTOCOL_VERSION);
+        assert_eq!(
+            ProtocolVersion::from_protocol(PROTOCOL_VERSION),
+            Some(ProtocolVersion::LATEST)
+        );
+        assert_eq!(ProtocolVersion::from_protocol(4), None);
+    }
+
+    #[test]
+    fn ids_shift_between_versions() {
+        let keep_alive = |version: ProtocolVersion| {
+            version.packet_id(State::Configuration, Direction::Clientbound, "keep_alive")
+        };
+        assert_eq!(keep_alive(ProtocolVersion::V1_20_2), Some(0x03));
+        assert_eq!(keep_alive(ProtocolVersion::V1_21_7), Some(0x04));
+
+        let version = ProtocolVersion::V1_21_5;
+        assert_eq!(
+            version.packet_id(State::Play, Direction::Serverbound, "keep_alive"),
+            Some(0x1A)
+        );
+        assert_eq!(
+            ProtocolVersion::V1_21_6.packet_id(State::Play, Direction::Serverbound, "keep_alive"),
+            Some(0x1B)
+        );
+        assert_eq!(
+            version.packet_name(State::Play, Direction::Clientbound, 0x27),
+            Some("level_chunk_with_light")
+        );
+        assert_eq!(
+            ProtocolVersion::V1_20_3.packet_id(
+                State::Login,
+                Direction::Clientbound,
+                "cookie_request"
+            ),
+            None
+        );
+        assert_eq!(
+            version.packet_id(State::Play, Direction::Clientbound, "system_chat"),
+            Some(0x72)
+        );
+        assert_eq!(
+            ProtocolVersion::V1_21_4.packet_id(State::Play, Direction::Clientbound, "keep_alive"),
+            Some(0x27)
+        );
+        assert!(
+            !ProtocolVersion::V1_21_2.supports_play(),
+            "1.21.2 play ids are not tabled"
+        );
+    }
+}
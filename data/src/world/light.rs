@@ -0,0 +1,73 @@
+use codec::dec::Decode;
+use codec::enc::Encode;
+
+use crate::json::ToJson;
+
+/// Sky and block light of a chunk column, as sent with chunks and in light
+/// updates. Light sections start one section below the world and end one
+/// above it.
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, ToJson)]
+pub struct LightData {
+    /// Light sections with sky light, one bit per section.
+    pub sky_y_mask: Vec<u64>,
+    /// Light sections with block light.
+    pub block_y_mask: Vec<u64>,
+    /// Light sections with no sky light at all.
+    pub empty_sky_y_mask: Vec<u64>,
+    /// Light sections with no block light at all.
+    pub empty_block_y_mask: Vec<u64>,
+    /// 2048-byte nibble arrays, one per bit set in `sky_y_mask`.
+    pub sky_updates: Vec<Vec<u8>>,
+    /// 2048-byte nibble arrays, one per bit set in `block_y_mask`.
+    pub block_updates: Vec<Vec<u8>>,
+}
+
+impl LightData {
+    /// Sky light of a light section, if sent.
+    #[must_use]
+    pub fn sky_light(
+        &self,
+        section: usize,
+    ) -> Option<&[u8]> {
+        nibbles(&self.sky_y_mask, &self.sky_updates, section)
+    }
+
+    /// Block light of a light section, if sent.
+    #[must_use]
+    pub fn block_light(
+        &self,
+        section: usize,
+    ) -> Option<&[u8]> {
+        nibbles(&self.block_y_mask, &self.block_updates, section)
+    }
+}
+
+/// Light level of a block in a nibble array, indexed as in a section.
+#[must_use]
+pub fn light_level(
+    nibbles: &[u8],
+    index: usize,
+) -> u8 {
+    let byte = nibbles.get(index / 2).copied().unwrap_or_default();
+    if index.is_multiple_of(2) {
+        byte & 0x0F
+    } else {
+        byte >> 4
+    }
+}
+
+fn nibbles<'data>(
+    mask: &[u64],
+    updates: &'data [Vec<u8>],
+    section: usize,
+) -> Option<&'data [u8]> {
+    let bit = |index: usize| {
+        mask.get(index / 64)
+            .is_some_and(|long| long >> (index % 64) & 1 == 1)
+    };
+    if !bit(section) {
+        return None;
+    }
+    let position = (0..section).filter(|index| bit(*index)).count();
+    updates.get(position).map(Vec::as_slice)
+}
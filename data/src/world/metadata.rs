@@ -0,0 +1,272 @@
+use std::io;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+    DecodeErrorContext as _,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+use codec::nbt::Nbt;
+use codec::{
+    PrefixedOption,
+    Uuid,
+    VarInt,
+    VarLong,
+};
+
+use super::BlockPos;
+use crate::json::ToJson;
+
+/// Entity metadata entries, as sent in `set_entity_data` and terminated by
+/// index `0xFF`.
+#[derive(Debug, Clone, PartialEq, Default, ToJson)]
+pub struct EntityMetadata {
+    pub entries: Vec<(u8, MetadataValue)>,
+}
+
+impl EntityMetadata {
+    /// Value at `index`, if sent.
+    #[must_use]
+    pub fn get(
+        &self,
+        index: u8,
+    ) -> Option<&MetadataValue> {
+        self.entries
+            .iter()
+            .find(|(other, _)| *other == index)
+            .map(|(_, value)| value)
+    }
+
+    /// Overwrites entries with those of a later update.
+    pub fn merge(
+        &mut self,
+        update: Self,
+    ) {
+        for (index, value) in update.entries {
+            match self.entries.iter_mut().find(|(other, _)| *other == index) {
+                Some((_, current)) => *current = value,
+                None => self.entries.push((index, value)),
+            }
+        }
+    }
+}
+
+/// A metadata value, by serializer id. Only serializers whose length can be
+/// told without registry data are supported; others fail to decode.
+#[derive(Debug, Clone, PartialEq, ToJson)]
+pub enum MetadataValue {
+    Byte(i8),
+    VarInt(i32),
+    VarLong(i64),
+    Float(f32),
+    String(String),
+    Component(Nbt),
+    OptionalComponent(Option<Nbt>),
+    Boolean(bool),
+    Rotations([f32; 3]),
+    BlockPos(BlockPos),
+    OptionalBlockPos(Option<BlockPos>),
+    Direction(i32),
+    OptionalLivingEntity(Option<Uuid>),
+    BlockState(i32),
+    /// A block state id, 0 standing for none.
+    OptionalBlockState(i32),
+    VillagerData {
+        kind: i32,
+        profession: i32,
+        level: i32,
+    },
+    /// A value plus one, 0 standing for none.
+    OptionalUnsignedInt(i32),
+    Pose(i32),
+}
+
+const END: u8 = 0xFF;
+
+fn varint<R: io::Read>(reader: &mut R) -> Result<i32, DecodeError> {
+    Ok(VarInt::decode(reader)?.value())
+}
+
+impl MetadataValue {
+    fn decode<R: io::Read>(
+        reader: &mut R,
+        serializer: i32,
+    ) -> Result<Self, DecodeError> {
+        Ok(match serializer {
+            0 => Self::Byte(i8::decode(reader)?),
+            1 => Self::VarInt(varint(reader)?),
+            2 => Self::VarLong(VarLong::decode(reader)?.value()),
+            3 => Self::Float(f32::decode(reader)?),
+            4 => Self::String(String::decode(reader)?),
+            5 => Self::Component(Nbt::decode(reader)?),
+            6 => Self::OptionalComponent(PrefixedOption::decode(reader)?.into()),
+            8 => Self::Boolean(bool::decode(reader)?),
+            9 => Self::Rotations([
+                f32::decode(reader)?,
+                f32::decode(reader)?,
+                f32::decode(reader)?,
+            ]),
+            10 => Self::BlockPos(BlockPos::decode(reader)?),
+            11 => Self::OptionalBlockPos(PrefixedOption::decode(reader)?.into()),
+            12 => Self::Direction(varint(reader)?),
+            13 => Self::OptionalLivingEntity(PrefixedOption::decode(reader)?.into()),
+            14 => Self::BlockState(varint(reader)?),
+            15 => Self::OptionalBlockState(varint(reader)?),
+            18 => Self::VillagerData {
+                kind: varint(reader)?,
+                profession: varint(reader)?,
+                level: varint(reader)?,
+            },
+            19 => Self::OptionalUnsignedInt(varint(reader)?),
+            20 => Self::Pose(varint(reader)?),
+            _ => {
+                return Err(DecodeError::Custom {
+                    message: format!("Unsupported metadata serializer {serializer}"),
+                });
+            }
+        })
+    }
+
+    const fn serializer(&self) -> i32 {
+        match self {
+            Self::Byte(_) => 0,
+            Self::VarInt(_) => 1,
+            Self::VarLong(_) => 2,
+            Self::Float(_) => 3,
+            Self::String(_) => 4,
+            Self::Component(_) => 5,
+            Self::OptionalComponent(_) => 6,
+            Self::Boolean(_) => 8,
+            Self::Rotations(_) => 9,
+            Self::BlockPos(_) => 10,
+            Self::OptionalBlockPos(_) => 11,
+            Self::Direction(_) => 12,
+            Self::OptionalLivingEntity(_) => 13,
+            Self::BlockState(_) => 14,
+            Self::OptionalBlockState(_) => 15,
+            Self::VillagerData {
+                ..
+            } => 18,
+            Self::OptionalUnsignedInt(_) => 19,
+            Self::Pose(_) => 20,
+        }
+    }
+}
+
+impl Encode for MetadataValue {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut written_bytes = VarInt::new(self.serializer()).encode(writer)?;
+        written_bytes += match self {
+            Self::Byte(value) => value.encode(writer)?,
+            Self::VarInt(value)
+            | Self::Direction(value)
+            | Self::BlockState(value)
+            | Self::OptionalBlockState(value)
+            | Self::OptionalUnsignedInt(value)
+            | Self::Pose(value) => VarInt::new(*value).encode(writer)?,
+            Self::VarLong(value) => VarLong::new(*value).encode(writer)?,
+            Self::Float(value) => value.encode(writer)?,
+            Self::String(value) => value.encode(writer)?,
+            Self::Component(value) => value.encode(writer)?,
+            Self::OptionalComponent(value) => {
+                PrefixedOption::from(value.as_ref()).encode(writer)?
+            }
+            Self::Boolean(value) => value.encode(writer)?,
+            Self::Rotations(values) => {
+                values[0].encode(writer)? + values[1].encode(writer)? + values[2].encode(writer)?
+            }
+            Self::BlockPos(value) => value.encode(writer)?,
+            Self::OptionalBlockPos(value) => PrefixedOption::from(value.as_ref()).encode(writer)?,
+            Self::OptionalLivingEntity(value) => {
+                PrefixedOption::from(value.as_ref()).encode(writer)?
+            }
+            Self::VillagerData {
+                kind,
+                profession,
+                level,
+            } => {
+                VarInt::new(*kind).encode(writer)?
+                    + VarInt::new(*profession).encode(writer)?
+                    + VarInt::new(*level).encode(writer)?
+            }
+        };
+        Ok(written_bytes)
+    }
+}
+
+impl Decode for EntityMetadata {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let mut entries = Vec::new();
+        loop {
+            let index = u8::decode(reader)?;
+            if index == END {
+                return Ok(Self {
+                    entries,
+                });
+            }
+            let serializer = varint(reader)?;
+            let value = MetadataValue::decode(reader, serializer)
+                .err_context(format!("Failed to decode metadata entry {index}"))?;
+            entries.push((index, value));
+        }
+    }
+}
+
+impl Encode for EntityMetadata {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut written_bytes = 0;
+        for (index, value) in &self.entries {
+            written_bytes += index.encode(writer)? + value.encode(writer)?;
+        }
+        Ok(written_bytes + END.encode(writer)?)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_and_merge() {
+        let bytes = [
+            0x00, 0x00, 0x20, // flags: invisible
+            0x06, 0x14, 0x01, // pose: crouching
+            0x02, 0x06, 0x00, // no custom name
+            0xFF,
+        ];
+        let mut metadata = EntityMetadata::decode(&mut bytes.as_slice()).unwrap();
+        assert_eq!(metadata.get(6), Some(&MetadataValue::Pose(1)));
+        assert_eq!(
+            metadata.get(2),
+            Some(&MetadataValue::OptionalComponent(None))
+        );
+
+        let mut encoded = Vec::new();
+        metadata.encode(&mut encoded).unwrap();
+        assert_eq!(encoded, bytes, "re-encoding should give back the input");
+
+        metadata.merge(EntityMetadata {
+            entries: vec![(6, MetadataValue::Pose(0)), (8, MetadataValue::Float(20.0))],
+        });
+        assert_eq!(metadata.get(6), Some(&MetadataValue::Pose(0)));
+        assert_eq!(metadata.entries.len(), 4);
+    }
+
+    #[test]
+    fn unsupported_serializer() {
+        assert!(
+            EntityMetadata::decode(&mut [0x00, 0x07, 0x00].as_slice()).is_err(),
+            "item stacks cannot be decoded"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Chunk data as sent in `level_chunk_with_light`: sections of paletted
+//! block states and biomes, heightmaps, block entities and light. Also
+//! positions and entity metadata used by the other world packets.
+//!
+//! Everything re-encodes to the bytes it was decoded from, so the proxy can
+//! inspect or edit chunks in flight.
+
+mod light;
+mod metadata;
+mod palette;
+mod pos;
+
+use std::io;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+use codec::nbt::Nbt;
+pub use light::{
+    LightData,
+    light_level,
+};
+pub use metadata::{
+    EntityMetadata,
+    MetadataValue,
+};
+pub use palette::{
+    ContainerKind,
+    Palette,
+    PalettedContainer,
+};
+pub use pos::{
+    BlockPos,
+    ChunkPos,
+    SectionPos,
+};
+
+use crate::json::ToJson;
+use crate::registry::BlockState;
+
+#[derive(Debug, Clone, PartialEq, Decode, Encode, ToJson)]
+pub struct ChunkData {
+    pub heightmaps: Vec<Heightmap>,
+    /// Sections from the bottom of the world up.
+    #[codec(with = sections)]
+    pub sections: Vec<Section>,
+    pub block_entities: Vec<BlockEntity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, ToJson)]
+pub struct Heightmap {
+    pub kind: HeightmapKind,
+    /// Heights packed like a paletted container, 9 bits per column for a
+    /// world 384 blocks high.
+    pub data: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, ToJson)]
+#[codec(varint)]
+pub enum HeightmapKind {
+    WorldSurfaceWg = 0,
+    WorldSurface = 1,
+    OceanFloorWg = 2,
+    OceanFloor = 3,
+    MotionBlocking = 4,
+    MotionBlockingNoLeaves = 5,
+}
+
+/// A 16×16×16 section of a chunk.
+#[derive(Debug, Clone, PartialEq, Eq, ToJson)]
+pub struct Section {
+    /// Number of non-air blocks. Not updated by [`Section::set_block_state`].
+    pub block_count: i16,
+    pub block_states: PalettedContainer,
+    pub biomes: PalettedContainer,
+}
+
+impl Section {
+    /// A section filled with one block state and one biome.
+    #[must_use]
+    pub fn filled(
+        block_state: BlockState,
+        biome: i32,
+    ) -> Self {
+        Self {
+            block_count: if block_state == BlockState::AIR {
+                0
+            } else {
+                4096
+            },
+            block_states: PalettedContainer::single(ContainerKind::BlockStates, block_state.id()),
+            biomes: PalettedContainer::single(ContainerKind::Biomes, biome),
+        }
+    }
+
+    /// Block state id at section-relative coordinates (0 to 15).
+    #[must_use]
+    pub fn block_state(
+        &self,
+        x: usize,
+        y: usize,
+        z: usize,
+    ) -> i32 {
+        self.block_states.get(block_index(x, y, z))
+    }
+
+    pub fn set_block_state(
+        &mut self,
+        x: usize,
+        y: usize,
+        z: usize,
+        id: i32,
+    ) {
+        self.block_states.set(block_index(x, y, z), id);
+    }
+
+    /// Biome id at biome coordinates (0 to 3, one per 4×4×4 cell).
+    #[must_use]
+    pub fn biome(
+        &self,
+        x: usize,
+        y: usize,
+        z: usize,
+    ) -> i32 {
+        self.biomes.get((y * 4 + z) * 4 + x)
+    }
+}
+
+const fn block_index(
+    x: usize,
+    y: usize,
+    z: usize,
+) -> usize {
+    (y * 16 + z) * 16 + x
+}
+
+impl Decode for Section {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(Self {
+            block_count: i16::decode(reader)?,
+            block_states: PalettedContainer::decode(reader, ContainerKind::BlockStates)?,
+            biomes: PalettedContainer::decode(reader, ContainerKind::Biomes)?,
+        })
+    }
+}
+
+impl Encode for Section {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        Ok(self.block_count.encode(writer)?
+            + self.block_states.encode(writer)?
+            + self.biomes.encode(writer)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Decode, Encode, ToJson)]
+pub struct BlockEntity {
+    /// Chunk-relative x in the high nibble, z in the low one.
+    pub packed_xz: u8,
+    pub y: i16,
+    /// Id in the `minecraft:block_entity_type` registry.
+    #[codec(varint)]
+    pub kind: i32,
+    pub data: Option<Nbt>,
+}
+
+impl BlockEntity {
+    #[must_use]
+    pub const fn x(&self) -> u8 { self.packed_xz >> 4 }
+
+    #[must_use]
+    pub const fn z(&self) -> u8 { self.packed_xz & 0x0F }
+}
+
+/// Sections are sent as a length-prefixed buffer, read until exhausted.
+mod sections {
+    use std::io;
+
+    use codec::dec::{
+        Decode as _,
+        DecodeError,
+        DecodeErrorContext as _,
+    };
+    use codec::enc::{
+        Encode as _,
+        EncodeError,
+    };
+
+    use super::Section;
+
+    pub fn decode<R: io::Read>(reader: &mut R) -> Result<Vec<Section>, DecodeError> {
+        let buffer = Vec::<u8>::decode(reader).err_context("Failed to decode section buffer")?;
+        let mut buffer = buffer.as_slice();
+        let mut sections = Vec::new();
+        while !buffer.is_empty() {
+            sections.push(
+                Section::decode(&mut buffer)
+                    .err_context(format!("Failed to decode section {}", sections.len()))?,
+            );
+        }
+        Ok(sections)
+    }
+
+    pub fn encode<W: io::Write>(
+        sections: &[Section],
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut buffer = Vec::new();
+        for section in sections {
+            section.encode(&mut buffer)?;
+        }
+        buffer.encode(writer)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use codec::VarInt;
+
+    use super::*;
+    use crate::model::play::LevelChunkWithLight;
+    use crate::registry::{
+        Block,
+        BlockState,
+    };
+
+    /// A chunk packet payload built field by field.
+    fn chunk_bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        2_i32.encode(&mut bytes).unwrap();
+        (-3_i32).encode(&mut bytes).unwrap();
+
+        // heightmaps: one MOTION_BLOCKING of 37 longs
+        bytes.extend([0x01, 0x04, 37]);
+        for long in 0..37_u64 {
+            long.encode(&mut bytes).unwrap();
+        }
+
+        let mut sections = Vec::new();
+        // a section of air in plains
+        sections.extend([0x00, 0x00, 0x00, 0x00, 0x00, 0x28]);
+        // a section of stone with one oak log, biomes alternating two ids
+        sections.extend(4095_i16.to_be_bytes());
+        sections.extend([0x04, 0x02, 0x01, 0x89, 0x01]);
+        sections.extend(0x1_0000_u64.to_be_bytes());
+        sections.extend([0; 8 * 255]);
+        sections.extend([0x01, 0x02, 0x28, 0x03]);
+        sections.extend(0xAAAA_AAAA_AAAA_AAAA_u64.to_be_bytes());
+        sections.encode(&mut bytes).unwrap();
+
+        // block entities: a sign with data, a bell without
+        bytes.extend([0x02, 0x3A]);
+        bytes.extend(70_i16.to_be_bytes());
+        bytes.push(0x07);
+        let mut sign = Nbt::compound();
+        sign.insert("is_waxed", Nbt::Byte(1));
+        sign.encode(&mut bytes).unwrap();
+        bytes.extend([0x00]);
+        bytes.extend(64_i16.to_be_bytes());
+        bytes.extend([0x1A, 0x00]);
+
+        // light: sky light in light sections 1 and 3, none empty
+        bytes.extend([0x01]);
+        0b1010_u64.encode(&mut bytes).unwrap();
+        bytes.extend([0x00, 0x00, 0x00]);
+        bytes.extend([0x02]);
+        for level in [0x00, 0xFF] {
+            VarInt::new(2048).encode(&mut bytes).unwrap();
+            bytes.extend([level; 2048]);
+        }
+        bytes.extend([0x00]);
+        bytes
+    }
+
+    #[test]
+    fn chunk_roundtrip() {
+        let bytes = chunk_bytes();
+        let packet = LevelChunkWithLight::decode(&mut bytes.as_slice()).unwrap();
+        assert_eq!((packet.x, packet.z), (2, -3));
+
+        let chunk = &packet.chunk;
+        assert_eq!(chunk.heightmaps[0].kind, HeightmapKind::MotionBlocking);
+        assert_eq!(chunk.sections.len(), 2);
+        assert_eq!(
+            chunk.sections[0].block_state(15, 15, 15),
+            BlockState::AIR.id()
+        );
+        assert_eq!(chunk.sections[0].biome(0, 0, 0), 40);
+
+        let section = &chunk.sections[1];
+        assert_eq!(section.block_count, 4095);
+        assert_eq!(
+            section.block_state(4, 0, 0),
+            Block::OakLog.default_state().id()
+        );
+        assert_eq!(
+            section.block_state(0, 0, 0),
+            Block::Stone.default_state().id()
+        );
+        assert_eq!(section.biome(0, 0, 0), 40);
+        assert_eq!(section.biome(1, 0, 0), 3);
+
+        assert_eq!(chunk.block_entities.len(), 2);
+        assert_eq!(
+            (chunk.block_entities[0].x(), chunk.block_entities[0].z()),
+            (3, 10)
+        );
+        assert!(
+            chunk.block_entities[1].data.is_none(),
+            "the bell has no data"
+        );
+
+        assert_eq!(packet.light.sky_light(0), None);
+        assert_eq!(packet.light.sky_light(3).map(|light| light[0]), Some(0xFF));
+        assert_eq!(light_level(packet.light.sky_light(3).unwrap(), 1), 15);
+        assert_eq!(packet.light.block_light(1), None);
+
+        let mut encoded = Vec::new();
+        packet.encode(&mut encoded).unwrap();
+        assert_eq!(encoded, bytes, "re-encoding should give back the input");
+    }
+
+    #[test]
+    fn edit_section() {
+        let mut section = Section::filled(Block::Stone.default_state(), 0);
+        assert_eq!(section.block_count, 4096);
+        section.set_block_state(1, 2, 3, BlockState::AIR.id());
+        assert_eq!(section.block_state(1, 2, 3), BlockState::AIR.id());
+        assert_eq!(
+            section.block_state(3, 2, 1),
+            Block::Stone.default_state().id()
+        );
+
+        let mut bytes = Vec::new();
+        section.encode(&mut bytes).unwrap();
+        assert_eq!(Section::decode(&mut bytes.as_slice()).unwrap(), section);
+    }
+}
@@ -0,0 +1,447 @@
+//! Paletted containers: the compact storage of the block states and biomes
+//! of a chunk section.
+
+use std::io;
+
+use codec::VarInt;
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+    DecodeErrorContext as _,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+
+use crate::json::ToJson;
+
+/// What a container stores, which decides its size and palette thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToJson)]
+pub enum ContainerKind {
+    /// 16×16×16 block states.
+    BlockStates,
+    /// 4×4×4 biomes.
+    Biomes,
+}
+
+impl ContainerKind {
+    /// Number of entries.
+    #[must_use]
+    pub const fn size(self) -> usize {
+        match self {
+            Self::BlockStates => 4096,
+            Self::Biomes => 64,
+        }
+    }
+
+    /// Fewest bits an indirect palette uses.
+    const fn min_indirect_bits(self) -> u8 {
+        match self {
+            Self::BlockStates => 4,
+            Self::Biomes => 1,
+        }
+    }
+
+    /// Most bits an indirect palette uses; above, ids are stored directly.
+    const fn max_indirect_bits(self) -> u8 {
+        match self {
+            Self::BlockStates => 8,
+            Self::Biomes => 3,
+        }
+    }
+
+    /// Bits of a direct container, from the size of the vanilla registries.
+    const fn direct_bits(self) -> u8 {
+        match self {
+            Self::BlockStates => 15,
+            Self::Biomes => 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ToJson)]
+pub enum Palette {
+    /// Every entry holds the same id.
+    Single(i32),
+    /// Entries are indices into a list of ids.
+    Indirect(Vec<i32>),
+    /// Entries are the ids themselves.
+    Direct,
+}
+
+/// Entries packed into longs, without spanning two longs, as sent in the
+/// chunk packet. The layout of 1.21.5 onwards, where the long count is
+/// implied by the bits per entry.
+#[derive(Debug, Clone, PartialEq, Eq, ToJson)]
+pub struct PalettedContainer {
+    kind: ContainerKind,
+    /// Bits per entry as sent, which can be below those actually used.
+    bits_per_entry: u8,
+    palette: Palette,
+    data: Vec<u64>,
+}
+
+impl PalettedContainer {
+    /// A container where every entry is `id`.
+    #[must_use]
+    pub const fn single(
+        kind: ContainerKind,
+        id: i32,
+    ) -> Self {
+        Self {
+            kind,
+            bits_per_entry: 0,
+            palette: Palette::Single(id),
+            data: Vec::new(),
+        }
+    }
+
+    /// The smallest container holding `values`, given in index order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not exactly [`ContainerKind::size`] values.
+    #[must_use]
+    pub fn from_values(
+        kind: ContainerKind,
+        values: &[i32],
+    ) -> Self {
+        assert_eq!(
+            values.len(),
+            kind.size(),
+            "a container holds {} entries",
+            kind.size()
+        );
+        let mut container = Self::single(kind, values[0]);
+        container.repack(values);
+        container
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the palette or data cannot be read, or if
+    /// entries take more bits than a direct container.
+    pub fn decode<R: io::Read>(
+        reader: &mut R,
+        kind: ContainerKind,
+    ) -> Result<Self, DecodeError> {
+        let bits_per_entry = u8::decode(reader)?;
+        if bits_per_entry > kind.direct_bits() {
+            return Err(DecodeError::Custom {
+                message: format!(
+                    "{bits_per_entry} bits per entry, {kind:?} take at most {}",
+                    kind.direct_bits()
+                ),
+            });
+        }
+        let palette = if bits_per_entry == 0 {
+            Palette::Single(VarInt::decode(reader)?.value())
+        } else if bits_per_entry <= kind.max_indirect_bits() {
+            let palette: Vec<VarInt> =
+                Vec::decode(reader).err_context("Failed to decode palette")?;
+            Palette::Indirect(palette.into_iter().map(|id| id.value()).collect())
+        } else {
+            Palette::Direct
+        };
+
+        let mut container = Self {
+            kind,
+            bits_per_entry,
+            palette,
+            data: Vec::new(),
+        };
+        container.data = (0..container.long_count())
+            .map(|_| u64::decode(reader))
+            .collect::<Result<_, _>>()
+            .err_context("Failed to decode paletted data")?;
+        Ok(container)
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> ContainerKind { self.kind }
+
+    #[must_use]
+    pub const fn palette(&self) -> &Palette { &self.palette }
+
+    /// Bits per entry as sent.
+    #[must_use]
+    pub const fn bits_per_entry(&self) -> u8 { self.bits_per_entry }
+
+    /// Registry id of the entry at `index`, where blocks are indexed as
+    /// `(y * 16 + z) * 16 + x` and biomes as `(y * 4 + z) * 4 + x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn get(
+        &self,
+        index: usize,
+    ) -> i32 {
+        assert!(index < self.kind.size(), "index {index} out of bounds");
+        match &self.palette {
+            Palette::Single(id) => *id,
+            Palette::Indirect(palette) => {
+                let entry = self.entry(index);
+                // entries past the palette are invalid; read them as the first id
+                palette
+                    .get(usize::try_from(entry).unwrap_or(usize::MAX))
+                    .or_else(|| palette.first())
+                    .copied()
+                    .unwrap_or_default()
+            }
+            Palette::Direct => i32::try_from(self.entry(index)).unwrap_or_default(),
+        }
+    }
+
+    /// Every entry, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = i32> + '_ {
+        (0..self.kind.size()).map(|index| self.get(index))
+    }
+
+    /// Sets the entry at `index`, growing the palette if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(
+        &mut self,
+        index: usize,
+        id: i32,
+    ) {
+        assert!(index < self.kind.size(), "index {index} out of bounds");
+        let bits = self.bits();
+        match &mut self.palette {
+            Palette::Single(current) if *current == id => {}
+            Palette::Single(_) => {
+                let mut values: Vec<i32> = self.iter().collect();
+                values[index] = id;
+                self.repack(&values);
+            }
+            Palette::Indirect(palette) => {
+                if let Some(entry) = palette.iter().position(|other| *other == id) {
+                    self.set_entry(index, entry as u64);
+                } else if palette.len() < 1 << bits {
+                    palette.push(id);
+                    let entry = palette.len() - 1;
+                    self.set_entry(index, entry as u64);
+                } else {
+                    let mut values: Vec<i32> = self.iter().collect();
+                    values[index] = id;
+                    self.repack(&values);
+                }
+            }
+            Palette::Direct => self.set_entry(index, u64::from(id.cast_unsigned())),
+        }
+    }
+
+    /// Rebuilds the container with the smallest palette holding `values`.
+    fn repack(
+        &mut self,
+        values: &[i32],
+    ) {
+        let mut palette: Vec<i32> = Vec::new();
+        for value in values {
+            if !palette.contains(value) {
+                palette.push(*value);
+            }
+        }
+        if let [id] = palette[..] {
+            *self = Self::single(self.kind, id);
+            return;
+        }
+
+        let needed = usize::BITS - (palette.len() - 1).leading_zeros();
+        let needed = u8::try_from(needed).unwrap_or(u8::MAX);
+        let (bits_per_entry, palette) = if needed <= self.kind.max_indirect_bits() {
+            (
+                needed.max(self.kind.min_indirect_bits()),
+                Palette::Indirect(palette),
+            )
+        } else {
+            (self.kind.direct_bits(), Palette::Direct)
+        };
+
+        let entries: Vec<u64> = values
+            .iter()
+            .map(|value| match &palette {
+                Palette::Indirect(palette) => palette
+                    .iter()
+                    .position(|other| other == value)
+                    .unwrap_or_default() as u64,
+                _ => u64::from(value.cast_unsigned()),
+            })
+            .collect();
+        self.bits_per_entry = bits_per_entry;
+        self.palette = palette;
+        self.data = vec![0; self.long_count()];
+        for (index, entry) in entries.into_iter().enumerate() {
+            self.set_entry(index, entry);
+        }
+    }
+
+    /// Bits each entry actually takes in the packed longs.
+    fn bits(&self) -> u8 {
+        match self.palette {
+            Palette::Single(_) => 0,
+            Palette::Indirect(_) => self.bits_per_entry.max(self.kind.min_indirect_bits()),
+            Palette::Direct => self.bits_per_entry,
+        }
+    }
+
+    fn long_count(&self) -> usize {
+        match self.bits() {
+            0 => 0,
+            bits => self.kind.size().div_ceil(64 / usize::from(bits)),
+        }
+    }
+
+    fn entry(
+        &self,
+        index: usize,
+    ) -> u64 {
+        let (long, shift, mask) = self.position(index);
+        self.data[long] >> shift & mask
+    }
+
+    fn set_entry(
+        &mut self,
+        index: usize,
+        entry: u64,
+    ) {
+        let (long, shift, mask) = self.position(index);
+        self.data[long] = self.data[long] & !(mask << shift) | (entry & mask) << shift;
+    }
+
+    /// Long index, bit shift and mask of the entry at `index`.
+    fn position(
+        &self,
+        index: usize,
+    ) -> (usize, usize, u64) {
+        let bits = usize::from(self.bits());
+        let per_long = 64 / bits;
+        (index / per_long, index % per_long * bits, (1 << bits) - 1)
+    }
+}
+
+impl Encode for PalettedContainer {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let mut written_bytes = self.bits_per_entry.encode(writer)?;
+        match &self.palette {
+            Palette::Single(id) => written_bytes += VarInt::new(*id).encode(writer)?,
+            Palette::Indirect(palette) => {
+                let palette: Vec<VarInt> = palette.iter().copied().map(VarInt::new).collect();
+                written_bytes += palette.encode(writer)?;
+            }
+            Palette::Direct => {}
+        }
+        for long in &self.data {
+            written_bytes += long.encode(writer)?;
+        }
+        Ok(written_bytes)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(container: &PalettedContainer) -> PalettedContainer {
+        let mut bytes = Vec::new();
+        container.encode(&mut bytes).unwrap();
+        let decoded = PalettedContainer::decode(&mut bytes.as_slice(), container.kind).unwrap();
+        assert_eq!(&decoded, container, "containers should survive a roundtrip");
+        decoded
+    }
+
+    #[test]
+    fn indirect_decode() {
+        // 4 bits per entry, palette [0, 137], first entries 1, 0, 1
+        let mut bytes = vec![0x04, 0x02, 0x00, 0x89, 0x01];
+        bytes.extend(0x101_u64.to_be_bytes());
+        bytes.extend([0; 8 * 255]);
+        let container =
+            PalettedContainer::decode(&mut bytes.as_slice(), ContainerKind::BlockStates).unwrap();
+        assert_eq!(container.get(0), 137);
+        assert_eq!(container.get(1), 0);
+        assert_eq!(container.get(2), 137);
+        assert_eq!(container.get(4095), 0);
+
+        let mut encoded = Vec::new();
+        container.encode(&mut encoded).unwrap();
+        assert_eq!(encoded, bytes, "re-encoding should give back the input");
+    }
+
+    #[test]
+    fn biomes_keep_sent_bits() {
+        // biomes use 1 to 3 bits as sent, unlike block states
+        let mut bytes = vec![0x01, 0x02, 0x00, 0x01];
+        bytes.extend(u64::MAX.to_be_bytes());
+        let container =
+            PalettedContainer::decode(&mut bytes.as_slice(), ContainerKind::Biomes).unwrap();
+        assert!(container.iter().all(|id| id == 1), "every biome is 1");
+    }
+
+    #[test]
+    fn reject_oversized_entries() {
+        for (kind, bits) in [
+            (ContainerKind::BlockStates, 16),
+            (ContainerKind::BlockStates, 64),
+            (ContainerKind::BlockStates, 65),
+            (ContainerKind::Biomes, 8),
+            (ContainerKind::Biomes, 64),
+            (ContainerKind::Biomes, 200),
+        ] {
+            let mut bytes = vec![bits];
+            bytes.resize(1 + 8 * 4096, 0);
+            assert!(
+                PalettedContainer::decode(&mut bytes.as_slice(), kind).is_err(),
+                "{kind:?} cannot take {bits} bits per entry"
+            );
+        }
+
+        // the widest direct containers are fine
+        let mut bytes = vec![7];
+        bytes.extend([0; 8 * 8]);
+        let container =
+            PalettedContainer::decode(&mut bytes.as_slice(), ContainerKind::Biomes).unwrap();
+        assert_eq!(container.palette(), &Palette::Direct);
+        assert!(container.iter().all(|id| id == 0), "every biome is 0");
+    }
+
+    #[test]
+    fn set_grows_palette() {
+        let mut container = PalettedContainer::single(ContainerKind::BlockStates, 0);
+        container.set(5, 0);
+        assert_eq!(container.palette(), &Palette::Single(0));
+
+        container.set(5, 1);
+        assert_eq!(container.bits_per_entry(), 4);
+        assert_eq!(container.get(5), 1);
+        assert_eq!(container.get(6), 0);
+        roundtrip(&container);
+
+        for id in 2..20 {
+            container.set(100 + usize::try_from(id).unwrap(), id);
+        }
+        assert_eq!(container.bits_per_entry(), 5);
+        assert_eq!(container.get(119), 19);
+        assert_eq!(container.get(5), 1);
+        roundtrip(&container);
+
+        for id in 20..300 {
+            container.set(1000 + usize::try_from(id).unwrap(), id * 10);
+        }
+        assert_eq!(container.palette(), &Palette::Direct);
+        assert_eq!(container.bits_per_entry(), 15);
+        assert_eq!(container.get(1299), 2990);
+        assert_eq!(container.get(119), 19);
+        let decoded = roundtrip(&container);
+        assert_eq!(decoded.get(1299), 2990);
+    }
+}
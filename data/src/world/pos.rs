@@ -0,0 +1,202 @@
+use std::io;
+
+use codec::dec::{
+    Decode,
+    DecodeError,
+};
+use codec::enc::{
+    Encode,
+    EncodeError,
+};
+
+use crate::json::ToJson;
+
+/// A block position, sent packed in a long: 26 bits of x, 26 of z and 12
+/// of y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ToJson)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BlockPos {
+    #[must_use]
+    pub const fn new(
+        x: i32,
+        y: i32,
+        z: i32,
+    ) -> Self {
+        Self {
+            x,
+            y,
+            z,
+        }
+    }
+
+    #[must_use]
+    pub const fn chunk(self) -> ChunkPos {
+        ChunkPos {
+            x: self.x >> 4,
+            z: self.z >> 4,
+        }
+    }
+
+    /// Section-relative coordinates, each 0 to 15.
+    #[must_use]
+    pub const fn in_section(self) -> (usize, usize, usize) {
+        #[allow(clippy::cast_sign_loss, reason = "masked to 0 to 15")]
+        (
+            (self.x & 15) as usize,
+            (self.y & 15) as usize,
+            (self.z & 15) as usize,
+        )
+    }
+
+    #[must_use]
+    pub const fn to_long(self) -> i64 {
+        (self.x as i64 & 0x3FF_FFFF) << 38
+            | (self.z as i64 & 0x3FF_FFFF) << 12
+            | (self.y as i64 & 0xFFF)
+    }
+
+    #[must_use]
+    pub const fn from_long(packed: i64) -> Self {
+        #[allow(
+            clippy::cast_possible_truncation,
+            reason = "each coordinate is sign-extended from at most 26 bits"
+        )]
+        Self {
+            x: (packed >> 38) as i32,
+            y: (packed << 52 >> 52) as i32,
+            z: (packed << 26 >> 38) as i32,
+        }
+    }
+}
+
+impl Decode for BlockPos {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(Self::from_long(i64::decode(reader)?))
+    }
+}
+
+impl Encode for BlockPos {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        self.to_long().encode(writer)
+    }
+}
+
+/// Position of a 16×16×16 section, sent packed in a long: 22 bits of x, 22
+/// of z and 20 of y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ToJson)]
+pub struct SectionPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl SectionPos {
+    #[must_use]
+    pub const fn chunk(self) -> ChunkPos {
+        ChunkPos {
+            x: self.x,
+            z: self.z,
+        }
+    }
+
+    /// Block position of section-relative coordinates.
+    #[must_use]
+    pub const fn block(
+        self,
+        x: i32,
+        y: i32,
+        z: i32,
+    ) -> BlockPos {
+        BlockPos {
+            x: self.x * 16 + x,
+            y: self.y * 16 + y,
+            z: self.z * 16 + z,
+        }
+    }
+}
+
+impl Decode for SectionPos {
+    fn decode<R: io::Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        let packed = i64::decode(reader)?;
+        #[allow(
+            clippy::cast_possible_truncation,
+            reason = "each coordinate is sign-extended from at most 22 bits"
+        )]
+        Ok(Self {
+            x: (packed >> 42) as i32,
+            y: (packed << 44 >> 44) as i32,
+            z: (packed << 22 >> 42) as i32,
+        })
+    }
+}
+
+impl Encode for SectionPos {
+    fn encode<W: io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<usize, EncodeError> {
+        let packed = (i64::from(self.x) & 0x3F_FFFF) << 42
+            | (i64::from(self.z) & 0x3F_FFFF) << 20
+            | (i64::from(self.y) & 0xF_FFFF);
+        packed.encode(writer)
+    }
+}
+
+/// Position of a chunk column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ToJson)]
+pub struct ChunkPos {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl ChunkPos {
+    /// Region holding the chunk, as in `r.<x>.<z>.mca`.
+    #[must_use]
+    pub const fn region(self) -> (i32, i32) { (self.x >> 5, self.z >> 5) }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_pos() {
+        let pos = BlockPos::new(-1, -64, 33_554_431);
+        assert_eq!(BlockPos::from_long(pos.to_long()), pos);
+        assert_eq!(pos.chunk(), ChunkPos {
+            x: -1,
+            z: 2_097_151
+        });
+        assert_eq!(pos.in_section(), (15, 0, 15));
+
+        // example from the protocol documentation
+        #[allow(clippy::unusual_byte_groupings, reason = "grouped by x, z and y")]
+        let packed = 0b01000110000001110110001100_10110000010101101101001000_001100111111;
+        assert_eq!(
+            BlockPos::from_long(packed),
+            BlockPos::new(18_357_644, 831, -20_882_616)
+        );
+    }
+
+    #[test]
+    fn section_pos() {
+        let pos = SectionPos {
+            x: -2,
+            y: -4,
+            z: 3,
+        };
+        let mut bytes = Vec::new();
+        pos.encode(&mut bytes).unwrap();
+        assert_eq!(SectionPos::decode(&mut bytes.as_slice()).unwrap(), pos);
+        assert_eq!(pos.block(1, 2, 3), BlockPos::new(-31, -62, 51));
+    }
+}
@@ -0,0 +1,69 @@
+#!/usr/bin/env bash
+# Downloads the vanilla server of every pinned protocol version, runs its data
+# generator and copies the reports `datagen` reads to datagen/reports/<protocol>,
+# then regenerates data/src/generated from the latest version.
+#
+# Needs curl, jq, sha1sum and a Java runtime recent enough for the newest
+# server. Run from the workspace root:
+#
+#     datagen/fetch-reports.sh            # every pinned version
+#     datagen/fetch-reports.sh 772        # only some protocols
+
+set -euo pipefail
+
+MANIFEST=https://piston-meta.mojang.com/mc/game/version_manifest_v2.json
+LATEST=772
+
+# Newest release of each protocol in `data::version::ProtocolVersion::ALL`.
+declare -A RELEASES=(
+    [764]=1.20.2
+    [765]=1.20.4
+    [766]=1.20.6
+    [767]=1.21.1
+    [768]=1.21.3
+    [769]=1.21.4
+    [770]=1.21.5
+    [771]=1.21.6
+    [772]=1.21.8
+)
+
+if [ "$#" -gt 0 ]; then
+    protocols=("$@")
+else
+    mapfile -t protocols < <(printf '%s\n' "${!RELEASES[@]}" | sort -n)
+fi
+
+work=$(mktemp -d)
+trap 'rm -rf "$work"' EXIT
+
+curl -fsSL "$MANIFEST" -o "$work/manifest.json"
+
+for protocol in "${protocols[@]}"; do
+    release=${RELEASES[$protocol]:?unknown protocol $protocol}
+    echo "protocol $protocol: $release" >&2
+
+    version_url=$(jq -r --arg id "$release" '.versions[] | select(.id == $id) | .url' \
+        "$work/manifest.json")
+    curl -fsSL "$version_url" -o "$work/version.json"
+    jar_url=$(jq -r .downloads.server.url "$work/version.json")
+    jar_sha1=$(jq -r .downloads.server.sha1 "$work/version.json")
+
+    run="$work/$protocol"
+    mkdir -p "$run"
+    curl -fsSL "$jar_url" -o "$run/server.jar"
+    echo "$jar_sha1  $run/server.jar" | sha1sum --check --quiet
+
+    (cd "$run" && java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar \
+        --reports --server --output generated >/dev/null)
+
+    reports="datagen/reports/$protocol"
+    mkdir -p "$reports/data/minecraft/worldgen/biome"
+    cp "$run"/generated/reports/{blocks,packets,registries}.json "$reports/"
+    # only the names of data-driven entries are used
+    for biome in "$run"/generated/data/minecraft/worldgen/biome/*.json; do
+        : >"$reports/data/minecraft/worldgen/biome/$(basename "$biome")"
+    done
+done
+
+cargo run -p datagen -- --reports "datagen/reports/$LATEST" \
+    --data "datagen/reports/$LATEST/data" --out data/src/generated --protocol "$LATEST"
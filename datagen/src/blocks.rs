@@ -0,0 +1,281 @@
+use core::fmt::Write as _;
+
+use json::JsonValue;
+
+use crate::error::Error;
+use crate::naming;
+use crate::registries::Registry;
+
+/// State layout of a block of `blocks.json`.
+pub struct BlockStates {
+    pub identifier: String,
+    pub first_state: i32,
+    pub default_state: i32,
+    pub state_count: i32,
+    /// Property names and values, in the order the state ids enumerate them
+    /// (the last property changes fastest).
+    pub properties: Vec<(String, Vec<String>)>,
+}
+
+/// Reads the blocks report, one entry per block of the `block` registry.
+pub fn parse(
+    report: &JsonValue,
+    blocks: &Registry,
+) -> Result<Vec<BlockStates>, Error> {
+    let mut next_state = 0;
+    blocks
+        .entries
+        .iter()
+        .map(|(identifier, _)| {
+            let block = &report[identifier.as_str()];
+            if block.is_null() {
+                return Err(Error::Report(format!("{identifier} has no block states")));
+            }
+            let invalid = |message: &str| Error::Report(format!("{identifier}: {message}"));
+
+            let properties: Vec<(String, Vec<String>)> = block["properties"]
+                .entries()
+                .map(|(name, values)| {
+                    let values = values
+                        .members()
+                        .map(|value| value.as_str().map(str::to_owned))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| invalid("property values should be strings"))?;
+                    Ok((name.to_owned(), values))
+                })
+                .collect::<Result<_, Error>>()?;
+
+            let first_state = next_state;
+            let mut default_state = None;
+            for (index, state) in block["states"].members().enumerate() {
+                let id = state["id"]
+                    .as_i32()
+                    .ok_or_else(|| invalid("state without id"))?;
+                if id != next_state {
+                    return Err(invalid("state ids are not contiguous"));
+                }
+                if expected_properties(&properties, index) != actual_properties(state, &properties)
+                {
+                    return Err(invalid(&format!("state {id} is out of order")));
+                }
+                if state["default"].as_bool() == Some(true) {
+                    default_state = Some(id);
+                }
+                next_state += 1;
+            }
+            let state_count: usize = properties.iter().map(|(_, values)| values.len()).product();
+            if usize::try_from(next_state - first_state).ok() != Some(state_count) {
+                return Err(invalid("state count does not match its properties"));
+            }
+
+            Ok(BlockStates {
+                identifier: identifier.clone(),
+                first_state,
+                default_state: default_state.ok_or_else(|| invalid("no default state"))?,
+                state_count: next_state - first_state,
+                properties,
+            })
+        })
+        .collect()
+}
+
+/// Property values of the `index`th state of a block.
+fn expected_properties(
+    properties: &[(String, Vec<String>)],
+    mut index: usize,
+) -> Vec<String> {
+    let mut values: Vec<_> = properties
+        .iter()
+        .rev()
+        .map(|(_, values)| {
+            let value = values[index % values.len()].clone();
+            index /= values.len();
+            value
+        })
+        .collect();
+    values.reverse();
+    values
+}
+
+fn actual_properties(
+    state: &JsonValue,
+    properties: &[(String, Vec<String>)],
+) -> Vec<String> {
+    properties
+        .iter()
+        .map(|(name, _)| {
+            state["properties"][name.as_str()]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned()
+        })
+        .collect()
+}
+
+/// Emits the state layout table, indexed by block id, with one constant per
+/// distinct property.
+pub fn emit(
+    protocol: i32,
+    blocks: &[BlockStates],
+) -> String {
+    let mut out = crate::packets::header(protocol, "blocks.json");
+    out.push_str("\nuse crate::registry::block::{BlockStates, Property};\n");
+
+    let mut constants: Vec<(String, &str, &[String])> = Vec::new();
+    for block in blocks {
+        for (name, values) in &block.properties {
+            if constants
+                .iter()
+                .any(|(_, other_name, other_values)| other_name == name && other_values == values)
+            {
+                continue;
+            }
+            let same_name = constants
+                .iter()
+                .filter(|(_, other_name, _)| other_name == name)
+                .count();
+            let mut constant = naming::screaming_snake_case(name);
+            if same_name > 0 {
+                _ = write!(constant, "_{}", same_name + 1);
+            }
+            constants.push((constant, name, values));
+        }
+    }
+
+    for (constant, name, values) in &constants {
+        let values: Vec<_> = values.iter().map(|value| format!("\"{value}\"")).collect();
+        _ = writeln!(
+            out,
+            "\nconst {constant}: Property = Property {{\n    name: \"{name}\",\n    values: \
+             &[{}],\n}};",
+            values.join(", ")
+        );
+    }
+
+    let state_count = blocks
+        .last()
+        .map_or(0, |block| block.first_state + block.state_count);
+    _ = writeln!(out, "\n/// Number of block states.");
+    _ = writeln!(out, "pub const STATE_COUNT: u32 = {state_count};");
+
+    _ = writeln!(
+        out,
+        "\n/// State layout of every block, indexed by block id."
+    );
+    _ = writeln!(
+        out,
+        "pub static BLOCKS: [BlockStates; {}] = [",
+        blocks.len()
+    );
+    for block in blocks {
+        let properties: Vec<_> = block
+            .properties
+            .iter()
+            .map(|(name, values)| {
+                constants
+                    .iter()
+                    .find(|(_, other_name, other_values)| {
+                        other_name == name && *other_values == values.as_slice()
+                    })
+                    .map(|(constant, _, _)| constant.as_str())
+                    .unwrap_or_default()
+            })
+            .collect();
+        _ = writeln!(out, "    // {}", block.identifier);
+        _ = writeln!(
+            out,
+            "    BlockStates {{ first_state: {}, default_state: {}, properties: &[{}] }},",
+            block.first_state,
+            block.default_state,
+            properties.join(", ")
+        );
+    }
+    _ = writeln!(out, "];");
+
+    out
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> Registry {
+        Registry {
+            name: "block".to_owned(),
+            entries: vec![
+                ("minecraft:air".to_owned(), 0),
+                ("minecraft:oak_log".to_owned(), 1),
+            ],
+            data_driven: false,
+        }
+    }
+
+    #[test]
+    fn parse_and_emit() {
+        let report = json::parse(
+            r#"{
+                "minecraft:air": {"states": [{"default": true, "id": 0}]},
+                "minecraft:oak_log": {
+                    "properties": {"axis": ["x", "y", "z"]},
+                    "states": [
+                        {"id": 1, "properties": {"axis": "x"}},
+                        {"default": true, "id": 2, "properties": {"axis": "y"}},
+                        {"id": 3, "properties": {"axis": "z"}}
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+        let blocks = parse(&report, &registry()).unwrap();
+        assert_eq!(blocks[1].default_state, 2);
+
+        let code = emit(772, &blocks);
+        assert!(code.contains("pub const STATE_COUNT: u32 = 4;"), "{code}");
+        assert!(
+            code.contains(
+                "    // minecraft:oak_log\n    BlockStates { first_state: 1, default_state: 2, \
+                 properties: &[AXIS] },"
+            ),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn rejects_unordered_states() {
+        let report = json::parse(
+            r#"{
+                "minecraft:air": {"states": [{"default": true, "id": 0}]},
+                "minecraft:oak_log": {
+                    "properties": {"axis": ["x", "y", "z"]},
+                    "states": [
+                        {"id": 1, "properties": {"axis": "y"}},
+                        {"default": true, "id": 2, "properties": {"axis": "x"}},
+                        {"id": 3, "properties": {"axis": "z"}}
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert!(
+            parse(&report, &registry()).is_err(),
+            "states should enumerate property values in order"
+        );
+    }
+
+    #[test]
+    fn property_order() {
+        let properties = vec![
+            ("half".to_owned(), vec![
+                "top".to_owned(),
+                "bottom".to_owned(),
+            ]),
+            ("waterlogged".to_owned(), vec![
+                "true".to_owned(),
+                "false".to_owned(),
+            ]),
+        ];
+        assert_eq!(expected_properties(&properties, 1), ["top", "false"]);
+        assert_eq!(expected_properties(&properties, 2), ["bottom", "true"]);
+    }
+}
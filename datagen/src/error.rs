@@ -0,0 +1,28 @@
+use core::{
+    error,
+    fmt,
+};
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, io::Error),
+    Json(PathBuf, json::Error),
+    Report(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "IO error on {}: {err}", path.display()),
+            Self::Json(path, err) => write!(f, "JSON error in {}: {err}", path.display()),
+            Self::Report(message) => write!(f, "Invalid report: {message}"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,112 @@
+extern crate alloc;
+
+mod blocks;
+mod error;
+mod naming;
+mod packets;
+mod registries;
+
+use std::fs;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::process::ExitCode;
+
+use clap::Parser;
+use json::JsonValue;
+
+use crate::error::Error;
+
+/// Generates packet ids, registry enums and packet skeletons from the reports
+/// of the vanilla data generator
+/// (`java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar
+/// --reports`).
+#[derive(Parser, Debug)]
+#[command(about, version, author)]
+struct Cli {
+    /// Directory holding `packets.json` and `registries.json`.
+    #[arg(long)]
+    reports: PathBuf,
+    /// Directory the generated modules are written to.
+    #[arg(long)]
+    out: PathBuf,
+    /// Protocol number the reports were generated for.
+    #[arg(long)]
+    protocol: i32,
+    /// Registries to emit as enums. Block states are emitted from
+    /// `blocks.json` when `block` is one of them.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "block,block_entity_type,item,entity_type,particle_type,\
+                         data_component_type"
+    )]
+    registries: Vec<String>,
+    /// `data` directory of the vanilla data pack written by the data
+    /// generator, for registries that are not in `registries.json`.
+    #[arg(long)]
+    data: Option<PathBuf>,
+    /// Data-driven registries to emit as enums, read from `--data`.
+    #[arg(long, value_delimiter = ',', default_value = "worldgen/biome")]
+    data_registries: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Cli) -> Result<(), Error> {
+    let packets = packets::parse(&read_report(&args.reports.join("packets.json"))?)?;
+    let mut registries = registries::parse(
+        &read_report(&args.reports.join("registries.json"))?,
+        &args.registries,
+    )?;
+    let blocks = registries
+        .iter()
+        .find(|registry| registry.name == "block")
+        .map(|registry| blocks::parse(&read_report(&args.reports.join("blocks.json"))?, registry))
+        .transpose()?;
+    if let Some(data) = &args.data {
+        registries.extend(registries::parse_data_driven(data, &args.data_registries)?);
+    }
+
+    fs::create_dir_all(&args.out).map_err(|err| Error::Io(args.out.clone(), err))?;
+    write(
+        &args.out.join("packets.rs"),
+        &packets::emit_ids(args.protocol, &packets),
+    )?;
+    write(
+        &args.out.join("registries.rs"),
+        &registries::emit(args.protocol, &registries)?,
+    )?;
+    if let Some(blocks) = &blocks {
+        write(
+            &args.out.join("blocks.rs"),
+            &blocks::emit(args.protocol, blocks),
+        )?;
+    }
+    write(
+        &args.out.join("skeletons.rs"),
+        &packets::emit_skeletons(args.protocol, &packets),
+    )
+}
+
+fn read_report(path: &Path) -> Result<JsonValue, Error> {
+    let text = fs::read_to_string(path).map_err(|err| Error::Io(path.to_owned(), err))?;
+    json::parse(&text).map_err(|err| Error::Json(path.to_owned(), err))
+}
+
+fn write(
+    path: &Path,
+    contents: &str,
+) -> Result<(), Error> {
+    fs::write(path, contents).map_err(|err| Error::Io(path.to_owned(), err))
+}
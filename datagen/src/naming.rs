@@ -0,0 +1,39 @@
+/// Strips the `minecraft:` namespace of an identifier.
+pub fn path(identifier: &str) -> &str {
+    identifier.strip_prefix("minecraft:").unwrap_or(identifier)
+}
+
+/// `minecraft:tnt_minecart` to `TntMinecart`.
+pub fn pascal_case(identifier: &str) -> String {
+    let mut out = String::new();
+    for word in path(identifier).split(['_', '/', '.', ':']) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// `minecraft:keep_alive` to `KEEP_ALIVE`.
+pub fn screaming_snake_case(identifier: &str) -> String {
+    path(identifier)
+        .replace(['/', '.', ':'], "_")
+        .to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(pascal_case("minecraft:tnt_minecart"), "TntMinecart");
+        assert_eq!(pascal_case("minecraft:music_disc_5"), "MusicDisc5");
+        assert_eq!(screaming_snake_case("minecraft:keep_alive"), "KEEP_ALIVE");
+    }
+}
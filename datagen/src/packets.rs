@@ -0,0 +1,217 @@
+use core::fmt::Write as _;
+
+use json::JsonValue;
+
+use crate::error::Error;
+use crate::naming;
+
+/// States in the order they appear in the generated files.
+const STATES: [(&str, &str); 5] = [
+    ("handshake", "Handshake"),
+    ("status", "Status"),
+    ("login", "Login"),
+    ("configuration", "Configuration"),
+    ("play", "Play"),
+];
+const DIRECTIONS: [(&str, &str); 2] = [
+    ("serverbound", "Serverbound"),
+    ("clientbound", "Clientbound"),
+];
+
+/// A packet of `packets.json`.
+pub struct PacketEntry {
+    pub state: &'static str,
+    pub direction: &'static str,
+    pub name: String,
+    pub id: i32,
+}
+
+/// Reads the packets report, sorted by state, direction then id.
+pub fn parse(report: &JsonValue) -> Result<Vec<PacketEntry>, Error> {
+    let mut entries = Vec::new();
+
+    for (state, _) in STATES {
+        for (direction, _) in DIRECTIONS {
+            let packets = &report[state][direction];
+            let mut packets_of_side = Vec::new();
+            for (name, packet) in packets.entries() {
+                let id = packet["protocol_id"].as_i32().ok_or_else(|| {
+                    Error::Report(format!("{state} {direction} {name} has no protocol_id"))
+                })?;
+                packets_of_side.push(PacketEntry {
+                    state,
+                    direction,
+                    name: naming::path(name).to_owned(),
+                    id,
+                });
+            }
+            packets_of_side.sort_by_key(|packet| packet.id);
+            for (expected, packet) in (0..).zip(&packets_of_side) {
+                if packet.id != expected {
+                    return Err(Error::Report(format!(
+                        "{state} {direction} ids are not contiguous at {}",
+                        packet.name
+                    )));
+                }
+            }
+            entries.extend(packets_of_side);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Emits packet id constants and name tables, one module per state and
+/// direction.
+pub fn emit_ids(
+    protocol: i32,
+    packets: &[PacketEntry],
+) -> String {
+    let mut out = header(protocol, "packets.json");
+
+    for (state, _) in STATES {
+        if !packets.iter().any(|packet| packet.state == state) {
+            continue;
+        }
+        _ = writeln!(out, "\npub mod {state} {{");
+        let mut first = true;
+        for (direction, _) in DIRECTIONS {
+            let side: Vec<_> = packets
+                .iter()
+                .filter(|packet| packet.state == state && packet.direction == direction)
+                .collect();
+            if side.is_empty() {
+                continue;
+            }
+            if !first {
+                out.push('\n');
+            }
+            first = false;
+            _ = writeln!(out, "    pub mod {direction} {{");
+            _ = writeln!(out, "        /// Packet names in id order.");
+            _ = writeln!(out, "        pub const NAMES: &[&str] = &[");
+            for packet in &side {
+                _ = writeln!(out, "            \"{}\",", packet.name);
+            }
+            _ = writeln!(out, "        ];\n");
+            for packet in &side {
+                _ = writeln!(
+                    out,
+                    "        pub const {}: i32 = 0x{:02X};",
+                    naming::screaming_snake_case(&packet.name),
+                    packet.id
+                );
+            }
+            _ = writeln!(out, "    }}");
+        }
+        _ = writeln!(out, "}}");
+    }
+
+    out
+}
+
+/// Emits one raw-payload struct per packet, ready to be filled in and moved
+/// to `data::model`.
+pub fn emit_skeletons(
+    protocol: i32,
+    packets: &[PacketEntry],
+) -> String {
+    let mut out = header(protocol, "packets.json");
+    out.push_str(
+        "\nuse codec::dec::Decode;\nuse codec::enc::Encode;\n\nuse crate::protocol::Packet;\n",
+    );
+
+    for (state, state_variant) in STATES {
+        for (direction, direction_variant) in DIRECTIONS {
+            let side: Vec<_> = packets
+                .iter()
+                .filter(|packet| packet.state == state && packet.direction == direction)
+                .collect();
+            for packet in side {
+                let struct_name = format!(
+                    "{}{}{}",
+                    naming::pascal_case(state),
+                    naming::pascal_case(direction),
+                    naming::pascal_case(&packet.name)
+                );
+                _ = write!(
+                    out,
+                    "\n#[derive(Debug, Decode, Encode, Packet)]\n#[packet(id = 0x{id:02X}, state \
+                     = {state_variant}, direction = {direction_variant}, name = \"{name}\")]\npub \
+                     struct {struct_name} {{\n    #[codec(rest)]\n    pub data: Box<[u8]>,\n}}\n",
+                    id = packet.id,
+                    name = packet.name,
+                );
+            }
+        }
+    }
+
+    out
+}
+
+pub fn header(
+    protocol: i32,
+    report: &str,
+) -> String {
+    format!(
+        "//! Generated by `datagen` from the protocol {protocol} `{report}` report. Do not edit \
+         by hand.\n"
+    )
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> JsonValue {
+        json::parse(
+            r#"{
+                "handshake": {"serverbound": {"minecraft:intention": {"protocol_id": 0}}},
+                "status": {
+                    "clientbound": {
+                        "minecraft:pong_response": {"protocol_id": 1},
+                        "minecraft:status_response": {"protocol_id": 0}
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_sorts_by_id() {
+        let packets = parse(&report()).unwrap();
+        let names: Vec<_> = packets.iter().map(|packet| packet.name.as_str()).collect();
+        assert_eq!(names, ["intention", "status_response", "pong_response"]);
+    }
+
+    #[test]
+    fn parse_rejects_gaps() {
+        let report =
+            json::parse(r#"{"play": {"clientbound": {"minecraft:a": {"protocol_id": 1}}}}"#)
+                .unwrap();
+        assert!(
+            parse(&report).is_err(),
+            "ids should start at 0 without gaps"
+        );
+    }
+
+    #[test]
+    fn emit() {
+        let packets = parse(&report()).unwrap();
+        let ids = emit_ids(772, &packets);
+        assert!(
+            ids.contains("pub const PONG_RESPONSE: i32 = 0x01;"),
+            "{ids}"
+        );
+        let skeletons = emit_skeletons(772, &packets);
+        assert!(
+            skeletons.contains(
+                "#[packet(id = 0x00, state = Handshake, direction = Serverbound, name = \
+                 \"intention\")]\npub struct HandshakeServerboundIntention {"
+            ),
+            "{skeletons}"
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use alloc::collections::BTreeSet;
+use core::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use json::JsonValue;
+
+use crate::error::Error;
+use crate::naming;
+
+/// A registry of `registries.json` or of the vanilla data pack.
+pub struct Registry {
+    pub name: String,
+    pub entries: Vec<(String, i32)>,
+    /// Whether entries come from a data pack, whose ids are assigned by the
+    /// server when it sends the registry.
+    pub data_driven: bool,
+}
+
+/// Reads the requested registries, entries sorted by id.
+pub fn parse(
+    report: &JsonValue,
+    names: &[String],
+) -> Result<Vec<Registry>, Error> {
+    names
+        .iter()
+        .map(|name| {
+            let key = format!("minecraft:{}", naming::path(name));
+            let registry = &report[key.as_str()];
+            if registry.is_null() {
+                return Err(Error::Report(format!("missing registry {key}")));
+            }
+            let mut entries = Vec::new();
+            for (identifier, entry) in registry["entries"].entries() {
+                let id = entry["protocol_id"].as_i32().ok_or_else(|| {
+                    Error::Report(format!("{key} entry {identifier} has no protocol_id"))
+                })?;
+                entries.push((identifier.to_owned(), id));
+            }
+            entries.sort_by_key(|(_, id)| *id);
+            for (expected, (identifier, id)) in (0..).zip(&entries) {
+                if *id != expected {
+                    return Err(Error::Report(format!(
+                        "{key} ids are not contiguous at {identifier}"
+                    )));
+                }
+            }
+            Ok(Registry {
+                name: naming::path(name).to_owned(),
+                entries,
+                data_driven: false,
+            })
+        })
+        .collect()
+}
+
+/// Reads data-driven registries from the `data` directory written by the
+/// data generator. Entries are numbered in identifier order, as a vanilla
+/// server sends them.
+pub fn parse_data_driven(
+    data: &Path,
+    names: &[String],
+) -> Result<Vec<Registry>, Error> {
+    names
+        .iter()
+        .map(|name| {
+            let dir = data.join("minecraft").join(naming::path(name));
+            let files = fs::read_dir(&dir).map_err(|err| Error::Io(dir.clone(), err))?;
+            let mut identifiers = Vec::new();
+            for file in files {
+                let path = file.map_err(|err| Error::Io(dir.clone(), err))?.path();
+                if path
+                    .extension()
+                    .is_some_and(|extension| extension == "json")
+                    && let Some(stem) = path.file_stem().and_then(|stem| stem.to_str())
+                {
+                    identifiers.push(format!("minecraft:{stem}"));
+                }
+            }
+            identifiers.sort();
+            Ok(Registry {
+                name: naming::path(name).to_owned(),
+                entries: identifiers.into_iter().zip(0..).collect(),
+                data_driven: true,
+            })
+        })
+        .collect()
+}
+
+/// Emits one enum per registry, with the protocol id as discriminant.
+pub fn emit(
+    protocol: i32,
+    registries: &[Registry],
+) -> Result<String, Error> {
+    let mut out = crate::packets::header(protocol, "registries.json");
+    out.push_str(
+        "\nuse codec::dec::Decode;\nuse codec::enc::Encode;\n\nuse crate::registry::Registry;\n",
+    );
+
+    for registry in registries {
+        let type_name = naming::pascal_case(registry.name.rsplit('/').next().unwrap_or_default());
+        let mut variants = BTreeSet::new();
+        for (identifier, _) in &registry.entries {
+            let variant = naming::pascal_case(identifier);
+            if !variants.insert(variant.clone()) {
+                return Err(Error::Report(format!(
+                    "{} has two entries named {variant}",
+                    registry.name
+                )));
+            }
+        }
+
+        _ = writeln!(
+            out,
+            "\n/// Entries of the `minecraft:{}` registry.",
+            registry.name
+        );
+        if registry.data_driven {
+            _ = writeln!(
+                out,
+                "///\n/// Ids are those of the vanilla data pack. Servers with other data packs \
+                 assign\n/// their own in the registry data they send."
+            );
+        }
+        _ = writeln!(
+            out,
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode)]"
+        );
+        _ = writeln!(out, "#[codec(varint)]");
+        _ = writeln!(out, "pub enum {type_name} {{");
+        for (identifier, id) in &registry.entries {
+            _ = writeln!(out, "    {} = {id},", naming::pascal_case(identifier));
+        }
+        _ = writeln!(out, "}}\n");
+
+        _ = writeln!(out, "impl {type_name} {{");
+        _ = writeln!(
+            out,
+            "    const IDENTIFIERS: [&'static str; {}] = [",
+            registry.entries.len()
+        );
+        for (identifier, _) in &registry.entries {
+            _ = writeln!(out, "        \"{identifier}\",");
+        }
+        _ = writeln!(out, "    ];");
+        _ = writeln!(out, "}}\n");
+
+        _ = writeln!(out, "impl Registry for {type_name} {{");
+        _ = writeln!(
+            out,
+            "    const REGISTRY: &'static str = \"minecraft:{}\";",
+            registry.name
+        );
+        _ = writeln!(out, "    const ENTRIES: &'static [Self] = &[");
+        for (identifier, _) in &registry.entries {
+            _ = writeln!(out, "        Self::{},", naming::pascal_case(identifier));
+        }
+        _ = writeln!(out, "    ];\n");
+        _ = writeln!(
+            out,
+            "    fn identifier(self) -> &'static str {{ Self::IDENTIFIERS[self as usize] }}\n"
+        );
+        _ = writeln!(out, "    fn id(self) -> i32 {{ self as i32 }}");
+        _ = writeln!(out, "}}");
+    }
+
+    Ok(out)
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> JsonValue {
+        json::parse(
+            r#"{
+                "minecraft:particle_type": {
+                    "protocol_id": 9,
+                    "entries": {
+                        "minecraft:bubble": {"protocol_id": 1},
+                        "minecraft:angry_villager": {"protocol_id": 0}
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_and_emit() {
+        let registries = parse(&report(), &["particle_type".to_owned()]).unwrap();
+        assert_eq!(registries[0].entries, [
+            ("minecraft:angry_villager".to_owned(), 0),
+            ("minecraft:bubble".to_owned(), 1)
+        ]);
+
+        let code = emit(772, &registries).unwrap();
+        assert!(
+            code.contains("pub enum ParticleType {\n    AngryVillager = 0,"),
+            "{code}"
+        );
+        assert!(
+            code.contains("const REGISTRY: &'static str = \"minecraft:particle_type\";"),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn missing_registry() {
+        assert!(
+            parse(&report(), &["item".to_owned()]).is_err(),
+            "unknown registries should be reported"
+        );
+    }
+
+    #[test]
+    fn data_driven() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("reports/772/data");
+        let registries = parse_data_driven(&data, &["worldgen/biome".to_owned()]).unwrap();
+        assert_eq!(registries[0].name, "worldgen/biome");
+        assert_eq!(
+            registries[0].entries[0],
+            ("minecraft:badlands".to_owned(), 0)
+        );
+        assert!(
+            emit(772, &registries).unwrap().contains("pub enum Biome {"),
+            "data-driven registries are emitted like the others"
+        );
+    }
+}
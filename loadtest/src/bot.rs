@@ -0,0 +1,376 @@
+//! A simulated player, reconnecting or not depending on its behavior.
+
+use core::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use clap::ValueEnum;
+use client::{
+    Client,
+    ClientOptions,
+    Error,
+    Event,
+    Position,
+};
+use log::debug;
+
+use crate::stats::Stats;
+
+/// How often a bot wakes up when it has nothing to do, to report its
+/// packets and notice the end of the test.
+const IDLE_WAKE_UP: Duration = Duration::from_secs(1);
+const WALK_STEP: Duration = Duration::from_millis(250);
+/// Side of the square walking bots go around, in steps of one block.
+const WALK_SIDE: u32 = 4;
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Behavior {
+    /// Stays where it spawned.
+    Idle,
+    /// Walks around a small square.
+    Walk,
+    /// Sends a chat message at every chat interval.
+    Chat,
+    /// Leaves after each session and logs in again.
+    Reconnect,
+}
+
+#[derive(Debug, Clone)]
+pub struct BotConfig {
+    pub host: String,
+    pub port: u16,
+    pub behavior: Behavior,
+    pub chat_interval: Duration,
+    /// How long a reconnecting bot stays logged in.
+    pub session: Duration,
+}
+
+/// Runs a bot until `stop` is set, or until it is disconnected unless it
+/// reconnects.
+pub fn run(
+    config: &BotConfig,
+    name: &str,
+    stats: &Stats,
+    stop: &AtomicBool,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        session(config, name, stats, stop);
+        if config.behavior != Behavior::Reconnect {
+            return;
+        }
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+/// Logs in and plays until the session ends.
+fn session(
+    config: &BotConfig,
+    name: &str,
+    stats: &Stats,
+    stop: &AtomicBool,
+) {
+    let started = Instant::now();
+    let (mut client, spawn) = match join(config, name) {
+        Ok(joined) => joined,
+        Err(err) => {
+            debug!("{name} could not log in: {err}");
+            stats.login_failed(reason(&err));
+            return;
+        }
+    };
+    stats.logged_in(started.elapsed());
+
+    let mut bot = Bot {
+        config,
+        stats,
+        spawn,
+        steps: 0,
+        messages: 0,
+        reported: (0, 0),
+    };
+    let result = bot.play(&mut client, stop);
+    bot.report_packets(&client);
+    stats.left(result.err().map(|err| reason(&err)));
+}
+
+/// Connects, returning the client once spawned, with its spawn position.
+fn join(
+    config: &BotConfig,
+    name: &str,
+) -> Result<(Client, Position), Error> {
+    let mut client = Client::connect(&config.host, config.port, ClientOptions::new(name))?;
+    loop {
+        if let Event::Teleported {
+            position, ..
+        } = client.next_event()?
+        {
+            return Ok((client, position));
+        }
+    }
+}
+
+/// Why a bot lost its connection, grouping errors of the same kind.
+fn reason(err: &Error) -> String {
+    match err {
+        Error::Disconnected(reason) => reason.clone(),
+        Error::Io(err) => format!("IO error: {}", err.kind()),
+        err => err.to_string(),
+    }
+}
+
+struct Bot<'config> {
+    config: &'config BotConfig,
+    stats: &'config Stats,
+    spawn: Position,
+    steps: u32,
+    messages: u64,
+    /// Packets received and sent already added to the stats.
+    reported: (u64, u64),
+}
+
+impl Bot<'_> {
+    /// Plays until stopped, returning an error if disconnected.
+    fn play(
+        &mut self,
+        client: &mut Client,
+        stop: &AtomicBool,
+    ) -> Result<(), Error> {
+        let mut next_action = Instant::now() + self.interval();
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= next_action {
+                match self.config.behavior {
+                    Behavior::Idle => {}
+                    Behavior::Walk => self.walk(client)?,
+                    Behavior::Chat => {
+                        self.messages += 1;
+                        client.chat(&format!("Message {}", self.messages))?;
+                    }
+                    Behavior::Reconnect => return Ok(()),
+                }
+                next_action = now + self.interval();
+            }
+            let wake_up = next_action.min(now + IDLE_WAKE_UP);
+            client.next_event_timeout(wake_up.saturating_duration_since(now))?;
+            self.report_packets(client);
+        }
+    }
+
+    /// Time until the next action, or the end of the session of a
+    /// reconnecting bot.
+    const fn interval(&self) -> Duration {
+        match self.config.behavior {
+            Behavior::Idle => IDLE_WAKE_UP,
+            Behavior::Walk => WALK_STEP,
+            Behavior::Chat => self.config.chat_interval,
+            Behavior::Reconnect => self.config.session,
+        }
+    }
+
+    /// Takes one step around the square, starting from the spawn.
+    #[allow(clippy::float_arithmetic, reason = "positions are doubles on the wire")]
+    fn walk(
+        &mut self,
+        client: &mut Client,
+    ) -> Result<(), Error> {
+        self.steps = (self.steps + 1) % (WALK_SIDE * 4);
+        let side = self.steps / WALK_SIDE;
+        let along = f64::from(self.steps % WALK_SIDE);
+        let side_length = f64::from(WALK_SIDE);
+        let (x, z) = match side {
+            0 => (along, 0.0),
+            1 => (side_length, along),
+            2 => (side_length - along, side_length),
+            _ => (0.0, side_length - along),
+        };
+        client.move_to(Position {
+            x: self.spawn.x + x,
+            y: self.spawn.y,
+            z: self.spawn.z + z,
+        })
+    }
+
+    fn report_packets(
+        &mut self,
+        client: &Client,
+    ) {
+        let current = (client.packets_received(), client.packets_sent());
+        self.stats
+            .packets(current.0 - self.reported.0, current.1 - self.reported.1);
+        self.reported = current;
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+pub mod tests {
+    use std::net::TcpListener;
+
+    use server::{
+        Server,
+        ServerConfig,
+        ServerHandle,
+    };
+
+    use super::*;
+
+    /// Starts a server in-process, returning it with its port.
+    pub fn start(config: ServerConfig) -> (ServerHandle, u16) {
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let handle = server.handle();
+        let port = server.local_addr().unwrap().port();
+        thread::spawn(move || server.run());
+        (handle, port)
+    }
+
+    pub fn bot_config(
+        port: u16,
+        behavior: Behavior,
+    ) -> BotConfig {
+        BotConfig {
+            host: "127.0.0.1".to_owned(),
+            port,
+            behavior,
+            chat_interval: Duration::from_millis(100),
+            session: Duration::from_millis(100),
+        }
+    }
+
+    /// Runs a bot on its own thread for `duration`, then stops it.
+    fn run_for(
+        config: &BotConfig,
+        stats: &Stats,
+        duration: Duration,
+    ) {
+        let stop = AtomicBool::new(false);
+        thread::scope(|scope| {
+            scope.spawn(|| run(config, "bot", stats, &stop));
+            thread::sleep(duration);
+            stop.store(true, Ordering::Relaxed);
+        });
+    }
+
+    #[test]
+    fn idle_bots_stay_until_stopped() {
+        let (handle, port) = start(ServerConfig::default());
+        let config = bot_config(port, Behavior::Idle);
+        let stats = Stats::default();
+        let stop = AtomicBool::new(false);
+        thread::scope(|scope| {
+            scope.spawn(|| run(&config, "bot", &stats, &stop));
+            let joined = (0..100).any(|_| {
+                thread::sleep(Duration::from_millis(10));
+                stats.snapshot().connected == 1
+            });
+            assert!(joined, "the bot should log in");
+            assert_eq!(handle.players(), ["bot"]);
+            stop.store(true, Ordering::Relaxed);
+        });
+
+        let snapshot = stats.snapshot();
+        assert_eq!((snapshot.logins, snapshot.connected), (1, 0));
+        assert!(snapshot.disconnects.is_empty(), "the bot left on its own");
+        // login, game event, chunk cache center, 25 chunks and the position
+        assert!(
+            snapshot.packets_received >= 29,
+            "the packets of the join should be counted, got {}",
+            snapshot.packets_received
+        );
+    }
+
+    #[test]
+    fn walking_bots_go_around_a_square() {
+        let (_, port) = start(ServerConfig::default());
+        let config = bot_config(port, Behavior::Walk);
+        let stats = Stats::default();
+        let (mut client, spawn) = join(&config, "walker").unwrap();
+        let mut bot = Bot {
+            config: &config,
+            stats: &stats,
+            spawn,
+            steps: 0,
+            messages: 0,
+            reported: (0, 0),
+        };
+        let offsets: Vec<_> = (0..WALK_SIDE * 4)
+            .map(|_| {
+                bot.walk(&mut client).unwrap();
+                let position = client.position().unwrap();
+                assert_eq!(position.y.to_bits(), spawn.y.to_bits(), "bots stay level");
+                #[allow(clippy::float_arithmetic, reason = "offsets from the spawn")]
+                #[allow(clippy::cast_possible_truncation, reason = "whole blocks")]
+                let offset = ((position.x - spawn.x) as i32, (position.z - spawn.z) as i32);
+                offset
+            })
+            .collect();
+        assert_eq!(offsets[..5], [(1, 0), (2, 0), (3, 0), (4, 0), (4, 1)]);
+        assert_eq!(offsets[11], (0, 4), "the third side goes back along x");
+        assert_eq!(
+            offsets.last(),
+            Some(&(0, 0)),
+            "a full round should end at the spawn"
+        );
+
+        bot.report_packets(&client);
+        assert_eq!(
+            stats.snapshot().packets_sent,
+            client.packets_sent(),
+            "the moves should be counted"
+        );
+    }
+
+    #[test]
+    fn reconnecting_bots_log_in_again() {
+        let (_, port) = start(ServerConfig::default());
+        let stats = Stats::default();
+        run_for(
+            &bot_config(port, Behavior::Reconnect),
+            &stats,
+            RECONNECT_DELAY + Duration::from_millis(500),
+        );
+
+        let snapshot = stats.snapshot();
+        assert!(
+            snapshot.logins >= 2,
+            "the bot should log in again after its session, got {} logins",
+            snapshot.logins
+        );
+        assert_eq!(snapshot.connected, 0);
+        assert!(snapshot.disconnects.is_empty(), "sessions end on purpose");
+    }
+
+    #[test]
+    fn failed_logins_are_grouped_by_reason() {
+        // a port nothing listens on anymore
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let stats = Stats::default();
+        for behavior in [Behavior::Idle, Behavior::Chat] {
+            run(
+                &bot_config(port, behavior),
+                "bot",
+                &stats,
+                &AtomicBool::new(false),
+            );
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!((snapshot.logins, snapshot.failed_logins), (0, 2));
+        assert_eq!(
+            snapshot.disconnects["IO error: connection refused"], 2,
+            "IO errors should be grouped by kind: {:?}",
+            snapshot.disconnects
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Spawns simulated players against a server, directly or through the proxy,
+//! and reports login latency, packet throughput and disconnect reasons.
+
+#![allow(
+    clippy::multiple_crate_versions,
+    reason = "the workspace lockfile holds the syn of the proxy dependencies too"
+)]
+
+extern crate alloc;
+
+mod bot;
+mod stats;
+
+use alloc::sync::Arc;
+use core::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+use core::time::Duration;
+use std::thread::{
+    self,
+    JoinHandle,
+};
+use std::time::Instant;
+
+use clap::Parser;
+
+use crate::bot::{
+    Behavior,
+    BotConfig,
+};
+use crate::stats::{
+    Report,
+    Snapshot,
+    Stats,
+};
+
+#[derive(Parser, Debug)]
+#[command(about, version, author)]
+struct Cli {
+    #[arg(long, env, default_value = "127.0.0.1")]
+    host: String,
+    #[arg(long, env, default_value = "25565")]
+    port: u16,
+    /// Number of bots.
+    #[arg(long, short = 'n', env, default_value = "10")]
+    bots: u32,
+    /// Seconds over which the bots are started, evenly spread.
+    #[arg(long, env, default_value = "10")]
+    ramp_up: u64,
+    /// Bots are named after it followed by their number, which must fit
+    /// in 16 characters.
+    #[arg(long, env, default_value = "bot")]
+    name_prefix: String,
+    #[arg(long, env, value_enum, default_value_t = Behavior::Idle)]
+    behavior: Behavior,
+    /// Seconds between the messages of chatting bots.
+    #[arg(long, env, default_value = "5")]
+    chat_interval: u64,
+    /// Seconds a reconnecting bot stays before logging in again.
+    #[arg(long, env, default_value = "10")]
+    session: u64,
+    /// Seconds between reports.
+    #[arg(long, env, default_value = "5")]
+    report_interval: u64,
+    /// Seconds after which the bots leave and the final report is made.
+    /// Runs until killed when not set.
+    #[arg(long, env)]
+    duration: Option<u64>,
+}
+
+/// Starts the bots, one every `delay`, until `stop` is set.
+fn spawn_bots(
+    config: &Arc<BotConfig>,
+    names: Vec<String>,
+    delay: Duration,
+    stats: &Arc<Stats>,
+    stop: &Arc<AtomicBool>,
+) -> Vec<JoinHandle<()>> {
+    let mut bots = Vec::new();
+    for name in names {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let (config, stats, stop) = (Arc::clone(config), Arc::clone(stats), Arc::clone(stop));
+        bots.push(thread::spawn(move || {
+            bot::run(&config, &name, &stats, &stop);
+        }));
+        thread::sleep(delay);
+    }
+    bots
+}
+
+fn main() {
+    env_logger::init();
+
+    let args = Cli::parse();
+    let config = Arc::new(BotConfig {
+        host: args.host,
+        port: args.port,
+        behavior: args.behavior,
+        chat_interval: Duration::from_secs(args.chat_interval),
+        session: Duration::from_secs(args.session),
+    });
+    let names = (0..args.bots)
+        .map(|index| format!("{}{index}", args.name_prefix))
+        .collect();
+    let delay = Duration::from_secs(args.ramp_up) / args.bots.max(1);
+    let stats = Arc::new(Stats::default());
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let spawner = {
+        let (stats, stop) = (Arc::clone(&stats), Arc::clone(&stop));
+        thread::spawn(move || spawn_bots(&config, names, delay, &stats, &stop))
+    };
+
+    let started = Instant::now();
+    let end = args
+        .duration
+        .map(|duration| started + Duration::from_secs(duration));
+    let interval = Duration::from_secs(args.report_interval.max(1));
+    let mut previous = (started, stats.snapshot());
+    while end.is_none_or(|end| Instant::now() < end) {
+        let now = Instant::now();
+        let next = (now + interval).min(end.unwrap_or(now + interval));
+        thread::sleep(next - now);
+        previous = report(&stats, previous);
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    for bot in spawner.join().expect("the spawner should not panic") {
+        bot.join().expect("bots should not panic");
+    }
+    println!("Final report:");
+    report(&stats, (started, Snapshot::default()));
+}
+
+/// Prints what happened since the previous report, returning the new
+/// snapshot.
+fn report(
+    stats: &Stats,
+    (since, previous): (Instant, Snapshot),
+) -> (Instant, Snapshot) {
+    let now = Instant::now();
+    let current = stats.snapshot();
+    println!("{}", Report {
+        previous: &previous,
+        current: &current,
+        elapsed: now - since,
+    });
+    (now, current)
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use server::ServerConfig;
+
+    use super::*;
+    use crate::bot::tests::{
+        bot_config,
+        start,
+    };
+
+    #[test]
+    fn bots_log_in_chat_and_leave() {
+        let (_, port) = start(ServerConfig {
+            max_players: 2,
+            ..ServerConfig::default()
+        });
+        let config = Arc::new(bot_config(port, Behavior::Chat));
+        let names = vec!["bot0".to_owned(), "bot1".to_owned(), "bot2".to_owned()];
+        let stats = Arc::new(Stats::default());
+        let stop = Arc::new(AtomicBool::new(false));
+        let bots = spawn_bots(&config, names, Duration::ZERO, &stats, &stop);
+        thread::sleep(Duration::from_secs(1));
+        stop.store(true, Ordering::Relaxed);
+        for bot in bots {
+            bot.join().unwrap();
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.logins, 2, "two bots should fit in the server");
+        assert_eq!(snapshot.failed_logins, 1);
+        assert_eq!(snapshot.disconnects["The server is full"], 1);
+        assert_eq!(snapshot.connected, 0, "every bot should have left");
+        assert!(
+            snapshot.packets_sent > 10,
+            "chat messages should be counted, got {}",
+            snapshot.packets_sent
+        );
+        assert!(snapshot.latency.is_some(), "logins should be timed");
+    }
+
+    #[test]
+    fn ramp_up_spreads_bots_until_stopped() {
+        let (handle, port) = start(ServerConfig::default());
+        let config = Arc::new(bot_config(port, Behavior::Idle));
+        let names = (0..5).map(|index| format!("bot{index}")).collect();
+        let stats = Arc::new(Stats::default());
+        let stop = Arc::new(AtomicBool::new(false));
+        let spawner = {
+            let (stats, stop) = (Arc::clone(&stats), Arc::clone(&stop));
+            thread::spawn(move || {
+                spawn_bots(&config, names, Duration::from_millis(300), &stats, &stop)
+            })
+        };
+        // the first bots start at 0 and 300 ms, the third would at 600 ms
+        thread::sleep(Duration::from_millis(450));
+        assert_eq!(
+            handle.players(),
+            ["bot0", "bot1"],
+            "bots should be started one delay apart"
+        );
+        stop.store(true, Ordering::Relaxed);
+        let bots = spawner.join().unwrap();
+        assert_eq!(bots.len(), 2, "no bot should start once stopped");
+        for bot in bots {
+            bot.join().unwrap();
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!((snapshot.logins, snapshot.connected), (2, 0));
+        let report = Report {
+            previous: &Snapshot::default(),
+            current: &snapshot,
+            elapsed: Duration::from_secs(1),
+        }
+        .to_string();
+        assert!(
+            report.starts_with("0 connected, 2 logins, 0 failed | login min "),
+            "unexpected report: {report}"
+        );
+    }
+}
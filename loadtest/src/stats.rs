@@ -0,0 +1,222 @@
+//! Counters shared by the bots, and the reports made from them.
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+use core::sync::atomic::{
+    AtomicU64,
+    AtomicUsize,
+    Ordering,
+};
+use core::time::Duration;
+use std::sync::{
+    Mutex,
+    MutexGuard,
+    PoisonError,
+};
+
+#[derive(Default)]
+pub struct Stats {
+    connected: AtomicUsize,
+    logins: AtomicU64,
+    failed_logins: AtomicU64,
+    packets_received: AtomicU64,
+    packets_sent: AtomicU64,
+    /// Time from connecting to spawning, of every login.
+    latencies: Mutex<Vec<Duration>>,
+    /// Number of failed logins and lost connections, by reason.
+    disconnects: Mutex<BTreeMap<String, u64>>,
+}
+
+impl Stats {
+    pub fn logged_in(
+        &self,
+        latency: Duration,
+    ) {
+        self.connected.fetch_add(1, Ordering::Relaxed);
+        self.logins.fetch_add(1, Ordering::Relaxed);
+        lock(&self.latencies).push(latency);
+    }
+
+    pub fn login_failed(
+        &self,
+        reason: String,
+    ) {
+        self.failed_logins.fetch_add(1, Ordering::Relaxed);
+        *lock(&self.disconnects).entry(reason).or_default() += 1;
+    }
+
+    /// A logged in bot left, with the reason when it did not leave on its
+    /// own.
+    pub fn left(
+        &self,
+        reason: Option<String>,
+    ) {
+        self.connected.fetch_sub(1, Ordering::Relaxed);
+        if let Some(reason) = reason {
+            *lock(&self.disconnects).entry(reason).or_default() += 1;
+        }
+    }
+
+    pub fn packets(
+        &self,
+        received: u64,
+        sent: u64,
+    ) {
+        self.packets_received.fetch_add(received, Ordering::Relaxed);
+        self.packets_sent.fetch_add(sent, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            connected: self.connected.load(Ordering::Relaxed),
+            logins: self.logins.load(Ordering::Relaxed),
+            failed_logins: self.failed_logins.load(Ordering::Relaxed),
+            packets_received: self.packets_received.load(Ordering::Relaxed),
+            packets_sent: self.packets_sent.load(Ordering::Relaxed),
+            latency: Latency::of(&lock(&self.latencies)),
+            disconnects: lock(&self.disconnects).clone(),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The counters at one point in time.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub connected: usize,
+    pub logins: u64,
+    pub failed_logins: u64,
+    pub packets_received: u64,
+    pub packets_sent: u64,
+    pub latency: Option<Latency>,
+    pub disconnects: BTreeMap<String, u64>,
+}
+
+/// Distribution of the login latencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Latency {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Latency {
+    fn of(latencies: &[Duration]) -> Option<Self> {
+        let mut sorted = latencies.to_vec();
+        sorted.sort_unstable();
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let percentile = |percent: usize| sorted[(sorted.len() - 1) * percent / 100];
+        Some(Self {
+            min,
+            median: percentile(50),
+            p95: percentile(95),
+            max,
+        })
+    }
+}
+
+/// What happened between two snapshots.
+pub struct Report<'snapshot> {
+    pub previous: &'snapshot Snapshot,
+    pub current: &'snapshot Snapshot,
+    pub elapsed: Duration,
+}
+
+impl Report<'_> {
+    /// Packets per second over the report's interval.
+    fn rate(
+        &self,
+        previous: u64,
+        current: u64,
+    ) -> u64 {
+        let millis = u64::try_from(self.elapsed.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1);
+        current.saturating_sub(previous) * 1000 / millis
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let current = self.current;
+        write!(
+            f,
+            "{} connected, {} logins, {} failed",
+            current.connected, current.logins, current.failed_logins
+        )?;
+        if let Some(latency) = current.latency {
+            write!(
+                f,
+                " | login min {}ms, median {}ms, p95 {}ms, max {}ms",
+                latency.min.as_millis(),
+                latency.median.as_millis(),
+                latency.p95.as_millis(),
+                latency.max.as_millis()
+            )?;
+        }
+        write!(
+            f,
+            " | {} packets/s received, {} packets/s sent",
+            self.rate(self.previous.packets_received, current.packets_received),
+            self.rate(self.previous.packets_sent, current.packets_sent)
+        )?;
+        for (reason, count) in &current.disconnects {
+            write!(f, "\n  {count} × {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_counters() {
+        let stats = Stats::default();
+        for millis in 1..=100 {
+            stats.logged_in(Duration::from_millis(millis));
+        }
+        stats.left(None);
+        stats.left(Some("Timed out".to_owned()));
+        stats.login_failed("The server is full".to_owned());
+        stats.login_failed("The server is full".to_owned());
+        stats.packets(500, 20);
+
+        let current = stats.snapshot();
+        assert_eq!(current.connected, 98, "leaving bots should be subtracted");
+        assert_eq!(
+            current.latency,
+            Some(Latency {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(50),
+                p95: Duration::from_millis(95),
+                max: Duration::from_millis(100),
+            })
+        );
+        assert_eq!(current.disconnects["The server is full"], 2);
+        assert!(
+            !current.disconnects.contains_key(""),
+            "leaving on purpose should not count as a disconnect"
+        );
+
+        let report = Report {
+            previous: &Snapshot::default(),
+            current: &current,
+            elapsed: Duration::from_secs(5),
+        };
+        assert_eq!(
+            report.to_string(),
+            "98 connected, 100 logins, 2 failed | login min 1ms, median 50ms, p95 95ms, max 100ms \
+             | 100 packets/s received, 4 packets/s sent\n  2 × The server is full\n  1 × Timed out"
+        );
+    }
+}
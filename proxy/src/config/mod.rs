@@ -0,0 +1,636 @@
+//! Configuration of the proxy, read from a TOML file into [`Config`] then
+//! validated.
+//!
+//! The file is reloaded on SIGHUP or when it changes: new connections use
+//! the new configuration while open ones keep theirs. Listen addresses, the
+//! JSON log and the metrics address only change on restart.
+
+mod error;
+mod reload;
+
+use alloc::collections::BTreeMap;
+use core::net::SocketAddr;
+use core::time::Duration;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use data::version::ProtocolVersion;
+pub use error::ConfigError;
+use log::LevelFilter;
+pub use reload::{
+    SharedConfig,
+    watch,
+};
+use serde::de::Error as _;
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Addresses to accept clients on.
+    #[serde(default)]
+    pub listen: Vec<SocketAddr>,
+    #[serde(default)]
+    pub backends: Vec<Backend>,
+    #[serde(default)]
+    pub routing: Routing,
+    /// Replaces the description of the servers' status responses.
+    pub motd: Option<String>,
+    /// Shown to every player as a boss bar.
+    pub boss_bar: Option<String>,
+    /// Pushed to every client, whichever backend it plays on.
+    pub resource_pack: Option<ResourcePack>,
+    #[serde(default)]
+    pub tab_list: TabList,
+    #[serde(default, deserialize_with = "forwarding")]
+    pub forwarding: Forwarding,
+    #[serde(default)]
+    pub transfers: Transfers,
+    #[serde(default)]
+    pub limits: Limits,
+    #[serde(default)]
+    pub logging: Logging,
+    /// WebAssembly plugins, run on every connection in order.
+    #[serde(default)]
+    pub plugins: Vec<Plugin>,
+    /// Rhai scripts, run after the plugins in order.
+    #[serde(default)]
+    pub scripts: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Backend {
+    pub name: String,
+    /// `host:port` of the server, resolved on each connection.
+    pub address: String,
+    /// Protocol version of the server. When set, clients of other versions
+    /// are refused at login.
+    #[serde(default, deserialize_with = "protocol")]
+    pub protocol: Option<ProtocolVersion>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Routing {
+    /// Backend of the hosts not listed, by name. The first backend when
+    /// empty in the file.
+    pub default: String,
+    /// Backend by the lowercase host clients connect with.
+    pub hosts: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TabList {
+    /// Whether clients also list the players of the other backends.
+    pub enabled: bool,
+}
+
+impl Default for TabList {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+        }
+    }
+}
+
+/// A WebAssembly module handling packets, see `plugin::wasm` for its ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Plugin {
+    pub path: PathBuf,
+    /// Fuel each call into the plugin may burn, about one per instruction.
+    #[serde(default = "Plugin::default_fuel")]
+    pub fuel: u64,
+    /// Largest linear memory of the plugin, in bytes.
+    #[serde(default = "Plugin::default_memory")]
+    pub memory: usize,
+    /// Whether the plugin also receives the packets' decoded fields.
+    #[serde(default)]
+    pub decode: bool,
+}
+
+impl Plugin {
+    const fn default_fuel() -> u64 { 10_000_000 }
+
+    const fn default_memory() -> usize { 16 << 20 }
+}
+
+/// A resource pack pushed by the proxy, on top of those of the backends.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourcePack {
+    pub url: String,
+    /// SHA-1 of the pack as lowercase hexadecimal digits, or empty for the
+    /// client to download it every time.
+    #[serde(default)]
+    pub hash: String,
+    /// Whether clients declining the pack disconnect themselves.
+    #[serde(default)]
+    pub forced: bool,
+    /// Shown when asking clients to download the pack.
+    pub prompt: Option<String>,
+    /// State of the client the pack is pushed in.
+    #[serde(default)]
+    pub stage: PackStage,
+    /// What happens when a client declines the pack or fails to load it.
+    #[serde(default)]
+    pub on_failure: PackPolicy,
+    /// Pushes after the first one before `retry` kicks the client.
+    #[serde(default = "ResourcePack::default_retries")]
+    pub retries: usize,
+}
+
+impl ResourcePack {
+    const fn default_retries() -> usize { 1 }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackStage {
+    /// While the client is first configured, before it sees the world.
+    #[default]
+    Configuration,
+    /// Once the client plays, which shows the world while the pack loads.
+    Play,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackPolicy {
+    /// Lets the client play without the pack.
+    #[default]
+    Ignore,
+    Kick,
+    /// Pushes the pack again, then kicks the client.
+    Retry,
+}
+
+/// How the client's address and identity are passed to the backend, the
+/// `mode` of the `forwarding` table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Forwarding {
+    #[default]
+    None,
+    /// As `BungeeCord` does: the client address and uuid are appended to the
+    /// server address of the handshake, separated by NUL characters.
+    Legacy,
+}
+
+/// Clients coming with the transfer intent, and those the proxy transfers.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Transfers {
+    pub accept: TransferPolicy,
+    /// Shared by the proxies transferring players to each other, signs the
+    /// cookie telling where the players come from.
+    pub secret: Option<String>,
+    /// Name of this proxy in the cookies it signs.
+    pub name: String,
+    /// How long a signed cookie is valid after the transfer, in seconds in
+    /// the file.
+    #[serde(deserialize_with = "seconds")]
+    pub max_age: Duration,
+    /// Host and port every player is transferred to, to drain this proxy.
+    #[serde(deserialize_with = "host_port")]
+    pub drain: Option<(String, u16)>,
+}
+
+impl Default for Transfers {
+    fn default() -> Self {
+        Self {
+            accept: TransferPolicy::Any,
+            secret: None,
+            name: "proxy".to_owned(),
+            max_age: Duration::from_secs(30),
+            drain: None,
+        }
+    }
+}
+
+/// Which clients coming with the transfer intent may log in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferPolicy {
+    #[default]
+    Any,
+    /// Those with a cookie signed with the `secret` of the proxies.
+    Signed,
+    Refuse,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Connections open at once, from every client.
+    pub max_connections: Option<usize>,
+    /// Connections open at once from a single IP address.
+    pub max_connections_per_ip: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Logging {
+    /// Most verbose level logged, unless `RUST_LOG` is set.
+    #[serde(deserialize_with = "level")]
+    pub level: LevelFilter,
+    /// Writes every relayed packet and connection event as a line of JSON
+    /// to this file, or to stdout when `-`.
+    pub json: Option<PathBuf>,
+    /// Serves Prometheus metrics at `http://<metrics>/metrics`.
+    pub metrics: Option<SocketAddr>,
+}
+
+impl Default for Logging {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Error,
+            json: None,
+            metrics: None,
+        }
+    }
+}
+
+/// The `forwarding` table, which only has a `mode`.
+fn forwarding<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Forwarding, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Table {
+        #[serde(default)]
+        mode: Forwarding,
+    }
+    Table::deserialize(deserializer).map(|table| table.mode)
+}
+
+fn protocol<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<Option<ProtocolVersion>, D::Error> {
+    let protocol = i32::deserialize(deserializer)?;
+    ProtocolVersion::from_protocol(protocol)
+        .map(Some)
+        .ok_or_else(|| D::Error::custom(format!("`{protocol}` is not a known version")))
+}
+
+fn seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_secs)
+}
+
+fn host_port<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<Option<(String, u16)>, D::Error> {
+    let address = String::deserialize(deserializer)?;
+    split_host_port(&address)
+        .map(|(host, port)| Some((host.to_owned(), port)))
+        .ok_or_else(|| D::Error::custom(format!("`{address}` is not `host:port`")))
+}
+
+fn level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LevelFilter, D::Error> {
+    let level = String::deserialize(deserializer)?;
+    level
+        .parse()
+        .map_err(|_| D::Error::custom(format!("`{level}` is not a log level")))
+}
+
+/// The host and port of `host:port`.
+fn split_host_port(address: &str) -> Option<(&str, u16)> {
+    let (host, port) = address.rsplit_once(':')?;
+    Some((host, port.parse().ok()?)).filter(|(host, _)| !host.is_empty())
+}
+
+impl Config {
+    /// Relays every client accepted on `listen` to `backend`.
+    #[must_use]
+    pub fn new(
+        listen: SocketAddr,
+        backend: Backend,
+    ) -> Self {
+        Self {
+            listen: vec![listen],
+            routing: Routing {
+                default: backend.name.clone(),
+                hosts: BTreeMap::new(),
+            },
+            backends: vec![backend],
+            motd: None,
+            boss_bar: None,
+            resource_pack: None,
+            tab_list: TabList::default(),
+            forwarding: Forwarding::None,
+            transfers: Transfers::default(),
+            limits: Limits::default(),
+            logging: Logging::default(),
+            plugins: Vec::new(),
+            scripts: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let mut config: Self = toml::from_str(text)?;
+        if config.routing.default.is_empty()
+            && let Some(backend) = config.backends.first()
+        {
+            config.routing.default.clone_from(&backend.name);
+        }
+        let mut hosts = BTreeMap::new();
+        for (host, backend) in core::mem::take(&mut config.routing.hosts) {
+            let host = host.trim_end_matches('.').to_ascii_lowercase();
+            if hosts.insert(host.clone(), backend).is_some() {
+                return Err(ConfigError::Invalid(format!(
+                    "`routing.hosts` routes `{host}` twice"
+                )));
+            }
+        }
+        config.routing.hosts = hosts;
+        if let Some(pack) = &mut config.resource_pack {
+            pack.hash.make_ascii_lowercase();
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the values the types of the fields allow but the proxy does
+    /// not, and the settings that depend on each other.
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.listen.is_empty() {
+            return Err(ConfigError::Invalid(
+                "`listen` needs at least one address".to_owned(),
+            ));
+        }
+        if self.backends.is_empty() {
+            return Err(ConfigError::Invalid(
+                "`backends` needs at least one backend".to_owned(),
+            ));
+        }
+        let mut names = HashSet::new();
+        for (index, backend) in self.backends.iter().enumerate() {
+            if split_host_port(&backend.address).is_none() {
+                return Err(invalid(
+                    &format!("backends[{index}].address"),
+                    "is not `host:port`",
+                    &backend.address,
+                ));
+            }
+            if !names.insert(backend.name.as_str()) {
+                return Err(ConfigError::Invalid(format!(
+                    "backend `{}` is defined twice",
+                    backend.name
+                )));
+            }
+        }
+        let routes = core::iter::once(("routing.default", &self.routing.default)).chain(
+            self.routing
+                .hosts
+                .values()
+                .map(|name| ("routing.hosts", name)),
+        );
+        for (key, name) in routes {
+            if !names.contains(name.as_str()) {
+                return Err(ConfigError::Invalid(format!(
+                    "`{key}` refers to unknown backend `{name}`"
+                )));
+            }
+        }
+        if self.transfers.accept == TransferPolicy::Signed && self.transfers.secret.is_none() {
+            return Err(ConfigError::Invalid(
+                "`transfers.accept = \"signed\"` needs a `transfers.secret`".to_owned(),
+            ));
+        }
+        if let Some(pack) = &self.resource_pack {
+            let hash = &pack.hash;
+            if !hash.is_empty()
+                && (hash.len() != 40 || !hash.bytes().all(|b| b.is_ascii_hexdigit()))
+            {
+                return Err(invalid(
+                    "resource_pack.hash",
+                    "is not a SHA-1 in hexadecimal",
+                    hash,
+                ));
+            }
+        }
+        self.validate_counts()
+    }
+
+    /// Checks the settings counting something are positive.
+    fn validate_counts(&self) -> Result<(), ConfigError> {
+        positive("limits.max_connections", self.limits.max_connections)?;
+        positive(
+            "limits.max_connections_per_ip",
+            self.limits.max_connections_per_ip,
+        )?;
+        positive("transfers.max_age", Some(self.transfers.max_age))?;
+        positive(
+            "resource_pack.retries",
+            self.resource_pack.as_ref().map(|pack| pack.retries),
+        )?;
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            positive(&format!("plugins[{index}].fuel"), Some(plugin.fuel))?;
+            positive(&format!("plugins[{index}].memory"), Some(plugin.memory))?;
+        }
+        Ok(())
+    }
+
+    /// Backend of a client that connected with `host`, the server address
+    /// of its handshake.
+    #[must_use]
+    pub fn route(
+        &self,
+        host: &str,
+    ) -> Option<&Backend> {
+        // Forge appends its marker after a NUL, and FQDNs end with a dot
+        let host = host
+            .split('\0')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.')
+            .to_ascii_lowercase();
+        let name = self
+            .routing
+            .hosts
+            .get(&host)
+            .unwrap_or(&self.routing.default);
+        self.backends.iter().find(|backend| &backend.name == name)
+    }
+
+    /// Settings changed in `other` which only apply on restart.
+    #[must_use]
+    pub fn restart_required(
+        &self,
+        other: &Self,
+    ) -> Vec<&'static str> {
+        let mut settings = Vec::new();
+        if self.listen != other.listen {
+            settings.push("listen");
+        }
+        if self.logging.json != other.logging.json {
+            settings.push("logging.json");
+        }
+        if self.logging.metrics != other.logging.metrics {
+            settings.push("logging.metrics");
+        }
+        if self.plugins != other.plugins {
+            settings.push("plugins");
+        }
+        settings
+    }
+}
+
+/// Fails if `count` is zero.
+fn positive<T: Copy + Default + PartialEq>(
+    key: &str,
+    count: Option<T>,
+) -> Result<(), ConfigError> {
+    if count == Some(T::default()) {
+        return Err(invalid(key, "should be positive", 0));
+    }
+    Ok(())
+}
+
+fn invalid(
+    key: &str,
+    problem: &str,
+    value: impl core::fmt::Display,
+) -> ConfigError {
+    ConfigError::Invalid(format!("`{key}` {problem}: `{value}`"))
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_example() {
+        let config = Config::parse(include_str!("../../proxy.example.toml")).unwrap();
+
+        assert_eq!(config.listen, [
+            "0.0.0.0:25565".parse().unwrap(),
+            "[::]:25565".parse().unwrap()
+        ]);
+        assert_eq!(config.backends.len(), 2);
+        assert_eq!(
+            config.backends[1].protocol,
+            ProtocolVersion::from_protocol(772)
+        );
+        assert_eq!(config.forwarding, Forwarding::Legacy);
+        assert_eq!(config.limits.max_connections_per_ip, Some(5));
+        assert_eq!(config.logging.level, LevelFilter::Info);
+        assert_eq!(config.boss_bar.as_deref(), Some("Welcome to the network"));
+        assert!(config.tab_list.enabled, "the tab list is on by default");
+        assert_eq!(config.transfers.accept, TransferPolicy::Signed);
+        assert_eq!(config.transfers.drain, None);
+        let pack = config.resource_pack.as_ref().unwrap();
+        assert_eq!(pack.hash, "0123456789abcdef0123456789abcdef01234567");
+        assert_eq!(pack.on_failure, PackPolicy::Retry);
+        assert_eq!(pack.retries, 2);
+        assert_eq!(config.scripts, [PathBuf::from("scripts/example.rhai")]);
+
+        assert_eq!(config.route("lobby.example.com").unwrap().name, "lobby");
+        assert_eq!(
+            config.route("Survival.Example.com.\0FML3\0").unwrap().name,
+            "survival",
+            "hosts should match regardless of case, trailing dot and Forge marker"
+        );
+        assert_eq!(config.route("127.0.0.1").unwrap().name, "lobby");
+    }
+
+    #[test]
+    fn reject_invalid_configurations() {
+        for (text, message) in [
+            ("lsten = []", "unknown field `lsten`"),
+            ("listen = [\"localhost\"]", "invalid socket address syntax"),
+            (
+                "[[backends]]\naddress = \"localhost:1\"",
+                "missing field `name`",
+            ),
+            (
+                "[[backends]]\nname = \"a\"\naddress = \"a:1\"\nprotocol = 1",
+                "`1` is not a known version",
+            ),
+            ("[logging]\nlevel = 3", "invalid type: integer `3`"),
+            ("[logging]\nlevel = \"loud\"", "`loud` is not a log level"),
+            (
+                "[forwarding]\nmode = \"velocity\"",
+                "unknown variant `velocity`",
+            ),
+            (
+                "[transfers]\ndrain = \"proxy-2\"",
+                "`proxy-2` is not `host:port`",
+            ),
+            (
+                "[resource_pack]\nurl = \"a\"\non_failure = \"ban\"",
+                "unknown variant `ban`, expected one of `ignore`, `kick`, `retry`",
+            ),
+            ("listen = [", "unclosed array"),
+        ] {
+            let err = Config::parse(text).unwrap_err();
+            assert!(matches!(err, ConfigError::Parse(_)), "{text:?}: {err}");
+            assert!(err.to_string().contains(message), "{text:?}: {err}");
+        }
+
+        let listen = "listen = [\"0.0.0.0:1\"]\n";
+        let backend = "[[backends]]\nname = \"lobby\"\naddress = \"localhost:25566\"\n";
+        for (text, message) in [
+            (String::new(), "`listen` needs at least one address"),
+            (listen.to_owned(), "`backends` needs at least one backend"),
+            (
+                format!("{listen}[[backends]]\nname = \"a\"\naddress = \"localhost\""),
+                "`backends[0].address` is not `host:port`: `localhost`",
+            ),
+            (
+                format!("{listen}{backend}{backend}"),
+                "backend `lobby` is defined twice",
+            ),
+            (
+                format!("{listen}[routing]\ndefault = \"hub\"\n{backend}"),
+                "`routing.default` refers to unknown backend `hub`",
+            ),
+            (
+                format!(
+                    "{listen}[routing.hosts]\n\"a.com\" = \"lobby\"\n\"A.com\" = \
+                     \"lobby\"\n{backend}"
+                ),
+                "`routing.hosts` routes `a.com` twice",
+            ),
+            (
+                format!("{listen}{backend}[limits]\nmax_connections = 0"),
+                "`limits.max_connections` should be positive: `0`",
+            ),
+            (
+                format!("{listen}{backend}[transfers]\nmax_age = 0"),
+                "`transfers.max_age` should be positive: `0`",
+            ),
+            (
+                format!("{listen}{backend}[transfers]\naccept = \"signed\""),
+                "`transfers.accept = \"signed\"` needs a `transfers.secret`",
+            ),
+            (
+                format!("{listen}{backend}[resource_pack]\nurl = \"a\"\nhash = \"abc\""),
+                "`resource_pack.hash` is not a SHA-1 in hexadecimal: `abc`",
+            ),
+            (
+                format!("{listen}{backend}[[plugins]]\npath = \"a.wasm\"\nfuel = 0"),
+                "`plugins[0].fuel` should be positive: `0`",
+            ),
+        ] {
+            let err = Config::parse(&text).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid configuration: {message}"),
+                "{text:?}"
+            );
+        }
+    }
+}
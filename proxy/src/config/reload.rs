@@ -0,0 +1,160 @@
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::path::PathBuf;
+use std::sync::{
+    PoisonError,
+    RwLock,
+};
+use std::time::SystemTime;
+use std::{
+    fs,
+    thread,
+};
+
+use log::{
+    error,
+    info,
+    warn,
+};
+
+use super::Config;
+
+/// Time between two checks of the file's modification time.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The configuration new connections use, replaced on reload.
+#[derive(Debug)]
+pub struct SharedConfig(RwLock<Arc<Config>>);
+
+impl SharedConfig {
+    #[must_use]
+    pub fn new(config: Config) -> Self { Self(RwLock::new(Arc::new(config))) }
+
+    #[must_use]
+    pub fn get(&self) -> Arc<Config> {
+        Arc::clone(&self.0.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn set(
+        &self,
+        config: Config,
+    ) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+    }
+}
+
+/// Reloads `path` into `config` when the file changes and, on Unix, on
+/// SIGHUP, calling `applied` with each new configuration. Invalid files are
+/// logged and the previous configuration kept.
+pub fn watch(
+    path: PathBuf,
+    config: Arc<SharedConfig>,
+    applied: fn(&Config),
+) {
+    let watcher = Arc::new(Watcher {
+        path,
+        config,
+        applied,
+    });
+    #[cfg(unix)]
+    thread::spawn({
+        let watcher = Arc::clone(&watcher);
+        move || watcher.on_hangup()
+    });
+    thread::spawn(move || watcher.poll());
+}
+
+#[derive(Debug)]
+struct Watcher {
+    path: PathBuf,
+    config: Arc<SharedConfig>,
+    applied: fn(&Config),
+}
+
+impl Watcher {
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn poll(&self) {
+        let mut modified = self.modified();
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let now = self.modified();
+            if now != modified {
+                modified = now;
+                if now.is_some() {
+                    self.reload();
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn on_hangup(&self) {
+        let mut signals = match signal_hook::iterator::Signals::new([signal_hook::consts::SIGHUP]) {
+            Ok(signals) => signals,
+            Err(err) => {
+                error!("Failed to listen for SIGHUP: {err}");
+                return;
+            }
+        };
+        for _ in signals.forever() {
+            self.reload();
+        }
+    }
+
+    fn reload(&self) {
+        let config = match Config::load(&self.path) {
+            Ok(config) => config,
+            Err(err) => {
+                error!(
+                    "Failed to reload {}, keeping the previous configuration: {err}",
+                    self.path.display()
+                );
+                return;
+            }
+        };
+        for setting in self.config.get().restart_required(&config) {
+            warn!("Changes to `{setting}` only apply on restart");
+        }
+        (self.applied)(&config);
+        self.config.set(config);
+        info!("Reloaded the configuration from {}", self.path.display());
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reload_valid_files_only() {
+        let path = std::env::temp_dir().join(format!("proxy-config-{}.toml", std::process::id()));
+        let text = include_str!("../../proxy.example.toml");
+        fs::write(&path, text).unwrap();
+        let config = Arc::new(SharedConfig::new(Config::load(&path).unwrap()));
+        let watcher = Watcher {
+            path: path.clone(),
+            config: Arc::clone(&config),
+            applied: |_| {},
+        };
+
+        fs::write(&path, text.replace("A proxied server", "Reloaded")).unwrap();
+        watcher.reload();
+        assert_eq!(config.get().motd.as_deref(), Some("Reloaded"));
+
+        fs::write(&path, "listen = [").unwrap();
+        watcher.reload();
+        assert_eq!(
+            config.get().motd.as_deref(),
+            Some("Reloaded"),
+            "an invalid file should keep the previous configuration"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
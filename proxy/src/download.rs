@@ -0,0 +1,311 @@
+//! World download: saves the chunks a player receives into an Anvil world,
+//! from the play packets going through the relay. Biomes are named and
+//! dimensions sized from the registries the backend sent.
+//!
+//! Like the world view, only packets in the layout of
+//! [`ProtocolVersion::LATEST`](data::version::ProtocolVersion::LATEST) are
+//! understood.
+
+use std::collections::hash_map::Entry;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+};
+use data::anvil::{
+    AnvilError,
+    Compression,
+    DiskChunk,
+    LevelData,
+    Region,
+    RegionChunk,
+};
+use data::identifier::Identifier;
+use data::model::play;
+use data::world::{
+    BlockPos,
+    ChunkPos,
+};
+use log::info;
+
+use crate::error::Error;
+use crate::world::Registries;
+
+#[derive(Debug)]
+pub struct WorldDownload {
+    dir: PathBuf,
+    level: LevelData,
+    /// Whether the spawn was set from the first player position.
+    spawn_set: bool,
+    registries: Registries,
+    dimension: Identifier,
+    /// Section y of the lowest section, once the dimension is known.
+    min_section: Option<i32>,
+    /// Chunks loaded on the client, saved once unloaded.
+    chunks: HashMap<ChunkPos, DiskChunk>,
+    /// Regions with unloaded chunks, by region directory and position,
+    /// written and dropped once none of their chunks is loaded.
+    regions: HashMap<(PathBuf, i32, i32), Region>,
+}
+
+/// Whether `name` is a valid player name, which downloads are named after.
+#[must_use]
+pub fn is_player_name(name: &str) -> bool {
+    (1..=16).contains(&name.len())
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+}
+
+impl WorldDownload {
+    /// A download into the world directory `dir`, named `name`.
+    pub fn new(
+        dir: PathBuf,
+        name: &str,
+    ) -> Self {
+        Self {
+            dir,
+            level: LevelData {
+                name: name.to_owned(),
+                game_mode: 0,
+                hardcore: false,
+                spawn: BlockPos::new(0, 64, 0),
+                seed: 0,
+                last_played: 0,
+            },
+            spawn_set: false,
+            registries: Registries::default(),
+            dimension: Identifier::minecraft("overworld").expect("overworld should be valid"),
+            min_section: None,
+            chunks: HashMap::new(),
+            regions: HashMap::new(),
+        }
+    }
+
+    /// Updates the download from a clientbound play packet, or
+    /// `registry_data`, of the latest protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if a tracked packet cannot be decoded, or a chunk
+    /// cannot be converted or its region read.
+    pub fn observe(
+        &mut self,
+        name: &str,
+        mut data: &[u8],
+    ) -> Result<(), Error> {
+        let data = &mut data;
+        match name {
+            "registry_data" => self.registries.observe(data)?,
+            "login" => {
+                let packet = play::Login::decode(data)?;
+                self.level.hardcore = packet.hardcore;
+                self.spawn(&packet.spawn)?;
+            }
+            "respawn" => {
+                let packet = play::Respawn::decode(data)?;
+                self.spawn(&packet.spawn)?;
+            }
+            "start_configuration" => self.unload_all()?,
+            "level_chunk_with_light" => {
+                let packet = play::LevelChunkWithLight::decode(data)?;
+                let pos = ChunkPos {
+                    x: packet.x,
+                    z: packet.z,
+                };
+                let min_section = self.min_section.ok_or_else(|| DecodeError::Custom {
+                    message: "chunk before the dimension is known".to_owned(),
+                })?;
+                let chunk =
+                    DiskChunk::from_network(pos, min_section, &packet.chunk, &packet.light)?;
+                self.chunks.insert(pos, chunk);
+            }
+            "forget_level_chunk" => {
+                let packet = play::ForgetLevelChunk::decode(data)?;
+                if let Some(chunk) = self.chunks.remove(&ChunkPos {
+                    x: packet.x,
+                    z: packet.z,
+                }) {
+                    self.store(&chunk)?;
+                    self.write_unloaded()?;
+                }
+            }
+            "block_update" => {
+                let packet = play::BlockUpdate::decode(data)?;
+                if let Some(chunk) = self.chunks.get_mut(&packet.position.chunk()) {
+                    chunk.set_block_state(packet.position, packet.block_state);
+                }
+            }
+            "section_blocks_update" => {
+                let packet = play::SectionBlocksUpdate::decode(data)?;
+                for (position, block_state) in packet.blocks() {
+                    if let Some(chunk) = self.chunks.get_mut(&position.chunk()) {
+                        chunk.set_block_state(position, block_state);
+                    }
+                }
+            }
+            "block_entity_data" => {
+                let packet = play::BlockEntityData::decode(data)?;
+                if let Some(chunk) = self.chunks.get_mut(&packet.position.chunk()) {
+                    chunk.set_block_entity(packet.position, packet.kind, packet.data.as_ref())?;
+                }
+            }
+            "player_position" if !self.spawn_set => {
+                let packet = play::PlayerPosition::decode(data)?;
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    reason = "positions are within the world border"
+                )]
+                let spawn = BlockPos::new(
+                    packet.x.floor() as i32,
+                    packet.y.floor() as i32,
+                    packet.z.floor() as i32,
+                );
+                self.level.spawn = spawn;
+                self.spawn_set = true;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Writes every chunk received so far, then `level.dat`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if a chunk cannot be converted or a file cannot be
+    /// written.
+    pub fn save(&mut self) -> Result<(), Error> {
+        self.unload_all()?;
+        fs::create_dir_all(&self.dir).map_err(AnvilError::from)?;
+        self.level.last_played = now().saturating_mul(1000);
+        self.level.write(&self.dir)?;
+        info!("Saved world download to {}", self.dir.display());
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        spawn: &play::SpawnInfo,
+    ) -> Result<(), Error> {
+        self.unload_all()?;
+        self.level.game_mode = spawn.game_mode;
+        self.level.seed = spawn.hashed_seed;
+        self.dimension = spawn.dimension.clone();
+        self.min_section = None;
+        self.min_section = Some(
+            self.registries
+                .dimension_type(spawn.dimension_type)?
+                .min_section(),
+        );
+        Ok(())
+    }
+
+    fn unload_all(&mut self) -> Result<(), Error> {
+        let chunks: Vec<_> = self.chunks.drain().map(|(_, chunk)| chunk).collect();
+        for chunk in &chunks {
+            self.store(chunk)?;
+        }
+        self.write_unloaded()
+    }
+
+    /// Writes the regions none of whose chunks is loaded, and forgets them.
+    fn write_unloaded(&mut self) -> Result<(), Error> {
+        let dir = self.dir.join(region_dir(&self.dimension));
+        let loaded: HashSet<_> = self.chunks.keys().map(|pos| pos.region()).collect();
+        let unloaded: Vec<_> = self
+            .regions
+            .keys()
+            .filter(|(region_dir, x, z)| *region_dir != dir || !loaded.contains(&(*x, *z)))
+            .cloned()
+            .collect();
+        for key in unloaded {
+            if let Some(region) = self.regions.remove(&key) {
+                fs::create_dir_all(&key.0).map_err(AnvilError::from)?;
+                region.write(&key.0)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Puts a chunk into its region, read from disk first so chunks saved by
+    /// earlier sessions are kept.
+    fn store(
+        &mut self,
+        chunk: &DiskChunk,
+    ) -> Result<(), Error> {
+        let dir = self.dir.join(region_dir(&self.dimension));
+        let (x, z) = chunk.pos.region();
+        let region = match self.regions.entry((dir, x, z)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let region = Region::read(&entry.key().0, x, z)?;
+                entry.insert(region)
+            }
+        };
+        region.insert(chunk.pos, RegionChunk {
+            timestamp: u32::try_from(now()).unwrap_or(u32::MAX),
+            compression: Compression::Zlib,
+            nbt: chunk.to_nbt_with_biomes(|id| {
+                self.registries
+                    .biome(id)
+                    .map(|biome| biome.as_str().to_owned())
+            })?,
+        });
+        Ok(())
+    }
+}
+
+/// Region directory of a dimension, relative to the world directory.
+fn region_dir(dimension: &Identifier) -> PathBuf {
+    match dimension.as_str() {
+        "minecraft:overworld" => PathBuf::from("region"),
+        "minecraft:the_nether" => PathBuf::from("DIM-1/region"),
+        "minecraft:the_end" => PathBuf::from("DIM1/region"),
+        _ => PathBuf::from("dimensions")
+            .join(dimension.namespace())
+            .join(dimension.path())
+            .join("region"),
+    }
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_names() {
+        for name in ["Steve", "a", "Notch_1234567890"] {
+            assert!(is_player_name(name), "{name} is valid");
+        }
+        for name in [
+            "",
+            "../../etc",
+            "/etc",
+            "Steve ",
+            "seventeen_chars_x",
+            "Stéve",
+        ] {
+            assert!(!is_player_name(name), "{name} is not a player name");
+        }
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::mpsc;
 
 use codec::dec::DecodeError;
 use codec::enc::EncodeError;
+use data::anvil::AnvilError;
 
 #[derive(Debug)]
 pub enum Error {
@@ -14,7 +15,34 @@ pub enum Error {
     TcpStreamClone(io::Error),
     Decode(DecodeError),
     Encode(EncodeError),
-    UnknownPacketId(i32),
+    Anvil(AnvilError),
+    UnsupportedProtocolVersion(i32),
+    BackendConnect(io::Error),
+    /// No backend is configured for the host the client connected with.
+    NoBackend(String),
+    /// A packet handler ended the connection for this reason.
+    Kicked(String),
+    /// A play packet was sent to a client out of play.
+    NotPlaying,
+}
+
+impl Error {
+    /// Name of the variant, to group errors by.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::MpscRecv(_) => "mpsc_recv",
+            Self::TcpStreamClone(_) => "tcp_stream_clone",
+            Self::Decode(_) => "decode",
+            Self::Encode(_) => "encode",
+            Self::Anvil(_) => "anvil",
+            Self::UnsupportedProtocolVersion(_) => "unsupported_protocol_version",
+            Self::BackendConnect(_) => "backend_connect",
+            Self::NoBackend(_) => "no_backend",
+            Self::Kicked(_) => "kicked",
+            Self::NotPlaying => "not_playing",
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -27,7 +55,14 @@ impl fmt::Display for Error {
             Self::TcpStreamClone(err) => write!(f, "TCP stream clone error: {err}"),
             Self::Decode(err) => write!(f, "Decode error: {err}"),
             Self::Encode(err) => write!(f, "Encode error: {err}"),
-            Self::UnknownPacketId(id) => write!(f, "Unknown packet ID: {id}"),
+            Self::Anvil(err) => write!(f, "Anvil error: {err}"),
+            Self::UnsupportedProtocolVersion(version) => {
+                write!(f, "Unsupported protocol version: {version}")
+            }
+            Self::BackendConnect(err) => write!(f, "Backend connect error: {err}"),
+            Self::NoBackend(host) => write!(f, "No backend for host: {host}"),
+            Self::Kicked(reason) => write!(f, "Kicked: {reason}"),
+            Self::NotPlaying => write!(f, "The client is not in play"),
         }
     }
 }
@@ -45,3 +80,7 @@ impl From<DecodeError> for Error {
 impl From<EncodeError> for Error {
     fn from(err: EncodeError) -> Self { Self::Encode(err) }
 }
+
+impl From<AnvilError> for Error {
+    fn from(err: AnvilError) -> Self { Self::Anvil(err) }
+}
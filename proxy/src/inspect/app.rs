@@ -0,0 +1,324 @@
+use alloc::collections::VecDeque;
+use core::fmt::Write as _;
+
+use data::protocol::describe;
+use ratatui::crossterm::event::{
+    KeyCode,
+    KeyEvent,
+    KeyModifiers,
+};
+
+use super::{
+    Filter,
+    PacketRecord,
+};
+
+/// Packets kept, the oldest being dropped past it.
+const MAX_RECORDS: usize = 10_000;
+const PAGE: isize = 20;
+/// Bytes shown of packets without a model.
+const MAX_HEX: usize = 1024;
+
+/// State of the packet viewer, updated by keys and incoming packets.
+#[derive(Debug, Default)]
+pub struct App {
+    /// Packets shown, with their sequence number.
+    records: VecDeque<(u64, PacketRecord)>,
+    /// Packets received while paused.
+    pending: Vec<PacketRecord>,
+    next_sequence: u64,
+    filter: Filter,
+    /// Text of the filter, kept while editing it.
+    pub filter_text: String,
+    pub editing_filter: bool,
+    pub paused: bool,
+    /// Records the proxy dropped for the viewer falling behind.
+    pub dropped: u64,
+    /// Sequence number of the selected packet, the last one shown when
+    /// `None`.
+    selected: Option<u64>,
+    pub expanded: bool,
+}
+
+impl App {
+    pub fn push(
+        &mut self,
+        record: PacketRecord,
+    ) {
+        if self.paused {
+            self.pending.push(record);
+            return;
+        }
+        self.records.push_back((self.next_sequence, record));
+        self.next_sequence += 1;
+        if self.records.len() > MAX_RECORDS {
+            self.records.pop_front();
+        }
+    }
+
+    /// Packets received while paused.
+    pub const fn pending(&self) -> usize { self.pending.len() }
+
+    pub fn total(&self) -> usize { self.records.len() }
+
+    /// Packets matching the filter, oldest first.
+    pub fn visible(&self) -> Vec<&PacketRecord> {
+        self.visible_entries()
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    fn visible_entries(&self) -> Vec<(u64, &PacketRecord)> {
+        self.records
+            .iter()
+            .filter(|(_, record)| self.filter.matches(record))
+            .map(|(sequence, record)| (*sequence, record))
+            .collect()
+    }
+
+    /// Index of the selected packet among the visible ones.
+    pub fn selected_index(&self) -> Option<usize> {
+        let visible = self.visible_entries();
+        let last = visible.len().checked_sub(1);
+        match self.selected {
+            Some(selected) => visible
+                .iter()
+                .position(|(sequence, _)| *sequence >= selected)
+                .or(last),
+            None => last,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&PacketRecord> {
+        let index = self.selected_index()?;
+        self.visible().get(index).copied()
+    }
+
+    /// Whether new packets are followed, rather than a packet selected.
+    pub const fn following(&self) -> bool { self.selected.is_none() }
+
+    /// Applies a key press, returning `false` once the viewer should quit.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+    ) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        if self.editing_filter {
+            self.edit_filter(key.code);
+            return true;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('/') => self.editing_filter = true,
+            KeyCode::Char('p' | ' ') => self.toggle_pause(),
+            KeyCode::Char('c') => {
+                self.records.clear();
+                self.selected = None;
+            }
+            KeyCode::Enter => self.expanded = !self.expanded,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-PAGE),
+            KeyCode::PageDown => self.move_selection(PAGE),
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.selected = self
+                    .visible_entries()
+                    .first()
+                    .map(|(sequence, _)| *sequence);
+            }
+            KeyCode::End | KeyCode::Char('G') => self.selected = None,
+            _ => {}
+        }
+        true
+    }
+
+    fn edit_filter(
+        &mut self,
+        code: KeyCode,
+    ) {
+        match code {
+            KeyCode::Enter | KeyCode::Esc => self.editing_filter = false,
+            KeyCode::Backspace => {
+                self.filter_text.pop();
+            }
+            KeyCode::Char(char) => self.filter_text.push(char),
+            _ => return,
+        }
+        self.filter = Filter::parse(&self.filter_text);
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        if !self.paused {
+            for record in core::mem::take(&mut self.pending) {
+                self.push(record);
+            }
+        }
+    }
+
+    /// Moves the selection by `offset` visible packets. Reaching the last
+    /// one follows new packets again.
+    fn move_selection(
+        &mut self,
+        offset: isize,
+    ) {
+        let visible = self.visible_entries();
+        let Some(current) = self.selected_index() else {
+            return;
+        };
+        let target = current.saturating_add_signed(offset);
+        self.selected = visible
+            .get(target)
+            .filter(|_| target + 1 < visible.len())
+            .map(|(sequence, _)| *sequence);
+    }
+
+    /// Decoded fields of the selected packet, or a hex dump when it has no
+    /// model.
+    pub fn details(&self) -> Option<String> {
+        let record = self.selected()?;
+        let mut details = format!(
+            "{name} ({id:#04X}), {size} bytes, {version}\n\n",
+            name = record.name().unwrap_or("unknown"),
+            id = record.packet.id,
+            size = record.size(),
+            version = record.version,
+        );
+        match describe(
+            record.version,
+            record.state,
+            record.direction,
+            &record.packet,
+        ) {
+            Ok(Some(fields)) => {
+                let _ = write!(details, "{fields:#?}");
+            }
+            Ok(None) => details.push_str(&hex_dump(&record.packet.data)),
+            Err(err) => {
+                let _ = writeln!(details, "Failed to decode: {err}\n");
+                details.push_str(&hex_dump(&record.packet.data));
+            }
+        }
+        Some(details)
+    }
+}
+
+/// Rows of 16 bytes with their offset.
+fn hex_dump(data: &[u8]) -> String {
+    let mut dump = String::new();
+    for (row, bytes) in data[..data.len().min(MAX_HEX)].chunks(16).enumerate() {
+        let _ = write!(dump, "{:04x} ", row * 16);
+        for byte in bytes {
+            let _ = write!(dump, " {byte:02x}");
+        }
+        dump.push('\n');
+    }
+    if data.len() > MAX_HEX {
+        let _ = writeln!(dump, "… {} more bytes", data.len() - MAX_HEX);
+    }
+    dump
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use data::model::play;
+    use data::packet::Packet;
+    use data::protocol::{
+        Direction,
+        ProtocolPacket as _,
+        State,
+    };
+    use data::version::ProtocolVersion;
+
+    use super::*;
+
+    fn record(packet: Packet) -> PacketRecord {
+        PacketRecord {
+            connection: 1,
+            time: Duration::ZERO,
+            direction: Direction::Clientbound,
+            state: State::Play,
+            version: ProtocolVersion::LATEST,
+            compressed_size: packet.data.len() + 2,
+            packet,
+        }
+    }
+
+    fn press(
+        app: &mut App,
+        keys: &str,
+    ) {
+        for char in keys.chars() {
+            assert!(
+                app.handle_key(KeyCode::Char(char).into()),
+                "keys should not quit"
+            );
+        }
+    }
+
+    #[test]
+    fn pause_filter_and_select() {
+        let mut app = App::default();
+        let keep_alive = play::KeepAlive {
+            id: 7,
+        }
+        .to_packet()
+        .unwrap();
+        app.push(record(keep_alive.clone()));
+        app.push(record(Packet::new(0x7F, &[1, 2, 3])));
+        assert_eq!(app.selected_index(), Some(1), "the last packet is followed");
+
+        press(&mut app, "p");
+        app.push(record(keep_alive.clone()));
+        assert_eq!(app.visible().len(), 2, "packets wait while paused");
+        assert_eq!(app.pending(), 1);
+        press(&mut app, "p");
+        assert_eq!(
+            app.visible().len(),
+            3,
+            "pending packets are shown on resume"
+        );
+
+        press(&mut app, "/keep");
+        app.handle_key(KeyCode::Enter.into());
+        assert!(!app.editing_filter, "enter should end the filter");
+        assert_eq!(app.visible().len(), 2, "only keep-alives should match");
+
+        app.handle_key(KeyCode::Up.into());
+        assert!(!app.following(), "moving up should stop following");
+        assert_eq!(app.selected_index(), Some(0));
+        app.push(record(keep_alive));
+        assert_eq!(app.selected_index(), Some(0), "the selection should stay");
+
+        let details = app.details().unwrap();
+        assert!(
+            details.starts_with("keep_alive (0x26), 8 bytes"),
+            "{details}"
+        );
+        assert!(
+            details.contains("id: 7"),
+            "fields should be decoded: {details}"
+        );
+
+        app.handle_key(KeyCode::End.into());
+        assert!(app.following(), "end should follow again");
+        assert!(!app.handle_key(KeyCode::Char('q').into()), "q should quit");
+    }
+
+    #[test]
+    fn hex_dump_of_unknown_packets() {
+        let mut app = App::default();
+        app.push(record(Packet::new(0x7F, &[0xAB; 17])));
+        let details = app.details().unwrap();
+        assert!(
+            details.ends_with("0000  ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab\n0010  ab\n"),
+            "{details}"
+        );
+    }
+}
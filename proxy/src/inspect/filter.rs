@@ -0,0 +1,135 @@
+use data::protocol::{
+    Direction,
+    State,
+};
+
+use super::PacketRecord;
+
+/// Space-separated terms a packet must all match:
+///
+/// - `#3`: connection 3,
+/// - `c2s` or `s2c`: serverbound or clientbound,
+/// - `handshake`, `status`, `login`, `config` or `play`: the stage,
+/// - `0x27`: the packet id,
+/// - anything else: part of the packet name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Connection(u64),
+    Direction(Direction),
+    State(State),
+    Id(i32),
+    Name(String),
+}
+
+impl Filter {
+    #[must_use]
+    pub fn parse(filter: &str) -> Self {
+        Self {
+            terms: filter.split_whitespace().map(Term::parse).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn matches(
+        &self,
+        record: &PacketRecord,
+    ) -> bool {
+        self.terms.iter().all(|term| term.matches(record))
+    }
+}
+
+impl Term {
+    fn parse(term: &str) -> Self {
+        let lowercase = term.to_lowercase();
+        if let Some(connection) = term.strip_prefix('#')
+            && let Ok(connection) = connection.parse()
+        {
+            return Self::Connection(connection);
+        }
+        if let Some(id) = lowercase.strip_prefix("0x")
+            && let Ok(id) = i32::from_str_radix(id, 16)
+        {
+            return Self::Id(id);
+        }
+        match lowercase.as_str() {
+            "c2s" | "serverbound" => Self::Direction(Direction::Serverbound),
+            "s2c" | "clientbound" => Self::Direction(Direction::Clientbound),
+            "handshake" => Self::State(State::Handshake),
+            "status" => Self::State(State::Status),
+            "login" => Self::State(State::Login),
+            "config" | "configuration" => Self::State(State::Configuration),
+            "play" => Self::State(State::Play),
+            _ => Self::Name(lowercase),
+        }
+    }
+
+    fn matches(
+        &self,
+        record: &PacketRecord,
+    ) -> bool {
+        match self {
+            Self::Connection(connection) => record.connection == *connection,
+            Self::Direction(direction) => record.direction == *direction,
+            Self::State(state) => record.state == *state,
+            Self::Id(id) => record.packet.id == *id,
+            Self::Name(name) => record.name().is_some_and(|record| record.contains(name)),
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use data::packet::Packet;
+    use data::version::ProtocolVersion;
+
+    use super::*;
+
+    fn record(
+        connection: u64,
+        state: State,
+        direction: Direction,
+        id: i32,
+    ) -> PacketRecord {
+        PacketRecord {
+            connection,
+            time: Duration::ZERO,
+            direction,
+            state,
+            version: ProtocolVersion::LATEST,
+            packet: Packet::new(id, &[]),
+            compressed_size: 3,
+        }
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        let chunk = record(2, State::Play, Direction::Clientbound, 0x27);
+        let hello = record(1, State::Login, Direction::Serverbound, 0x00);
+
+        assert!(Filter::parse("").matches(&chunk), "no terms match all");
+        assert!(Filter::parse("chunk s2c").matches(&chunk));
+        assert!(Filter::parse("#2 PLAY 0x27").matches(&chunk));
+        assert!(!Filter::parse("chunk c2s").matches(&chunk));
+        assert!(!Filter::parse("#1").matches(&chunk));
+        assert!(Filter::parse("hello login").matches(&hello));
+        assert!(
+            !Filter::parse("hello").matches(&chunk),
+            "the name should be matched"
+        );
+        assert_eq!(
+            Filter::parse("0xzz"),
+            Filter {
+                terms: vec![Term::Name("0xzz".to_owned())],
+            },
+            "malformed ids are names"
+        );
+    }
+}
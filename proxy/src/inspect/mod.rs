@@ -0,0 +1,244 @@
+//! Packet inspection: every relayed packet and connection event is
+//! recorded and sent to sinks, a terminal UI listing them live with
+//! filtering, pausing and decoded fields, and an NDJSON log.
+//!
+//! The terminal UI comes with the `tui` feature.
+
+#[cfg(feature = "tui")]
+mod app;
+#[cfg(feature = "tui")]
+mod filter;
+mod ndjson;
+#[cfg(feature = "tui")]
+mod tui;
+
+use alloc::sync::Arc;
+use core::net::SocketAddr;
+use core::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use core::time::Duration;
+use std::sync::mpsc::{
+    self,
+    Receiver,
+    RecvError,
+    SyncSender,
+    TryRecvError,
+    TrySendError,
+};
+use std::time::Instant;
+
+#[cfg(feature = "tui")]
+pub use app::App;
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    State,
+};
+use data::version::ProtocolVersion;
+#[cfg(feature = "tui")]
+use filter::Filter;
+pub use ndjson::write;
+#[cfg(feature = "tui")]
+pub use tui::run;
+
+/// Something that happened on a connection.
+#[derive(Debug, Clone)]
+pub enum Record {
+    Connected {
+        connection: u64,
+        time: Duration,
+        client: SocketAddr,
+    },
+    Packet(PacketRecord),
+    /// The connection ended, with the error that ended it if any.
+    Disconnected {
+        connection: u64,
+        time: Duration,
+        error: Option<String>,
+    },
+}
+
+/// A packet seen by the proxy, as read from its sender.
+#[derive(Debug, Clone)]
+pub struct PacketRecord {
+    /// Number of the connection, in accept order.
+    pub connection: u64,
+    /// Time since the proxy started.
+    pub time: Duration,
+    pub direction: Direction,
+    pub state: State,
+    /// Version of the sender, whose ids and layout the packet has.
+    pub version: ProtocolVersion,
+    pub packet: Packet,
+    /// Bytes the packet took on the wire, compressed once the connection
+    /// is.
+    pub compressed_size: usize,
+}
+
+impl PacketRecord {
+    /// Vanilla name of the packet, if its id is known.
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        self.version
+            .packet_name(self.state, self.direction, self.packet.id)
+    }
+
+    /// Size of the uncompressed payload, in bytes.
+    #[must_use]
+    pub fn size(&self) -> usize { self.packet.data.len() }
+}
+
+/// Name of a direction, as logged.
+pub const fn direction(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Serverbound => "serverbound",
+        Direction::Clientbound => "clientbound",
+    }
+}
+
+/// Short name of a connection stage, as shown and filtered on.
+pub const fn stage(state: State) -> &'static str {
+    match state {
+        State::Handshake => "handshake",
+        State::Status => "status",
+        State::Login => "login",
+        State::Configuration => "config",
+        State::Play => "play",
+    }
+}
+
+/// Records a sink holds before it drops new ones, so that a slow sink
+/// cannot hold every relayed packet.
+const SINK_CAPACITY: usize = 16_384;
+
+/// The sending end of a sink, shared by every connection.
+#[derive(Debug, Clone)]
+pub struct Sink {
+    records: SyncSender<Record>,
+    dropped: Arc<AtomicU64>,
+}
+
+/// The receiving end of a sink.
+#[derive(Debug)]
+pub struct Records {
+    records: Receiver<Record>,
+    dropped: Arc<AtomicU64>,
+}
+
+/// A new sink, bounded to [`SINK_CAPACITY`] records.
+#[must_use]
+pub fn sink() -> (Sink, Records) {
+    let (sender, receiver) = mpsc::sync_channel(SINK_CAPACITY);
+    let dropped = Arc::new(AtomicU64::new(0));
+    (
+        Sink {
+            records: sender,
+            dropped: Arc::clone(&dropped),
+        },
+        Records {
+            records: receiver,
+            dropped,
+        },
+    )
+}
+
+impl Sink {
+    /// Sends `record`, or counts it as dropped when the sink is full.
+    fn send(
+        &self,
+        record: Record,
+    ) {
+        match self.records.try_send(record) {
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            // a sink stopping does not stop the relay
+            Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+        }
+    }
+}
+
+impl Records {
+    pub fn recv(&self) -> Result<Record, RecvError> { self.records.recv() }
+
+    pub fn try_recv(&self) -> Result<Record, TryRecvError> { self.records.try_recv() }
+
+    /// Records dropped since the last call, the sink being full.
+    pub fn take_dropped(&self) -> u64 { self.dropped.swap(0, Ordering::Relaxed) }
+}
+
+/// Records the packets and events of one connection.
+#[derive(Debug, Clone)]
+pub struct Inspector {
+    connection: u64,
+    started: Instant,
+    sinks: Vec<Sink>,
+}
+
+impl Inspector {
+    /// `started` is shared by every connection, so that times compare.
+    #[must_use]
+    pub const fn new(
+        connection: u64,
+        started: Instant,
+        sinks: Vec<Sink>,
+    ) -> Self {
+        Self {
+            connection,
+            started,
+            sinks,
+        }
+    }
+
+    fn send(
+        &self,
+        record: &Record,
+    ) {
+        for sink in &self.sinks {
+            sink.send(record.clone());
+        }
+    }
+
+    pub fn connected(
+        &self,
+        client: SocketAddr,
+    ) {
+        self.send(&Record::Connected {
+            connection: self.connection,
+            time: self.started.elapsed(),
+            client,
+        });
+    }
+
+    pub fn packet(
+        &self,
+        state: State,
+        direction: Direction,
+        version: ProtocolVersion,
+        packet: &Packet,
+        compressed_size: usize,
+    ) {
+        self.send(&Record::Packet(PacketRecord {
+            connection: self.connection,
+            time: self.started.elapsed(),
+            direction,
+            state,
+            version,
+            packet: packet.clone(),
+            compressed_size,
+        }));
+    }
+
+    pub fn disconnected(
+        &self,
+        error: Option<String>,
+    ) {
+        self.send(&Record::Disconnected {
+            connection: self.connection,
+            time: self.started.elapsed(),
+            error,
+        });
+    }
+}
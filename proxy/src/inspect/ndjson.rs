@@ -0,0 +1,220 @@
+use core::net::SocketAddr;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::io;
+use std::sync::mpsc::TryRecvError;
+
+use data::json::ToJson as _;
+use data::protocol::describe;
+use json::{
+    JsonValue,
+    object,
+};
+
+use super::{
+    PacketRecord,
+    Record,
+    Records,
+    direction,
+    stage,
+};
+
+/// Writes every record as a line of JSON until the proxy stops. Lines are
+/// flushed whenever no record is waiting, so that the log can be followed.
+/// Records dropped for falling behind are counted by a `dropped` line.
+///
+/// # Errors
+///
+/// Returns [`io::Error`] if writing fails.
+pub fn write(
+    records: &Records,
+    mut out: impl io::Write,
+) -> io::Result<()> {
+    // packets only carry their connection, the address is added back
+    let mut clients = HashMap::new();
+    loop {
+        let record = match records.try_recv() {
+            Ok(record) => record,
+            Err(TryRecvError::Empty) => {
+                write_dropped(records, &mut out)?;
+                out.flush()?;
+                match records.recv() {
+                    Ok(record) => record,
+                    Err(_) => return Ok(()),
+                }
+            }
+            Err(TryRecvError::Disconnected) => {
+                write_dropped(records, &mut out)?;
+                return out.flush();
+            }
+        };
+        let line = match record {
+            Record::Connected {
+                connection,
+                time,
+                client,
+            } => {
+                clients.insert(connection, client);
+                object! {
+                    event: "connected",
+                    connection: connection,
+                    time_ms: millis(time),
+                    client: client.to_string(),
+                }
+            }
+            Record::Packet(record) => packet(&record, clients.get(&record.connection)),
+            Record::Disconnected {
+                connection,
+                time,
+                error,
+            } => object! {
+                event: "disconnected",
+                connection: connection,
+                time_ms: millis(time),
+                client: clients.remove(&connection).map(|client| client.to_string()),
+                error: error,
+            },
+        };
+        writeln!(out, "{}", line.dump())?;
+    }
+}
+
+fn write_dropped(
+    records: &Records,
+    out: &mut impl io::Write,
+) -> io::Result<()> {
+    match records.take_dropped() {
+        0 => Ok(()),
+        dropped => writeln!(out, "{}", object! {
+            event: "dropped",
+            records: dropped,
+        }),
+    }
+}
+
+fn packet(
+    record: &PacketRecord,
+    client: Option<&SocketAddr>,
+) -> JsonValue {
+    let mut line = object! {
+        event: "packet",
+        connection: record.connection,
+        time_ms: millis(record.time),
+        client: client.map(ToString::to_string),
+        direction: direction(record.direction),
+        stage: stage(record.state),
+        protocol: record.version.protocol(),
+        id: record.packet.id,
+        name: record.name(),
+        size: record.size(),
+        compressed_size: record.compressed_size,
+    };
+    match describe(
+        record.version,
+        record.state,
+        record.direction,
+        &record.packet,
+    ) {
+        Ok(Some(decoded)) => line["fields"] = decoded.to_json(),
+        Ok(None) => {}
+        Err(err) => line["decode_error"] = err.to_string().into(),
+    }
+    line
+}
+
+fn millis(time: Duration) -> u64 { u64::try_from(time.as_millis()).unwrap_or(u64::MAX) }
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use data::packet::Packet;
+    use data::protocol::{
+        Direction,
+        State,
+    };
+    use data::version::ProtocolVersion;
+
+    use super::*;
+    use crate::inspect::{
+        SINK_CAPACITY,
+        sink,
+    };
+
+    #[test]
+    fn one_line_per_record() {
+        let (sender, receiver) = sink();
+        let client = "127.0.0.1:50000".parse().unwrap();
+        sender.send(Record::Connected {
+            connection: 1,
+            time: Duration::ZERO,
+            client,
+        });
+        for packet in [
+            Packet::new(0x1B, &[0, 0, 0, 0, 0, 0, 0, 7]),
+            Packet::new(0x7F, &[]),
+        ] {
+            sender.send(Record::Packet(PacketRecord {
+                connection: 1,
+                time: Duration::from_millis(1500),
+                direction: Direction::Serverbound,
+                state: State::Play,
+                version: ProtocolVersion::LATEST,
+                compressed_size: packet.data.len() + 3,
+                packet,
+            }));
+        }
+        sender.send(Record::Disconnected {
+            connection: 1,
+            time: Duration::from_secs(2),
+            error: Some("Connection reset".to_owned()),
+        });
+        drop(sender);
+
+        let mut out = Vec::new();
+        write(&receiver, &mut out).unwrap();
+        let lines = String::from_utf8(out).unwrap();
+        let lines = lines
+            .lines()
+            .map(|line| json::parse(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4, "{lines:?}");
+
+        assert_eq!(lines[0]["event"], "connected");
+        let keep_alive = &lines[1];
+        assert_eq!(keep_alive["client"], "127.0.0.1:50000");
+        assert_eq!(keep_alive["time_ms"], 1500);
+        assert_eq!(keep_alive["direction"], "serverbound");
+        assert_eq!(keep_alive["stage"], "play");
+        assert_eq!(keep_alive["name"], "keep_alive");
+        assert_eq!(keep_alive["size"], 8);
+        assert_eq!(keep_alive["compressed_size"], 11);
+        assert_eq!(keep_alive["fields"], json::object! { id: 7 });
+        assert!(lines[2]["name"].is_null(), "unknown packets have no name");
+        assert!(
+            lines[2]["fields"].is_null(),
+            "unknown packets have no fields"
+        );
+        assert_eq!(lines[3]["error"], "Connection reset");
+        assert_eq!(lines[3]["client"], "127.0.0.1:50000");
+    }
+
+    #[test]
+    fn count_dropped_records() {
+        let (sender, receiver) = sink();
+        for connection in 0..SINK_CAPACITY + 3 {
+            sender.send(Record::Disconnected {
+                connection: u64::try_from(connection).unwrap(),
+                time: Duration::ZERO,
+                error: None,
+            });
+        }
+        drop(sender);
+
+        let mut out = Vec::new();
+        write(&receiver, &mut out).unwrap();
+        let lines = String::from_utf8(out).unwrap();
+        assert_eq!(lines.lines().count(), SINK_CAPACITY + 1);
+        let dropped = json::parse(lines.lines().last().unwrap()).unwrap();
+        assert_eq!(dropped, json::object! { event: "dropped", records: 3 });
+    }
+}
@@ -0,0 +1,168 @@
+use core::fmt::Write as _;
+use core::time::Duration;
+use std::io;
+
+use data::protocol::Direction;
+use ratatui::crossterm::event::{
+    self,
+    Event,
+    KeyEventKind,
+};
+use ratatui::layout::{
+    Constraint,
+    Layout,
+    Rect,
+};
+use ratatui::style::{
+    Color,
+    Modifier,
+    Style,
+};
+use ratatui::widgets::{
+    Block,
+    Paragraph,
+    Row,
+    Table,
+    TableState,
+};
+use ratatui::{
+    DefaultTerminal,
+    Frame,
+};
+
+use super::{
+    App,
+    Record,
+    Records,
+    stage,
+};
+
+/// How long to wait for a key before drawing new packets.
+const REFRESH: Duration = Duration::from_millis(100);
+
+/// Shows the recorded packets until the user quits, restoring the terminal
+/// afterwards.
+pub fn run(records: &Records) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, records);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    records: &Records,
+) -> io::Result<()> {
+    let mut app = App::default();
+    loop {
+        // once the proxy stops, what was recorded stays shown
+        while let Ok(record) = records.try_recv() {
+            if let Record::Packet(record) = record {
+                app.push(record);
+            }
+        }
+        app.dropped += records.take_dropped();
+        terminal.draw(|frame| draw(frame, &app))?;
+        if event::poll(REFRESH)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !app.handle_key(key)
+        {
+            return Ok(());
+        }
+    }
+}
+
+fn draw(
+    frame: &mut Frame<'_>,
+    app: &App,
+) {
+    let [packets, status] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+    if app.expanded {
+        let [packets, details] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(packets);
+        draw_packets(frame, app, packets);
+        let details_text = app.details().unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(details_text).block(Block::bordered().title(" Details ")),
+            details,
+        );
+    } else {
+        draw_packets(frame, app, packets);
+    }
+    draw_status(frame, app, status);
+}
+
+fn draw_packets(
+    frame: &mut Frame<'_>,
+    app: &App,
+    area: Rect,
+) {
+    let rows = app.visible().into_iter().map(|record| {
+        Row::new([
+            format!(
+                "{}.{:03}",
+                record.time.as_secs(),
+                record.time.subsec_millis()
+            ),
+            format!("#{}", record.connection),
+            match record.direction {
+                Direction::Serverbound => "C→S".to_owned(),
+                Direction::Clientbound => "S→C".to_owned(),
+            },
+            stage(record.state).to_owned(),
+            format!("{:#04X}", record.packet.id),
+            record.name().unwrap_or("unknown").to_owned(),
+            record.size().to_string(),
+        ])
+    });
+    let table = Table::new(rows, [
+        Constraint::Length(10),
+        Constraint::Length(5),
+        Constraint::Length(4),
+        Constraint::Length(9),
+        Constraint::Length(5),
+        Constraint::Fill(1),
+        Constraint::Length(8),
+    ])
+    .header(
+        Row::new(["time", "conn", "dir", "stage", "id", "name", "size"])
+            .style(Style::new().add_modifier(Modifier::BOLD)),
+    )
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+    .block(Block::bordered().title(" Packets "));
+    let mut state = TableState::default().with_selected(app.selected_index());
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_status(
+    frame: &mut Frame<'_>,
+    app: &App,
+    area: Rect,
+) {
+    let status = if app.editing_filter {
+        format!("filter: {}▏", app.filter_text)
+    } else {
+        let mut status = format!("{}/{} packets", app.visible().len(), app.total());
+        if app.paused {
+            let _ = write!(status, " | paused, {} waiting", app.pending());
+        }
+        if app.dropped > 0 {
+            let _ = write!(status, " | {} dropped", app.dropped);
+        }
+        if !app.filter_text.is_empty() {
+            let _ = write!(status, " | filter: {}", app.filter_text);
+        }
+        if !app.following() {
+            status.push_str(" | End to follow");
+        }
+        status.push_str(" | q quit, p pause, / filter, enter details, c clear");
+        status
+    };
+    frame.render_widget(
+        Paragraph::new(status).style(Style::new().fg(Color::Black).bg(Color::Gray)),
+        area,
+    );
+}
@@ -0,0 +1,110 @@
+use alloc::sync::Arc;
+use core::net::IpAddr;
+use std::collections::HashMap;
+use std::sync::{
+    Mutex,
+    PoisonError,
+};
+
+use crate::config::Limits;
+
+/// Open connections, in total and by IP address, counted against the
+/// configured limits.
+#[derive(Debug, Default)]
+pub struct Limiter {
+    open: Mutex<Open>,
+}
+
+#[derive(Debug, Default)]
+struct Open {
+    total: usize,
+    by_ip: HashMap<IpAddr, usize>,
+}
+
+impl Limiter {
+    /// Counts a new connection from `ip`, unless it would exceed `limits`.
+    /// The connection is counted until the permit is dropped.
+    #[must_use]
+    pub fn acquire(
+        self: &Arc<Self>,
+        ip: IpAddr,
+        limits: &Limits,
+    ) -> Option<Permit> {
+        let mut open = self.open.lock().unwrap_or_else(PoisonError::into_inner);
+        let from_ip = open.by_ip.get(&ip).copied().unwrap_or_default();
+        if limits.max_connections.is_some_and(|max| open.total >= max)
+            || limits
+                .max_connections_per_ip
+                .is_some_and(|max| from_ip >= max)
+        {
+            return None;
+        }
+        open.total += 1;
+        open.by_ip.insert(ip, from_ip + 1);
+        Some(Permit {
+            limiter: Arc::clone(self),
+            ip,
+        })
+    }
+}
+
+/// A connection counted by a [`Limiter`].
+#[derive(Debug)]
+pub struct Permit {
+    limiter: Arc<Limiter>,
+    ip: IpAddr,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut open = self
+            .limiter
+            .open
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        open.total -= 1;
+        if let Some(count) = open.by_ip.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                open.by_ip.remove(&self.ip);
+            }
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use core::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn limit_total_and_per_ip() {
+        let limiter = Arc::new(Limiter::default());
+        let limits = Limits {
+            max_connections: Some(3),
+            max_connections_per_ip: Some(2),
+        };
+        let first = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let second = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+        let a = limiter.acquire(first, &limits).unwrap();
+        let _b = limiter.acquire(first, &limits).unwrap();
+        assert!(
+            limiter.acquire(first, &limits).is_none(),
+            "a third connection from the same IP should be refused"
+        );
+        let _c = limiter.acquire(second, &limits).unwrap();
+        assert!(
+            limiter.acquire(second, &limits).is_none(),
+            "a fourth connection should be refused"
+        );
+
+        drop(a);
+        assert!(
+            limiter.acquire(first, &limits).is_some(),
+            "closed connections should free their place"
+        );
+    }
+}
@@ -1,195 +1,918 @@
+#![allow(
+    clippy::multiple_crate_versions,
+    reason = "ratatui and wasmtime depend on several versions of syn, rustix and windows-sys"
+)]
+
 extern crate alloc;
 
+mod config;
+mod download;
 mod error;
+mod inspect;
+mod limits;
+mod metrics;
+mod players;
+mod plugin;
+mod transfer;
 mod utils;
+mod world;
 
+use alloc::sync::Arc;
+use core::net::{
+    Ipv4Addr,
+    SocketAddr,
+};
+use core::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use std::fs::File;
+use std::io::{
+    self,
+    BufRead as _,
+    BufReader,
+    BufWriter,
+};
 use std::net::{
+    Shutdown,
     TcpListener,
     TcpStream,
 };
-use std::sync::mpsc;
-use std::thread;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::{
+    Mutex,
+    PoisonError,
+    mpsc,
+};
+use std::time::Instant;
+use std::{
+    env,
+    process,
+    thread,
+};
 
 use clap::Parser;
-use codec::dec::Decode as _;
+use codec::VarInt;
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+};
 use data::model::{
     handshake,
     login,
 };
 use data::packet::{
+    Packet,
     ReadPacket as _,
     WritePacket as _,
 };
+use data::protocol::{
+    Direction,
+    LoginClientbound,
+    ProtocolPacket,
+    State,
+};
+use data::version::ProtocolVersion;
 use log::{
+    LevelFilter,
     debug,
     error,
     info,
     trace,
+    warn,
 };
 
+use crate::config::{
+    Backend,
+    Config,
+    Forwarding,
+    SharedConfig,
+    TransferPolicy,
+};
+use crate::download::WorldDownload;
 use crate::error::Error;
+use crate::inspect::{
+    Inspector,
+    Records,
+    Sink,
+};
+use crate::limits::Limiter;
+use crate::metrics::Metrics;
+use crate::players::{
+    Client,
+    Players,
+};
+use crate::plugin::boss_bars::BossBars;
+#[cfg(feature = "scripting")]
+use crate::plugin::script::{
+    ScriptHandler,
+    Scripts,
+};
+use crate::plugin::{
+    ConnectionInfo,
+    Context,
+    CookieJar,
+    GlobalTabList,
+    Handlers,
+    NetworkResourcePack,
+    Pipeline,
+    Player,
+    ProxyCommands,
+    StatusDescription,
+};
+use crate::transfer::{
+    ORIGIN_COOKIE,
+    Origin,
+    OriginError,
+};
+use crate::world::WorldView;
 
 #[derive(Parser, Debug)]
 #[command(about, version, author)]
 struct Cli {
-    #[arg(long, env, default_value = "35565")]
+    /// Reads the listen addresses, backends and other settings from this
+    /// TOML file, reloaded when it changes or on SIGHUP.
+    #[arg(long, env, conflicts_with_all = ["server_host", "server_port", "server_protocol"])]
+    config: Option<PathBuf>,
+    #[arg(long, env, default_value = "35565", conflicts_with = "config")]
     proxy_port: u16,
-    #[arg(long, env)]
-    server_host: String,
+    #[arg(long, env, required_unless_present = "config")]
+    server_host: Option<String>,
     #[arg(long, env, default_value = "25565")]
     server_port: u16,
+    /// Protocol version of the server. When set, clients of other versions
+    /// are refused at login.
+    #[arg(long, env)]
+    server_protocol: Option<i32>,
+    /// Saves the chunks each player receives as an Anvil world in
+    /// `<DOWNLOAD>/<player name>`.
+    #[arg(long, env)]
+    download: Option<PathBuf>,
+    /// Shows the relayed packets in a terminal UI instead of logging, with
+    /// the `tui` feature.
+    #[arg(long, env)]
+    inspect: bool,
+    /// Writes every relayed packet and connection event as a line of JSON
+    /// to this file, or to stdout when `-`. Overrides `logging.json`.
+    #[arg(long, env)]
+    log_json: Option<PathBuf>,
+    /// Serves Prometheus metrics at `http://<METRICS_ADDR>/metrics`.
+    /// Overrides `logging.metrics`.
+    #[arg(long, env)]
+    metrics_addr: Option<SocketAddr>,
 }
 
-fn main() {
-    env_logger::init();
+impl Cli {
+    /// The configuration given by the arguments, without `--config`.
+    fn config(&self) -> Config {
+        let server_version = self.server_protocol.map(|protocol| {
+            ProtocolVersion::from_protocol(protocol)
+                .expect("Server protocol version should be known")
+        });
+        let server_host = self
+            .server_host
+            .as_deref()
+            .expect("The server host is required without a configuration file");
+        Config::new(
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, self.proxy_port)),
+            Backend {
+                name: "server".to_owned(),
+                address: format!(
+                    "{server_host}:{server_port}",
+                    server_port = self.server_port
+                ),
+                protocol: server_version,
+            },
+        )
+    }
+}
 
+fn main() {
     let args = Cli::parse();
+    let mut config = match &args.config {
+        Some(path) => Config::load(path).unwrap_or_else(|err| {
+            eprintln!("Failed to load {}: {err}", path.display());
+            process::exit(1);
+        }),
+        None => args.config(),
+    };
+    if args.log_json.is_some() {
+        config.logging.json.clone_from(&args.log_json);
+    }
+    if args.metrics_addr.is_some() {
+        config.logging.metrics = args.metrics_addr;
+    }
+    if !args.inspect {
+        if env::var_os("RUST_LOG").is_some() {
+            env_logger::init();
+        } else {
+            // the configured level is applied, and reloaded, as the max level
+            env_logger::Builder::new()
+                .filter_level(LevelFilter::Trace)
+                .init();
+            apply_logging(&config);
+        }
+    }
 
-    let proxy_addr = format!(
-        "{proxy_host}:{proxy_port}",
-        proxy_host = "0.0.0.0",
-        proxy_port = args.proxy_port
-    );
-    let server_addr = format!(
-        "{server_host}:{server_port}",
-        server_host = args.server_host,
-        server_port = args.server_port
-    );
-
-    let listener = TcpListener::bind(proxy_addr).expect("Failed to bind to proxy address");
+    let listeners: Vec<_> = config
+        .listen
+        .iter()
+        .map(|addr| TcpListener::bind(addr).expect("Failed to bind to proxy address"))
+        .collect();
 
-    for client in listener.incoming() {
-        let client = match client {
-            Ok(client) => client,
-            Err(err) => {
-                error!("Failed to accept client connection: {err}");
-                continue;
-            }
+    let mut sinks = Vec::new();
+    if let Some(path) = &config.logging.json {
+        let out: Box<dyn io::Write + Send> = if path.as_os_str() == "-" {
+            Box::new(io::stdout())
+        } else {
+            Box::new(BufWriter::new(
+                File::create(path).expect("Failed to create the JSON log"),
+            ))
         };
+        let (records, receiver) = inspect::sink();
+        sinks.push(records);
+        thread::spawn(move || {
+            if let Err(err) = inspect::write(&receiver, out) {
+                error!("Failed to write the JSON log: {err}");
+            }
+        });
+    }
+    let inspected = args.inspect.then(|| {
+        let (records, receiver) = inspect::sink();
+        sinks.push(records);
+        receiver
+    });
+
+    let metrics = config.logging.metrics.map(|addr| {
+        let metrics = Arc::new(Metrics::default());
+        let metrics_listener = TcpListener::bind(addr).expect("Failed to bind to metrics address");
+        let served = Arc::clone(&metrics);
+        thread::spawn(move || metrics::serve(&metrics_listener, &served));
+        metrics
+    });
+
+    let players = Arc::new(Players::default());
+    let started = Instant::now();
+    let mut pipeline = Pipeline::default();
+    let boss_bars = register_builtins(&mut pipeline, &players, started);
+    register_plugins(&mut pipeline, &config.plugins);
 
-        if let Ok(client_addr) = client.peer_addr() {
-            info!("Accepted client connection from {client_addr}",);
+    let config = Arc::new(SharedConfig::new(config));
+    if let Some(path) = args.config {
+        config::watch(path, Arc::clone(&config), apply_logging);
+    }
+    plugin::boss_bars::watch(boss_bars, Arc::clone(&config));
+    transfer::watch(Arc::clone(&players), Arc::clone(&config));
+    register_scripts(&mut pipeline, &config);
+
+    let proxy = Arc::new(Proxy {
+        config,
+        download: args.download,
+        sinks,
+        metrics,
+        pipeline,
+        limiter: Arc::new(Limiter::default()),
+        players,
+        started,
+        connections: AtomicU64::new(0),
+    });
+    let accepting: Vec<_> = listeners
+        .into_iter()
+        .map(|listener| {
+            let proxy = Arc::clone(&proxy);
+            thread::spawn(move || proxy.accept(&listener))
+        })
+        .collect();
+
+    if let Some(receiver) = inspected {
+        show_inspector(&receiver);
+    } else {
+        for accepting in accepting {
+            _ = accepting.join();
         }
+    }
+}
 
-        let server = match TcpStream::connect(&server_addr) {
-            Ok(server) => server,
-            Err(err) => {
-                error!("Failed to connect to server: {err}");
-                continue;
+/// Shows the relayed packets in the terminal until the inspector quits.
+#[cfg(feature = "tui")]
+fn show_inspector(receiver: &Records) {
+    inspect::run(receiver).expect("The packet inspector should run in a terminal");
+}
+
+#[cfg(not(feature = "tui"))]
+fn show_inspector(_: &Records) {
+    eprintln!("The packet inspector needs the proxy built with the tui feature");
+    process::exit(1);
+}
+
+/// Registers the built-in handlers, returning the boss bars they show.
+fn register_builtins(
+    pipeline: &mut Pipeline,
+    players: &Arc<Players>,
+    started: Instant,
+) -> Arc<BossBars> {
+    let commands = ProxyCommands::builtin(Arc::clone(players), started);
+    let listed = Arc::clone(players);
+    let boss_bars = Arc::new(BossBars::new(Arc::clone(players)));
+    let shown = Arc::clone(&boss_bars);
+    pipeline.register(|| Box::new(StatusDescription));
+    pipeline.register(move || Box::new(commands.clone()));
+    pipeline.register(move || Box::new(GlobalTabList::new(Arc::clone(&listed))));
+    pipeline.register(move || shown.handler());
+    pipeline.register(|| Box::<NetworkResourcePack>::default());
+    let jar = Arc::clone(players);
+    pipeline.register(move || Box::new(CookieJar::new(Arc::clone(&jar))));
+    boss_bars
+}
+
+/// Registers a handler for each WebAssembly plugin, after the built-in ones.
+#[cfg(feature = "wasm")]
+fn register_plugins(
+    pipeline: &mut Pipeline,
+    plugins: &[config::Plugin],
+) {
+    let plugins = plugin::wasm::load(plugins).unwrap_or_else(|err| {
+        eprintln!("{err:#}");
+        process::exit(1);
+    });
+    for plugin in plugins {
+        pipeline.register(move || plugin.handler());
+    }
+}
+
+#[cfg(not(feature = "wasm"))]
+fn register_plugins(
+    _: &mut Pipeline,
+    plugins: &[config::Plugin],
+) {
+    if !plugins.is_empty() {
+        eprintln!("Plugins are configured but the proxy was built without the wasm feature");
+        process::exit(1);
+    }
+}
+
+/// Registers the handler running the scripts, which are reloaded with the
+/// configuration.
+#[cfg(feature = "scripting")]
+fn register_scripts(
+    pipeline: &mut Pipeline,
+    config: &Arc<SharedConfig>,
+) {
+    let scripts = Scripts::load(&config.get().scripts).unwrap_or_else(|err| {
+        eprintln!("Failed to load {err}");
+        process::exit(1);
+    });
+    let scripts = Arc::new(scripts);
+    plugin::script::watch(Arc::clone(&scripts), Arc::clone(config));
+    pipeline.register(move || Box::new(ScriptHandler::new(Arc::clone(&scripts))));
+}
+
+#[cfg(not(feature = "scripting"))]
+fn register_scripts(
+    _: &mut Pipeline,
+    config: &Arc<SharedConfig>,
+) {
+    if !config.get().scripts.is_empty() {
+        eprintln!("Scripts are configured but the proxy was built without the scripting feature");
+        process::exit(1);
+    }
+}
+
+/// Logs up to the configured level, unless `RUST_LOG` decides.
+fn apply_logging(config: &Config) {
+    if env::var_os("RUST_LOG").is_none() {
+        log::set_max_level(config.logging.level);
+    }
+}
+
+/// What the connections of every listener share.
+#[derive(Debug)]
+struct Proxy {
+    config: Arc<SharedConfig>,
+    download: Option<PathBuf>,
+    /// Where packets and connection events are recorded.
+    sinks: Vec<Sink>,
+    metrics: Option<Arc<Metrics>>,
+    pipeline: Pipeline,
+    limiter: Arc<Limiter>,
+    /// The players online on every listener.
+    players: Arc<Players>,
+    started: Instant,
+    connections: AtomicU64,
+}
+
+impl Proxy {
+    /// Relays every client accepted on `listener` to its backend, each in
+    /// its own thread, with the configuration current when it connected.
+    fn accept(
+        self: &Arc<Self>,
+        listener: &TcpListener,
+    ) {
+        for client in listener.incoming() {
+            let (client, client_addr) = match client.and_then(|client| {
+                let client_addr = client.peer_addr()?;
+                Ok((client, client_addr))
+            }) {
+                Ok(client) => client,
+                Err(err) => {
+                    error!("Failed to accept client connection: {err}");
+                    continue;
+                }
+            };
+
+            info!("Accepted client connection from {client_addr}");
+            if let Some(metrics) = &self.metrics {
+                metrics.connection_accepted();
             }
-        };
 
-        thread::spawn(move || {
-            if let Err(err) = handle_connection(client, server) {
-                error!("Failed to handle connection: {err}");
+            let config = self.config.get();
+            let Some(permit) = self.limiter.acquire(client_addr.ip(), &config.limits) else {
+                info!("Refused {client_addr}: too many connections");
+                if let Some(metrics) = &self.metrics {
+                    metrics.connection_failed("limits");
+                }
+                continue;
+            };
+
+            let connection = ConnectionInfo {
+                id: self.connections.fetch_add(1, Ordering::Relaxed) + 1,
+                client: client_addr,
+                backend: None,
+                port: 0,
+                player: None,
+                transferred: false,
+            };
+            let inspector = (!self.sinks.is_empty())
+                .then(|| Inspector::new(connection.id, self.started, self.sinks.clone()));
+            if let Some(inspector) = &inspector {
+                inspector.connected(client_addr);
             }
-        });
+            let id = connection.id;
+            let proxy = Arc::clone(self);
+            let handlers = self.pipeline.handlers();
+            thread::spawn(move || {
+                let result = handle_connection(
+                    client,
+                    connection,
+                    config,
+                    &proxy,
+                    handlers.clone(),
+                    inspector.clone(),
+                );
+                drop(permit);
+                proxy.players.left(id);
+                match &result {
+                    Err(Error::Kicked(reason)) => info!("Kicked the client: {reason}"),
+                    Err(err) => error!("Failed to handle connection: {err}"),
+                    Ok(()) => {}
+                }
+                handlers.disconnected(result.as_ref().err().map(ToString::to_string).as_deref());
+                if let Some(inspector) = inspector {
+                    inspector.disconnected(result.err().map(|err| err.to_string()));
+                }
+            });
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct ConnectionState {
     pub stage: ConnectionStage,
+    pub connection: ConnectionInfo,
+    pub config: Arc<Config>,
+    pub handlers: Handlers,
+    /// Version of the client, and of the backend it is relayed to.
+    pub version: ProtocolVersion,
     pub packet_min_compression: Option<usize>,
+    /// Handshake held until the login start completes its forwarded
+    /// address.
+    pub pending_handshake: Option<handshake::Handshake>,
+    /// What the player sees, kept when a handler reads it.
+    pub world: Option<Arc<Mutex<WorldView>>>,
+    pub download: Option<Arc<Mutex<WorldDownload>>>,
+    pub inspector: Option<Inspector>,
+    pub metrics: Option<Arc<Metrics>>,
+    pub players: Arc<Players>,
 }
 
-#[derive(Debug)]
+impl ConnectionState {
+    /// Whether the client plays after the clientbound packet named `name`,
+    /// if it moves the client into or out of play.
+    fn plays_after(
+        &self,
+        name: &str,
+    ) -> Option<bool> {
+        match (self.stage, name) {
+            (ConnectionStage::Play, "login") => Some(true),
+            (ConnectionStage::Play, "start_configuration") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Records a packet read from the sender, before the handlers.
+    fn record(
+        &self,
+        state: State,
+        direction: Direction,
+        packet: &Packet,
+        compressed_size: usize,
+    ) {
+        if let Some(inspector) = &self.inspector {
+            inspector.packet(state, direction, self.version, packet, compressed_size);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.packet(
+                direction,
+                state,
+                self.version.packet_name(state, direction, packet.id),
+                compressed_size,
+            );
+        }
+    }
+
+    /// Writes a packet, compressed if the connection is.
+    fn write(
+        &self,
+        to: &mut impl io::Write,
+        packet: &Packet,
+    ) -> Result<(), Error> {
+        let written = to.write_packet(packet, self.packet_min_compression)?;
+        if let (Some(metrics), Some(min_compression)) = (&self.metrics, self.packet_min_compression)
+        {
+            let uncompressed = VarInt::new(packet.id).as_slice().len() + packet.data.len();
+            if uncompressed >= min_compression {
+                metrics.compressed(uncompressed, written);
+            }
+        }
+        Ok(())
+    }
+
+    /// Saves what the connection leaves behind once it ended.
+    fn ended(&self) {
+        if let Some(world) = &self.world
+            && let Ok(world) = world.lock()
+        {
+            trace!("World at disconnect: {}", world.snapshot().to_json());
+        }
+        if let Some(download) = &self.download
+            && let Ok(mut download) = download.lock()
+            && let Err(err) = download.save()
+        {
+            error!("Failed to save world download: {err}");
+        }
+    }
+
+    /// Runs a packet read from the sender through the handlers.
+    fn intercept(
+        &self,
+        state: State,
+        direction: Direction,
+        packet: Packet,
+    ) -> Result<Intercepted, Error> {
+        let world = self
+            .world
+            .as_ref()
+            .map(|world| world.lock().unwrap_or_else(PoisonError::into_inner));
+        let mut context = Context::new(
+            &self.connection,
+            &self.config,
+            state,
+            direction,
+            self.version,
+        );
+        if let Some(world) = &world {
+            context = context.with_world(world);
+        }
+        let forwarded = self.handlers.run(&mut context, packet)?;
+        let mut injected = context.take_injected();
+        let disconnect = context.take_disconnect();
+        if let Some(reason) = &disconnect
+            && let Some(packet) = crate::utils::disconnect(state, self.version, reason)?
+        {
+            injected.push((Direction::Clientbound, packet));
+        }
+        Ok(Intercepted {
+            forwarded,
+            injected,
+            disconnect,
+        })
+    }
+
+    /// Counts the connection as failed in `stage`, or as failed to reach
+    /// the backend or kicked by a handler.
+    fn failed(
+        &self,
+        stage: ConnectionStage,
+        err: &Error,
+    ) {
+        if let Some(metrics) = &self.metrics {
+            metrics.connection_failed(match err {
+                Error::BackendConnect(_) => "backend",
+                Error::Kicked(_) => "kicked",
+                _ => stage.name(),
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ConnectionStage {
     Handshake,
     Status,
     Login,
     Configuration,
     Play,
+    /// Relayed as is, the client's version being unknown to the proxy.
+    Opaque,
     End,
 }
 
+impl ConnectionStage {
+    const fn protocol_state(self) -> Option<State> {
+        match self {
+            Self::Handshake => Some(State::Handshake),
+            Self::Status => Some(State::Status),
+            Self::Login => Some(State::Login),
+            Self::Configuration => Some(State::Configuration),
+            Self::Play => Some(State::Play),
+            Self::Opaque | Self::End => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Opaque => "opaque",
+            stage => stage.protocol_state().map_or("end", inspect::stage),
+        }
+    }
+
+    /// Stage after a packet named `name` went through in `direction`. Each
+    /// direction switches on its own packets, so both relays agree.
+    fn after(
+        self,
+        direction: Direction,
+        name: &str,
+    ) -> Self {
+        match (self, direction, name) {
+            (Self::Configuration, _, "finish_configuration") => Self::Play,
+            (Self::Play, Direction::Clientbound, "start_configuration")
+            | (Self::Play, Direction::Serverbound, "configuration_acknowledged") => {
+                Self::Configuration
+            }
+            (stage, ..) => stage,
+        }
+    }
+}
+
 fn handle_connection(
     mut client: TcpStream,
-    mut server: TcpStream,
+    connection: ConnectionInfo,
+    config: Arc<Config>,
+    proxy: &Proxy,
+    handlers: Handlers,
+    inspector: Option<Inspector>,
 ) -> Result<(), Error> {
     _ = client.set_nodelay(true);
-    _ = server.set_nodelay(true);
 
     let mut connection_state = ConnectionState {
         stage: ConnectionStage::Handshake,
+        connection,
+        config,
+        world: handlers
+            .wants_world()
+            .then(|| Arc::new(Mutex::new(WorldView::new()))),
+        handlers,
+        version: ProtocolVersion::LATEST,
         packet_min_compression: None,
+        pending_handshake: None,
+        download: None,
+        inspector,
+        metrics: proxy.metrics.clone(),
+        players: Arc::clone(&proxy.players),
     };
 
+    // the handshake decides which backend to connect to
+    let mut server = handle_handshake(&mut client, &mut connection_state)
+        .inspect_err(|err| connection_state.failed(ConnectionStage::Handshake, err))?;
+    connection_state
+        .handlers
+        .connected(&connection_state.connection);
+
     loop {
-        match connection_state.stage {
-            ConnectionStage::Handshake => {
-                handle_handshake(&mut client, &mut server, &mut connection_state)?;
-            }
+        let stage = connection_state.stage;
+        let result = match stage {
             ConnectionStage::Status => {
-                handle_status(&mut client, &mut server, &mut connection_state)?;
+                handle_status(&mut client, &mut server, &mut connection_state)
             }
-            ConnectionStage::Login => {
-                handle_login(&mut client, &mut server, &mut connection_state)?;
-            }
-            ConnectionStage::Configuration => {
-                // TODO: parse configuration packets
-                break;
-            }
-            ConnectionStage::Play => {
-                // TODO: parse play packets
-                break;
+            ConnectionStage::Login => handle_login(
+                &mut client,
+                &mut server,
+                &mut connection_state,
+                proxy.download.as_deref(),
+            ),
+            ConnectionStage::Opaque => {
+                relay_opaque(client, server, &mut connection_state)
+                    .inspect_err(|err| connection_state.failed(stage, err))?;
+                return Ok(());
             }
-            ConnectionStage::End => return Ok(()),
+            ConnectionStage::Configuration | ConnectionStage::Play => break,
+            ConnectionStage::Handshake | ConnectionStage::End => return Ok(()),
+        };
+        if let Err(err) = result {
+            connection_state.failed(stage, &err);
+            return Err(err);
         }
     }
 
     // Pump remaining data between client and server
     let client_read = client.try_clone().map_err(Error::TcpStreamClone)?;
     let server_read = server.try_clone().map_err(Error::TcpStreamClone)?;
+    // both relays write to each side, for the packets handlers inject
+    let client = Arc::new(Mutex::new(client));
+    let server = Arc::new(Mutex::new(server));
+    proxy.players.joined(
+        &connection_state.connection,
+        Some(Client::new(
+            Arc::clone(&client),
+            connection_state.version,
+            connection_state.packet_min_compression,
+        )),
+    );
 
     let (tx1, rx1) = mpsc::channel();
     let (tx2, rx2) = mpsc::channel();
 
+    let mut client_state = connection_state.clone();
+    let (to, back) = (Arc::clone(&server), Arc::clone(&client));
     thread::spawn(move || {
-        tx1.send(relay(
+        let result = relay(
             Relay::ClientToServer,
             client_read,
-            server,
-            connection_state.packet_min_compression,
-        ))
+            &to,
+            &back,
+            &mut client_state,
+        );
+        shutdown(&to, &back);
+        tx1.send(result.map_err(|err| (client_state.stage, err)))
     });
+    let mut server_state = connection_state.clone();
     thread::spawn(move || {
-        tx2.send(relay(
+        let result = relay(
             Relay::ServerToClient,
-            client,
             server_read,
-            connection_state.packet_min_compression,
-        ))
+            &client,
+            &server,
+            &mut server_state,
+        );
+        shutdown(&client, &server);
+        tx2.send(result.map_err(|err| (server_state.stage, err)))
     });
 
-    rx1.recv()??;
-    rx2.recv()??;
+    // the first relay to fail ends the connection
+    let failure = [rx1, rx2].iter().find_map(|relayed| match relayed.recv() {
+        Ok(Ok(())) => None,
+        Ok(Err(failure)) => Some(failure),
+        Err(err) => Some((connection_state.stage, err.into())),
+    });
+    let result = match failure {
+        Some((stage, err)) => {
+            connection_state.failed(stage, &err);
+            Err(err)
+        }
+        None => Ok(()),
+    };
 
-    Ok(())
+    connection_state.ended();
+    result
 }
 
+/// Relays the handshake to the backend routed from its server address, and
+/// returns the connection to it.
 fn handle_handshake(
     client: &mut TcpStream,
-    server: &mut TcpStream,
     state: &mut ConnectionState,
-) -> Result<(), Error> {
-    // 0x00 intention
-    let packet = client.read_packet(state.packet_min_compression.is_some())?;
-    server.write_packet(&packet, state.packet_min_compression)?;
+) -> Result<TcpStream, Error> {
+    let (packet, compressed_size) =
+        client.read_packet_sized(state.packet_min_compression.is_some())?;
 
-    let handshake = handshake::Handshake::decode(&mut packet.data.as_ref())?;
+    let mut handshake = handshake::Handshake::from_packet(&packet)?;
     debug!("{handshake:?}");
 
+    let client_version = ProtocolVersion::from_protocol(handshake.protocol_version);
+    if let Some(inspector) = &state.inspector {
+        inspector.packet(
+            State::Handshake,
+            Direction::Serverbound,
+            client_version.unwrap_or(ProtocolVersion::LATEST),
+            &packet,
+            compressed_size,
+        );
+    }
+    if let Some(metrics) = &state.metrics {
+        metrics.packet(
+            Direction::Serverbound,
+            State::Handshake,
+            client_version
+                .unwrap_or(ProtocolVersion::LATEST)
+                .packet_name(State::Handshake, Direction::Serverbound, packet.id),
+            compressed_size,
+        );
+        metrics.handshake(match handshake.intent {
+            handshake::Intent::Status => "status",
+            handshake::Intent::Login => "login",
+            handshake::Intent::Transfer => "transfer",
+        });
+    }
+
+    let backend = state
+        .config
+        .route(&handshake.server_address)
+        .cloned()
+        .ok_or_else(|| Error::NoBackend(handshake.server_address.clone()))?;
+    let server_version = backend.protocol;
+    state.connection.backend = Some(backend.name.clone());
+    state.connection.port = handshake.server_port;
+
     match handshake.intent {
+        // the status exchange is the same for every version
         handshake::Intent::Status => state.stage = ConnectionStage::Status,
-        handshake::Intent::Login => state.stage = ConnectionStage::Login,
-        handshake::Intent::Transfer => {
+        handshake::Intent::Login | handshake::Intent::Transfer => {
             // after a transfer command from the Configuration or Play state, we should
             // recieve a handshake with the Transfer intent, and then a login packet
-            state.stage = ConnectionStage::Login;
+            route_login(client, state, &handshake, server_version)?;
         }
     }
+    if handshake.intent == handshake::Intent::Transfer {
+        // the proxy decides which transfers to accept, the backend sees a
+        // plain login
+        state.connection.transferred = true;
+        handshake.intent = handshake::Intent::Login;
+    }
+
+    let connecting = Instant::now();
+    let mut server = TcpStream::connect(&backend.address).map_err(Error::BackendConnect)?;
+    if let Some(metrics) = &state.metrics {
+        metrics.backend_connected(connecting.elapsed());
+    }
+    _ = server.set_nodelay(true);
+    debug!(
+        "Relaying to backend {} at {}",
+        backend.name, backend.address
+    );
+
+    let forward = state.config.forwarding == Forwarding::Legacy
+        && handshake.intent != handshake::Intent::Status;
+    if forward {
+        // the uuid is appended once the login start is read
+        let client_ip = state.connection.client.ip();
+        handshake.server_address = format!("{}\0{client_ip}", handshake.server_address);
+        state.pending_handshake = Some(handshake);
+    } else if !state.connection.transferred {
+        server.write_packet(&packet, state.packet_min_compression)?;
+    } else {
+        server.write_packet(&handshake.to_packet()?, state.packet_min_compression)?;
+    }
+
+    Ok(server)
+}
 
+/// Picks how a login is relayed to a backend of `server` version: as is,
+/// or opaque when the proxy does not know the client's version but the
+/// backend may. Clients of another version than `server` are refused.
+fn route_login(
+    client: &mut TcpStream,
+    state: &mut ConnectionState,
+    handshake: &handshake::Handshake,
+    server: Option<ProtocolVersion>,
+) -> Result<(), Error> {
+    let protocol = handshake.protocol_version;
+    state.version = match (ProtocolVersion::from_protocol(protocol), server) {
+        (Some(version), None) => version,
+        (Some(version), Some(server)) if version == server => version,
+        (None, None) => {
+            // its transfers cannot be checked
+            if handshake.intent == handshake::Intent::Transfer
+                && state.config.transfers.accept != TransferPolicy::Any
+            {
+                return Err(Error::UnsupportedProtocolVersion(protocol));
+            }
+            debug!("Relaying unknown protocol {protocol} as is");
+            state.stage = ConnectionStage::Opaque;
+            return Ok(());
+        }
+        (_, Some(server)) => {
+            // the login disconnect is the same for every version
+            let reason = format!("This server runs {}", server.releases());
+            if let Some(packet) = crate::utils::disconnect(State::Login, server, &reason)? {
+                state.write(client, &packet)?;
+            }
+            return Err(Error::UnsupportedProtocolVersion(protocol));
+        }
+    };
+    state.stage = ConnectionStage::Login;
     Ok(())
 }
 
@@ -199,21 +922,24 @@ fn handle_status(
     state: &mut ConnectionState,
 ) -> Result<(), Error> {
     // 0x00 status_request
-    let packet = client.read_packet(state.packet_min_compression.is_some())?;
-    server.write_packet(&packet, state.packet_min_compression)?;
+    let (packet, size) = client.read_packet_sized(state.packet_min_compression.is_some())?;
+    state.record(State::Status, Direction::Serverbound, &packet, size);
+    send(client, server, state, Direction::Serverbound, packet)?;
 
     // 0x00 status_response
-    let packet = server.read_packet(state.packet_min_compression.is_some())?;
-    let packet = crate::utils::inject_status_description_message(&packet)?;
-    client.write_packet(&packet, state.packet_min_compression)?;
+    let (packet, size) = server.read_packet_sized(state.packet_min_compression.is_some())?;
+    state.record(State::Status, Direction::Clientbound, &packet, size);
+    send(client, server, state, Direction::Clientbound, packet)?;
 
     // 0x01 ping_request
-    let packet = client.read_packet(state.packet_min_compression.is_some())?;
-    server.write_packet(&packet, state.packet_min_compression)?;
+    let (packet, size) = client.read_packet_sized(state.packet_min_compression.is_some())?;
+    state.record(State::Status, Direction::Serverbound, &packet, size);
+    send(client, server, state, Direction::Serverbound, packet)?;
 
     // 0x01 pong_response
-    let packet = server.read_packet(state.packet_min_compression.is_some())?;
-    client.write_packet(&packet, state.packet_min_compression)?;
+    let (packet, size) = server.read_packet_sized(state.packet_min_compression.is_some())?;
+    state.record(State::Status, Direction::Clientbound, &packet, size);
+    send(client, server, state, Direction::Clientbound, packet)?;
 
     state.stage = ConnectionStage::End;
 
@@ -224,51 +950,85 @@ fn handle_login(
     client: &mut TcpStream,
     server: &mut TcpStream,
     state: &mut ConnectionState,
+    download_dir: Option<&Path>,
 ) -> Result<(), Error> {
-    // 0x00 hello
-    let packet = client.read_packet(state.packet_min_compression.is_some())?;
-    server.write_packet(&packet, state.packet_min_compression)?;
+    let (packet, size) = client.read_packet_sized(state.packet_min_compression.is_some())?;
+    state.record(State::Login, Direction::Serverbound, &packet, size);
 
-    let hello = login::Hello::decode(&mut packet.data.as_ref())?;
+    let hello = login::Hello::from_packet_versioned(state.version, &packet)?;
     debug!("{hello:?}");
 
+    let player = Player {
+        name: hello.name.clone(),
+        uuid: hello.uuid.clone(),
+    };
+    if state.connection.transferred {
+        admit_transfer(client, state, &player)?;
+    }
+
+    // the name the client sent becomes a directory
+    if let Some(dir) = download_dir {
+        if download::is_player_name(&hello.name) {
+            state.download = Some(Arc::new(Mutex::new(WorldDownload::new(
+                dir.join(&hello.name),
+                &hello.name,
+            ))));
+        } else {
+            warn!(
+                "Not downloading the world of invalid player name {:?}",
+                hello.name
+            );
+        }
+    }
+
+    if let Some(mut handshake) = state.pending_handshake.take() {
+        handshake.server_address = format!(
+            "{}\0{:032x}",
+            handshake.server_address,
+            hello.uuid.as_u128()
+        );
+        server.write_packet(&handshake.to_packet()?, state.packet_min_compression)?;
+    }
+    state.connection.player = Some(player);
+    send(client, server, state, Direction::Serverbound, packet)?;
+
     loop {
-        let packet = server.read_packet(state.packet_min_compression.is_some())?;
-        client.write_packet(&packet, state.packet_min_compression)?;
+        let (packet, size) = server.read_packet_sized(state.packet_min_compression.is_some())?;
+        state.record(State::Login, Direction::Clientbound, &packet, size);
 
-        let mut data = packet.data.as_ref();
+        let login_packet = LoginClientbound::from_packet_versioned(state.version, &packet)?;
+        trace!(
+            "{state:?}: Received from server: {id:#04X} {name}",
+            id = packet.id,
+            name = login_packet.name()
+        );
+
+        send(client, server, state, Direction::Clientbound, packet)?;
 
-        match packet.id {
-            // 0x00 login_disconnect
-            0x00 => {
-                trace!("{state:?}: Received from server: 0x00 login_disconnect");
+        if let Some(metrics) = &state.metrics {
+            match login_packet {
+                LoginClientbound::LoginDisconnect(_) => metrics.login("disconnect"),
+                LoginClientbound::LoginCompression(_) => metrics.login("compression"),
+                LoginClientbound::LoginFinished(_) => metrics.login("finished"),
+                _ => {}
+            }
+        }
+
+        match login_packet {
+            LoginClientbound::LoginDisconnect(_) => {
                 state.stage = ConnectionStage::End;
                 break;
             }
-            // 0x01 hello (set encryption)
-            0x01 => {
-                trace!("{state:?}: Received from server: 0x01 hello");
-                // 0x01 key
-                let packet = client.read_packet(state.packet_min_compression.is_some())?;
-                server.write_packet(&packet, state.packet_min_compression)?;
-                trace!("{state:?}: Sent to server: 0x01 key");
+            LoginClientbound::EncryptionRequest(_) => {
+                forward_client_packet::<login::Key>(client, server, state)?;
             }
-            // 0x02 login_finished
-            0x02 => {
-                trace!("{state:?}: Received from server: 0x02 login_finished");
-                // 0x03 login_acknowledged
-                let packet = client.read_packet(state.packet_min_compression.is_some())?;
-                server.write_packet(&packet, state.packet_min_compression)?;
-                trace!("{state:?}: Sent to server: 0x03 login_acknowledged");
+            LoginClientbound::LoginFinished(_) => {
+                forward_client_packet::<login::LoginAcknowledged>(client, server, state)?;
 
                 state.stage = ConnectionStage::Configuration;
                 break;
             }
-            // 0x03 login_compression (set compression)
-            0x03 => {
-                trace!("{state:?}: Received from server: 0x03 login_compression");
-                let login_compression = login::LoginCompression::decode(&mut data)?;
-
+            LoginClientbound::LoginCompression(login_compression) => {
                 if login_compression.size >= 0 {
                     let min_packet_compression = login_compression.size.cast_unsigned() as usize;
                     state.packet_min_compression = Some(min_packet_compression);
@@ -276,58 +1036,630 @@ fn handle_login(
                     state.packet_min_compression = None;
                 }
             }
-            // 0x04 custom_query
-            0x04 => {
-                trace!("{state:?}: Received from server: 0x04 custom_query");
-                // 0x02 custom_query_answer
-                let packet = client.read_packet(state.packet_min_compression.is_some())?;
-                server.write_packet(&packet, state.packet_min_compression)?;
-                trace!("{state:?}: Sent to server: 0x02 custom_query_answer");
+            LoginClientbound::CustomQuery(_) => {
+                forward_client_packet::<login::CustomQueryAnswer>(client, server, state)?;
             }
-            // 0x05 cookie_request
-            0x05 => {
-                trace!("{state:?}: Received from server: 0x05 cookie_request");
-                // 0x04 cookie_response
-                let packet = client.read_packet(state.packet_min_compression.is_some())?;
-                server.write_packet(&packet, state.packet_min_compression)?;
-                trace!("{state:?}: Sent to server: 0x04 cookie_response");
+            LoginClientbound::CookieRequest(_) => {
+                forward_client_packet::<login::CookieResponse>(client, server, state)?;
             }
-            _ => return Err(Error::UnknownPacketId(packet.id)),
         }
     }
 
     Ok(())
 }
 
+/// Relays a connection whose version the proxy does not know, decoding
+/// nothing past the handshake but the login start, to forward the player.
+fn relay_opaque(
+    mut client: TcpStream,
+    mut server: TcpStream,
+    state: &mut ConnectionState,
+) -> Result<(), Error> {
+    if let Some(mut handshake) = state.pending_handshake.take() {
+        // every version starts the login with the name, and ends it with
+        // the uuid
+        let packet = client.read_packet(false)?;
+        let mut data = packet.data.as_ref();
+        String::decode(&mut data)?;
+        let uuid = data
+            .last_chunk::<16>()
+            .map(|uuid| u128::from_be_bytes(*uuid))
+            .ok_or(Error::UnsupportedProtocolVersion(
+                handshake.protocol_version,
+            ))?;
+        handshake.server_address = format!("{}\0{uuid:032x}", handshake.server_address);
+        server.write_packet(&handshake.to_packet()?, None)?;
+        server.write_packet(&packet, None)?;
+    }
+
+    let mut client_read = client.try_clone().map_err(Error::TcpStreamClone)?;
+    let mut server_read = server.try_clone().map_err(Error::TcpStreamClone)?;
+    let upstream = thread::spawn(move || {
+        _ = io::copy(&mut client_read, &mut server);
+        _ = server.shutdown(Shutdown::Both);
+    });
+    _ = io::copy(&mut server_read, &mut client);
+    _ = client.shutdown(Shutdown::Both);
+    _ = upstream.join();
+    state.stage = ConnectionStage::End;
+    Ok(())
+}
+
+/// Lets a transferred client log in if `transfers.accept` allows it, asking
+/// for its origin cookie when the transfer must be signed.
+fn admit_transfer(
+    client: &mut TcpStream,
+    state: &ConnectionState,
+    player: &Player,
+) -> Result<(), Error> {
+    let transfers = &state.config.transfers;
+    let refused = match transfers.accept {
+        TransferPolicy::Any => return Ok(()),
+        TransferPolicy::Refuse => "This server does not accept transfers".to_owned(),
+        TransferPolicy::Signed => {
+            let request = login::CookieRequest {
+                key: ORIGIN_COOKIE.to_owned(),
+            };
+            let version = state.version;
+            state.write(client, &request.to_packet_versioned(version)?)?;
+            let (packet, size) =
+                client.read_packet_sized(state.packet_min_compression.is_some())?;
+            state.record(State::Login, Direction::Serverbound, &packet, size);
+            let response = login::CookieResponse::from_packet_versioned(version, &packet)?;
+            let origin = match response.payload {
+                Some(payload) if response.key == ORIGIN_COOKIE => {
+                    Origin::verify(&payload, transfers, player)
+                }
+                _ => Err(OriginError::Missing),
+            };
+            match origin {
+                Ok(origin) => {
+                    info!("{} was transferred by {}", player.name, origin.proxy);
+                    return Ok(());
+                }
+                Err(err) => format!("Transfer refused: {err}"),
+            }
+        }
+    };
+    if let Some(packet) = crate::utils::disconnect(State::Login, state.version, &refused)? {
+        state.write(client, &packet)?;
+    }
+    Err(Error::Kicked(refused))
+}
+
+/// Forwards the client's reply to a server packet, which must be a `P`.
+fn forward_client_packet<P: ProtocolPacket>(
+    client: &mut TcpStream,
+    server: &mut TcpStream,
+    state: &ConnectionState,
+) -> Result<(), Error> {
+    let (packet, size) = client.read_packet_sized(state.packet_min_compression.is_some())?;
+    state.record(P::STATE, P::DIRECTION, &packet, size);
+    let id = state.version.packet_id(P::STATE, P::DIRECTION, P::NAME);
+    if id != Some(packet.id) {
+        return Err(DecodeError::UnknownPacketId(packet.id).into());
+    }
+    trace!(
+        "{state:?}: Sent to server: {id:#04X} {name}",
+        id = packet.id,
+        name = P::NAME
+    );
+    send(client, server, state, Direction::Serverbound, packet)
+}
+
+/// Writes a packet of the current stage, and the packets handlers inject,
+/// to their receivers.
+fn send(
+    client: &mut TcpStream,
+    server: &mut TcpStream,
+    state: &ConnectionState,
+    direction: Direction,
+    packet: Packet,
+) -> Result<(), Error> {
+    let Some(protocol_state) = state.stage.protocol_state() else {
+        return Ok(());
+    };
+    let mut intercepted = state.intercept(protocol_state, direction, packet)?;
+    for (direction, packet) in intercepted.packets(direction) {
+        let to = match direction {
+            Direction::Serverbound => &mut *server,
+            Direction::Clientbound => &mut *client,
+        };
+        state.write(to, &packet)?;
+    }
+    intercepted.result()
+}
+
+/// A packet after the handlers, and the packets they
+/// injected.
+#[derive(Debug, Default)]
+struct Intercepted {
+    forwarded: Option<Packet>,
+    injected: Vec<(Direction, Packet)>,
+    /// Why a handler ended the connection, once the packets are written.
+    disconnect: Option<String>,
+}
+
+impl Intercepted {
+    /// The packets to write in order, with their direction.
+    fn packets(
+        &mut self,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Direction, Packet)> {
+        self.forwarded
+            .take()
+            .map(|packet| (direction, packet))
+            .into_iter()
+            .chain(self.injected.drain(..))
+    }
+
+    /// Ends the connection if a handler asked to.
+    fn result(self) -> Result<(), Error> {
+        match self.disconnect {
+            Some(reason) => Err(Error::Kicked(reason)),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Relay {
     ClientToServer,
     ServerToClient,
 }
 
-fn relay(
+/// Closes both sides of a connection, so that the relay of the other
+/// direction ends too.
+fn shutdown(
+    to: &Mutex<TcpStream>,
+    back: &Mutex<TcpStream>,
+) {
+    for stream in [to, back] {
+        _ = stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .shutdown(Shutdown::Both);
+    }
+}
+
+/// Relays packets in one direction until the sender closes the connection,
+/// counting the error that ended it otherwise. Packets injected back to the
+/// sender are written to `back`.
+fn relay<W: io::Write>(
     relay: Relay,
-    mut client: TcpStream,
-    mut server: TcpStream,
-    min_compression: Option<usize>,
+    from: impl io::Read,
+    to: &Mutex<W>,
+    back: &Mutex<W>,
+    state: &mut ConnectionState,
+) -> Result<(), Error> {
+    relay_packets(relay, from, to, back, state).inspect_err(|err| {
+        if let Some(metrics) = &state.metrics {
+            metrics.relay_error(err);
+        }
+    })
+}
+
+fn relay_packets<W: io::Write>(
+    relay: Relay,
+    from: impl io::Read,
+    to: &Mutex<W>,
+    back: &Mutex<W>,
+    state: &mut ConnectionState,
 ) -> Result<(), Error> {
-    let (from, to) = match relay {
-        Relay::ClientToServer => (&mut client, &mut server),
-        Relay::ServerToClient => (&mut server, &mut client),
+    let direction = match relay {
+        Relay::ClientToServer => Direction::Serverbound,
+        Relay::ServerToClient => Direction::Clientbound,
     };
+    let mut from = BufReader::new(from);
+    // the world view understands the latest layout only
+    let observed = state.version == ProtocolVersion::LATEST;
 
     loop {
         // Check if EOF has been reached
-        if let Ok(n) = from.peek(&mut [0_u8])
-            && n == 0
+        if let Ok(buffer) = from.fill_buf()
+            && buffer.is_empty()
         {
             // Connection closed
             return Ok(());
         }
 
-        let packet = from.read_packet(min_compression.is_some())?;
-        to.write_packet(&packet, min_compression)?;
+        let (packet, size) = from.read_packet_sized(state.packet_min_compression.is_some())?;
+
+        let Some(protocol_state) = state.stage.protocol_state() else {
+            return Ok(());
+        };
+        let name = state
+            .version
+            .packet_name(protocol_state, direction, packet.id);
+
+        debug!(
+            "{relay:?} {stage:?} {id:#04X} {name} ({size} bytes)",
+            stage = state.stage,
+            id = packet.id,
+            name = name.unwrap_or("unknown"),
+            size = packet.data.len()
+        );
+        state.record(protocol_state, direction, &packet, size);
+
+        // chunks refer to the registries sent during configuration
+        let tracked = name.filter(|name| match state.stage {
+            ConnectionStage::Play => true,
+            ConnectionStage::Configuration => *name == "registry_data",
+            _ => false,
+        });
+        if observed && let Some(name) = tracked {
+            observe(state, direction, name, &packet);
+        }
+
+        let mut intercepted = state.intercept(protocol_state, direction, packet)?;
+        let plays = name
+            .filter(|_| direction == Direction::Clientbound)
+            .and_then(|name| state.plays_after(name));
+        // packets sent from outside the relays stop before the client
+        // leaves play, and start once it entered it
+        if plays == Some(false) {
+            state.players.playing(state.connection.id, false);
+        }
+        for (packet_direction, packet) in intercepted.packets(direction) {
+            let out = if packet_direction == direction {
+                to
+            } else {
+                back
+            };
+            state.write(
+                &mut *out.lock().unwrap_or_else(PoisonError::into_inner),
+                &packet,
+            )?;
+        }
+        intercepted.result()?;
+
+        if plays == Some(true) {
+            state.players.playing(state.connection.id, true);
+        }
+        if let Some(name) = name {
+            state.stage = state.stage.after(direction, name);
+        }
+    }
+}
+
+/// Feeds a play packet, or registry data, to the world view and the world
+/// download. Packets they cannot decode are only logged, the relay goes on.
+fn observe(
+    state: &ConnectionState,
+    direction: Direction,
+    name: &str,
+    packet: &Packet,
+) {
+    if let Some(world) = &state.world
+        && let Ok(mut world) = world.lock()
+        && let Err(err) = world.observe(direction, name, &packet.data)
+    {
+        debug!("Failed to track {name} in the world view: {err}");
+    }
+    if direction == Direction::Clientbound
+        && let Some(download) = &state.download
+        && let Ok(mut download) = download.lock()
+        && let Err(err) = download.observe(name, &packet.data)
+    {
+        debug!("Failed to download {name}: {err}");
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{
+        Read as _,
+        Write as _,
+    };
+
+    use codec::nbt::Nbt;
+    use data::anvil::{
+        DiskChunk,
+        Region,
+    };
+    use data::model::play;
+    use data::registry::{
+        Block,
+        BlockEntityType,
+        BlockState,
+        Registry as _,
+    };
+    use data::world::{
+        BlockPos,
+        ChunkPos,
+    };
+
+    use super::*;
+    use crate::plugin::{
+        PacketHandler,
+        Verdict,
+    };
+
+    fn state(stage: ConnectionStage) -> ConnectionState {
+        ConnectionState {
+            stage,
+            connection: plugin::tests::connection(),
+            config: Arc::new(plugin::tests::config()),
+            handlers: Handlers::default(),
+            version: ProtocolVersion::LATEST,
+            packet_min_compression: None,
+            pending_handshake: None,
+            world: None,
+            download: None,
+            inspector: None,
+            metrics: None,
+            players: Arc::default(),
+        }
+    }
+
+    #[test]
+    fn relay_tracks_play() {
+        let packet = |name| {
+            let id = ProtocolVersion::LATEST
+                .packet_id(State::Play, Direction::Clientbound, name)
+                .unwrap();
+            Packet::new(id, b"")
+        };
+        let mut state = state(ConnectionStage::Play);
+        state.players.joined(&state.connection, None);
+        let relayed = |state: &mut ConnectionState, name| {
+            let mut traffic = Vec::new();
+            traffic.write_packet(&packet(name), None).unwrap();
+            let (output, back) = (Mutex::new(Vec::new()), Mutex::new(Vec::new()));
+            relay(
+                Relay::ServerToClient,
+                traffic.as_slice(),
+                &output,
+                &back,
+                state,
+            )
+            .unwrap();
+        };
+
+        relayed(&mut state, "login");
+        assert_eq!(
+            state.players.in_play().len(),
+            1,
+            "the client plays once it logged in"
+        );
+        relayed(&mut state, "start_configuration");
+        assert!(
+            state.players.in_play().is_empty(),
+            "the client is configured again"
+        );
+        assert_eq!(state.stage, ConnectionStage::Configuration);
+    }
+
+    #[test]
+    fn relay_unknown_versions_as_is() {
+        let handshake = |protocol_version| handshake::Handshake {
+            protocol_version,
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            intent: handshake::Intent::Login,
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut accepted, _) = listener.accept().unwrap();
+        let mut server = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (backend, _) = listener.accept().unwrap();
+
+        let mut routed = state(ConnectionStage::Handshake);
+        let latest = Some(ProtocolVersion::LATEST);
+        route_login(&mut accepted, &mut routed, &handshake(771), latest).unwrap_err();
+        let refused = login::LoginDisconnect::from_packet(&client.read_packet(false).unwrap());
+        assert_eq!(
+            refused.unwrap().reason["text"],
+            "This server runs 1.21.7-1.21.8"
+        );
+        route_login(&mut accepted, &mut routed, &handshake(773), None).unwrap();
+        assert_eq!(routed.stage, ConnectionStage::Opaque);
+        routed.pending_handshake = Some(handshake(773));
+        let relayed = thread::spawn(move || relay_opaque(accepted, backend, &mut routed));
+
+        let hello = login::Hello {
+            name: "Steve".to_owned(),
+            uuid: codec::Uuid::from_u128(0xAB),
+        };
+        client
+            .write_packet(&hello.to_packet().unwrap(), None)
+            .unwrap();
+        client.write_all(b"anything").unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+
+        let forwarded = handshake::Handshake::from_packet(&server.read_packet(false).unwrap());
+        assert_eq!(
+            forwarded.unwrap().server_address,
+            format!("localhost\0{:032x}", 0xAB),
+            "the player is forwarded"
+        );
+        server.read_packet(false).unwrap();
+        let mut rest = Vec::new();
+        server.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"anything", "the rest is relayed as is");
+        relayed.join().unwrap().unwrap();
+    }
+
+    /// Records the block state at a position as each packet is handled.
+    struct Watch(BlockPos, Arc<Mutex<Vec<Option<i32>>>>);
+
+    impl PacketHandler for Watch {
+        fn name(&self) -> &'static str { "watch" }
+
+        fn wants_world(&self) -> bool { true }
+
+        fn handle(
+            &mut self,
+            context: &mut Context<'_>,
+            packet: Packet,
+        ) -> Result<Verdict, Error> {
+            let world = context.world().expect("the world is kept for watch");
+            self.1.lock().unwrap().push(world.block_state(self.0));
+            Ok(Verdict::Forward(packet))
+        }
+    }
+
+    #[test]
+    fn relay_world_to_handlers() {
+        let block = BlockPos::new(1, 64, 2);
+        let stone = Block::Stone.default_state().id();
+        let mut traffic = Vec::new();
+        for packet in [
+            world::tests::chunk(0, 0).to_packet(),
+            play::BlockUpdate {
+                position: block,
+                block_state: stone,
+            }
+            .to_packet(),
+        ] {
+            traffic.write_packet(&packet.unwrap(), None).unwrap();
+        }
+
+        let mut pipeline = Pipeline::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let watched = Arc::clone(&seen);
+        pipeline.register(move || Box::new(Watch(block, Arc::clone(&watched))));
+        let mut state = state(ConnectionStage::Play);
+        assert!(
+            !Handlers::default().wants_world(),
+            "no handler reads the world"
+        );
+        state.handlers = pipeline.handlers();
+        assert!(state.handlers.wants_world(), "watch reads the world");
+        state.world = Some(Arc::new(Mutex::new(WorldView::new())));
+        let (output, back) = (Mutex::new(Vec::new()), Mutex::new(Vec::new()));
+        relay(
+            Relay::ServerToClient,
+            traffic.as_slice(),
+            &output,
+            &back,
+            &mut state,
+        )
+        .unwrap();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [Some(BlockState::AIR.id()), Some(stone)],
+            "handlers see the world up to the handled packet"
+        );
+    }
+
+    /// A login into the overworld, then chunk 0, 0 with a stone block and a
+    /// sign, unloaded at the end.
+    fn world_traffic(stone: i32) -> Vec<u8> {
+        let block = BlockPos::new(1, 64, 2);
+        let sign = BlockPos::new(1, 65, 2);
+        let mut text = Nbt::compound();
+        text.insert("text", Nbt::String("hello".to_owned()));
+        let packets = [
+            play::Login {
+                entity_id: 1,
+                hardcore: false,
+                dimensions: vec!["minecraft:overworld".parse().unwrap()],
+                max_players: 20,
+                view_distance: 10,
+                simulation_distance: 10,
+                reduced_debug_info: false,
+                show_death_screen: true,
+                do_limited_crafting: false,
+                spawn: world::tests::spawn_info("minecraft:overworld"),
+                enforces_secure_chat: false,
+            }
+            .to_packet(),
+            world::tests::chunk(0, 0).to_packet(),
+            play::BlockUpdate {
+                position: block,
+                block_state: stone,
+            }
+            .to_packet(),
+            play::BlockEntityData {
+                position: sign,
+                kind: BlockEntityType::Sign.id(),
+                data: Some(text),
+            }
+            .to_packet(),
+            play::ForgetLevelChunk {
+                z: 0,
+                x: 0,
+            }
+            .to_packet(),
+        ];
+        let mut traffic = Vec::new();
+        for packet in packets {
+            traffic.write_packet(&packet.unwrap(), None).unwrap();
+        }
+        traffic
+    }
+
+    #[test]
+    fn relay_downloads_world() {
+        let dir = std::env::temp_dir().join(format!("proxy-download-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let stone = Block::Stone.default_state().id();
+        let traffic = world_traffic(stone);
+
+        let mut download = WorldDownload::new(dir.clone(), "Archive");
+        for registry in world::tests::registries() {
+            let packet = registry.to_packet().unwrap();
+            download.observe("registry_data", &packet.data).unwrap();
+        }
+        let download = Arc::new(Mutex::new(download));
+        let mut state = state(ConnectionStage::Play);
+        state.download = Some(Arc::clone(&download));
+        let (output, back) = (Mutex::new(Vec::new()), Mutex::new(Vec::new()));
+        relay(
+            Relay::ServerToClient,
+            traffic.as_slice(),
+            &output,
+            &back,
+            &mut state,
+        )
+        .unwrap();
+        assert_eq!(
+            output.into_inner().unwrap(),
+            traffic,
+            "packets should be relayed unchanged"
+        );
+        assert!(back.into_inner().unwrap().is_empty(), "nothing is injected");
+
+        assert!(
+            dir.join("region").join(Region::file_name(0, 0)).exists(),
+            "the region should be written once its last chunk unloads"
+        );
+        download.lock().unwrap().save().unwrap();
+        assert!(
+            dir.join("level.dat").exists(),
+            "level.dat should be written"
+        );
+        let region = Region::read(&dir.join("region"), 0, 0).unwrap();
+        let saved = region
+            .get(ChunkPos {
+                x: 0,
+                z: 0,
+            })
+            .unwrap();
+        let biomes = saved
+            .nbt
+            .get("sections")
+            .and_then(Nbt::as_list)
+            .and_then(|sections| sections[1].get("biomes")?.get("palette"))
+            .unwrap();
+        assert_eq!(
+            biomes,
+            &Nbt::list(vec![Nbt::String("minecraft:plains".to_owned())]),
+            "biomes are named from the registry the backend sent"
+        );
+        let chunk = DiskChunk::from_nbt(&saved.nbt).unwrap();
+        assert_eq!(chunk.sections[8].block_state(1, 0, 2), stone);
+        assert_eq!(chunk.block_entities.len(), 1, "the sign should be saved");
+        assert_eq!(
+            chunk.block_entities[0].get("text").and_then(Nbt::as_str),
+            Some("hello")
+        );
 
-        debug!("{relay:?} {packet:?}");
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,231 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{
+    AtomicUsize,
+    Ordering,
+};
+use core::time::Duration;
+use std::io::{
+    self,
+    BufRead as _,
+    BufReader,
+    Read,
+    Write as _,
+};
+use std::net::{
+    TcpListener,
+    TcpStream,
+};
+use std::thread;
+use std::time::Instant;
+
+use log::{
+    debug,
+    error,
+};
+
+use super::Metrics;
+
+/// Time given to a scraper to send its whole request, and then to read the
+/// response.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Header lines read before giving up on a request.
+const MAX_HEADERS: usize = 100;
+/// Bytes of a request read before giving up on it.
+const MAX_REQUEST_LEN: u64 = 8 << 10;
+/// Requests answered at once, those beyond are closed unanswered.
+const MAX_REQUESTS: usize = 4;
+
+/// Answers `GET /metrics` with the metrics, each connection on its own
+/// thread so that a slow scraper does not hold up the others, up to
+/// [`MAX_REQUESTS`] at once.
+pub fn serve(
+    listener: &TcpListener,
+    metrics: &Arc<Metrics>,
+) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let Some(slot) = Slot::take(&active) else {
+                    debug!("Closed a metrics connection, {MAX_REQUESTS} are already open");
+                    continue;
+                };
+                let metrics = Arc::clone(metrics);
+                thread::spawn(move || {
+                    if let Err(err) = respond(&stream, &metrics) {
+                        debug!("Failed to answer a metrics request: {err}");
+                    }
+                    drop(slot);
+                });
+            }
+            Err(err) => error!("Failed to accept metrics connection: {err}"),
+        }
+    }
+}
+
+/// One of the [`MAX_REQUESTS`] requests answered at once, given back when
+/// dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_REQUESTS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) { self.0.fetch_sub(1, Ordering::AcqRel); }
+}
+
+/// Reads from a stream until a deadline, however the reads are spread.
+struct Deadline<'stream> {
+    stream: &'stream TcpStream,
+    at: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(
+        &mut self,
+        buf: &mut [u8],
+    ) -> io::Result<usize> {
+        let remaining = self.at.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+}
+
+fn respond(
+    stream: &TcpStream,
+    metrics: &Metrics,
+) -> io::Result<()> {
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let deadline = Deadline {
+        stream,
+        at: Instant::now() + REQUEST_TIMEOUT,
+    };
+    let mut reader = BufReader::new(deadline.take(MAX_REQUEST_LEN));
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    for _ in 0..MAX_HEADERS {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut request = request.split_whitespace();
+    let (status, body) = match (request.next(), request.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", metrics.render()),
+        (Some("GET"), _) => ("404 Not Found", "Not found\n".to_owned()),
+        _ => ("405 Method Not Allowed", "Method not allowed\n".to_owned()),
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4; \
+         charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn try_get(
+        port: u16,
+        path: &str,
+    ) -> io::Result<String> {
+        let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n")?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    fn get(
+        port: u16,
+        path: &str,
+    ) -> String {
+        try_get(port, path).unwrap()
+    }
+
+    #[test]
+    fn serves_metrics() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let metrics = Arc::new(Metrics::default());
+        metrics.connection_accepted();
+        let served = Arc::clone(&metrics);
+        thread::spawn(move || serve(&listener, &served));
+
+        let response = get(port, "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(
+            response.ends_with(&metrics.render()),
+            "the body should be the metrics: {response}"
+        );
+        assert!(
+            response.contains("\nproxy_connections_accepted_total 1\n"),
+            "{response}"
+        );
+
+        let response = get(port, "/");
+        assert!(
+            response.starts_with("HTTP/1.1 404 Not Found\r\n"),
+            "{response}"
+        );
+    }
+
+    #[test]
+    fn limit_open_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let metrics = Arc::new(Metrics::default());
+        thread::spawn(move || serve(&listener, &metrics));
+
+        let stalled: Vec<_> = (0..MAX_REQUESTS)
+            .map(|_| TcpStream::connect(("127.0.0.1", port)).unwrap())
+            .collect();
+        let mut refused = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let mut response = String::new();
+        refused.read_to_string(&mut response).unwrap();
+        assert_eq!(response, "", "requests beyond the limit should be closed");
+
+        drop(stalled);
+        let answered = (0..50).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            try_get(port, "/metrics").is_ok_and(|response| response.starts_with("HTTP/1.1 200"))
+        });
+        assert!(answered, "closed requests should free their slot");
+    }
+
+    #[test]
+    fn slow_scraper_does_not_block() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let metrics = Arc::new(Metrics::default());
+        thread::spawn(move || serve(&listener, &metrics));
+
+        let mut stalled = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stalled, "GET /metrics HTTP/1.1\r\n").unwrap();
+        let started = Instant::now();
+        let response = get(port, "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(
+            started.elapsed() < REQUEST_TIMEOUT,
+            "the second scrape should not wait for the first"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Prometheus metrics of the proxy, served over HTTP in the text exposition
+//! format.
+//!
+//! Histograms are kept in integer units, a fixed fraction of their base
+//! unit, and only printed as decimals.
+
+mod http;
+
+use alloc::collections::BTreeMap;
+use core::fmt::Write as _;
+use core::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use core::time::Duration;
+use std::sync::{
+    Mutex,
+    PoisonError,
+};
+
+use data::protocol::{
+    Direction,
+    State,
+};
+pub use http::serve;
+
+use crate::error::Error;
+use crate::inspect::{
+    direction,
+    stage,
+};
+
+/// Labels of a packet: direction, stage and name.
+type PacketLabels = (&'static str, &'static str, &'static str);
+
+#[derive(Debug)]
+pub struct Metrics {
+    connections_accepted: Counter<()>,
+    connections_failed: Counter<&'static str>,
+    handshakes: Counter<&'static str>,
+    logins: Counter<&'static str>,
+    packets: Counter<PacketLabels>,
+    bytes: Counter<PacketLabels>,
+    relay_errors: Counter<&'static str>,
+    compression_ratio: Histogram,
+    backend_connect: Histogram,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            connections_accepted: Counter::new(
+                "proxy_connections_accepted_total",
+                "Client connections accepted.",
+                |()| String::new(),
+            ),
+            connections_failed: Counter::new(
+                "proxy_connections_failed_total",
+                "Connections ended by an error, by the stage they were in.",
+                |stage| format!("stage=\"{stage}\""),
+            ),
+            handshakes: Counter::new(
+                "proxy_handshakes_total",
+                "Handshakes, by intent.",
+                |intent| format!("intent=\"{intent}\""),
+            ),
+            logins: Counter::new(
+                "proxy_login_outcomes_total",
+                "Login packets from the server, by outcome.",
+                |outcome| format!("outcome=\"{outcome}\""),
+            ),
+            packets: Counter::new(
+                "proxy_packets_total",
+                "Packets read, by direction, stage and name.",
+                packet_labels,
+            ),
+            bytes: Counter::new(
+                "proxy_bytes_total",
+                "Bytes read on the wire, by direction, stage and packet name.",
+                packet_labels,
+            ),
+            relay_errors: Counter::new(
+                "proxy_relay_errors_total",
+                "Errors ending a relay, by kind.",
+                |error| format!("error=\"{error}\""),
+            ),
+            compression_ratio: Histogram::new(
+                "proxy_compression_ratio",
+                "Compressed size over uncompressed size of the packets written compressed.",
+                100,
+                &[10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 110],
+            ),
+            backend_connect: Histogram::new(
+                "proxy_backend_connect_seconds",
+                "Time taken to connect to the server.",
+                1_000_000,
+                &[
+                    500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000,
+                    1_000_000,
+                ],
+            ),
+        }
+    }
+}
+
+fn packet_labels((direction, stage, name): &PacketLabels) -> String {
+    format!("direction=\"{direction}\",stage=\"{stage}\",packet=\"{name}\"")
+}
+
+impl Metrics {
+    pub fn connection_accepted(&self) { self.connections_accepted.add((), 1); }
+
+    /// A connection ended with an error while in `stage`, `backend` when
+    /// the server could not be reached.
+    pub fn connection_failed(
+        &self,
+        stage: &'static str,
+    ) {
+        self.connections_failed.add(stage, 1);
+    }
+
+    pub fn handshake(
+        &self,
+        intent: &'static str,
+    ) {
+        self.handshakes.add(intent, 1);
+    }
+
+    pub fn login(
+        &self,
+        outcome: &'static str,
+    ) {
+        self.logins.add(outcome, 1);
+    }
+
+    /// A packet of `wire_size` bytes was read. Packets the protocol version
+    /// does not name are counted together under `unknown`, so that clients
+    /// sending made up ids cannot grow the label set.
+    pub fn packet(
+        &self,
+        packet_direction: Direction,
+        state: State,
+        name: Option<&'static str>,
+        wire_size: usize,
+    ) {
+        let labels = (
+            direction(packet_direction),
+            stage(state),
+            name.unwrap_or("unknown"),
+        );
+        self.packets.add(labels, 1);
+        self.bytes.add(labels, wire_size as u64);
+    }
+
+    pub fn relay_error(
+        &self,
+        error: &Error,
+    ) {
+        self.relay_errors.add(error.kind(), 1);
+    }
+
+    /// A packet of `uncompressed` bytes was written compressed in
+    /// `compressed` bytes.
+    pub fn compressed(
+        &self,
+        uncompressed: usize,
+        compressed: usize,
+    ) {
+        if let Some(ratio) = compressed.saturating_mul(100).checked_div(uncompressed) {
+            self.compression_ratio.observe(ratio as u64);
+        }
+    }
+
+    pub fn backend_connected(
+        &self,
+        took: Duration,
+    ) {
+        self.backend_connect
+            .observe(u64::try_from(took.as_micros()).unwrap_or(u64::MAX));
+    }
+
+    /// Every metric, in the text exposition format.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.connections_accepted.render(&mut out);
+        self.connections_failed.render(&mut out);
+        self.handshakes.render(&mut out);
+        self.logins.render(&mut out);
+        self.packets.render(&mut out);
+        self.bytes.render(&mut out);
+        self.relay_errors.render(&mut out);
+        self.compression_ratio.render(&mut out);
+        self.backend_connect.render(&mut out);
+        out
+    }
+}
+
+/// A counter for each value of its labels.
+#[derive(Debug)]
+struct Counter<K> {
+    name: &'static str,
+    help: &'static str,
+    /// Prints the labels of a value, without braces.
+    labels: fn(&K) -> String,
+    values: Mutex<BTreeMap<K, u64>>,
+}
+
+impl<K: Ord> Counter<K> {
+    const fn new(
+        name: &'static str,
+        help: &'static str,
+        labels: fn(&K) -> String,
+    ) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn add(
+        &self,
+        key: K,
+        value: u64,
+    ) {
+        let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+        *values.entry(key).or_default() += value;
+    }
+
+    fn render(
+        &self,
+        out: &mut String,
+    ) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} counter", self.name);
+        let values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+        for (key, value) in values.iter() {
+            let labels = (self.labels)(key);
+            if labels.is_empty() {
+                let _ = writeln!(out, "{} {value}", self.name);
+            } else {
+                let _ = writeln!(out, "{}{{{labels}}} {value}", self.name);
+            }
+        }
+    }
+}
+
+/// A histogram of values in `1 / scale` units.
+#[derive(Debug)]
+struct Histogram {
+    name: &'static str,
+    help: &'static str,
+    /// Units in one of the base unit, a power of ten.
+    scale: u64,
+    /// Upper bounds of the buckets, in units.
+    bounds: &'static [u64],
+    /// Observations in each bucket, and past the last one.
+    buckets: Vec<AtomicU64>,
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    fn new(
+        name: &'static str,
+        help: &'static str,
+        scale: u64,
+        bounds: &'static [u64],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            scale,
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(
+        &self,
+        value: u64,
+    ) {
+        let bucket = self.bounds.partition_point(|&bound| bound < value);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+    }
+
+    fn decimal(
+        &self,
+        value: u64,
+    ) -> String {
+        let digits = self.scale.ilog10() as usize;
+        if digits == 0 {
+            return value.to_string();
+        }
+        format!("{}.{:0digits$}", value / self.scale, value % self.scale)
+    }
+
+    fn render(
+        &self,
+        out: &mut String,
+    ) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} histogram", self.name);
+        let mut count = 0;
+        for (bound, bucket) in self.bounds.iter().zip(&self.buckets) {
+            count += bucket.load(Ordering::Relaxed);
+            let le = self.decimal(*bound);
+            let _ = writeln!(out, "{}_bucket{{le=\"{le}\"}} {count}", self.name);
+        }
+        if let Some(last) = self.buckets.last() {
+            count += last.load(Ordering::Relaxed);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {count}", self.name);
+        let sum = self.decimal(self.sum.load(Ordering::Relaxed));
+        let _ = writeln!(out, "{}_sum {sum}", self.name);
+        let _ = writeln!(out, "{}_count {count}", self.name);
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_counters_and_histograms() {
+        let metrics = Metrics::default();
+        metrics.connection_accepted();
+        metrics.connection_accepted();
+        metrics.handshake("login");
+        metrics.packet(
+            Direction::Clientbound,
+            State::Play,
+            Some("level_chunk_with_light"),
+            1200,
+        );
+        metrics.packet(
+            Direction::Clientbound,
+            State::Play,
+            Some("level_chunk_with_light"),
+            800,
+        );
+        metrics.packet(Direction::Serverbound, State::Play, None, 10);
+        metrics.packet(Direction::Serverbound, State::Play, None, 20);
+        metrics.compressed(1000, 250);
+        metrics.compressed(1000, 1050);
+        metrics.backend_connected(Duration::from_micros(1_500));
+
+        let text = metrics.render();
+        for line in [
+            "# TYPE proxy_connections_accepted_total counter",
+            "proxy_connections_accepted_total 2",
+            "proxy_handshakes_total{intent=\"login\"} 1",
+            "proxy_packets_total{direction=\"clientbound\",stage=\"play\",packet=\"\
+             level_chunk_with_light\"} 2",
+            "proxy_bytes_total{direction=\"clientbound\",stage=\"play\",packet=\"\
+             level_chunk_with_light\"} 2000",
+            "proxy_packets_total{direction=\"serverbound\",stage=\"play\",packet=\"unknown\"} 2",
+            "proxy_bytes_total{direction=\"serverbound\",stage=\"play\",packet=\"unknown\"} 30",
+            "proxy_compression_ratio_bucket{le=\"0.20\"} 0",
+            "proxy_compression_ratio_bucket{le=\"0.30\"} 1",
+            "proxy_compression_ratio_bucket{le=\"1.10\"} 2",
+            "proxy_compression_ratio_bucket{le=\"+Inf\"} 2",
+            "proxy_compression_ratio_sum 1.30",
+            "proxy_backend_connect_seconds_bucket{le=\"0.001000\"} 0",
+            "proxy_backend_connect_seconds_bucket{le=\"0.002500\"} 1",
+            "proxy_backend_connect_seconds_sum 0.001500",
+            "proxy_backend_connect_seconds_count 1",
+        ] {
+            assert!(
+                text.lines().any(|text| text == line),
+                "missing {line:?} in:\n{text}"
+            );
+        }
+        assert!(
+            text.contains("# TYPE proxy_relay_errors_total counter\n# HELP"),
+            "metrics without values should only be described"
+        );
+    }
+}
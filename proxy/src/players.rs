@@ -0,0 +1,284 @@
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+use std::net::TcpStream;
+use std::sync::{
+    Mutex,
+    MutexGuard,
+    PoisonError,
+};
+
+use data::packet::{
+    Packet,
+    WritePacket as _,
+};
+use data::version::ProtocolVersion;
+use log::debug;
+
+use crate::error::Error;
+use crate::plugin::ConnectionInfo;
+
+/// The players past login on every listener, by connection.
+#[derive(Debug, Default)]
+pub struct Players {
+    online: Mutex<BTreeMap<u64, Online>>,
+}
+
+#[derive(Debug, Clone)]
+struct Online {
+    connection: ConnectionInfo,
+    client: Option<Client>,
+    /// Whether the client is in play, where it accepts play packets.
+    playing: bool,
+    /// The cookies the client was seen storing or sending, by key.
+    cookies: BTreeMap<String, Vec<u8>>,
+}
+
+/// Writes packets to a client from outside its relays.
+#[derive(Debug, Clone)]
+pub struct Client {
+    stream: Arc<Mutex<TcpStream>>,
+    pub version: ProtocolVersion,
+    compression: Option<usize>,
+    /// Whether the client plays, only changed with `stream` locked.
+    playing: Arc<AtomicBool>,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(
+        stream: Arc<Mutex<TcpStream>>,
+        version: ProtocolVersion,
+        compression: Option<usize>,
+    ) -> Self {
+        Self {
+            stream,
+            version,
+            compression,
+            playing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Writes the play packet `packet`, in the version of the client,
+    /// between the relayed ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotPlaying`] if the client is not in play anymore,
+    /// checked with the stream locked so that no packet follows the one
+    /// taking the client out of play.
+    pub fn send(
+        &self,
+        packet: &Packet,
+    ) -> Result<(), Error> {
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        if !self.playing.load(Ordering::Acquire) {
+            return Err(Error::NotPlaying);
+        }
+        stream.write_packet(packet, self.compression)?;
+        Ok(())
+    }
+
+    /// Waits for the packet being sent, if any, then lets packets be sent
+    /// or not.
+    fn set_playing(
+        &self,
+        playing: bool,
+    ) {
+        let _stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        self.playing.store(playing, Ordering::Release);
+    }
+}
+
+impl Players {
+    /// Counts the player of `connection` as online, if it logged in.
+    /// `client` lets packets be sent to it once it plays.
+    pub fn joined(
+        &self,
+        connection: &ConnectionInfo,
+        client: Option<Client>,
+    ) {
+        if connection.player.is_some() {
+            self.lock().insert(connection.id, Online {
+                connection: connection.clone(),
+                client,
+                playing: false,
+                cookies: BTreeMap::new(),
+            });
+        }
+    }
+
+    /// The connection numbered `id` ended.
+    pub fn left(
+        &self,
+        id: u64,
+    ) {
+        self.lock().remove(&id);
+    }
+
+    /// The client of connection `id` entered play, or is about to leave
+    /// it to be configured again. Once this returns, [`Client::send`] no
+    /// longer writes to a client leaving play.
+    pub fn playing(
+        &self,
+        id: u64,
+        playing: bool,
+    ) {
+        let client = self.lock().get_mut(&id).and_then(|online| {
+            online.playing = playing;
+            online.client.clone()
+        });
+        // the stream is not locked with the players, it may be slow
+        if let Some(client) = client {
+            client.set_playing(playing);
+        }
+    }
+
+    /// The connections of the online players, oldest first.
+    #[must_use]
+    pub fn online(&self) -> Vec<ConnectionInfo> {
+        self.lock()
+            .values()
+            .map(|online| online.connection.clone())
+            .collect()
+    }
+
+    /// The connections of the players in play, oldest first.
+    #[must_use]
+    pub fn in_play(&self) -> Vec<ConnectionInfo> {
+        self.lock()
+            .values()
+            .filter(|online| online.playing)
+            .map(|online| online.connection.clone())
+            .collect()
+    }
+
+    /// Remembers that the client of connection `id` holds the cookie
+    /// `key`.
+    pub fn store_cookie(
+        &self,
+        id: u64,
+        key: String,
+        payload: Vec<u8>,
+    ) {
+        if let Some(online) = self.lock().get_mut(&id) {
+            online.cookies.insert(key, payload);
+        }
+    }
+
+    /// The cookies the client of connection `id` is known to hold, by key.
+    #[must_use]
+    pub fn cookies(
+        &self,
+        id: u64,
+    ) -> BTreeMap<String, Vec<u8>> {
+        self.lock()
+            .get(&id)
+            .map(|online| online.cookies.clone())
+            .unwrap_or_default()
+    }
+
+    /// The clients in play that packets can be sent to, with their
+    /// connection.
+    #[must_use]
+    pub fn clients(&self) -> Vec<(ConnectionInfo, Client)> {
+        self.lock()
+            .values()
+            .filter(|online| online.playing)
+            .filter_map(|online| Some((online.connection.clone(), online.client.clone()?)))
+            .collect()
+    }
+
+    /// Sends each client in play the packet `packet` makes for its
+    /// connection, in its version, if any. Failures are only logged, the
+    /// relays of the client notice them.
+    pub fn broadcast(
+        &self,
+        packet: impl Fn(&ConnectionInfo, ProtocolVersion) -> Result<Option<Packet>, Error>,
+    ) {
+        // the lock is not held while writing to slow clients
+        for (connection, client) in self.clients() {
+            let sent = packet(&connection, client.version)
+                .and_then(|packet| packet.map_or(Ok(()), |packet| client.send(&packet)));
+            if let Err(err) = sent {
+                debug!("Failed to send to connection {}: {err}", connection.id);
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u64, Online>> {
+        self.online.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use data::packet::ReadPacket as _;
+
+    use super::*;
+    use crate::plugin::tests::connection;
+
+    #[test]
+    fn broadcast_to_players_in_play() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut received, _) = listener.accept().unwrap();
+        let client = Client::new(Arc::new(Mutex::new(stream)), ProtocolVersion::LATEST, None);
+
+        let players = Players::default();
+        let mut anonymous = connection();
+        anonymous.id = 2;
+        anonymous.player = None;
+        players.joined(&anonymous, None);
+        players.joined(&connection(), Some(client));
+        assert_eq!(players.online().len(), 1, "only players are online");
+
+        players.broadcast(|_, _| Ok(Some(Packet::new(0x01, b"configuring"))));
+        players.playing(1, true);
+        assert_eq!(players.in_play().len(), 1);
+        players.broadcast(|_, _| Ok(Some(Packet::new(0x01, b"playing"))));
+        let packet = received.read_packet(false).unwrap();
+        assert_eq!(
+            packet.data.as_ref(),
+            b"playing",
+            "only clients in play receive"
+        );
+
+        players.left(1);
+        assert!(players.online().is_empty(), "the player left");
+    }
+
+    #[test]
+    fn stop_sending_when_leaving_play() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut received, _) = listener.accept().unwrap();
+        let client = Client::new(Arc::new(Mutex::new(stream)), ProtocolVersion::LATEST, None);
+
+        let players = Players::default();
+        players.joined(&connection(), Some(client));
+        players.playing(1, true);
+        let clients = players.clients();
+        players.playing(1, false);
+
+        // a broadcast that listed the client before it left play
+        let (_, client) = &clients[0];
+        assert!(
+            matches!(
+                client.send(&Packet::new(0x01, b"late")),
+                Err(Error::NotPlaying)
+            ),
+            "nothing should follow the end of play"
+        );
+        players.playing(1, true);
+        client.send(&Packet::new(0x01, b"again")).unwrap();
+        let packet = received.read_packet(false).unwrap();
+        assert_eq!(packet.data.as_ref(), b"again");
+    }
+}
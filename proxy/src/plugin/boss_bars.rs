@@ -0,0 +1,241 @@
+//! Boss bars shown by the proxy to every player, besides those of the
+//! backends.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::sync::{
+    Mutex,
+    PoisonError,
+};
+use std::thread;
+
+use codec::Uuid;
+use data::model::play::{
+    self,
+    BossBarColor,
+    BossBarOverlay,
+    BossEventAction,
+};
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    ProtocolPacket as _,
+    State,
+};
+use data::text::json_to_nbt;
+use data::version::ProtocolVersion;
+
+use super::{
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::config::SharedConfig;
+use crate::error::Error;
+use crate::players::Players;
+
+/// Id of the bar showing the configured `boss_bar`.
+const CONFIGURED: Uuid = Uuid::from_u128(0x7072_6F78_792D_626F_7373_2D62_6172_0001);
+/// How often the configured bar is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BossBar {
+    pub title: String,
+    /// From 0 to 1.
+    pub progress: f32,
+    pub color: BossBarColor,
+    pub overlay: BossBarOverlay,
+}
+
+impl BossBar {
+    /// A full white bar titled `title`.
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            progress: 1.0,
+            color: BossBarColor::White,
+            overlay: BossBarOverlay::Progress,
+        }
+    }
+
+    fn show(
+        &self,
+        id: Uuid,
+        version: ProtocolVersion,
+    ) -> Result<Packet, Error> {
+        Ok(play::BossEvent {
+            id,
+            action: BossEventAction::Add {
+                title: json_to_nbt(&json::object! {
+                    text: self.title.as_str(),
+                }),
+                progress: self.progress,
+                color: self.color,
+                overlay: self.overlay,
+                flags: 0,
+            },
+        }
+        .to_packet_versioned(version)?)
+    }
+}
+
+/// The bars shown to every player in play, and to those who join.
+#[derive(Debug)]
+pub struct BossBars {
+    players: Arc<Players>,
+    bars: Mutex<BTreeMap<u128, BossBar>>,
+}
+
+impl BossBars {
+    #[must_use]
+    pub const fn new(players: Arc<Players>) -> Self {
+        Self {
+            players,
+            bars: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Shows `bar` to every player, replacing the bar `id` if shown.
+    pub fn show(
+        &self,
+        id: &Uuid,
+        bar: BossBar,
+    ) {
+        let mut bars = self.bars.lock().unwrap_or_else(PoisonError::into_inner);
+        if bars.get(&id.as_u128()) == Some(&bar) {
+            return;
+        }
+        self.players
+            .broadcast(|_, version| bar.show(id.clone(), version).map(Some));
+        bars.insert(id.as_u128(), bar);
+    }
+
+    /// Hides the bar `id` from every player.
+    pub fn hide(
+        &self,
+        id: &Uuid,
+    ) {
+        let mut bars = self.bars.lock().unwrap_or_else(PoisonError::into_inner);
+        if bars.remove(&id.as_u128()).is_none() {
+            return;
+        }
+        self.players.broadcast(|_, version| {
+            let hide = play::BossEvent {
+                id: id.clone(),
+                action: BossEventAction::Remove,
+            };
+            Ok(Some(hide.to_packet_versioned(version)?))
+        });
+    }
+
+    /// A handler showing the bars to its client when it enters play.
+    #[must_use]
+    pub fn handler(self: &Arc<Self>) -> Box<dyn PacketHandler> {
+        Box::new(BossBarHandler(Arc::clone(self)))
+    }
+}
+
+/// Shows the configured bar, following the configuration as it reloads.
+pub fn watch(
+    bars: Arc<BossBars>,
+    config: Arc<SharedConfig>,
+) {
+    let update = move || match &config.get().boss_bar {
+        Some(title) => bars.show(&CONFIGURED, BossBar::new(title)),
+        None => bars.hide(&CONFIGURED),
+    };
+    update();
+    thread::spawn(move || {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            update();
+        }
+    });
+}
+
+#[derive(Debug)]
+struct BossBarHandler(Arc<BossBars>);
+
+impl PacketHandler for BossBarHandler {
+    fn name(&self) -> &'static str { "boss_bars" }
+
+    fn interested(
+        &self,
+        state: State,
+        direction: Direction,
+    ) -> bool {
+        state == State::Play && direction == Direction::Clientbound
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        let name = context
+            .version
+            .packet_name(context.state, context.direction, packet.id);
+        // the client forgets its bars with the world it leaves
+        if name == Some("login") {
+            let bars = self.0.bars.lock().unwrap_or_else(PoisonError::into_inner);
+            for (&id, bar) in bars.iter() {
+                let packet = bar.show(Uuid::from_u128(id), context.version)?;
+                context.inject(Direction::Clientbound, packet);
+            }
+        }
+        Ok(Verdict::Forward(packet))
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    #[test]
+    fn show_bars_on_login() {
+        let bars = Arc::new(BossBars::new(Arc::default()));
+        bars.show(&CONFIGURED, BossBar::new("Hello"));
+        bars.show(&Uuid::from_u128(1), BossBar::new("Event"));
+        bars.hide(&Uuid::from_u128(1));
+
+        let (connection, config) = (connection(), config());
+        let version = ProtocolVersion::LATEST;
+        let mut context = Context::new(
+            &connection,
+            &config,
+            State::Play,
+            Direction::Clientbound,
+            version,
+        );
+        let login = Packet::new(
+            ProtocolVersion::LATEST
+                .packet_id(State::Play, Direction::Clientbound, "login")
+                .unwrap(),
+            b"",
+        );
+        bars.handler().handle(&mut context, login).unwrap();
+
+        let injected = context.take_injected();
+        assert_eq!(injected.len(), 1, "hidden bars should not be shown");
+        let event = play::BossEvent::from_packet(&injected[0].1).unwrap();
+        assert_eq!(event.id, CONFIGURED);
+        let BossEventAction::Add {
+            title, ..
+        } = event.action
+        else {
+            panic!("the bar should be added: {:?}", event.action);
+        };
+        assert_eq!(
+            title.get("text").and_then(|text| text.as_str()),
+            Some("Hello")
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Commands answered by the proxy itself, such as `/glist`.
+//!
+//! Their chat commands are answered with a system message and never reach
+//! the backend. They are added to the command graph the backend sends, in
+//! place of its own commands of the same name, so that clients suggest
+//! them. Their arguments are plain strings, which clients never sign.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt;
+use std::time::Instant;
+
+use data::command::{
+    CommandGraph,
+    CommandNode,
+};
+use data::model::play;
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    ProtocolPacket as _,
+    State,
+};
+use data::version::ProtocolVersion;
+use log::{
+    debug,
+    warn,
+};
+
+use super::{
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::config::TransferPolicy;
+use crate::error::Error;
+use crate::players::Players;
+use crate::transfer;
+use crate::utils::system_chat;
+
+/// Answers a command given its arguments, the text after its name.
+type Command = dyn Fn(&Context<'_>, &str) -> String + Send + Sync;
+
+/// The proxy commands by name, as a handler of every connection.
+#[derive(Clone, Default)]
+pub struct ProxyCommands {
+    commands: BTreeMap<String, Arc<Command>>,
+}
+
+impl fmt::Debug for ProxyCommands {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_list().entries(self.commands.keys()).finish()
+    }
+}
+
+impl ProxyCommands {
+    /// The built-in commands: `/proxyinfo`, `/glist`, `/server` and
+    /// `/cookies`.
+    #[must_use]
+    pub fn builtin(
+        players: Arc<Players>,
+        started: Instant,
+    ) -> Self {
+        let mut commands = Self::default();
+        let online = Arc::clone(&players);
+        commands.register("proxyinfo", move |_, _| {
+            let uptime = started.elapsed().as_secs();
+            format!(
+                "{} {} up for {}h {}m {}s, {} players online",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION"),
+                uptime / 3600,
+                uptime / 60 % 60,
+                uptime % 60,
+                online.online().len(),
+            )
+        });
+        let holders = Arc::clone(&players);
+        commands.register("cookies", move |context, _| {
+            let cookies = holders.cookies(context.connection.id);
+            if cookies.is_empty() {
+                return "No cookies stored".to_owned();
+            }
+            let listed: Vec<_> = cookies
+                .iter()
+                .map(|(key, payload)| format!("{key} ({} bytes)", payload.len()))
+                .collect();
+            format!("Cookies: {}", listed.join(", "))
+        });
+        let clients = Arc::clone(&players);
+        commands.register("glist", move |_, _| glist(&players));
+        commands.register("server", move |context, arguments| {
+            server(&clients, context, arguments.trim())
+        });
+        commands
+    }
+
+    /// Adds the command `name`, replacing the one of that name if any.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        command: impl Fn(&Context<'_>, &str) -> String + Send + Sync + 'static,
+    ) {
+        self.commands.insert(name.into(), Arc::new(command));
+    }
+
+    /// The answer to `command`, or `None` if it is not a proxy command.
+    fn run(
+        &self,
+        context: &Context<'_>,
+        command: &str,
+    ) -> Option<String> {
+        let (name, arguments) = command.split_once(' ').unwrap_or((command, ""));
+        let answer = self.commands.get(name)?;
+        Some(answer(context, arguments))
+    }
+
+    /// Replaces the commands of `graph` named like proxy commands with
+    /// them, each taking an optional string of arguments.
+    fn inject(
+        &self,
+        graph: &mut CommandGraph,
+    ) {
+        let mut commands = CommandGraph::new();
+        for name in self.commands.keys() {
+            graph.remove(name);
+            let arguments = commands.push(CommandNode {
+                executable: true,
+                ..CommandNode::greedy_string("arguments".to_owned())
+            });
+            let command = commands.push(CommandNode {
+                executable: true,
+                children: vec![arguments],
+                ..CommandNode::literal(name.clone())
+            });
+            commands.nodes[0].children.push(command);
+        }
+        graph.merge(&commands);
+    }
+}
+
+/// Lists the online players by backend.
+fn glist(players: &Players) -> String {
+    let online = players.online();
+    let mut by_backend = BTreeMap::<_, Vec<_>>::new();
+    for connection in &online {
+        if let Some(player) = &connection.player {
+            let backend = connection.backend.as_deref().unwrap_or("none");
+            by_backend
+                .entry(backend)
+                .or_default()
+                .push(player.name.as_str());
+        }
+    }
+    let mut lines = vec![format!("{} players online", online.len())];
+    for (backend, names) in by_backend {
+        lines.push(format!(
+            "[{backend}] ({}): {}",
+            names.len(),
+            names.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Lists the backends, or moves the player to the backend `name` by
+/// transferring it back to the proxy with a host routed to that backend.
+fn server(
+    players: &Players,
+    context: &Context<'_>,
+    name: &str,
+) -> String {
+    let config = context.config;
+    let current = context.connection.backend.as_deref().unwrap_or("none");
+    let names: Vec<_> = config
+        .backends
+        .iter()
+        .map(|backend| backend.name.as_str())
+        .collect();
+    if name.is_empty() {
+        return format!(
+            "You are connected to {current}. Servers: {}",
+            names.join(", ")
+        );
+    }
+    if name == current {
+        return format!("You are already connected to {name}");
+    }
+    if !names.contains(&name) {
+        return format!("Unknown server {name}");
+    }
+    if config.transfers.accept == TransferPolicy::Refuse {
+        return "Moving between servers is disabled".to_owned();
+    }
+    let Some(host) = config
+        .routing
+        .hosts
+        .iter()
+        .find_map(|(host, backend)| (backend == name).then_some(host))
+    else {
+        return format!("{name} has no host to be reached at");
+    };
+    let Some((_, client)) = players
+        .clients()
+        .into_iter()
+        .find(|(connection, _)| connection.id == context.connection.id)
+    else {
+        return "You can only move once playing".to_owned();
+    };
+    let port = context.connection.port;
+    match transfer::transfer(context.connection, &client, &config.transfers, (host, port)) {
+        Ok(()) => format!("Moving to {name}"),
+        Err(err) => format!("Failed to move to {name}: {err}"),
+    }
+}
+
+impl PacketHandler for ProxyCommands {
+    fn name(&self) -> &'static str { "proxy_commands" }
+
+    fn interested(
+        &self,
+        state: State,
+        _: Direction,
+    ) -> bool {
+        state == State::Play
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        let version = context.version;
+        let name = version.packet_name(context.state, context.direction, packet.id);
+        match (context.direction, name) {
+            (Direction::Serverbound, Some("chat")) => {
+                let chat = match play::Chat::from_packet_versioned(version, &packet) {
+                    Ok(chat) => chat,
+                    Err(err) => {
+                        warn!("Failed to read a chat message: {err}");
+                        return Ok(Verdict::Forward(packet));
+                    }
+                };
+                let player = context.connection.player.as_ref();
+                debug!(
+                    "<{}> {}",
+                    player.map_or("?", |player| player.name.as_str()),
+                    chat.message
+                );
+            }
+            (Direction::Serverbound, Some("chat_command")) => {
+                let command = play::ChatCommand::from_packet_versioned(version, &packet)?;
+                if let Some(answer) = self.run(context, &command.command) {
+                    let packet = system_chat(context.version, &answer)?;
+                    context.inject(Direction::Clientbound, packet);
+                    return Ok(Verdict::Drop);
+                }
+            }
+            // argument parsers are only known by their latest ids
+            (Direction::Clientbound, Some("commands")) if version == ProtocolVersion::LATEST => {
+                let mut commands = match play::Commands::from_packet(&packet) {
+                    Ok(commands) => commands,
+                    Err(err) => {
+                        warn!("Failed to add the proxy commands: {err}");
+                        return Ok(Verdict::Forward(packet));
+                    }
+                };
+                self.inject(&mut commands.graph);
+                return Ok(Verdict::Forward(commands.to_packet()?));
+            }
+            _ => {}
+        }
+        Ok(Verdict::Forward(packet))
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::net::{
+        TcpListener,
+        TcpStream,
+    };
+    use std::sync::Mutex;
+
+    use data::packet::ReadPacket as _;
+
+    use super::*;
+    use crate::config::Backend;
+    use crate::players::Client;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    /// Handles `packet` sent in `direction`, returning the forwarded packet
+    /// and the injected ones.
+    fn handle(
+        commands: &mut ProxyCommands,
+        direction: Direction,
+        packet: Packet,
+    ) -> (Option<Packet>, Vec<(Direction, Packet)>) {
+        let (connection, config) = (connection(), config());
+        let version = ProtocolVersion::LATEST;
+        let mut context = Context::new(&connection, &config, State::Play, direction, version);
+        let packet = match commands.handle(&mut context, packet).unwrap() {
+            Verdict::Forward(packet) => Some(packet),
+            Verdict::Drop => None,
+        };
+        (packet, context.take_injected())
+    }
+
+    fn command(command: &str) -> Packet {
+        play::ChatCommand {
+            command: command.to_owned(),
+        }
+        .to_packet()
+        .unwrap()
+    }
+
+    #[test]
+    fn answer_proxy_commands() {
+        let players = Arc::new(Players::default());
+        players.joined(&connection(), None);
+        let mut commands = ProxyCommands::builtin(players, Instant::now());
+
+        let (forwarded, injected) = handle(&mut commands, Direction::Serverbound, command("glist"));
+        assert!(
+            forwarded.is_none(),
+            "proxy commands should not be forwarded"
+        );
+        assert_eq!(injected.len(), 1, "the player should be answered");
+        let answer = play::SystemChat::from_packet(&injected[0].1).unwrap();
+        let answer = format!("{:?}", answer.content);
+        assert!(answer.contains("[server] (1): Steve"), "{answer}");
+
+        let (forwarded, _) = handle(
+            &mut commands,
+            Direction::Serverbound,
+            command("server server"),
+        );
+        assert!(forwarded.is_none(), "arguments should not matter");
+
+        let (forwarded, injected) = handle(&mut commands, Direction::Serverbound, command("give"));
+        assert!(forwarded.is_some(), "backend commands should be forwarded");
+        assert!(injected.is_empty(), "backend commands are not answered");
+    }
+
+    #[test]
+    fn forward_chat() {
+        let chat = play::Chat {
+            message: "hello".to_owned(),
+            timestamp: 0,
+            salt: 0,
+            signature: None,
+            offset: 0,
+            acknowledged: [0; 3],
+            checksum: 0,
+        };
+        // 1.21.4 clients send no checksum
+        let version = ProtocolVersion::V1_21_4;
+        let packet = chat.to_packet_versioned(version).unwrap();
+        let latest = chat.to_packet().unwrap();
+        assert_eq!(packet.data.len(), latest.data.len() - 1, "{packet:?}");
+        let read = play::Chat::from_packet_versioned(version, &packet).unwrap();
+        assert_eq!(read.message, "hello");
+
+        let (connection, config) = (connection(), config());
+        let mut commands = ProxyCommands::builtin(Arc::default(), Instant::now());
+        let state = (State::Play, Direction::Serverbound);
+        let mut context = Context::new(&connection, &config, state.0, state.1, version);
+        let forwarded = commands.handle(&mut context, packet.clone()).unwrap();
+        assert!(
+            matches!(forwarded, Verdict::Forward(forwarded) if forwarded.data == packet.data),
+            "the chat should be forwarded as is"
+        );
+
+        let truncated = Packet::new(packet.id, &packet.data[..4]);
+        let forwarded = commands.handle(&mut context, truncated).unwrap();
+        assert!(
+            matches!(forwarded, Verdict::Forward(forwarded) if forwarded.data.len() == 4),
+            "unreadable chat should be forwarded too"
+        );
+    }
+
+    #[test]
+    fn move_between_servers() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut received, _) = listener.accept().unwrap();
+        let client = Client::new(Arc::new(Mutex::new(stream)), ProtocolVersion::LATEST, None);
+        let players = Arc::new(Players::default());
+        let connection = connection();
+        players.joined(&connection, Some(client));
+        players.playing(connection.id, true);
+
+        let mut config = config();
+        config.backends.push(Backend {
+            name: "survival".to_owned(),
+            address: "localhost:25566".to_owned(),
+            protocol: None,
+        });
+        let version = ProtocolVersion::LATEST;
+        let context = Context::new(
+            &connection,
+            &config,
+            State::Play,
+            Direction::Serverbound,
+            version,
+        );
+        assert_eq!(
+            server(&players, &context, "survival"),
+            "survival has no host to be reached at"
+        );
+
+        config
+            .routing
+            .hosts
+            .insert("survival.example.com".to_owned(), "survival".to_owned());
+        let context = Context::new(
+            &connection,
+            &config,
+            State::Play,
+            Direction::Serverbound,
+            version,
+        );
+        assert_eq!(server(&players, &context, "survival"), "Moving to survival");
+        let transfer = play::Transfer::from_packet(&received.read_packet(false).unwrap()).unwrap();
+        assert_eq!(
+            (transfer.host.as_str(), transfer.port),
+            ("survival.example.com", 25565),
+            "the player should come back on the port it used"
+        );
+    }
+
+    #[test]
+    fn inject_into_command_graph() {
+        let mut graph = CommandGraph::new();
+        let give = graph.push(CommandNode::literal("give".to_owned()));
+        let server = graph.push(CommandNode::literal("server".to_owned()));
+        graph.nodes[0].children = vec![give, server];
+        let packet = play::Commands {
+            graph,
+        }
+        .to_packet()
+        .unwrap();
+
+        let mut commands = ProxyCommands::builtin(Arc::default(), Instant::now());
+        let (forwarded, _) = handle(&mut commands, Direction::Clientbound, packet);
+        let graph = play::Commands::from_packet(&forwarded.unwrap())
+            .unwrap()
+            .graph;
+        let names: Vec<_> = graph
+            .children(graph.root)
+            .filter_map(|(_, node)| node.name())
+            .collect();
+        assert_eq!(names, ["give", "cookies", "glist", "proxyinfo", "server"]);
+        assert!(
+            graph.find(&["server", "arguments"]).is_some(),
+            "the proxy's server takes arguments"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Keeps track of the cookies stored on the clients, see
+//! [`Players::cookies`].
+
+use alloc::sync::Arc;
+
+use data::model::{
+    configuration,
+    play,
+};
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    ProtocolPacket as _,
+    State,
+};
+use data::version::ProtocolVersion;
+use log::warn;
+
+use super::{
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::error::Error;
+use crate::players::Players;
+use crate::transfer::ORIGIN_COOKIE;
+
+/// Records the cookies the backends store and those the client sends
+/// back, and keeps the backends from overwriting [`ORIGIN_COOKIE`].
+#[derive(Debug)]
+pub struct CookieJar {
+    players: Arc<Players>,
+}
+
+impl CookieJar {
+    #[must_use]
+    pub const fn new(players: Arc<Players>) -> Self {
+        Self {
+            players,
+        }
+    }
+}
+
+impl PacketHandler for CookieJar {
+    fn name(&self) -> &'static str { "cookies" }
+
+    fn interested(
+        &self,
+        state: State,
+        _: Direction,
+    ) -> bool {
+        matches!(state, State::Configuration | State::Play)
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        // cookies came with 1.20.5
+        if context.version < ProtocolVersion::V1_20_5 {
+            return Ok(Verdict::Forward(packet));
+        }
+        let version = context.version;
+        let name = version.packet_name(context.state, context.direction, packet.id);
+        let (key, payload) = match (context.state, name) {
+            (State::Configuration, Some("store_cookie")) => {
+                let cookie = configuration::StoreCookie::from_packet_versioned(version, &packet)?;
+                (cookie.key, cookie.payload)
+            }
+            (State::Play, Some("store_cookie")) => {
+                let cookie = play::StoreCookie::from_packet_versioned(version, &packet)?;
+                (cookie.key, cookie.payload)
+            }
+            (State::Configuration, Some("cookie_response")) => {
+                let response =
+                    configuration::CookieResponse::from_packet_versioned(version, &packet)?;
+                let Some(payload) = response.payload else {
+                    return Ok(Verdict::Forward(packet));
+                };
+                (response.key, payload)
+            }
+            (State::Play, Some("cookie_response")) => {
+                let response = play::CookieResponse::from_packet_versioned(version, &packet)?;
+                let Some(payload) = response.payload else {
+                    return Ok(Verdict::Forward(packet));
+                };
+                (response.key, payload)
+            }
+            _ => return Ok(Verdict::Forward(packet)),
+        };
+        if context.direction == Direction::Clientbound && key == ORIGIN_COOKIE {
+            warn!(
+                "Backend {} tried to overwrite the origin cookie",
+                context.connection.backend.as_deref().unwrap_or("?")
+            );
+            return Ok(Verdict::Drop);
+        }
+        self.players
+            .store_cookie(context.connection.id, key, payload);
+        Ok(Verdict::Forward(packet))
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    fn handle(
+        jar: &mut CookieJar,
+        direction: Direction,
+        packet: Packet,
+    ) -> Option<Packet> {
+        let connection = connection();
+        let config = config();
+        let version = ProtocolVersion::LATEST;
+        let mut context = Context::new(&connection, &config, State::Play, direction, version);
+        match jar.handle(&mut context, packet).unwrap() {
+            Verdict::Forward(packet) => Some(packet),
+            Verdict::Drop => None,
+        }
+    }
+
+    fn store(key: &str) -> Packet {
+        play::StoreCookie {
+            key: key.to_owned(),
+            payload: b"backend".to_vec(),
+        }
+        .to_packet()
+        .unwrap()
+    }
+
+    #[test]
+    fn record_cookies() {
+        let players = Arc::new(Players::default());
+        players.joined(&connection(), None);
+        let mut jar = CookieJar::new(Arc::clone(&players));
+
+        assert!(handle(&mut jar, Direction::Clientbound, store("lobby:seen")).is_some());
+        let response = play::CookieResponse {
+            key: "lobby:visits".to_owned(),
+            payload: Some(b"3".to_vec()),
+        }
+        .to_packet()
+        .unwrap();
+        assert!(handle(&mut jar, Direction::Serverbound, response).is_some());
+        assert!(
+            handle(&mut jar, Direction::Clientbound, store(ORIGIN_COOKIE)).is_none(),
+            "backends cannot overwrite the origin cookie"
+        );
+
+        let cookies = players.cookies(1);
+        assert_eq!(cookies.keys().collect::<Vec<_>>(), [
+            "lobby:seen",
+            "lobby:visits"
+        ]);
+        assert_eq!(cookies["lobby:visits"], b"3");
+    }
+}
@@ -0,0 +1,445 @@
+//! Packet handlers, run on every packet the proxy relays after the
+//! handshake, in the order they were registered.
+//!
+//! Handlers may forward packets, modified or not, or drop them. Packets
+//! they inject are written after the handled one. The client and the backend
+//! speak the same version, [`Context::version`], which every packet is in.
+
+pub mod boss_bars;
+mod commands;
+mod cookies;
+mod resource_pack;
+#[cfg(feature = "scripting")]
+pub mod script;
+mod status;
+mod tab_list;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use alloc::sync::Arc;
+use core::fmt;
+use core::net::SocketAddr;
+use std::sync::{
+    Mutex,
+    PoisonError,
+};
+
+use codec::Uuid;
+pub use commands::ProxyCommands;
+pub use cookies::CookieJar;
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    State,
+};
+use data::version::ProtocolVersion;
+pub use resource_pack::NetworkResourcePack;
+pub use status::StatusDescription;
+pub use tab_list::GlobalTabList;
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::world::WorldView;
+
+/// What becomes of a handled packet.
+#[allow(dead_code, reason = "API for packet handlers")]
+#[derive(Debug)]
+pub enum Verdict {
+    /// Passes the packet to the next handler, then to its receiver.
+    Forward(Packet),
+    /// Neither later handlers nor the receiver see the packet.
+    Drop,
+}
+
+pub trait PacketHandler: Send {
+    /// Name of the handler, for logs.
+    fn name(&self) -> &str;
+
+    /// Whether the handler wants the packets of `state` sent in
+    /// `direction`. The others skip it.
+    fn interested(
+        &self,
+        state: State,
+        direction: Direction,
+    ) -> bool {
+        let _ = (state, direction);
+        true
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error>;
+
+    /// Whether the handler reads [`Context::world`]. The world view of a
+    /// connection is only kept when one of its handlers does.
+    fn wants_world(&self) -> bool { false }
+
+    /// The client was routed to a backend, before any packet is handled.
+    fn connected(
+        &mut self,
+        connection: &ConnectionInfo,
+    ) {
+        let _ = connection;
+    }
+
+    /// A connected client left, with the error that ended the connection
+    /// if any.
+    fn disconnected(
+        &mut self,
+        error: Option<&str>,
+    ) {
+        let _ = error;
+    }
+}
+
+/// The player logged in on a connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub uuid: Uuid,
+}
+
+/// What handlers know of a connection.
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    /// Number of the connection since the proxy started, from 1.
+    pub id: u64,
+    pub client: SocketAddr,
+    /// Name of the backend the client was routed to, once handshaken.
+    pub backend: Option<String>,
+    /// Port of the proxy the client connected to, from its handshake.
+    pub port: u16,
+    /// Set from the login start.
+    pub player: Option<Player>,
+    /// Whether the client came with the transfer intent.
+    pub transferred: bool,
+}
+
+/// A packet being handled.
+#[allow(dead_code, reason = "API for packet handlers")]
+#[derive(Debug)]
+pub struct Context<'connection> {
+    pub connection: &'connection ConnectionInfo,
+    /// Configuration the connection was accepted with.
+    pub config: &'connection Config,
+    pub state: State,
+    pub direction: Direction,
+    /// Version of the client, and of the backend it is relayed to.
+    pub version: ProtocolVersion,
+    world: Option<&'connection WorldView>,
+    injected: Vec<(Direction, Packet)>,
+    disconnect: Option<String>,
+}
+
+impl<'connection> Context<'connection> {
+    #[must_use]
+    pub const fn new(
+        connection: &'connection ConnectionInfo,
+        config: &'connection Config,
+        state: State,
+        direction: Direction,
+        version: ProtocolVersion,
+    ) -> Self {
+        Self {
+            connection,
+            config,
+            state,
+            direction,
+            version,
+            world: None,
+            injected: Vec::new(),
+            disconnect: None,
+        }
+    }
+
+    /// Lets the handlers read `world`, the world the player sees.
+    #[must_use]
+    pub const fn with_world(
+        mut self,
+        world: &'connection WorldView,
+    ) -> Self {
+        self.world = Some(world);
+        self
+    }
+
+    /// The world the player sees up to the handled packet, kept only when a
+    /// handler [wants it](PacketHandler::wants_world).
+    #[allow(dead_code, reason = "API for packet handlers")]
+    #[must_use]
+    pub const fn world(&self) -> Option<&'connection WorldView> { self.world }
+
+    /// Sends `packet` in `direction` after the handled packet. It is written
+    /// as is, so it must be in [`Self::version`].
+    #[allow(dead_code, reason = "API for packet handlers")]
+    pub fn inject(
+        &mut self,
+        direction: Direction,
+        packet: Packet,
+    ) {
+        self.injected.push((direction, packet));
+    }
+
+    /// The packets injected so far, in order.
+    pub fn take_injected(&mut self) -> Vec<(Direction, Packet)> {
+        core::mem::take(&mut self.injected)
+    }
+
+    /// Ends the connection once the injected packets are written, showing
+    /// `reason` to the client. The handled packet is dropped.
+    #[allow(dead_code, reason = "API for packet handlers")]
+    pub fn disconnect(
+        &mut self,
+        reason: impl Into<String>,
+    ) {
+        self.disconnect = Some(reason.into());
+    }
+
+    /// The reason the connection should end for, if a handler ended it.
+    pub const fn take_disconnect(&mut self) -> Option<String> { self.disconnect.take() }
+}
+
+type Factory = dyn Fn() -> Box<dyn PacketHandler> + Send + Sync;
+
+/// The registered handlers, created anew for each connection.
+#[derive(Default, Clone)]
+pub struct Pipeline {
+    factories: Vec<Arc<Factory>>,
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("factories", &self.factories.len())
+            .finish()
+    }
+}
+
+impl Pipeline {
+    /// Adds a handler after the registered ones. `factory` creates its
+    /// instance for each connection.
+    pub fn register(
+        &mut self,
+        factory: impl Fn() -> Box<dyn PacketHandler> + Send + Sync + 'static,
+    ) {
+        self.factories.push(Arc::new(factory));
+    }
+
+    /// The handlers of a new connection.
+    #[must_use]
+    pub fn handlers(&self) -> Handlers {
+        Handlers(Arc::new(Mutex::new(Connection {
+            handlers: self.factories.iter().map(|factory| factory()).collect(),
+            connected: false,
+        })))
+    }
+}
+
+/// The handlers of a connection, shared by both of its relays.
+#[derive(Clone, Default)]
+pub struct Handlers(Arc<Mutex<Connection>>);
+
+#[derive(Default)]
+struct Connection {
+    handlers: Vec<Box<dyn PacketHandler>>,
+    connected: bool,
+}
+
+impl fmt::Debug for Handlers {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let connection = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        f.debug_list()
+            .entries(connection.handlers.iter().map(|handler| handler.name()))
+            .finish()
+    }
+}
+
+impl Handlers {
+    /// Runs `packet` through the interested handlers, returning it unless
+    /// one dropped it or ended the connection.
+    pub fn run(
+        &self,
+        context: &mut Context<'_>,
+        mut packet: Packet,
+    ) -> Result<Option<Packet>, Error> {
+        let mut connection = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        for handler in &mut connection.handlers {
+            if !handler.interested(context.state, context.direction) {
+                continue;
+            }
+            match handler.handle(context, packet)? {
+                Verdict::Forward(_) if context.disconnect.is_some() => return Ok(None),
+                Verdict::Forward(forwarded) => packet = forwarded,
+                Verdict::Drop => return Ok(None),
+            }
+        }
+        Ok(Some(packet))
+    }
+
+    /// Whether a handler reads the world view.
+    pub fn wants_world(&self) -> bool {
+        let connection = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        connection
+            .handlers
+            .iter()
+            .any(|handler| handler.wants_world())
+    }
+
+    pub fn connected(
+        &self,
+        info: &ConnectionInfo,
+    ) {
+        let mut connection = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        connection.connected = true;
+        for handler in &mut connection.handlers {
+            handler.connected(info);
+        }
+    }
+
+    /// Tells the handlers the connection ended, if they were told it
+    /// started.
+    pub fn disconnected(
+        &self,
+        error: Option<&str>,
+    ) {
+        let mut connection = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if !core::mem::take(&mut connection.connected) {
+            return;
+        }
+        for handler in &mut connection.handlers {
+            handler.disconnected(error);
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+pub mod tests {
+    use core::net::Ipv4Addr;
+
+    use super::*;
+    use crate::config::Backend;
+
+    pub fn connection() -> ConnectionInfo {
+        ConnectionInfo {
+            id: 1,
+            client: SocketAddr::from((Ipv4Addr::LOCALHOST, 50000)),
+            backend: Some("server".to_owned()),
+            port: 25565,
+            player: Some(Player {
+                name: "Steve".to_owned(),
+                uuid: Uuid::null(),
+            }),
+            transferred: false,
+        }
+    }
+
+    pub fn config() -> Config {
+        Config::new(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), Backend {
+            name: "server".to_owned(),
+            address: "localhost:25565".to_owned(),
+            protocol: None,
+        })
+    }
+
+    /// Drops serverbound packets of id 0x01 and echoes the others back to
+    /// the client, tagged with the player name.
+    struct Echo;
+
+    impl PacketHandler for Echo {
+        fn name(&self) -> &'static str { "echo" }
+
+        fn interested(
+            &self,
+            _: State,
+            direction: Direction,
+        ) -> bool {
+            direction == Direction::Serverbound
+        }
+
+        fn handle(
+            &mut self,
+            context: &mut Context<'_>,
+            packet: Packet,
+        ) -> Result<Verdict, Error> {
+            if packet.id == 0x01 {
+                return Ok(Verdict::Drop);
+            }
+            let name = context.connection.player.as_ref().unwrap().name.clone();
+            context.inject(Direction::Clientbound, Packet::new(0x02, name.as_bytes()));
+            Ok(Verdict::Forward(Packet::new(packet.id + 1, &packet.data)))
+        }
+    }
+
+    /// Counts the packets it sees.
+    #[derive(Default)]
+    struct Count(Arc<Mutex<usize>>);
+
+    impl PacketHandler for Count {
+        fn name(&self) -> &'static str { "count" }
+
+        fn handle(
+            &mut self,
+            _: &mut Context<'_>,
+            packet: Packet,
+        ) -> Result<Verdict, Error> {
+            *self.0.lock().unwrap() += 1;
+            Ok(Verdict::Forward(packet))
+        }
+    }
+
+    #[test]
+    fn run_handlers_in_order() {
+        let seen = Arc::new(Mutex::new(0));
+        let mut pipeline = Pipeline::default();
+        pipeline.register(|| Box::new(Echo));
+        let counted = Arc::clone(&seen);
+        pipeline.register(move || Box::new(Count(Arc::clone(&counted))));
+        let handlers = pipeline.handlers();
+        assert_eq!(format!("{handlers:?}"), r#"["echo", "count"]"#);
+
+        let (connection, config) = (connection(), config());
+        let version = ProtocolVersion::LATEST;
+        let mut context = Context::new(
+            &connection,
+            &config,
+            State::Play,
+            Direction::Serverbound,
+            version,
+        );
+        let forwarded = handlers
+            .run(&mut context, Packet::new(0x04, b"hi"))
+            .unwrap();
+        assert_eq!(forwarded.map(|packet| packet.id), Some(0x05));
+        let injected = context.take_injected();
+        assert_eq!(injected.len(), 1, "echo should inject a packet");
+        assert_eq!(injected[0].0, Direction::Clientbound);
+        assert_eq!(injected[0].1.data.as_ref(), b"Steve");
+
+        let dropped = handlers.run(&mut context, Packet::new(0x01, b"")).unwrap();
+        assert!(dropped.is_none(), "echo should drop 0x01");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            1,
+            "dropped packets skip later handlers"
+        );
+
+        let mut context = Context::new(
+            &connection,
+            &config,
+            State::Play,
+            Direction::Clientbound,
+            version,
+        );
+        handlers.run(&mut context, Packet::new(0x04, b"")).unwrap();
+        assert!(context.take_injected().is_empty(), "echo is not interested");
+        assert_eq!(*seen.lock().unwrap(), 2);
+    }
+}
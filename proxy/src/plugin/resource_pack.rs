@@ -0,0 +1,319 @@
+//! The resource pack of the network, pushed by the proxy whichever backend
+//! the client plays on. See [`crate::config::ResourcePack`].
+//!
+//! The client's answers about the pack are dropped, the backends never
+//! pushed it. Clients before 1.20.3 are skipped, their answers do not tell
+//! packs apart.
+
+use codec::Uuid;
+use data::model::configuration::{
+    self,
+    ResourcePackStatus,
+};
+use data::model::play;
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    ProtocolPacket as _,
+    State,
+};
+use data::text::json_to_nbt;
+use data::version::ProtocolVersion;
+use log::{
+    debug,
+    info,
+};
+
+use super::{
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::config::{
+    PackPolicy,
+    PackStage,
+    ResourcePack,
+};
+use crate::error::Error;
+
+/// Id of the pack, told apart from those of the backends.
+const PACK: Uuid = Uuid::from_u128(0x7072_6F78_792D_7061_636B_0000_0000_0001);
+
+/// Pushes the configured pack once per connection and applies its
+/// `on_failure` policy to the client's answers.
+#[derive(Debug, Default)]
+pub struct NetworkResourcePack {
+    /// Times the pack was pushed.
+    pushes: usize,
+}
+
+impl NetworkResourcePack {
+    /// Pushes `pack` to the client, in `state`.
+    fn push(
+        &mut self,
+        context: &mut Context<'_>,
+        pack: &ResourcePack,
+        state: State,
+    ) -> Result<(), Error> {
+        let version = context.version;
+        let prompt = pack
+            .prompt
+            .as_ref()
+            .map(|prompt| json_to_nbt(&json::object! { text: prompt.as_str() }));
+        let packet = match state {
+            State::Configuration => configuration::ResourcePackPush {
+                id: PACK,
+                url: pack.url.clone(),
+                hash: pack.hash.clone(),
+                forced: pack.forced,
+                prompt: prompt.into(),
+            }
+            .to_packet_versioned(version)?,
+            _ => play::ResourcePackPush {
+                id: PACK,
+                url: pack.url.clone(),
+                hash: pack.hash.clone(),
+                forced: pack.forced,
+                prompt: prompt.into(),
+            }
+            .to_packet_versioned(version)?,
+        };
+        context.inject(Direction::Clientbound, packet);
+        self.pushes += 1;
+        Ok(())
+    }
+
+    /// Handles the client's answer about the pack.
+    fn answered(
+        &mut self,
+        context: &mut Context<'_>,
+        pack: &ResourcePack,
+        status: ResourcePackStatus,
+    ) -> Result<(), Error> {
+        let name = context
+            .connection
+            .player
+            .as_ref()
+            .map_or("?", |player| player.name.as_str());
+        if !status.failed() {
+            debug!("{name} answered the resource pack with {status:?}");
+            return Ok(());
+        }
+        match pack.on_failure {
+            PackPolicy::Ignore => info!("{name} plays without the resource pack: {status:?}"),
+            PackPolicy::Retry if self.pushes <= pack.retries => {
+                debug!("Pushing the resource pack to {name} again after {status:?}");
+                self.push(context, pack, context.state)?;
+            }
+            PackPolicy::Kick | PackPolicy::Retry => {
+                context.disconnect(format!(
+                    "This server requires its resource pack ({status:?})"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PacketHandler for NetworkResourcePack {
+    fn name(&self) -> &'static str { "resource_pack" }
+
+    fn interested(
+        &self,
+        state: State,
+        _: Direction,
+    ) -> bool {
+        matches!(state, State::Configuration | State::Play)
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        let Some(pack) = context.config.resource_pack.clone() else {
+            return Ok(Verdict::Forward(packet));
+        };
+        if context.version < ProtocolVersion::V1_20_3 {
+            return Ok(Verdict::Forward(packet));
+        }
+        let version = context.version;
+        let name = version.packet_name(context.state, context.direction, packet.id);
+        match (context.state, context.direction, name) {
+            // the client's first packet of configuration, answered before it
+            // can be told to play
+            (State::Configuration, Direction::Serverbound, Some("client_information"))
+                if pack.stage == PackStage::Configuration && self.pushes == 0 =>
+            {
+                self.push(context, &pack, State::Configuration)?;
+            }
+            (State::Play, Direction::Clientbound, Some("login"))
+                if pack.stage == PackStage::Play && self.pushes == 0 =>
+            {
+                self.push(context, &pack, State::Play)?;
+            }
+            (State::Configuration, Direction::Serverbound, Some("resource_pack")) => {
+                let answer =
+                    configuration::ResourcePackResponse::from_packet_versioned(version, &packet)?;
+                if answer.id == PACK {
+                    self.answered(context, &pack, answer.status)?;
+                    return Ok(Verdict::Drop);
+                }
+            }
+            (State::Play, Direction::Serverbound, Some("resource_pack")) => {
+                let answer = play::ResourcePackResponse::from_packet_versioned(version, &packet)?;
+                if answer.id == PACK {
+                    self.answered(context, &pack, answer.status)?;
+                    return Ok(Verdict::Drop);
+                }
+            }
+            _ => {}
+        }
+        Ok(Verdict::Forward(packet))
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    fn config_with(on_failure: PackPolicy) -> Config {
+        Config {
+            resource_pack: Some(ResourcePack {
+                url: "https://example.com/pack.zip".to_owned(),
+                hash: String::new(),
+                forced: false,
+                prompt: Some("Please".to_owned()),
+                stage: PackStage::Configuration,
+                on_failure,
+                retries: 1,
+            }),
+            ..config()
+        }
+    }
+
+    /// Handles `packet` sent in configuration, returning the forwarded
+    /// packet, the injected ones and the kick reason.
+    fn handle(
+        handler: &mut NetworkResourcePack,
+        config: &Config,
+        direction: Direction,
+        packet: Packet,
+    ) -> (Option<Packet>, Vec<(Direction, Packet)>, Option<String>) {
+        let connection = connection();
+        let version = ProtocolVersion::LATEST;
+        let mut context = Context::new(
+            &connection,
+            config,
+            State::Configuration,
+            direction,
+            version,
+        );
+        let forwarded = match handler.handle(&mut context, packet).unwrap() {
+            Verdict::Forward(packet) => Some(packet),
+            Verdict::Drop => None,
+        };
+        (
+            forwarded,
+            context.take_injected(),
+            context.take_disconnect(),
+        )
+    }
+
+    fn client_information() -> Packet {
+        configuration::ClientInformation {
+            locale: "en_us".to_owned(),
+            view_distance: 8,
+            chat_mode: 0,
+            chat_colors: true,
+            displayed_skin_parts: 0x7F,
+            main_hand: 1,
+            text_filtering: false,
+            allow_server_listings: true,
+            particle_status: 0,
+        }
+        .to_packet()
+        .unwrap()
+    }
+
+    fn answer(
+        id: Uuid,
+        status: ResourcePackStatus,
+    ) -> Packet {
+        configuration::ResourcePackResponse {
+            id,
+            status,
+        }
+        .to_packet()
+        .unwrap()
+    }
+
+    #[test]
+    fn push_and_retry() {
+        let config = config_with(PackPolicy::Retry);
+        let mut handler = NetworkResourcePack::default();
+
+        let (forwarded, injected, _) = handle(
+            &mut handler,
+            &config,
+            Direction::Serverbound,
+            client_information(),
+        );
+        assert!(forwarded.is_some(), "the backend needs the information");
+        assert_eq!(injected.len(), 1, "the pack should be pushed");
+        let push = configuration::ResourcePackPush::from_packet(&injected[0].1).unwrap();
+        assert_eq!(push.id, PACK);
+        assert_eq!(push.url, "https://example.com/pack.zip");
+        let (_, injected, _) = handle(
+            &mut handler,
+            &config,
+            Direction::Serverbound,
+            client_information(),
+        );
+        assert!(injected.is_empty(), "the pack is pushed once");
+
+        let backend_pack = answer(Uuid::from_u128(1), ResourcePackStatus::Declined);
+        let (forwarded, _, kicked) =
+            handle(&mut handler, &config, Direction::Serverbound, backend_pack);
+        assert!(
+            forwarded.is_some(),
+            "answers about the backend's packs pass"
+        );
+        assert!(kicked.is_none());
+
+        let declined = || answer(PACK, ResourcePackStatus::Declined);
+        let (forwarded, injected, kicked) =
+            handle(&mut handler, &config, Direction::Serverbound, declined());
+        assert!(forwarded.is_none(), "answers about the pack are dropped");
+        assert_eq!(injected.len(), 1, "the pack should be pushed again");
+        assert!(kicked.is_none(), "one retry is left");
+
+        let (_, injected, kicked) =
+            handle(&mut handler, &config, Direction::Serverbound, declined());
+        assert!(injected.is_empty(), "no retry is left");
+        assert!(kicked.unwrap().contains("Declined"));
+    }
+
+    #[test]
+    fn ignore_or_kick() {
+        let config = config_with(PackPolicy::Ignore);
+        let mut handler = NetworkResourcePack::default();
+        let failed = || answer(PACK, ResourcePackStatus::FailedDownload);
+        let (forwarded, injected, kicked) =
+            handle(&mut handler, &config, Direction::Serverbound, failed());
+        assert!(forwarded.is_none() && injected.is_empty() && kicked.is_none());
+
+        let config = config_with(PackPolicy::Kick);
+        let loaded = answer(PACK, ResourcePackStatus::SuccessfullyLoaded);
+        let (_, _, kicked) = handle(&mut handler, &config, Direction::Serverbound, loaded);
+        assert!(kicked.is_none(), "the pack loaded");
+        let (_, _, kicked) = handle(&mut handler, &config, Direction::Serverbound, failed());
+        assert!(kicked.is_some(), "the pack is required");
+    }
+}
@@ -0,0 +1,822 @@
+//! Rhai scripts, for rules quicker to change than a handler compiled into
+//! the proxy.
+//!
+//! A script may define any of these functions, called in the order of the
+//! configured scripts with the connection as `conn`:
+//!
+//! - `on_connect(conn)`, once the client is routed to a backend.
+//! - `on_login(conn, name)`, when the client logs in as `name`.
+//! - `on_chat(conn, message)`, for each chat message of the player.
+//! - `on_command(conn, command)`, for each command of the player, without its
+//!   slash.
+//! - `on_packet(conn, packet)`, for every packet, as a map of its `id`, `name`,
+//!   `state`, `direction`, raw `data` and, if the packet has a model, `fields`,
+//!   a map of the decoded fields as in the JSON log.
+//! - `on_disconnect(conn, error)`, once the client left, with the error that
+//!   ended the connection or `()`.
+//!
+//! The packet hooks drop the packet by returning `false`. `on_chat` and
+//! `on_command` may instead return a string to replace the message. Only
+//! unsigned messages and commands can be replaced, signed ones can only be
+//! dropped.
+//!
+//! `conn` has the `id`, `client` address, `backend`, `name` and `uuid` of the
+//! connection, the last two `()` before login, and the methods
+//! `kick(reason)` and `send_message(text)`. Both only act from the packet
+//! hooks, and messages are only shown in play.
+//!
+//! Only the functions of a script run, each within a number of operations.
+//! Failures are logged and the packet handled as if the script were absent.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+use core::{
+    error,
+    fmt,
+};
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::{
+    Mutex,
+    PoisonError,
+    RwLock,
+};
+use std::time::SystemTime;
+use std::{
+    fs,
+    thread,
+};
+
+use codec::dec::Decode as _;
+use data::json::ToJson as _;
+use data::model::{
+    login,
+    play,
+};
+use data::packet::Packet;
+use data::protocol::{
+    self,
+    Direction,
+    ProtocolPacket as _,
+    State,
+};
+use json::JsonValue;
+use log::{
+    debug,
+    error,
+    info,
+    warn,
+};
+use rhai::{
+    AST,
+    CallFnOptions,
+    Dynamic,
+    Engine,
+    EvalAltResult,
+    Map,
+    Scope,
+};
+
+use super::{
+    ConnectionInfo,
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::config::SharedConfig;
+use crate::error::Error;
+use crate::inspect::{
+    direction,
+    stage,
+};
+use crate::utils::system_chat;
+
+/// Operations a call may run before it is stopped.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Time between two checks of the scripts' modification times.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A script which failed to load.
+#[derive(Debug)]
+pub struct ScriptError {
+    path: PathBuf,
+    error: Box<EvalAltResult>,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+impl error::Error for ScriptError {}
+
+/// The configured scripts, shared by every connection.
+#[derive(Debug)]
+pub struct Scripts {
+    engine: Engine,
+    loaded: RwLock<Arc<[Script]>>,
+}
+
+#[derive(Debug, Clone)]
+struct Script {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    /// The last version which compiled, if any.
+    ast: Option<Arc<AST>>,
+}
+
+impl Script {
+    /// Whether the script defines `function` with `arity` parameters.
+    fn defines(
+        &self,
+        function: &str,
+        arity: usize,
+    ) -> bool {
+        self.ast.as_ref().is_some_and(|ast| {
+            ast.iter_functions()
+                .any(|defined| defined.name == function && defined.params.len() == arity)
+        })
+    }
+}
+
+impl Scripts {
+    /// Compiles the scripts at `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScriptError`] for the first script which cannot be read or
+    /// does not compile.
+    pub fn load(paths: &[PathBuf]) -> Result<Self, ScriptError> {
+        let engine = engine();
+        let loaded = paths
+            .iter()
+            .map(|path| {
+                Ok(Script {
+                    path: path.clone(),
+                    modified: modified(path),
+                    ast: Some(Arc::new(compile(&engine, path)?)),
+                })
+            })
+            .collect::<Result<_, ScriptError>>()?;
+        Ok(Self {
+            engine,
+            loaded: RwLock::new(loaded),
+        })
+    }
+
+    fn current(&self) -> Arc<[Script]> {
+        Arc::clone(&self.loaded.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Switches to the scripts at `paths`, compiling those which are new or
+    /// changed. Scripts which fail to keep their previous version.
+    fn reload(
+        &self,
+        paths: &[PathBuf],
+    ) {
+        let current = self.current();
+        let mut changed = current.len() != paths.len();
+        let reloaded: Arc<[Script]> = paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| {
+                let modified = modified(path);
+                let previous = current.iter().find(|script| script.path == *path);
+                if let Some(previous) = previous
+                    && previous.modified == modified
+                {
+                    changed |= current.get(index).is_none_or(|script| script.path != *path);
+                    return previous.clone();
+                }
+                changed = true;
+                let ast = match compile(&self.engine, path) {
+                    Ok(ast) => {
+                        info!("Loaded the script {}", path.display());
+                        Some(Arc::new(ast))
+                    }
+                    Err(err) => {
+                        error!("Failed to load {err}, keeping its previous version");
+                        previous.and_then(|script| script.ast.clone())
+                    }
+                };
+                Script {
+                    path: path.clone(),
+                    modified,
+                    ast,
+                }
+            })
+            .collect();
+        if changed {
+            *self.loaded.write().unwrap_or_else(PoisonError::into_inner) = reloaded;
+        }
+    }
+
+    /// Calls `function` of `script` if it defines it, logging failures.
+    fn call(
+        &self,
+        script: &Script,
+        function: &str,
+        args: Vec<Dynamic>,
+    ) -> Option<Dynamic> {
+        if !script.defines(function, args.len()) {
+            return None;
+        }
+        let ast = script.ast.as_ref()?;
+        let options = CallFnOptions::new().eval_ast(false);
+        self.engine
+            .call_fn_with_options(options, &mut Scope::new(), ast, function, args)
+            .inspect_err(|err| {
+                error!(
+                    "Script {} failed in {function}: {err}",
+                    script.path.display()
+                );
+            })
+            .ok()
+    }
+}
+
+/// Reloads the scripts when they change, or the configured list does.
+pub fn watch(
+    scripts: Arc<Scripts>,
+    config: Arc<SharedConfig>,
+) {
+    thread::spawn(move || {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            scripts.reload(&config.get().scripts);
+        }
+    });
+}
+
+/// `value`, or `()` when missing.
+fn optional(value: Option<impl Into<Dynamic>>) -> Dynamic {
+    value.map_or(Dynamic::UNIT, Into::into)
+}
+
+/// A JSON value as a Rhai one: objects become maps and `null` `()`.
+fn dynamic(value: &JsonValue) -> Dynamic {
+    match value {
+        JsonValue::Null => Dynamic::UNIT,
+        JsonValue::Boolean(value) => (*value).into(),
+        JsonValue::Number(_) => value
+            .as_i64()
+            .map_or_else(|| value.as_f64().unwrap_or_default().into(), Into::into),
+        JsonValue::Short(_) | JsonValue::String(_) => value.as_str().unwrap_or_default().into(),
+        JsonValue::Array(values) => Dynamic::from_array(values.iter().map(dynamic).collect()),
+        JsonValue::Object(object) => Dynamic::from_map(
+            object
+                .iter()
+                .map(|(key, value)| (key.into(), dynamic(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn compile(
+    engine: &Engine,
+    path: &Path,
+) -> Result<AST, ScriptError> {
+    engine
+        .compile_file(path.to_path_buf())
+        .map_err(|error| ScriptError {
+            path: path.to_path_buf(),
+            error,
+        })
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| info!("{text}"));
+    engine.on_debug(|text, _, position| debug!("{position}: {text}"));
+    engine
+        .register_type_with_name::<ScriptConnection>("Connection")
+        .register_get("id", |conn: &mut ScriptConnection| conn.id)
+        .register_get("client", |conn: &mut ScriptConnection| conn.client.clone())
+        .register_get("backend", |conn: &mut ScriptConnection| {
+            conn.backend.clone()
+        })
+        .register_get("name", |conn: &mut ScriptConnection| conn.name.clone())
+        .register_get("uuid", |conn: &mut ScriptConnection| conn.uuid.clone())
+        .register_fn("kick", ScriptConnection::kick)
+        .register_fn("send_message", ScriptConnection::send_message);
+    engine
+}
+
+/// The connection as scripts see it.
+#[derive(Debug, Clone)]
+struct ScriptConnection {
+    id: i64,
+    client: String,
+    backend: Dynamic,
+    name: Dynamic,
+    uuid: Dynamic,
+    actions: Arc<Mutex<Actions>>,
+}
+
+/// What scripts asked of the connection.
+#[derive(Debug, Default)]
+struct Actions {
+    kick: Option<String>,
+    messages: Vec<String>,
+}
+
+impl ScriptConnection {
+    fn new(connection: &ConnectionInfo) -> Self {
+        let player = connection.player.as_ref();
+        Self {
+            id: connection.id.cast_signed(),
+            client: connection.client.to_string(),
+            backend: optional(connection.backend.clone()),
+            name: optional(player.map(|player| player.name.clone())),
+            uuid: optional(player.map(|player| player.uuid.to_string())),
+            actions: Arc::default(),
+        }
+    }
+
+    fn kick(
+        &mut self,
+        reason: &str,
+    ) {
+        self.actions().kick = Some(reason.to_owned());
+    }
+
+    fn send_message(
+        &mut self,
+        text: &str,
+    ) {
+        self.actions().messages.push(text.to_owned());
+    }
+
+    fn actions(&self) -> std::sync::MutexGuard<'_, Actions> {
+        self.actions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn kicked(&self) -> bool { self.actions().kick.is_some() }
+}
+
+/// Runs the scripts on a connection.
+#[derive(Debug)]
+pub struct ScriptHandler {
+    scripts: Arc<Scripts>,
+    /// The connection as it was last seen, for `on_disconnect`.
+    connection: Option<ConnectionInfo>,
+}
+
+impl ScriptHandler {
+    #[must_use]
+    pub const fn new(scripts: Arc<Scripts>) -> Self {
+        Self {
+            scripts,
+            connection: None,
+        }
+    }
+
+    /// Calls `on_chat` or `on_command` with `message`, returning whether to
+    /// drop the packet and the message the scripts replaced it with.
+    fn message(
+        &self,
+        conn: &ScriptConnection,
+        function: &str,
+        message: &str,
+    ) -> (bool, Option<String>) {
+        let mut replaced = None;
+        for script in self.scripts.current().iter() {
+            let current = replaced.as_deref().unwrap_or(message);
+            let args = vec![Dynamic::from(conn.clone()), current.into()];
+            let result = self.scripts.call(script, function, args);
+            if conn.kicked()
+                || result
+                    .as_ref()
+                    .is_some_and(|result| result.as_bool() == Ok(false))
+            {
+                return (true, None);
+            }
+            if let Some(result) = result.filter(Dynamic::is_string)
+                && let Ok(result) = result.into_string()
+            {
+                replaced = Some(result);
+            }
+        }
+        (false, replaced)
+    }
+
+    fn on_packet(
+        &self,
+        conn: &ScriptConnection,
+        context: &Context<'_>,
+        packet: &Packet,
+    ) -> bool {
+        let scripts = self.scripts.current();
+        if !scripts.iter().any(|script| script.defines("on_packet", 2)) {
+            return false;
+        }
+        let version = context.version;
+        let mut map = Map::new();
+        map.insert("id".into(), i64::from(packet.id).into());
+        map.insert(
+            "name".into(),
+            optional(version.packet_name(context.state, context.direction, packet.id)),
+        );
+        map.insert("state".into(), stage(context.state).into());
+        map.insert("direction".into(), direction(context.direction).into());
+        map.insert("data".into(), Dynamic::from_blob(packet.data.to_vec()));
+        map.insert(
+            "fields".into(),
+            optional(
+                protocol::describe(version, context.state, context.direction, packet)
+                    .ok()
+                    .flatten()
+                    .map(|decoded| dynamic(&decoded.to_json())),
+            ),
+        );
+        let map = Dynamic::from_map(map);
+        scripts.iter().any(|script| {
+            let args = vec![Dynamic::from(conn.clone()), map.clone()];
+            let result = self.scripts.call(script, "on_packet", args);
+            conn.kicked() || result.is_some_and(|result| result.as_bool() == Ok(false))
+        })
+    }
+
+    /// Calls the hook of the scripts for `packet`, if any.
+    fn on_message(
+        &self,
+        conn: &mut ScriptConnection,
+        context: &Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        let version = context.version;
+        let name = version.packet_name(context.state, context.direction, packet.id);
+        match (context.state, context.direction, name) {
+            (State::Login, Direction::Serverbound, Some("hello")) => {
+                let hello = login::Hello::from_packet_versioned(version, &packet)?;
+                conn.name = hello.name.clone().into();
+                conn.uuid = hello.uuid.to_string().into();
+                for script in self.scripts.current().iter() {
+                    let args = vec![Dynamic::from(conn.clone()), hello.name.clone().into()];
+                    self.scripts.call(script, "on_login", args);
+                    if conn.kicked() {
+                        break;
+                    }
+                }
+                Ok(Verdict::Forward(packet))
+            }
+            (State::Play, Direction::Serverbound, Some("chat")) => {
+                let mut chat = match play::Chat::from_packet_versioned(version, &packet) {
+                    Ok(chat) => chat,
+                    Err(err) => {
+                        warn!("Failed to read a chat message for the scripts: {err}");
+                        return Ok(Verdict::Forward(packet));
+                    }
+                };
+                match self.message(conn, "on_chat", &chat.message) {
+                    (true, _) => Ok(Verdict::Drop),
+                    (false, Some(message)) if message != chat.message => {
+                        // servers enforcing secure chat kick unsigned
+                        // messages from players who sign theirs
+                        if chat.signature.is_some() {
+                            warn!(
+                                "Signed chat cannot be replaced, forwarding {}",
+                                chat.message
+                            );
+                            return Ok(Verdict::Forward(packet));
+                        }
+                        chat.message = message;
+                        Ok(Verdict::Forward(chat.to_packet_versioned(version)?))
+                    }
+                    (false, _) => Ok(Verdict::Forward(packet)),
+                }
+            }
+            (State::Play, Direction::Serverbound, Some("chat_command")) => {
+                let mut command = play::ChatCommand::from_packet_versioned(version, &packet)?;
+                match self.message(conn, "on_command", &command.command) {
+                    (true, _) => Ok(Verdict::Drop),
+                    (false, Some(replaced)) if replaced != command.command => {
+                        command.command = replaced;
+                        Ok(Verdict::Forward(command.to_packet_versioned(version)?))
+                    }
+                    (false, _) => Ok(Verdict::Forward(packet)),
+                }
+            }
+            (State::Play, Direction::Serverbound, Some("chat_command_signed")) => {
+                // the command leads the packet in every version
+                let command = String::decode(&mut packet.data.as_ref())?;
+                match self.message(conn, "on_command", &command) {
+                    (true, _) => Ok(Verdict::Drop),
+                    (false, replaced) => {
+                        if replaced.is_some_and(|replaced| replaced != command) {
+                            warn!("Signed commands cannot be replaced, forwarding /{command}");
+                        }
+                        Ok(Verdict::Forward(packet))
+                    }
+                }
+            }
+            _ => Ok(Verdict::Forward(packet)),
+        }
+    }
+}
+
+impl PacketHandler for ScriptHandler {
+    fn name(&self) -> &'static str { "scripts" }
+
+    fn interested(
+        &self,
+        state: State,
+        direction: Direction,
+    ) -> bool {
+        let scripts = self.scripts.current();
+        if scripts.iter().any(|script| script.defines("on_packet", 2)) {
+            return true;
+        }
+        direction == Direction::Serverbound && matches!(state, State::Login | State::Play)
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        if context.connection.player.is_some()
+            && self
+                .connection
+                .as_ref()
+                .is_none_or(|connection| connection.player.is_none())
+        {
+            self.connection = Some(context.connection.clone());
+        }
+
+        let mut conn = ScriptConnection::new(context.connection);
+        let verdict = if self.on_packet(&conn, context, &packet) {
+            Verdict::Drop
+        } else {
+            self.on_message(&mut conn, context, packet)?
+        };
+
+        let actions = core::mem::take(&mut *conn.actions());
+        for message in actions.messages {
+            if context.state == State::Play {
+                let packet = system_chat(context.version, &message)?;
+                context.inject(Direction::Clientbound, packet);
+            } else {
+                debug!("Dropped a script message outside of play: {message}");
+            }
+        }
+        if let Some(reason) = actions.kick {
+            context.disconnect(reason);
+            return Ok(Verdict::Drop);
+        }
+        Ok(verdict)
+    }
+
+    fn connected(
+        &mut self,
+        connection: &ConnectionInfo,
+    ) {
+        self.connection = Some(connection.clone());
+        let conn = ScriptConnection::new(connection);
+        for script in self.scripts.current().iter() {
+            self.scripts
+                .call(script, "on_connect", vec![Dynamic::from(conn.clone())]);
+        }
+    }
+
+    fn disconnected(
+        &mut self,
+        error: Option<&str>,
+    ) {
+        let Some(connection) = self.connection.take() else {
+            return;
+        };
+        let conn = ScriptConnection::new(&connection);
+        let error = optional(error);
+        for script in self.scripts.current().iter() {
+            let args = vec![Dynamic::from(conn.clone()), error.clone()];
+            self.scripts.call(script, "on_disconnect", args);
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use data::version::ProtocolVersion;
+
+    use super::*;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    fn path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("proxy-script-{name}-{}.rhai", std::process::id()))
+    }
+
+    fn chat(message: &str) -> Packet { signed_chat(message, false) }
+
+    fn signed_chat(
+        message: &str,
+        signed: bool,
+    ) -> Packet {
+        play::Chat {
+            message: message.to_owned(),
+            timestamp: 0,
+            salt: 0,
+            signature: signed.then_some([7; 256]),
+            offset: 0,
+            acknowledged: [0; 3],
+            checksum: 0,
+        }
+        .to_packet()
+        .unwrap()
+    }
+
+    /// Handles `packet` sent by the client in `state`, returning the
+    /// forwarded packet, the injected ones and the kick reason.
+    fn handle(
+        handler: &mut ScriptHandler,
+        state: State,
+        packet: Packet,
+    ) -> (Option<Packet>, Vec<(Direction, Packet)>, Option<String>) {
+        let (mut connection, config) = (connection(), config());
+        if state == State::Login {
+            connection.player = None;
+        }
+        let version = ProtocolVersion::LATEST;
+        let mut context =
+            Context::new(&connection, &config, state, Direction::Serverbound, version);
+        let packet = match handler.handle(&mut context, packet).unwrap() {
+            Verdict::Forward(packet) => Some(packet),
+            Verdict::Drop => None,
+        };
+        (packet, context.take_injected(), context.take_disconnect())
+    }
+
+    #[test]
+    fn run_example_script() {
+        let example = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/scripts/example.rhai"));
+        let scripts = Arc::new(Scripts::load(&[example]).unwrap());
+        let mut handler = ScriptHandler::new(scripts);
+
+        let hello = login::Hello {
+            name: "Griefer".to_owned(),
+            uuid: codec::Uuid::null(),
+        };
+        let (forwarded, _, kicked) = handle(&mut handler, State::Login, hello.to_packet().unwrap());
+        assert!(forwarded.is_none(), "a kicked login should be dropped");
+        assert_eq!(kicked.as_deref(), Some("You are banned from this network"));
+
+        let (forwarded, injected, _) = handle(&mut handler, State::Play, chat("hi noob"));
+        let forwarded = play::Chat::from_packet(&forwarded.unwrap()).unwrap();
+        assert_eq!(forwarded.message, "hi friend");
+        assert!(
+            injected.is_empty(),
+            "allowed messages should not be answered"
+        );
+
+        let (forwarded, ..) = handle(&mut handler, State::Play, signed_chat("hi", true));
+        assert!(
+            play::Chat::from_packet(&forwarded.unwrap())
+                .unwrap()
+                .signature
+                .is_some(),
+            "unchanged messages should keep their signature"
+        );
+
+        let (forwarded, ..) = handle(&mut handler, State::Play, signed_chat("hi noob", true));
+        let forwarded = play::Chat::from_packet(&forwarded.unwrap()).unwrap();
+        assert_eq!(
+            (forwarded.message.as_str(), forwarded.signature.is_some()),
+            ("hi noob", true),
+            "signed messages should not be replaced"
+        );
+
+        let (forwarded, injected, kicked) =
+            handle(&mut handler, State::Play, chat("join discord.gg/abc"));
+        assert!(forwarded.is_none(), "invites should be blocked");
+        assert_eq!(injected.len(), 1, "the player should be told why");
+        assert!(kicked.is_none(), "blocking a message should not kick");
+
+        let command = play::ChatCommand {
+            command: "op Steve".to_owned(),
+        };
+        let (_, _, kicked) = handle(&mut handler, State::Play, command.to_packet().unwrap());
+        assert_eq!(kicked.as_deref(), Some("Nice try"));
+    }
+
+    #[test]
+    fn older_chat() {
+        let example = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/scripts/example.rhai"));
+        let scripts = Arc::new(Scripts::load(&[example]).unwrap());
+        let mut handler = ScriptHandler::new(scripts);
+
+        // 1.21.4 clients send no checksum
+        let version = ProtocolVersion::V1_21_4;
+        let chat = play::Chat::from_packet(&chat("hi noob")).unwrap();
+        let (connection, config) = (connection(), config());
+        let state = (State::Play, Direction::Serverbound);
+        let mut context = Context::new(&connection, &config, state.0, state.1, version);
+        let packet = chat.to_packet_versioned(version).unwrap();
+        let Verdict::Forward(forwarded) = handler.handle(&mut context, packet).unwrap() else {
+            panic!("the message should be forwarded");
+        };
+        let forwarded = play::Chat::from_packet_versioned(version, &forwarded).unwrap();
+        assert_eq!(forwarded.message, "hi friend");
+
+        let id = version.packet_id(state.0, state.1, "chat").unwrap();
+        let truncated = Packet::new(id, &[2, b'h']);
+        let verdict = handler.handle(&mut context, truncated).unwrap();
+        assert!(
+            matches!(verdict, Verdict::Forward(packet) if packet.data.len() == 2),
+            "unreadable chat should be forwarded as is"
+        );
+    }
+
+    #[test]
+    fn packet_fields() {
+        let path = path("fields");
+        fs::write(
+            &path,
+            r#"fn on_packet(conn, packet) {
+                if packet.name == "chat" {
+                    packet.fields.message != "drop me" && packet.fields.signature == ()
+                }
+            }"#,
+        )
+        .unwrap();
+        let scripts = Arc::new(Scripts::load(core::slice::from_ref(&path)).unwrap());
+        let mut handler = ScriptHandler::new(scripts);
+        let (forwarded, ..) = handle(&mut handler, State::Play, chat("drop me"));
+        assert!(forwarded.is_none(), "scripts should read the fields");
+        let (forwarded, ..) = handle(&mut handler, State::Play, chat("keep me"));
+        assert!(forwarded.is_some(), "other messages should be forwarded");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reload_changed_scripts() {
+        let path = path("reload");
+        fs::write(&path, r#"fn on_chat(conn, message) { "first" }"#).unwrap();
+        let scripts = Arc::new(Scripts::load(core::slice::from_ref(&path)).unwrap());
+        let mut handler = ScriptHandler::new(Arc::clone(&scripts));
+        let message = |handler: &mut ScriptHandler| {
+            let (forwarded, ..) = handle(handler, State::Play, chat("hello"));
+            play::Chat::from_packet(&forwarded.unwrap())
+                .unwrap()
+                .message
+        };
+        assert_eq!(message(&mut handler), "first");
+
+        let touch = |text: &str, seconds| {
+            fs::write(&path, text).unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+                .unwrap();
+        };
+        touch(r#"fn on_chat(conn, message) { "second" }"#, 1);
+        scripts.reload(core::slice::from_ref(&path));
+        assert_eq!(
+            message(&mut handler),
+            "second",
+            "handlers should see changes"
+        );
+
+        touch("fn on_chat(conn, message) {", 2);
+        scripts.reload(core::slice::from_ref(&path));
+        assert_eq!(
+            message(&mut handler),
+            "second",
+            "scripts which fail to compile should keep their previous version"
+        );
+
+        scripts.reload(&[]);
+        assert_eq!(
+            message(&mut handler),
+            "hello",
+            "removed scripts should stop"
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert!(
+            Scripts::load(&[path]).is_err(),
+            "missing scripts should fail to load"
+        );
+    }
+}
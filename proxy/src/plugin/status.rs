@@ -0,0 +1,51 @@
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    State,
+};
+
+use super::{
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::error::Error;
+use crate::utils::{
+    inject_status_description_message,
+    replace_status_description,
+};
+
+/// Replaces the description of status responses with the configured MOTD,
+/// or marks it as proxied.
+#[derive(Debug, Default)]
+pub struct StatusDescription;
+
+impl PacketHandler for StatusDescription {
+    fn name(&self) -> &'static str { "status_description" }
+
+    fn interested(
+        &self,
+        state: State,
+        direction: Direction,
+    ) -> bool {
+        state == State::Status && direction == Direction::Clientbound
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        let name = context
+            .version
+            .packet_name(context.state, context.direction, packet.id);
+        if name != Some("status_response") {
+            return Ok(Verdict::Forward(packet));
+        }
+        let packet = match &context.config.motd {
+            Some(motd) => replace_status_description(&packet, motd)?,
+            None => inject_status_description_message(&packet)?,
+        };
+        Ok(Verdict::Forward(packet))
+    }
+}
@@ -0,0 +1,217 @@
+//! A tab list of the whole network: clients also list the players of the
+//! other backends, whom their own backend does not know of.
+
+use alloc::sync::Arc;
+
+use data::model::play::{
+    self,
+    PlayerInfo,
+    player_info,
+};
+use data::packet::Packet;
+use data::protocol::{
+    Direction,
+    ProtocolPacket as _,
+    State,
+};
+use data::version::ProtocolVersion;
+
+use super::{
+    ConnectionInfo,
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::error::Error;
+use crate::players::Players;
+
+/// Lists the players of the other backends to the client once it plays,
+/// and its player to theirs, unless `tab_list.enabled` is off for the
+/// connection.
+#[derive(Debug)]
+pub struct GlobalTabList {
+    players: Arc<Players>,
+    /// The connection, once its player was listed to the others.
+    listed: Option<ConnectionInfo>,
+}
+
+impl GlobalTabList {
+    #[must_use]
+    pub const fn new(players: Arc<Players>) -> Self {
+        Self {
+            players,
+            listed: None,
+        }
+    }
+}
+
+/// Whether the players of `a` and `b` do not already see each other.
+fn apart(
+    a: &ConnectionInfo,
+    b: &ConnectionInfo,
+) -> bool {
+    a.id != b.id && a.backend != b.backend
+}
+
+/// The tab list entry of the player of `connection`.
+fn entry(connection: &ConnectionInfo) -> Option<PlayerInfo> {
+    let player = connection.player.as_ref()?;
+    Some(PlayerInfo {
+        name: player.name.clone(),
+        ..PlayerInfo::new(player.uuid.clone())
+    })
+}
+
+fn add(
+    entries: Vec<PlayerInfo>,
+    version: ProtocolVersion,
+) -> Result<Packet, Error> {
+    Ok(play::PlayerInfoUpdate {
+        actions: player_info::ADD_PLAYER | player_info::UPDATE_LISTED,
+        entries,
+    }
+    .to_packet_versioned(version)?)
+}
+
+impl PacketHandler for GlobalTabList {
+    fn name(&self) -> &'static str { "global_tab_list" }
+
+    fn interested(
+        &self,
+        state: State,
+        direction: Direction,
+    ) -> bool {
+        state == State::Play && direction == Direction::Clientbound
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        if !context.config.tab_list.enabled {
+            return Ok(Verdict::Forward(packet));
+        }
+        let connection = context.connection;
+        let name = context
+            .version
+            .packet_name(context.state, context.direction, packet.id);
+        if name != Some("login") {
+            return Ok(Verdict::Forward(packet));
+        }
+        let others: Vec<_> = self
+            .players
+            .in_play()
+            .iter()
+            .filter(|other| apart(connection, other))
+            .filter_map(entry)
+            .collect();
+        if !others.is_empty() {
+            let packet = add(others, context.version)?;
+            context.inject(Direction::Clientbound, packet);
+        }
+        if let Some(entry) = entry(connection) {
+            self.players.broadcast(|other, version| {
+                apart(connection, other)
+                    .then(|| add(vec![entry.clone()], version))
+                    .transpose()
+            });
+            self.listed = Some(connection.clone());
+        }
+        Ok(Verdict::Forward(packet))
+    }
+
+    fn disconnected(
+        &mut self,
+        _: Option<&str>,
+    ) {
+        let Some(connection) = self.listed.take() else {
+            return;
+        };
+        let Some(player) = &connection.player else {
+            return;
+        };
+        self.players.broadcast(|other, version| {
+            apart(&connection, other)
+                .then(|| {
+                    play::PlayerInfoRemove {
+                        uuids: vec![player.uuid.clone()],
+                    }
+                    .to_packet_versioned(version)
+                })
+                .transpose()
+                .map_err(Error::from)
+        });
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use codec::Uuid;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::plugin::Player;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    #[test]
+    fn list_players_of_other_backends() {
+        let players = Arc::new(Players::default());
+        let mut lobby = connection();
+        lobby.id = 2;
+        lobby.backend = Some("lobby".to_owned());
+        lobby.player = Some(Player {
+            name: "Alex".to_owned(),
+            uuid: Uuid::from_u128(2),
+        });
+        let mut neighbour = connection();
+        neighbour.id = 3;
+        neighbour.player = Some(Player {
+            name: "Kai".to_owned(),
+            uuid: Uuid::from_u128(3),
+        });
+        for other in [&lobby, &neighbour] {
+            players.joined(other, None);
+            players.playing(other.id, true);
+        }
+
+        let connection = connection();
+        players.joined(&connection, None);
+        let mut config = config();
+        let login = |config: &Config| {
+            let version = ProtocolVersion::LATEST;
+            let mut context = Context::new(
+                &connection,
+                config,
+                State::Play,
+                Direction::Clientbound,
+                version,
+            );
+            let login = Packet::new(
+                ProtocolVersion::LATEST
+                    .packet_id(State::Play, Direction::Clientbound, "login")
+                    .unwrap(),
+                b"",
+            );
+            let mut tab_list = GlobalTabList::new(Arc::clone(&players));
+            tab_list.handle(&mut context, login).unwrap();
+            context.take_injected()
+        };
+
+        let injected = login(&config);
+        assert_eq!(injected.len(), 1, "the other players should be listed");
+        let update = play::PlayerInfoUpdate::from_packet(&injected[0].1).unwrap();
+        let names: Vec<_> = update.entries.iter().map(|entry| &entry.name).collect();
+        assert_eq!(names, ["Alex"], "Kai is listed by their server");
+
+        config.tab_list.enabled = false;
+        assert!(
+            login(&config).is_empty(),
+            "a disabled tab list should list no one"
+        );
+    }
+}
@@ -0,0 +1,669 @@
+//! WebAssembly plugins, run with wasmtime.
+//!
+//! A plugin is a core module, binary or text, instantiated for each
+//! connection. It exports:
+//!
+//! - `memory`, where buffers are passed.
+//! - `alloc(len: i32) -> i32`, a buffer of `len` bytes for the proxy to write
+//!   to.
+//! - `dealloc(ptr: i32, len: i32)`, optional, frees a buffer once the call it
+//!   was passed to returned.
+//! - `interests() -> i32`, optional, with bit `2 * state + direction` set for
+//!   the packets to handle. States are numbered handshake 0, status 1, login 2,
+//!   configuration 3 and play 4, directions serverbound 0 and clientbound
+//!   1. Every packet when missing.
+//! - `on_connect(ptr: i32, len: i32)`, optional, the connection as JSON with
+//!   `connection`, `client` and `backend`.
+//! - `on_packet(ptr: i32, len: i32, data: i32, data_len: i32) -> i32`, a packet
+//!   as JSON with `connection`, `player`, `uuid`, `state`, `direction`,
+//!   `protocol`, `id`, `name` and, when the plugin decodes, `fields`, followed
+//!   by its raw data. Returns 0 to forward the packet and 1 to drop it.
+//!
+//!   `fields` is null for packets without a model, and otherwise an object of
+//!   the decoded fields, as in the inspector's JSON log: options are null or
+//!   their content, UUIDs and identifiers strings, text components their
+//!   JSON form, and enum values their variant name, or an object holding
+//!   their content under it, e.g. `{"Add": {"title": ...}}`.
+//! - `on_disconnect(ptr: i32, len: i32)`, optional, the end of the connection
+//!   as JSON with `error`.
+//!
+//! It may import from the `proxy` module:
+//!
+//! - `log(level: i32, ptr: i32, len: i32)`, logs a UTF-8 message at a level
+//!   from 1 for errors to 5 for traces.
+//! - `replace(id: i32, ptr: i32, len: i32)`, forwards this packet instead of
+//!   the handled one.
+//! - `inject(direction: i32, id: i32, ptr: i32, len: i32)`, sends a packet
+//!   after the handled one, in the connection's version.
+//!
+//! Each call runs on the plugin's fuel and within its memory limit. A plugin
+//! which traps or exhausts either is disabled for the connection, whose
+//! packets then flow as if it were absent.
+
+use core::fmt;
+use std::path::Path;
+
+use data::json::ToJson as _;
+use data::packet::Packet;
+use data::protocol::{
+    self,
+    Direction,
+    State,
+};
+use json::JsonValue;
+use log::{
+    Level,
+    error,
+    log,
+};
+use wasmtime::{
+    Caller,
+    Engine,
+    Extern,
+    InstancePre,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use super::{
+    ConnectionInfo,
+    Context,
+    PacketHandler,
+    Verdict,
+};
+use crate::config;
+use crate::error::Error;
+use crate::inspect::{
+    direction,
+    stage,
+};
+
+/// A compiled plugin, instantiated for each connection.
+pub struct WasmPlugin {
+    name: String,
+    pre: InstancePre<Host>,
+    config: config::Plugin,
+}
+
+impl fmt::Debug for WasmPlugin {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("WasmPlugin")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compiles the configured plugins, checking their imports and exports.
+pub fn load(plugins: &[config::Plugin]) -> wasmtime::Result<Vec<WasmPlugin>> {
+    let mut engine_config = wasmtime::Config::new();
+    engine_config.consume_fuel(true);
+    let engine = Engine::new(&engine_config)?;
+    let linker = linker(&engine)?;
+    plugins
+        .iter()
+        .map(|plugin| {
+            WasmPlugin::new(&engine, &linker, plugin).map_err(|err| {
+                err.context(format!("Failed to load plugin {}", plugin.path.display()))
+            })
+        })
+        .collect()
+}
+
+impl WasmPlugin {
+    fn new(
+        engine: &Engine,
+        linker: &Linker<Host>,
+        config: &config::Plugin,
+    ) -> wasmtime::Result<Self> {
+        let module = Module::from_file(engine, &config.path)?;
+        for export in ["memory", "alloc", "on_packet"] {
+            if module.get_export(export).is_none() {
+                return Err(wasmtime::Error::msg(format!("missing export `{export}`")));
+            }
+        }
+        Ok(Self {
+            name: name(&config.path),
+            pre: linker.instantiate_pre(&module)?,
+            config: config.clone(),
+        })
+    }
+
+    /// The plugin's handler for a new connection. It handles nothing when
+    /// the plugin cannot be instantiated.
+    #[must_use]
+    pub fn handler(&self) -> Box<dyn PacketHandler> {
+        let instance = Instance::new(self).inspect_err(|err| {
+            error!("Failed to instantiate plugin {}: {err:#}", self.name);
+        });
+        Box::new(WasmHandler {
+            name: self.name.clone(),
+            fuel: self.config.fuel,
+            decode: self.config.decode,
+            instance: instance.ok(),
+        })
+    }
+}
+
+/// Name of a plugin, from its file name.
+fn name(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// What the proxy's imports act on during a call.
+#[derive(Debug)]
+struct Host {
+    name: String,
+    limits: StoreLimits,
+    replaced: Option<Packet>,
+    injected: Vec<(Direction, Packet)>,
+}
+
+fn linker(engine: &Engine) -> wasmtime::Result<Linker<Host>> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap(
+        "proxy",
+        "log",
+        |mut caller: Caller<'_, Host>, level: i32, ptr: i32, len: i32| {
+            let message = read(&mut caller, ptr, len)?;
+            let level = match level {
+                1 => Level::Error,
+                2 => Level::Warn,
+                3 => Level::Info,
+                4 => Level::Debug,
+                _ => Level::Trace,
+            };
+            log!(
+                level,
+                "[{}] {}",
+                caller.data().name,
+                String::from_utf8_lossy(&message)
+            );
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        "proxy",
+        "replace",
+        |mut caller: Caller<'_, Host>, id: i32, ptr: i32, len: i32| {
+            let data = read(&mut caller, ptr, len)?;
+            caller.data_mut().replaced = Some(Packet::new(id, &data));
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        "proxy",
+        "inject",
+        |mut caller: Caller<'_, Host>, direction: i32, id: i32, ptr: i32, len: i32| {
+            let direction = match direction {
+                0 => Direction::Serverbound,
+                1 => Direction::Clientbound,
+                _ => {
+                    return Err(wasmtime::Error::msg(format!(
+                        "invalid direction {direction}"
+                    )));
+                }
+            };
+            let data = read(&mut caller, ptr, len)?;
+            caller
+                .data_mut()
+                .injected
+                .push((direction, Packet::new(id, &data)));
+            Ok(())
+        },
+    )?;
+    Ok(linker)
+}
+
+/// Copies a buffer out of the caller's memory.
+fn read(
+    caller: &mut Caller<'_, Host>,
+    ptr: i32,
+    len: i32,
+) -> wasmtime::Result<Vec<u8>> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("missing export `memory`"))?;
+    let start = usize::try_from(ptr)?;
+    let end = start + usize::try_from(len)?;
+    memory
+        .data(&caller)
+        .get(start..end)
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| wasmtime::Error::msg("buffer out of bounds"))
+}
+
+/// A plugin instantiated for a connection.
+struct Instance {
+    store: Store<Host>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    dealloc: Option<TypedFunc<(i32, i32), ()>>,
+    on_connect: Option<TypedFunc<(i32, i32), ()>>,
+    on_packet: TypedFunc<(i32, i32, i32, i32), i32>,
+    on_disconnect: Option<TypedFunc<(i32, i32), ()>>,
+    /// Bit `2 * state + direction` of each kind of packet handled.
+    interests: u32,
+}
+
+impl Instance {
+    fn new(plugin: &WasmPlugin) -> wasmtime::Result<Self> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(plugin.config.memory)
+            .instances(1)
+            .build();
+        let mut store = Store::new(plugin.pre.module().engine(), Host {
+            name: plugin.name.clone(),
+            limits,
+            replaced: None,
+            injected: Vec::new(),
+        });
+        store.limiter(|host| &mut host.limits);
+        store.set_fuel(plugin.config.fuel)?;
+
+        let instance = plugin.pre.instantiate(&mut store)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("`memory` is not a memory"))?;
+        let interests = match instance.get_typed_func::<(), i32>(&mut store, "interests") {
+            Ok(interests) => interests.call(&mut store, ())?.cast_unsigned(),
+            Err(_) => u32::MAX,
+        };
+        Ok(Self {
+            alloc: instance.get_typed_func(&mut store, "alloc")?,
+            dealloc: instance.get_typed_func(&mut store, "dealloc").ok(),
+            on_connect: instance.get_typed_func(&mut store, "on_connect").ok(),
+            on_packet: instance.get_typed_func(&mut store, "on_packet")?,
+            on_disconnect: instance.get_typed_func(&mut store, "on_disconnect").ok(),
+            store,
+            memory,
+            interests,
+        })
+    }
+
+    /// Copies `bytes` into a buffer of the plugin.
+    fn write(
+        &mut self,
+        bytes: &[u8],
+    ) -> wasmtime::Result<(i32, i32)> {
+        let len = i32::try_from(bytes.len())?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        let start = usize::try_from(ptr)?;
+        self.memory
+            .data_mut(&mut self.store)
+            .get_mut(start..start + bytes.len())
+            .ok_or_else(|| wasmtime::Error::msg("buffer out of bounds"))?
+            .copy_from_slice(bytes);
+        Ok((ptr, len))
+    }
+
+    fn free(
+        &mut self,
+        (ptr, len): (i32, i32),
+    ) -> wasmtime::Result<()> {
+        match &self.dealloc {
+            Some(dealloc) => dealloc.call(&mut self.store, (ptr, len)),
+            None => Ok(()),
+        }
+    }
+
+    /// Calls an optional export taking a JSON buffer.
+    fn event(
+        &mut self,
+        fuel: u64,
+        export: Option<TypedFunc<(i32, i32), ()>>,
+        event: &JsonValue,
+    ) -> wasmtime::Result<()> {
+        let Some(export) = export else {
+            return Ok(());
+        };
+        self.store.set_fuel(fuel)?;
+        let buffer = self.write(event.dump().as_bytes())?;
+        export.call(&mut self.store, buffer)?;
+        self.free(buffer)
+    }
+
+    /// Passes a packet to the plugin, returning whether to drop it. Its
+    /// replacement and the injected packets are left in the host.
+    fn packet(
+        &mut self,
+        fuel: u64,
+        header: &JsonValue,
+        packet: &Packet,
+    ) -> wasmtime::Result<bool> {
+        self.store.set_fuel(fuel)?;
+        let header = self.write(header.dump().as_bytes())?;
+        let data = self.write(&packet.data)?;
+        let verdict = self
+            .on_packet
+            .call(&mut self.store, (header.0, header.1, data.0, data.1))?;
+        self.free(data)?;
+        self.free(header)?;
+        Ok(verdict == 1)
+    }
+}
+
+/// Number of a kind of packet in the interests of a plugin.
+const fn interest(
+    state: State,
+    packet_direction: Direction,
+) -> u32 {
+    let state = match state {
+        State::Handshake => 0,
+        State::Status => 1,
+        State::Login => 2,
+        State::Configuration => 3,
+        State::Play => 4,
+    };
+    let packet_direction = match packet_direction {
+        Direction::Serverbound => 0,
+        Direction::Clientbound => 1,
+    };
+    1 << (2 * state + packet_direction)
+}
+
+/// Runs a plugin's instance for one connection, until it fails.
+struct WasmHandler {
+    name: String,
+    fuel: u64,
+    decode: bool,
+    instance: Option<Instance>,
+}
+
+impl WasmHandler {
+    /// Disables the plugin after a failed call.
+    fn failed(
+        &mut self,
+        err: &wasmtime::Error,
+    ) {
+        error!(
+            "Plugin {} failed, disabling it for this connection: {err:#}",
+            self.name
+        );
+        self.instance = None;
+    }
+}
+
+impl PacketHandler for WasmHandler {
+    fn name(&self) -> &str { &self.name }
+
+    fn interested(
+        &self,
+        state: State,
+        direction: Direction,
+    ) -> bool {
+        self.instance
+            .as_ref()
+            .is_some_and(|instance| instance.interests & interest(state, direction) != 0)
+    }
+
+    fn handle(
+        &mut self,
+        context: &mut Context<'_>,
+        packet: Packet,
+    ) -> Result<Verdict, Error> {
+        let Some(instance) = &mut self.instance else {
+            return Ok(Verdict::Forward(packet));
+        };
+        let version = context.version;
+        let fields = if self.decode {
+            protocol::describe(version, context.state, context.direction, &packet)
+                .ok()
+                .flatten()
+                .map(|fields| fields.to_json())
+        } else {
+            None
+        };
+        let player = context.connection.player.as_ref();
+        let header = json::object! {
+            connection: context.connection.id,
+            player: player.map(|player| player.name.clone()),
+            uuid: player.map(|player| player.uuid.to_string()),
+            state: stage(context.state),
+            direction: direction(context.direction),
+            protocol: version.protocol(),
+            id: packet.id,
+            name: version.packet_name(context.state, context.direction, packet.id),
+            fields: fields,
+        };
+
+        match instance.packet(self.fuel, &header, &packet) {
+            Ok(dropped) => {
+                let host = instance.store.data_mut();
+                for (direction, injected) in host.injected.drain(..) {
+                    context.inject(direction, injected);
+                }
+                let replaced = host.replaced.take();
+                if dropped {
+                    Ok(Verdict::Drop)
+                } else {
+                    Ok(Verdict::Forward(replaced.unwrap_or(packet)))
+                }
+            }
+            Err(err) => {
+                self.failed(&err);
+                Ok(Verdict::Forward(packet))
+            }
+        }
+    }
+
+    fn connected(
+        &mut self,
+        connection: &ConnectionInfo,
+    ) {
+        let Some(instance) = &mut self.instance else {
+            return;
+        };
+        let event = json::object! {
+            connection: connection.id,
+            client: connection.client.to_string(),
+            backend: connection.backend.clone(),
+        };
+        let export = instance.on_connect.clone();
+        if let Err(err) = instance.event(self.fuel, export, &event) {
+            self.failed(&err);
+        }
+    }
+
+    fn disconnected(
+        &mut self,
+        error: Option<&str>,
+    ) {
+        let Some(instance) = &mut self.instance else {
+            return;
+        };
+        let event = json::object! {
+            error: error,
+        };
+        let export = instance.on_disconnect.clone();
+        if let Err(err) = instance.event(self.fuel, export, &event) {
+            self.failed(&err);
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use data::model::play;
+    use data::protocol::ProtocolPacket as _;
+    use data::version::ProtocolVersion;
+
+    use super::*;
+    use crate::plugin::tests::{
+        config,
+        connection,
+    };
+
+    /// Drops play packets starting with `x`, renumbers the others to 0x07
+    /// and injects their header back to the client.
+    const FILTER: &str = r#"
+        (module
+          (import "proxy" "replace" (func $replace (param i32 i32 i32)))
+          (import "proxy" "inject" (func $inject (param i32 i32 i32 i32)))
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "dealloc") (param i32 i32)
+            (global.set $next (i32.const 1024)))
+          ;; play serverbound
+          (func (export "interests") (result i32) (i32.const 0x100))
+          (func (export "on_packet")
+            (param $header i32) (param $header_len i32) (param $data i32) (param $len i32)
+            (result i32)
+            (if (i32.eq (i32.load8_u (local.get $data)) (i32.const 120))
+              (then (return (i32.const 1))))
+            (call $replace (i32.const 7) (local.get $data) (local.get $len))
+            (call $inject (i32.const 1) (i32.const 9) (local.get $header) (local.get $header_len))
+            (i32.const 0)))
+    "#;
+
+    const LOOP: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "on_packet") (param i32 i32 i32 i32) (result i32)
+            (loop $forever (br $forever))
+            (i32.const 1)))
+    "#;
+
+    fn plugin(
+        name: &str,
+        text: &str,
+        memory: usize,
+    ) -> WasmPlugin {
+        let path =
+            std::env::temp_dir().join(format!("proxy-plugin-{name}-{}.wat", std::process::id()));
+        fs::write(&path, text).unwrap();
+        let mut plugins = load(&[config::Plugin {
+            path: PathBuf::from(&path),
+            fuel: 100_000,
+            memory,
+            decode: true,
+        }])
+        .unwrap();
+        fs::remove_file(&path).unwrap();
+        plugins.pop().unwrap()
+    }
+
+    fn handle(
+        handler: &mut dyn PacketHandler,
+        direction: Direction,
+        packet: Packet,
+    ) -> (Option<Packet>, Vec<(Direction, Packet)>) {
+        let (connection, config) = (connection(), config());
+        let mut context = Context::new(
+            &connection,
+            &config,
+            State::Play,
+            direction,
+            ProtocolVersion::LATEST,
+        );
+        assert!(
+            handler.interested(State::Play, direction),
+            "the handler should want play packets"
+        );
+        let packet = match handler.handle(&mut context, packet).unwrap() {
+            Verdict::Forward(packet) => Some(packet),
+            Verdict::Drop => None,
+        };
+        (packet, context.take_injected())
+    }
+
+    #[test]
+    fn filter_packets() {
+        let mut handler = plugin("filter", FILTER, 1 << 16).handler();
+        assert!(
+            !handler.interested(State::Play, Direction::Clientbound),
+            "the plugin only wants serverbound play packets"
+        );
+
+        let (forwarded, injected) = handle(
+            &mut *handler,
+            Direction::Serverbound,
+            Packet::new(0x03, b"hello"),
+        );
+        let forwarded = forwarded.unwrap();
+        assert_eq!(forwarded.id, 0x07);
+        assert_eq!(forwarded.data.as_ref(), b"hello");
+        assert_eq!(injected.len(), 1, "the header should be injected");
+        let header = json::parse(core::str::from_utf8(&injected[0].1.data).unwrap()).unwrap();
+        assert_eq!(header["player"], "Steve");
+        assert_eq!(header["state"], "play");
+        assert_eq!(header["id"], 0x03);
+
+        let (forwarded, injected) = handle(
+            &mut *handler,
+            Direction::Serverbound,
+            Packet::new(0x03, b"xyz"),
+        );
+        assert!(forwarded.is_none(), "packets starting with x are dropped");
+        assert!(injected.is_empty(), "dropped packets inject nothing");
+    }
+
+    #[test]
+    fn decoded_fields() {
+        let mut handler = plugin("fields", FILTER, 1 << 16).handler();
+        let keep_alive = play::KeepAliveResponse {
+            id: 7,
+        };
+        let (_, injected) = handle(
+            &mut *handler,
+            Direction::Serverbound,
+            keep_alive
+                .to_packet_versioned(ProtocolVersion::LATEST)
+                .unwrap(),
+        );
+        let header = json::parse(core::str::from_utf8(&injected[0].1.data).unwrap()).unwrap();
+        assert_eq!(header["name"], "keep_alive");
+        assert_eq!(
+            header["fields"],
+            json::object! { id: 7 },
+            "the fields should be JSON as in the inspector's log"
+        );
+    }
+
+    #[test]
+    fn disable_plugins_out_of_fuel_or_memory() {
+        let mut handler = plugin("loop", LOOP, 1 << 16).handler();
+        let (forwarded, _) = handle(
+            &mut *handler,
+            Direction::Serverbound,
+            Packet::new(0x03, b"hello"),
+        );
+        assert_eq!(
+            forwarded.map(|packet| packet.id),
+            Some(0x03),
+            "packets should flow past a plugin out of fuel"
+        );
+        assert!(
+            !handler.interested(State::Play, Direction::Serverbound),
+            "the plugin should be disabled"
+        );
+
+        let handler = plugin("memory", FILTER, 1 << 10).handler();
+        assert!(
+            !handler.interested(State::Play, Direction::Serverbound),
+            "a plugin over its memory limit should not run"
+        );
+    }
+}
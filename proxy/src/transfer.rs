@@ -0,0 +1,348 @@
+//! Transfers between proxies, vouched for by a signed cookie.
+//!
+//! A proxy transferring a player first stores [`ORIGIN_COOKIE`] on it,
+//! signed with the secret the proxies share. The proxy the player arrives
+//! at asks for the cookie during login and, when it only accepts signed
+//! transfers, refuses players whose cookie is missing, forged, too old or
+//! issued to someone else, by name or uuid.
+
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use core::fmt;
+use core::time::Duration;
+use std::thread;
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use codec::Uuid;
+use data::model::play;
+use data::protocol::ProtocolPacket as _;
+use hmac::{
+    Hmac,
+    Mac as _,
+};
+use log::{
+    debug,
+    info,
+};
+use sha2::Sha256;
+
+use crate::config::{
+    SharedConfig,
+    Transfers,
+};
+use crate::error::Error;
+use crate::players::{
+    Client,
+    Players,
+};
+use crate::plugin::{
+    ConnectionInfo,
+    Player,
+};
+
+/// Key of the cookie telling where a transferred player comes from.
+pub const ORIGIN_COOKIE: &str = "proxy:origin";
+/// Length of the HMAC-SHA256 ending the cookie.
+const SIGNATURE_LEN: usize = 32;
+/// How often the drain target is checked for changes and new players.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where a transferred player comes from, as its cookie tells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    /// Name of the proxy which transferred the player.
+    pub proxy: String,
+    pub player: Player,
+    /// Seconds since the Unix epoch.
+    pub issued: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OriginError {
+    Missing,
+    Malformed,
+    /// Not signed with the secret of the proxies.
+    Forged,
+    Expired,
+    /// Issued to another player.
+    Stolen,
+}
+
+impl fmt::Display for OriginError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(match self {
+            Self::Missing => "no origin cookie",
+            Self::Malformed => "malformed origin cookie",
+            Self::Forged => "origin cookie not signed by a known proxy",
+            Self::Expired => "expired origin cookie",
+            Self::Stolen => "origin cookie issued to another player",
+        })
+    }
+}
+
+impl Origin {
+    /// The origin of `player`, transferred now by the proxy `proxy`.
+    #[must_use]
+    pub fn new(
+        proxy: String,
+        player: Player,
+    ) -> Self {
+        Self {
+            proxy,
+            player,
+            issued: now(),
+        }
+    }
+
+    /// The cookie payload: the origin as JSON, then its signature.
+    #[must_use]
+    pub fn sign(
+        &self,
+        secret: &str,
+    ) -> Vec<u8> {
+        let mut payload = json::object! {
+            proxy: self.proxy.as_str(),
+            player: self.player.name.as_str(),
+            uuid: self.player.uuid.to_string(),
+            issued: self.issued,
+        }
+        .dump()
+        .into_bytes();
+        let signature = mac(secret, &payload).finalize().into_bytes();
+        payload.extend_from_slice(&signature);
+        payload
+    }
+
+    /// Checks the origin cookie `payload` of `player`, whose name and uuid
+    /// must both match.
+    pub fn verify(
+        payload: &[u8],
+        transfers: &Transfers,
+        player: &Player,
+    ) -> Result<Self, OriginError> {
+        let secret = transfers.secret.as_deref().ok_or(OriginError::Forged)?;
+        let (claims, signature) = payload
+            .len()
+            .checked_sub(SIGNATURE_LEN)
+            .map(|split| payload.split_at(split))
+            .ok_or(OriginError::Malformed)?;
+        mac(secret, claims)
+            .verify_slice(signature)
+            .map_err(|_| OriginError::Forged)?;
+        let claims = core::str::from_utf8(claims)
+            .ok()
+            .and_then(|claims| json::parse(claims).ok())
+            .ok_or(OriginError::Malformed)?;
+        let field = |name: &str| claims[name].as_str().map(str::to_owned);
+        let uuid = field("uuid")
+            .and_then(|uuid| u128::from_str_radix(&uuid.replace('-', ""), 16).ok())
+            .ok_or(OriginError::Malformed)?;
+        let origin = Self {
+            proxy: field("proxy").ok_or(OriginError::Malformed)?,
+            player: Player {
+                name: field("player").ok_or(OriginError::Malformed)?,
+                uuid: Uuid::from_u128(uuid),
+            },
+            issued: claims["issued"].as_u64().ok_or(OriginError::Malformed)?,
+        };
+        if origin.player != *player {
+            return Err(OriginError::Stolen);
+        }
+        if now().saturating_sub(origin.issued) > transfers.max_age.as_secs() {
+            return Err(OriginError::Expired);
+        }
+        Ok(origin)
+    }
+}
+
+fn mac(
+    secret: &str,
+    data: &[u8],
+) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(data);
+    mac
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// Sends the player of `connection` to `host:port`, storing its signed
+/// origin first if the proxies share a secret.
+pub fn transfer(
+    connection: &ConnectionInfo,
+    client: &Client,
+    transfers: &Transfers,
+    (host, port): (&str, u16),
+) -> Result<(), Error> {
+    if let (Some(secret), Some(player)) = (&transfers.secret, &connection.player) {
+        let origin = Origin::new(transfers.name.clone(), player.clone());
+        let cookie = play::StoreCookie {
+            key: ORIGIN_COOKIE.to_owned(),
+            payload: origin.sign(secret),
+        };
+        client.send(&cookie.to_packet_versioned(client.version)?)?;
+    }
+    let transfer = play::Transfer {
+        host: host.to_owned(),
+        port: i32::from(port),
+    };
+    client.send(&transfer.to_packet_versioned(client.version)?)?;
+    let player = connection
+        .player
+        .as_ref()
+        .map_or("?", |player| player.name.as_str());
+    info!("Transferred {player} to {host}:{port}");
+    Ok(())
+}
+
+/// Transfers every player in play to `transfers.drain` while it is set,
+/// following the configuration as it reloads.
+pub fn watch(
+    players: Arc<Players>,
+    config: Arc<SharedConfig>,
+) {
+    thread::spawn(move || {
+        let mut target = None;
+        // the connections already sent to the target
+        let mut drained = BTreeSet::new();
+        loop {
+            let config = config.get();
+            let transfers = &config.transfers;
+            if transfers.drain != target {
+                target.clone_from(&transfers.drain);
+                drained.clear();
+                if let Some((host, port)) = &target {
+                    info!("Draining the players to {host}:{port}");
+                }
+            }
+            if let Some((host, port)) = &target {
+                let clients = players.clients();
+                drained.retain(|id| clients.iter().any(|(connection, _)| connection.id == *id));
+                for (connection, client) in clients {
+                    if !drained.insert(connection.id) {
+                        continue;
+                    }
+                    if let Err(err) = transfer(&connection, &client, transfers, (host, *port)) {
+                        debug!("Failed to transfer connection {}: {err}", connection.id);
+                    }
+                }
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use std::net::{
+        TcpListener,
+        TcpStream,
+    };
+    use std::sync::Mutex;
+
+    use data::packet::ReadPacket as _;
+    use data::version::ProtocolVersion;
+
+    use super::*;
+    use crate::plugin::tests::connection;
+
+    fn transfers() -> Transfers {
+        Transfers {
+            secret: Some("secret".to_owned()),
+            name: "proxy-1".to_owned(),
+            ..Transfers::default()
+        }
+    }
+
+    /// The player of `plugin::tests::connection`.
+    fn steve() -> Player {
+        Player {
+            name: "Steve".to_owned(),
+            uuid: Uuid::null(),
+        }
+    }
+
+    #[test]
+    fn verify_origins() {
+        let transfers = transfers();
+        let origin = Origin::new("proxy-1".to_owned(), steve());
+        let cookie = origin.sign("secret");
+        assert_eq!(Origin::verify(&cookie, &transfers, &steve()), Ok(origin));
+
+        let alex = Player {
+            name: "Alex".to_owned(),
+            ..steve()
+        };
+        assert_eq!(
+            Origin::verify(&cookie, &transfers, &alex),
+            Err(OriginError::Stolen)
+        );
+        let impostor = Player {
+            uuid: Uuid::from_u128(7),
+            ..steve()
+        };
+        assert_eq!(
+            Origin::verify(&cookie, &transfers, &impostor),
+            Err(OriginError::Stolen),
+            "a player of the same name but another uuid should be refused"
+        );
+        let mut forged = cookie.clone();
+        forged[2] ^= 1;
+        assert_eq!(
+            Origin::verify(&forged, &transfers, &steve()),
+            Err(OriginError::Forged)
+        );
+        let other = Origin::new("proxy-1".to_owned(), steve()).sign("guess");
+        assert_eq!(
+            Origin::verify(&other, &transfers, &steve()),
+            Err(OriginError::Forged)
+        );
+        assert_eq!(
+            Origin::verify(b"short", &transfers, &steve()),
+            Err(OriginError::Malformed)
+        );
+
+        let old = Origin {
+            issued: now() - 60,
+            ..Origin::new("proxy-1".to_owned(), steve())
+        };
+        assert_eq!(
+            Origin::verify(&old.sign("secret"), &transfers, &steve()),
+            Err(OriginError::Expired)
+        );
+    }
+
+    #[test]
+    fn transfer_with_signed_cookie() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut received, _) = listener.accept().unwrap();
+        let client = Client::new(Arc::new(Mutex::new(stream)), ProtocolVersion::LATEST, None);
+        let players = Players::default();
+        players.joined(&connection(), Some(client.clone()));
+        players.playing(connection().id, true);
+
+        let transfers = transfers();
+        transfer(&connection(), &client, &transfers, ("proxy-2", 25565)).unwrap();
+
+        let cookie = play::StoreCookie::from_packet(&received.read_packet(false).unwrap()).unwrap();
+        assert_eq!(cookie.key, ORIGIN_COOKIE);
+        let origin = Origin::verify(&cookie.payload, &transfers, &steve()).unwrap();
+        assert_eq!(origin.proxy, "proxy-1");
+        let transfer = play::Transfer::from_packet(&received.read_packet(false).unwrap()).unwrap();
+        assert_eq!((transfer.host.as_str(), transfer.port), ("proxy-2", 25565));
+    }
+}
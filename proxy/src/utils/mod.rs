@@ -3,7 +3,18 @@ use codec::enc::{
     Encode as _,
     EncodeErrorContext as _,
 };
+use data::model::{
+    configuration,
+    login,
+    play,
+};
 use data::packet::Packet;
+use data::protocol::{
+    ProtocolPacket as _,
+    State,
+};
+use data::text::json_to_nbt;
+use data::version::ProtocolVersion;
 use json::JsonValue;
 use log::debug;
 
@@ -53,3 +64,64 @@ pub fn inject_status_description_message(packet: &Packet) -> Result<Packet, Erro
 
     Ok(packet)
 }
+
+/// Replaces the description of a status response with `motd`.
+pub fn replace_status_description(
+    packet: &Packet,
+    motd: &str,
+) -> Result<Packet, Error> {
+    let mut json_response = JsonValue::decode(&mut packet.data.as_ref())?;
+
+    json_response["description"] = json::object! {
+        text: motd,
+    };
+
+    let mut data = Vec::new();
+    json_response
+        .encode(&mut data)
+        .err_context("Failed to encode status response")?;
+
+    Ok(Packet::new(packet.id, &data))
+}
+
+/// The packet disconnecting a client in `state` with `reason`, in its
+/// `version`, or `None` if the state has none.
+pub fn disconnect(
+    state: State,
+    version: ProtocolVersion,
+    reason: &str,
+) -> Result<Option<Packet>, Error> {
+    let reason = json::object! {
+        text: reason,
+    };
+    let packet = match state {
+        State::Handshake | State::Status => return Ok(None),
+        State::Login => login::LoginDisconnect {
+            reason,
+        }
+        .to_packet_versioned(version)?,
+        State::Configuration => configuration::Disconnect {
+            reason: json_to_nbt(&reason),
+        }
+        .to_packet_versioned(version)?,
+        State::Play => play::Disconnect {
+            reason: json_to_nbt(&reason),
+        }
+        .to_packet_versioned(version)?,
+    };
+    Ok(Some(packet))
+}
+
+/// A system chat message of `text` for a client in play, in its `version`.
+pub fn system_chat(
+    version: ProtocolVersion,
+    text: &str,
+) -> Result<Packet, Error> {
+    Ok(play::SystemChat {
+        content: json_to_nbt(&json::object! {
+            text: text,
+        }),
+        overlay: false,
+    }
+    .to_packet_versioned(version)?)
+}
@@ -0,0 +1,986 @@
+//! A per-connection mirror of the world the player sees, maintained from
+//! the play packets going through the relay and the registries sent during
+//! configuration.
+//!
+//! Only packets in the layout of [`ProtocolVersion::LATEST`] are understood,
+//! so the view stays empty when neither side speaks it.
+
+use std::collections::HashMap;
+
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+};
+use codec::nbt::Nbt;
+use codec::{
+    PrefixedOption,
+    Uuid,
+};
+use data::identifier::Identifier;
+use data::model::configuration::{
+    self,
+    RegistryEntry,
+};
+use data::model::play;
+use data::protocol::Direction;
+use data::registry::{
+    EntityType,
+    Registry as _,
+};
+use data::world::{
+    BlockPos,
+    ChunkData,
+    ChunkPos,
+    EntityMetadata,
+};
+use json::JsonValue;
+
+/// Lowest section of the overworld, assumed until the dimension is known.
+const DEFAULT_MIN_SECTION: i32 = -4;
+
+/// Lowest block and height of the vanilla dimension types, whose data is
+/// left out of the registry data when the client knows the core pack.
+const VANILLA_DIMENSION_TYPES: [(&str, DimensionType); 4] = [
+    ("minecraft:overworld", DimensionType {
+        min_y: -64,
+        height: 384,
+    }),
+    ("minecraft:overworld_caves", DimensionType {
+        min_y: -64,
+        height: 384,
+    }),
+    ("minecraft:the_end", DimensionType {
+        min_y: 0,
+        height: 256,
+    }),
+    ("minecraft:the_nether", DimensionType {
+        min_y: 0,
+        height: 256,
+    }),
+];
+
+/// The vertical extent of the dimensions of a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionType {
+    pub min_y: i32,
+    pub height: i32,
+}
+
+impl DimensionType {
+    /// Section y of the lowest section.
+    #[must_use]
+    pub const fn min_section(self) -> i32 { self.min_y >> 4 }
+
+    /// Number of sections of a chunk.
+    #[must_use]
+    pub fn sections(self) -> usize { usize::try_from(self.height >> 4).unwrap_or_default() }
+}
+
+/// The registries a backend sent during configuration that play packets
+/// refer to by id.
+#[derive(Debug, Clone, Default)]
+pub struct Registries {
+    /// In id order, `None` for the types whose data is unknown.
+    dimension_types: Vec<Option<DimensionType>>,
+    /// Biome names, in id order.
+    biomes: Vec<Identifier>,
+}
+
+impl Registries {
+    /// Takes the entries of a `registry_data` packet, replacing those the
+    /// registry had.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the packet cannot be decoded.
+    pub fn observe(
+        &mut self,
+        mut data: &[u8],
+    ) -> Result<(), DecodeError> {
+        let packet = configuration::RegistryData::decode(&mut data)?;
+        match packet.registry.as_str() {
+            "minecraft:dimension_type" => {
+                self.dimension_types = packet.entries.iter().map(dimension_type).collect();
+            }
+            "minecraft:worldgen/biome" => {
+                self.biomes = packet.entries.into_iter().map(|entry| entry.id).collect();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// The dimension type of id `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if the backend sent no such type, or without
+    /// its data.
+    pub fn dimension_type(
+        &self,
+        id: i32,
+    ) -> Result<DimensionType, DecodeError> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|index| *self.dimension_types.get(index)?)
+            .ok_or_else(|| DecodeError::Custom {
+                message: format!("unknown dimension type {id}"),
+            })
+    }
+
+    /// Name of the biome of id `id`.
+    #[must_use]
+    pub fn biome(
+        &self,
+        id: i32,
+    ) -> Option<&Identifier> {
+        self.biomes.get(usize::try_from(id).ok()?)
+    }
+}
+
+/// The extent of a dimension type entry, from its data or, when left out,
+/// from the vanilla types.
+fn dimension_type(entry: &RegistryEntry) -> Option<DimensionType> {
+    let PrefixedOption::Some(data) = &entry.data else {
+        return VANILLA_DIMENSION_TYPES
+            .iter()
+            .find(|(name, _)| *name == entry.id.as_str())
+            .map(|(_, dimension_type)| *dimension_type);
+    };
+    let int = |key: &str| {
+        data.get(key)
+            .and_then(Nbt::as_i64)
+            .and_then(|value| i32::try_from(value).ok())
+    };
+    Some(DimensionType {
+        min_y: int("min_y")?,
+        height: int("height")?,
+    })
+}
+
+#[derive(Debug)]
+pub struct WorldView {
+    registries: Registries,
+    min_section: i32,
+    /// Sections of a chunk, once the dimension is known.
+    sections: Option<usize>,
+    chunks: HashMap<ChunkPos, ChunkData>,
+    entities: HashMap<i32, Entity>,
+    player: Option<Player>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Position {
+    /// Moves by a relative offset in 4096ths of a block.
+    #[allow(
+        clippy::float_arithmetic,
+        reason = "entity positions are doubles on the wire"
+    )]
+    fn offset(
+        &mut self,
+        delta_x: i16,
+        delta_y: i16,
+        delta_z: i16,
+    ) {
+        self.x += f64::from(delta_x) / 4096.0;
+        self.y += f64::from(delta_y) / 4096.0;
+        self.z += f64::from(delta_z) / 4096.0;
+    }
+
+    /// Applies a teleport, where each coordinate flagged in `relatives` is an
+    /// offset.
+    #[allow(
+        clippy::float_arithmetic,
+        reason = "entity positions are doubles on the wire"
+    )]
+    fn teleport(
+        &mut self,
+        target: Self,
+        relatives: i32,
+    ) {
+        let apply = |current: f64, target: f64, flag: i32| {
+            if relatives & flag == 0 {
+                target
+            } else {
+                current + target
+            }
+        };
+        self.x = apply(self.x, target.x, play::relative::X);
+        self.y = apply(self.y, target.y, play::relative::Y);
+        self.z = apply(self.z, target.z, play::relative::Z);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entity {
+    pub id: i32,
+    pub uuid: Uuid,
+    /// Id in the `minecraft:entity_type` registry.
+    pub kind: i32,
+    pub position: Position,
+    /// Angles in 256ths of a turn.
+    pub yaw: u8,
+    pub pitch: u8,
+    pub on_ground: bool,
+    pub metadata: EntityMetadata,
+}
+
+impl Entity {
+    #[must_use]
+    pub fn entity_type(&self) -> Option<EntityType> { EntityType::from_id(self.kind) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Player {
+    pub position: Position,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+/// A copy of what a [`WorldView`] holds, without the chunk contents.
+#[derive(Debug, Clone)]
+pub struct WorldSnapshot {
+    pub chunks: Vec<ChunkPos>,
+    pub entities: Vec<Entity>,
+    pub player: Option<Player>,
+}
+
+impl WorldSnapshot {
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        let position = |position: &Position| json::array![position.x, position.y, position.z];
+        json::object! {
+            chunks: self.chunks.iter().map(|chunk| json::array![chunk.x, chunk.z]).collect::<Vec<_>>(),
+            entities: self.entities.iter().map(|entity| json::object! {
+                id: entity.id,
+                uuid: entity.uuid.to_string(),
+                kind: entity.entity_type().map_or_else(|| entity.kind.to_string(), |kind| kind.identifier().to_owned()),
+                position: position(&entity.position),
+            }).collect::<Vec<_>>(),
+            player: self.player.map(|player| json::object! {
+                position: position(&player.position),
+                yaw: player.yaw,
+                pitch: player.pitch,
+                on_ground: player.on_ground,
+            }),
+        }
+    }
+}
+
+impl Default for WorldView {
+    fn default() -> Self { Self::new() }
+}
+
+impl WorldView {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            registries: Registries::default(),
+            min_section: DEFAULT_MIN_SECTION,
+            sections: None,
+            chunks: HashMap::new(),
+            entities: HashMap::new(),
+            player: None,
+        }
+    }
+
+    /// Updates the view from a play packet of the latest protocol version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError`] if a tracked packet cannot be decoded. The
+    /// view is left as it was.
+    pub fn observe(
+        &mut self,
+        direction: Direction,
+        name: &str,
+        mut data: &[u8],
+    ) -> Result<(), DecodeError> {
+        let data = &mut data;
+        match (direction, name) {
+            (Direction::Clientbound, "registry_data") => self.registries.observe(data),
+            (Direction::Clientbound, "login" | "respawn" | "start_configuration") => {
+                self.chunks.clear();
+                self.entities.clear();
+                let spawn = match name {
+                    "login" => play::Login::decode(data)?.spawn,
+                    "respawn" => play::Respawn::decode(data)?.spawn,
+                    _ => return Ok(()),
+                };
+                self.min_section = DEFAULT_MIN_SECTION;
+                self.sections = None;
+                let dimension_type = self.registries.dimension_type(spawn.dimension_type)?;
+                self.min_section = dimension_type.min_section();
+                self.sections = Some(dimension_type.sections());
+                Ok(())
+            }
+            (
+                Direction::Clientbound,
+                "level_chunk_with_light"
+                | "forget_level_chunk"
+                | "block_update"
+                | "section_blocks_update",
+            ) => self.observe_blocks(name, data),
+            (
+                Direction::Clientbound,
+                "add_entity"
+                | "move_entity_pos"
+                | "move_entity_pos_rot"
+                | "move_entity_rot"
+                | "teleport_entity"
+                | "entity_position_sync"
+                | "remove_entities"
+                | "set_entity_data",
+            ) => self.observe_entity(name, data),
+            _ => self.observe_player(direction, name, data),
+        }
+    }
+
+    /// Chunk loads, unloads and block changes.
+    fn observe_blocks(
+        &mut self,
+        name: &str,
+        data: &mut &[u8],
+    ) -> Result<(), DecodeError> {
+        match name {
+            "level_chunk_with_light" => {
+                let packet = play::LevelChunkWithLight::decode(data)?;
+                if let Some(sections) = self.sections
+                    && packet.chunk.sections.len() != sections
+                {
+                    return Err(DecodeError::Custom {
+                        message: format!(
+                            "chunk of {} sections in a dimension of {sections}",
+                            packet.chunk.sections.len()
+                        ),
+                    });
+                }
+                self.chunks.insert(
+                    ChunkPos {
+                        x: packet.x,
+                        z: packet.z,
+                    },
+                    packet.chunk,
+                );
+            }
+            "forget_level_chunk" => {
+                let packet = play::ForgetLevelChunk::decode(data)?;
+                self.chunks.remove(&ChunkPos {
+                    x: packet.x,
+                    z: packet.z,
+                });
+            }
+            "block_update" => {
+                let packet = play::BlockUpdate::decode(data)?;
+                self.set_block_state(packet.position, packet.block_state);
+            }
+            "section_blocks_update" => {
+                let packet = play::SectionBlocksUpdate::decode(data)?;
+                for (position, block_state) in packet.blocks() {
+                    self.set_block_state(position, block_state);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Entity spawns, moves, removals and metadata.
+    fn observe_entity(
+        &mut self,
+        name: &str,
+        data: &mut &[u8],
+    ) -> Result<(), DecodeError> {
+        match name {
+            "add_entity" => {
+                let packet = play::AddEntity::decode(data)?;
+                self.entities.insert(packet.id, Entity {
+                    id: packet.id,
+                    uuid: packet.uuid,
+                    kind: packet.kind,
+                    position: Position {
+                        x: packet.x,
+                        y: packet.y,
+                        z: packet.z,
+                    },
+                    yaw: packet.yaw,
+                    pitch: packet.pitch,
+                    on_ground: false,
+                    metadata: EntityMetadata::default(),
+                });
+            }
+            "move_entity_pos" => {
+                let packet = play::MoveEntityPos::decode(data)?;
+                if let Some(entity) = self.entities.get_mut(&packet.id) {
+                    entity
+                        .position
+                        .offset(packet.delta_x, packet.delta_y, packet.delta_z);
+                    entity.on_ground = packet.on_ground;
+                }
+            }
+            "move_entity_pos_rot" => {
+                let packet = play::MoveEntityPosRot::decode(data)?;
+                if let Some(entity) = self.entities.get_mut(&packet.id) {
+                    entity
+                        .position
+                        .offset(packet.delta_x, packet.delta_y, packet.delta_z);
+                    entity.yaw = packet.yaw;
+                    entity.pitch = packet.pitch;
+                    entity.on_ground = packet.on_ground;
+                }
+            }
+            "move_entity_rot" => {
+                let packet = play::MoveEntityRot::decode(data)?;
+                if let Some(entity) = self.entities.get_mut(&packet.id) {
+                    entity.yaw = packet.yaw;
+                    entity.pitch = packet.pitch;
+                    entity.on_ground = packet.on_ground;
+                }
+            }
+            "teleport_entity" => {
+                let packet = play::TeleportEntity::decode(data)?;
+                if let Some(entity) = self.entities.get_mut(&packet.id) {
+                    let target = Position {
+                        x: packet.x,
+                        y: packet.y,
+                        z: packet.z,
+                    };
+                    entity.position.teleport(target, packet.relatives);
+                    entity.on_ground = packet.on_ground;
+                }
+            }
+            "entity_position_sync" => {
+                let packet = play::EntityPositionSync::decode(data)?;
+                if let Some(entity) = self.entities.get_mut(&packet.id) {
+                    entity.position = Position {
+                        x: packet.x,
+                        y: packet.y,
+                        z: packet.z,
+                    };
+                    entity.on_ground = packet.on_ground;
+                }
+            }
+            "remove_entities" => {
+                let packet = play::RemoveEntities::decode(data)?;
+                for id in packet.ids {
+                    self.entities.remove(&id.value());
+                }
+            }
+            "set_entity_data" => {
+                let packet = play::SetEntityData::decode(data)?;
+                if let Some(entity) = self.entities.get_mut(&packet.id) {
+                    entity.metadata.merge(packet.metadata);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Position of the player, told by the server or reported by the client.
+    fn observe_player(
+        &mut self,
+        direction: Direction,
+        name: &str,
+        data: &mut &[u8],
+    ) -> Result<(), DecodeError> {
+        match (direction, name) {
+            (Direction::Clientbound, "player_position") => {
+                let packet = play::PlayerPosition::decode(data)?;
+                let player = self.player.get_or_insert_default();
+                let target = Position {
+                    x: packet.x,
+                    y: packet.y,
+                    z: packet.z,
+                };
+                player.position.teleport(target, packet.relatives);
+                player.yaw = packet.yaw;
+                player.pitch = packet.pitch;
+            }
+            (Direction::Serverbound, "move_player_pos") => {
+                let packet = play::MovePlayerPos::decode(data)?;
+                let player = self.player.get_or_insert_default();
+                player.position = Position {
+                    x: packet.x,
+                    y: packet.y,
+                    z: packet.z,
+                };
+                player.on_ground = packet.flags & play::movement::ON_GROUND != 0;
+            }
+            (Direction::Serverbound, "move_player_pos_rot") => {
+                let packet = play::MovePlayerPosRot::decode(data)?;
+                let player = self.player.get_or_insert_default();
+                player.position = Position {
+                    x: packet.x,
+                    y: packet.y,
+                    z: packet.z,
+                };
+                player.yaw = packet.yaw;
+                player.pitch = packet.pitch;
+                player.on_ground = packet.flags & play::movement::ON_GROUND != 0;
+            }
+            (Direction::Serverbound, "move_player_rot") => {
+                let packet = play::MovePlayerRot::decode(data)?;
+                let player = self.player.get_or_insert_default();
+                player.yaw = packet.yaw;
+                player.pitch = packet.pitch;
+                player.on_ground = packet.flags & play::movement::ON_GROUND != 0;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn set_block_state(
+        &mut self,
+        position: BlockPos,
+        block_state: i32,
+    ) {
+        let (x, y, z) = position.in_section();
+        if let Some(index) = self.section_index(position)
+            && let Some(section) = self
+                .chunks
+                .get_mut(&position.chunk())
+                .and_then(|chunk| chunk.sections.get_mut(index))
+        {
+            section.set_block_state(x, y, z, block_state);
+        }
+    }
+
+    fn section_index(
+        &self,
+        position: BlockPos,
+    ) -> Option<usize> {
+        usize::try_from((position.y >> 4) - self.min_section).ok()
+    }
+}
+
+#[allow(dead_code, reason = "queries for proxy plugins")]
+impl WorldView {
+    /// Sets the lowest block of the dimension the player is in.
+    pub const fn set_min_y(
+        &mut self,
+        min_y: i32,
+    ) {
+        self.min_section = min_y >> 4;
+    }
+
+    /// Block state id at a position, if its chunk is loaded.
+    #[must_use]
+    pub fn block_state(
+        &self,
+        position: BlockPos,
+    ) -> Option<i32> {
+        let (x, y, z) = position.in_section();
+        self.section_index(position)
+            .and_then(|index| self.chunks.get(&position.chunk())?.sections.get(index))
+            .map(|section| section.block_state(x, y, z))
+    }
+
+    #[must_use]
+    pub fn chunk(
+        &self,
+        position: ChunkPos,
+    ) -> Option<&ChunkData> {
+        self.chunks.get(&position)
+    }
+
+    /// Positions of the loaded chunks, in no particular order.
+    pub fn chunks(&self) -> impl Iterator<Item = ChunkPos> + '_ { self.chunks.keys().copied() }
+
+    #[must_use]
+    pub fn entity(
+        &self,
+        id: i32,
+    ) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> { self.entities.values() }
+
+    #[must_use]
+    pub const fn player(&self) -> Option<&Player> { self.player.as_ref() }
+
+    #[must_use]
+    pub fn snapshot(&self) -> WorldSnapshot {
+        let mut chunks: Vec<_> = self.chunks().collect();
+        chunks.sort_unstable();
+        let mut entities: Vec<_> = self.entities.values().cloned().collect();
+        entities.sort_unstable_by_key(|entity| entity.id);
+        WorldSnapshot {
+            chunks,
+            entities,
+            player: self.player,
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+pub(crate) mod tests {
+    use codec::enc::Encode;
+    use codec::{
+        PrefixedOption,
+        VarInt,
+        VarLong,
+    };
+    use data::registry::{
+        Block,
+        BlockState,
+    };
+    use data::world::{
+        LightData,
+        MetadataValue,
+        Section,
+        SectionPos,
+    };
+
+    use super::*;
+
+    fn observe(
+        world: &mut WorldView,
+        direction: Direction,
+        name: &str,
+        packet: &impl Encode,
+    ) {
+        let mut data = Vec::new();
+        packet.encode(&mut data).unwrap();
+        world.observe(direction, name, &data).unwrap();
+    }
+
+    pub(crate) fn spawn_info(dimension: &str) -> play::SpawnInfo {
+        play::SpawnInfo {
+            dimension_type: 0,
+            dimension: dimension.parse().unwrap(),
+            hashed_seed: 42,
+            game_mode: 1,
+            previous_game_mode: -1,
+            debug: false,
+            flat: false,
+            death_location: PrefixedOption::None,
+            portal_cooldown: 0,
+            sea_level: 63,
+        }
+    }
+
+    /// The vanilla overworld then `lobby:flat`, four sections high, and the
+    /// biomes `minecraft:plains` then `lobby:void`.
+    pub(crate) fn registries() -> [configuration::RegistryData; 2] {
+        let entry = |id: &str, data: Option<Nbt>| RegistryEntry {
+            id: id.parse().unwrap(),
+            data: data.into(),
+        };
+        let mut flat = Nbt::compound();
+        flat.insert("min_y", Nbt::Int(0));
+        flat.insert("height", Nbt::Int(64));
+        [
+            configuration::RegistryData {
+                registry: "minecraft:dimension_type".parse().unwrap(),
+                entries: vec![
+                    entry("minecraft:overworld", None),
+                    entry("lobby:flat", Some(flat)),
+                ],
+            },
+            configuration::RegistryData {
+                registry: "minecraft:worldgen/biome".parse().unwrap(),
+                entries: vec![entry("minecraft:plains", None), entry("lobby:void", None)],
+            },
+        ]
+    }
+
+    pub(crate) fn chunk(
+        x: i32,
+        z: i32,
+    ) -> play::LevelChunkWithLight {
+        play::LevelChunkWithLight {
+            x,
+            z,
+            chunk: ChunkData {
+                heightmaps: Vec::new(),
+                sections: (0..24)
+                    .map(|_| Section::filled(BlockState::AIR, 0))
+                    .collect(),
+                block_entities: Vec::new(),
+            },
+            light: LightData {
+                sky_y_mask: Vec::new(),
+                block_y_mask: Vec::new(),
+                empty_sky_y_mask: Vec::new(),
+                empty_block_y_mask: Vec::new(),
+                sky_updates: Vec::new(),
+                block_updates: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn dimensions_from_registries() {
+        let mut world = WorldView::new();
+        for registry in registries() {
+            observe(
+                &mut world,
+                Direction::Clientbound,
+                "registry_data",
+                &registry,
+            );
+        }
+        assert_eq!(
+            world.registries.biome(1).map(Identifier::as_str),
+            Some("lobby:void")
+        );
+        assert_eq!(world.registries.dimension_type(0).unwrap(), DimensionType {
+            min_y: -64,
+            height: 384,
+        });
+
+        let respawn = |dimension_type| {
+            let mut data = Vec::new();
+            play::Respawn {
+                spawn: play::SpawnInfo {
+                    dimension_type,
+                    ..spawn_info("lobby:flat")
+                },
+                data_kept: 0,
+            }
+            .encode(&mut data)
+            .unwrap();
+            data
+        };
+        world
+            .observe(Direction::Clientbound, "respawn", &respawn(1))
+            .unwrap();
+        let mut data = Vec::new();
+        chunk(0, 0).encode(&mut data).unwrap();
+        assert!(
+            world
+                .observe(Direction::Clientbound, "level_chunk_with_light", &data)
+                .is_err(),
+            "lobby:flat chunks have 4 sections"
+        );
+        let mut flat = chunk(0, 0);
+        flat.chunk.sections.truncate(4);
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "level_chunk_with_light",
+            &flat,
+        );
+        let position = BlockPos::new(0, 0, 0);
+        assert_eq!(world.block_state(position), Some(BlockState::AIR.id()));
+        assert_eq!(world.block_state(BlockPos::new(0, -1, 0)), None);
+
+        assert!(
+            world
+                .observe(Direction::Clientbound, "respawn", &respawn(2))
+                .is_err(),
+            "the backend sent no dimension type 2"
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        let mut world = WorldView::new();
+        let stone = Block::Stone.default_state().id();
+        let position = BlockPos::new(-1, -64, 17);
+        assert_eq!(world.block_state(position), None);
+
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "level_chunk_with_light",
+            &chunk(-1, 1),
+        );
+        assert_eq!(world.block_state(position), Some(BlockState::AIR.id()));
+
+        let update = play::BlockUpdate {
+            position,
+            block_state: stone,
+        };
+        observe(&mut world, Direction::Clientbound, "block_update", &update);
+        assert_eq!(world.block_state(position), Some(stone));
+
+        let update = play::SectionBlocksUpdate {
+            section: SectionPos {
+                x: -1,
+                y: 0,
+                z: 1,
+            },
+            // x 2, z 3, y 4
+            blocks: vec![VarLong::new(i64::from(stone) << 12 | 0x234)],
+        };
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "section_blocks_update",
+            &update,
+        );
+        assert_eq!(world.block_state(BlockPos::new(-14, 4, 19)), Some(stone));
+
+        let forget = play::ForgetLevelChunk {
+            z: 1,
+            x: -1,
+        };
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "forget_level_chunk",
+            &forget,
+        );
+        assert_eq!(world.chunks().count(), 0);
+    }
+
+    #[test]
+    fn entities() {
+        let mut world = WorldView::new();
+        let spawn = play::AddEntity {
+            id: 7,
+            uuid: Uuid::null(),
+            kind: EntityType::Creeper.id(),
+            x: 1.0,
+            y: 64.0,
+            z: -1.0,
+            pitch: 0,
+            yaw: 0,
+            head_yaw: 0,
+            data: 0,
+            velocity_x: 0,
+            velocity_y: 0,
+            velocity_z: 0,
+        };
+        observe(&mut world, Direction::Clientbound, "add_entity", &spawn);
+
+        let step = play::MoveEntityPos {
+            id: 7,
+            delta_x: 4096,
+            delta_y: -2048,
+            delta_z: 0,
+            on_ground: true,
+        };
+        observe(&mut world, Direction::Clientbound, "move_entity_pos", &step);
+        let entity = world.entity(7).unwrap();
+        assert_eq!(entity.entity_type(), Some(EntityType::Creeper));
+        assert_eq!(entity.position, Position {
+            x: 2.0,
+            y: 63.5,
+            z: -1.0
+        });
+
+        let data = play::SetEntityData {
+            id: 7,
+            metadata: EntityMetadata {
+                entries: vec![(6, MetadataValue::Pose(1))],
+            },
+        };
+        observe(&mut world, Direction::Clientbound, "set_entity_data", &data);
+        assert_eq!(
+            world.entity(7).unwrap().metadata.get(6),
+            Some(&MetadataValue::Pose(1))
+        );
+
+        let snapshot = world.snapshot().to_json();
+        assert_eq!(snapshot["entities"][0]["kind"], "minecraft:creeper");
+
+        let remove = play::RemoveEntities {
+            ids: vec![VarInt::new(7)],
+        };
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "remove_entities",
+            &remove,
+        );
+        assert!(world.entity(7).is_none(), "removed entities are forgotten");
+    }
+
+    #[test]
+    fn player() {
+        let mut world = WorldView::new();
+        let teleport = play::PlayerPosition {
+            teleport_id: 1,
+            x: 10.0,
+            y: 70.0,
+            z: 10.0,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            velocity_z: 0.0,
+            yaw: 90.0,
+            pitch: 0.0,
+            relatives: 0,
+        };
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "player_position",
+            &teleport,
+        );
+        let nudge = play::PlayerPosition {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+            relatives: play::relative::X | play::relative::Y | play::relative::Z,
+            ..teleport
+        };
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "player_position",
+            &nudge,
+        );
+        assert_eq!(world.player().unwrap().position, Position {
+            x: 11.0,
+            y: 70.0,
+            z: 10.0
+        });
+
+        let walk = play::MovePlayerPos {
+            x: 12.0,
+            y: 70.0,
+            z: 10.0,
+            flags: play::movement::ON_GROUND,
+        };
+        observe(&mut world, Direction::Serverbound, "move_player_pos", &walk);
+        let player = world.player().unwrap();
+        assert_eq!(player.position, Position {
+            x: 12.0,
+            y: 70.0,
+            z: 10.0
+        });
+        assert!(player.on_ground, "the client reported being on the ground");
+
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "level_chunk_with_light",
+            &chunk(0, 0),
+        );
+        for registry in registries() {
+            observe(
+                &mut world,
+                Direction::Clientbound,
+                "registry_data",
+                &registry,
+            );
+        }
+        observe(
+            &mut world,
+            Direction::Clientbound,
+            "respawn",
+            &play::Respawn {
+                spawn: play::SpawnInfo {
+                    dimension_type: 1,
+                    ..spawn_info("lobby:flat")
+                },
+                data_kept: 0,
+            },
+        );
+        assert_eq!(world.chunks().count(), 0);
+        assert_eq!(world.min_section, 0, "lobby:flat starts at y 0");
+    }
+}
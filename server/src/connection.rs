@@ -0,0 +1,155 @@
+//! The two halves of a connection, each with its own cipher once encryption
+//! is on, so that reading and writing can happen on separate threads.
+
+use std::io::{
+    self,
+    BufReader,
+    Read,
+    Write,
+};
+
+use aes::Aes128;
+use aes::cipher::inout::InOutBuf;
+use aes::cipher::{
+    BlockDecryptMut as _,
+    BlockEncryptMut as _,
+    KeyIvInit as _,
+};
+use data::packet::{
+    Packet,
+    ReadPacket as _,
+    WritePacket as _,
+};
+use data::protocol::ProtocolPacket;
+
+use crate::error::Error;
+
+type Encryptor = cfb8::Encryptor<Aes128>;
+type Decryptor = cfb8::Decryptor<Aes128>;
+
+pub struct PacketReader<R> {
+    stream: BufReader<R>,
+    compressed: bool,
+    decryptor: Option<Decryptor>,
+}
+
+impl<R: Read> PacketReader<R> {
+    pub fn new(stream: R) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+            compressed: false,
+            decryptor: None,
+        }
+    }
+
+    pub const fn set_compressed(
+        &mut self,
+        compressed: bool,
+    ) {
+        self.compressed = compressed;
+    }
+
+    /// Decrypts everything received from now on with AES/CFB8, whose key and
+    /// IV are both the shared secret.
+    pub fn enable_encryption(
+        &mut self,
+        secret: &[u8; 16],
+    ) {
+        self.decryptor = Some(Decryptor::new(secret.into(), secret.into()));
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the stream fails or the packet is malformed.
+    pub fn receive(&mut self) -> Result<Packet, Error> {
+        let mut reader = Decrypting {
+            stream: &mut self.stream,
+            decryptor: self.decryptor.as_mut(),
+        };
+        Ok(reader.read_packet(self.compressed)?)
+    }
+}
+
+pub struct PacketWriter<W> {
+    stream: W,
+    /// Size from which packets are compressed, once enabled.
+    compression: Option<usize>,
+    encryptor: Option<Encryptor>,
+}
+
+impl<W: Write> PacketWriter<W> {
+    pub const fn new(stream: W) -> Self {
+        Self {
+            stream,
+            compression: None,
+            encryptor: None,
+        }
+    }
+
+    pub const fn set_compression(
+        &mut self,
+        compression: Option<usize>,
+    ) {
+        self.compression = compression;
+    }
+
+    /// Encrypts everything sent from now on, like
+    /// [`PacketReader::enable_encryption`].
+    pub fn enable_encryption(
+        &mut self,
+        secret: &[u8; 16],
+    ) {
+        self.encryptor = Some(Encryptor::new(secret.into(), secret.into()));
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be encoded or the stream
+    /// fails.
+    pub fn send_raw(
+        &mut self,
+        packet: &Packet,
+    ) -> Result<(), Error> {
+        // framed in memory first, so the packet goes out in one write
+        let mut buffer = Vec::new();
+        buffer.write_packet(packet, self.compression)?;
+        if let Some(encryptor) = &mut self.encryptor {
+            let (blocks, _) = InOutBuf::from(buffer.as_mut_slice()).into_chunks();
+            encryptor.encrypt_blocks_inout_mut(blocks);
+        }
+        self.stream.write_all(&buffer)?;
+        self.stream.flush()?;
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the packet cannot be encoded or the stream
+    /// fails.
+    pub fn send<P: ProtocolPacket>(
+        &mut self,
+        packet: &P,
+    ) -> Result<(), Error> {
+        self.send_raw(&packet.to_packet()?)
+    }
+}
+
+/// Reads from the stream, decrypting what was read.
+struct Decrypting<'stream, R> {
+    stream: &'stream mut BufReader<R>,
+    decryptor: Option<&'stream mut Decryptor>,
+}
+
+impl<R: Read> Read for Decrypting<'_, R> {
+    fn read(
+        &mut self,
+        buf: &mut [u8],
+    ) -> io::Result<usize> {
+        let read = self.stream.read(buf)?;
+        if let Some(decryptor) = &mut self.decryptor {
+            let (blocks, _) = InOutBuf::from(&mut buf[..read]).into_chunks();
+            decryptor.decrypt_blocks_inout_mut(blocks);
+        }
+        Ok(read)
+    }
+}
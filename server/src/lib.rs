@@ -0,0 +1,668 @@
+//! A limbo server: players log in, in offline mode, and are held in an empty
+//! world where they can chat until they are transferred elsewhere.
+//!
+//! [`Server`] answers status pings, logs players in with compression and
+//! encryption when configured, and sends the registries and chunks the
+//! client needs to join. Each connection runs on its own thread; a
+//! [`ServerHandle`] reaches the players from outside.
+
+#![allow(
+    clippy::multiple_crate_versions,
+    reason = "the workspace lockfile holds the syn of the proxy dependencies too"
+)]
+
+extern crate alloc;
+
+mod connection;
+mod error;
+mod registries;
+mod session;
+
+use alloc::sync::Arc;
+use core::net::SocketAddr;
+use std::collections::HashMap;
+use std::net::{
+    TcpListener,
+    ToSocketAddrs,
+};
+use std::sync::mpsc::Sender;
+use std::sync::{
+    Mutex,
+    MutexGuard,
+    PoisonError,
+};
+use std::{
+    io,
+    thread,
+};
+
+use codec::nbt::Nbt;
+use data::packet::Packet;
+use data::text::json_to_nbt;
+pub use error::Error;
+use log::{
+    debug,
+    info,
+    warn,
+};
+use rsa::RsaPrivateKey;
+use rsa::pkcs8::EncodePublicKey as _;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Description shown in the server list.
+    pub motd: String,
+    pub max_players: usize,
+    /// Whether the connection is encrypted. Players are not authenticated
+    /// either way.
+    pub encryption: bool,
+    /// Size from which packets are compressed, `None` for no compression.
+    pub compression_threshold: Option<usize>,
+    /// 0 for survival, 1 for creative, 2 for adventure, 3 for spectator.
+    pub game_mode: u8,
+    /// Radius of empty chunks sent around the spawn.
+    pub view_distance: i32,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            motd: "A limbo server".to_owned(),
+            max_players: 100,
+            encryption: false,
+            compression_threshold: Some(256),
+            // spectators do not fall through the empty world
+            game_mode: 3,
+            view_distance: 2,
+        }
+    }
+}
+
+/// What a player's session is told, by its reader thread or by others.
+enum Message {
+    /// A packet received from the player.
+    Packet(Packet),
+    /// The player's connection closed.
+    Closed,
+    /// A system message to show.
+    Chat(Nbt),
+    Transfer {
+        host: String,
+        port: i32,
+    },
+}
+
+/// State shared by every connection.
+struct Shared {
+    config: ServerConfig,
+    /// RSA key pair and the DER of its public key, when encryption is on.
+    key: Option<(RsaPrivateKey, Vec<u8>)>,
+    /// Players logged in, by name.
+    players: Mutex<HashMap<String, Sender<Message>>>,
+}
+
+impl Shared {
+    fn players(&self) -> MutexGuard<'_, HashMap<String, Sender<Message>>> {
+        // the map stays consistent even if a session panicked holding it
+        self.players.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn broadcast(
+        &self,
+        message: &Nbt,
+    ) {
+        for sender in self.players().values() {
+            // a closed channel means the session is ending anyway
+            let _ = sender.send(Message::Chat(message.clone()));
+        }
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+impl Server {
+    /// Binds the server, generating its key pair when encryption is on.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the address cannot be bound or the key cannot be
+    /// generated.
+    pub fn bind<A: ToSocketAddrs>(
+        addr: A,
+        config: ServerConfig,
+    ) -> Result<Self, Error> {
+        let key = if config.encryption {
+            let key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024)?;
+            let der = key
+                .to_public_key()
+                .to_public_key_der()
+                .map_err(|err| rsa::Error::Pkcs8(rsa::pkcs8::Error::PublicKey(err)))?;
+            Some((key, der.into_vec()))
+        } else {
+            None
+        };
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            shared: Arc::new(Shared {
+                config,
+                key,
+                players: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`io::Error`] if the address of the listener is unknown.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> { self.listener.local_addr() }
+
+    #[must_use]
+    pub fn handle(&self) -> ServerHandle {
+        ServerHandle {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    /// Accepts connections, each served on its own thread.
+    pub fn run(&self) {
+        info!("Listening on {:?}", self.listener.local_addr());
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!("Failed to accept a connection: {err}");
+                    continue;
+                }
+            };
+            let shared = Arc::clone(&self.shared);
+            thread::spawn(move || {
+                let peer = stream.peer_addr();
+                if let Err(err) = session::serve(&stream, &shared) {
+                    debug!("Connection {peer:?} ended: {err}");
+                }
+            });
+        }
+    }
+}
+
+/// Reaches the players of a running [`Server`].
+#[derive(Clone)]
+pub struct ServerHandle {
+    shared: Arc<Shared>,
+}
+
+impl ServerHandle {
+    /// Names of the players logged in, sorted.
+    #[must_use]
+    pub fn players(&self) -> Vec<String> {
+        let mut players: Vec<String> = self.shared.players().keys().cloned().collect();
+        players.sort();
+        players
+    }
+
+    /// Shows a system message to every player.
+    pub fn broadcast(
+        &self,
+        message: &str,
+    ) {
+        self.shared.broadcast(&json_to_nbt(&json::object! {
+            text: message,
+        }));
+    }
+
+    /// Sends a player to another server. Returns whether the player was
+    /// found.
+    #[must_use]
+    pub fn transfer(
+        &self,
+        name: &str,
+        host: &str,
+        port: u16,
+    ) -> bool {
+        self.shared.players().get(name).is_some_and(|sender| {
+            sender
+                .send(Message::Transfer {
+                    host: host.to_owned(),
+                    port: i32::from(port),
+                })
+                .is_ok()
+        })
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "tests")]
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::net::TcpStream;
+
+    use client::{
+        Client,
+        ClientOptions,
+        Event,
+    };
+    use data::model::{
+        configuration,
+        handshake,
+        login,
+        play,
+        status,
+    };
+    use data::protocol::{
+        ConfigurationClientbound,
+        Direction,
+        ProtocolPacket,
+        State,
+    };
+    use data::text::plain_text;
+    use data::version::ProtocolVersion;
+
+    use super::*;
+    use crate::connection::{
+        PacketReader,
+        PacketWriter,
+    };
+
+    fn start(config: ServerConfig) -> (ServerHandle, u16) {
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let handle = server.handle();
+        let port = server.local_addr().unwrap().port();
+        thread::spawn(move || server.run());
+        (handle, port)
+    }
+
+    fn join(
+        port: u16,
+        name: &str,
+    ) -> Client {
+        let mut client = Client::connect("127.0.0.1", port, ClientOptions::new(name)).unwrap();
+        wait_for(&mut client, |event| {
+            matches!(event, Event::Teleported { .. })
+        });
+        client
+    }
+
+    /// Returns the first event matching `predicate`, skipping the others.
+    fn wait_for(
+        client: &mut Client,
+        predicate: impl Fn(&Event) -> bool,
+    ) -> Event {
+        loop {
+            let event = client.next_event().unwrap();
+            if predicate(&event) {
+                return event;
+            }
+        }
+    }
+
+    type Reader = PacketReader<TcpStream>;
+    type Writer = PacketWriter<TcpStream>;
+
+    fn expect<P: ProtocolPacket>(reader: &mut Reader) -> P {
+        P::from_packet(&reader.receive().unwrap()).unwrap()
+    }
+
+    /// Connects without a client, sending the handshake with `protocol`
+    /// and `intent`.
+    fn handshake(
+        port: u16,
+        protocol: i32,
+        intent: handshake::Intent,
+    ) -> (Reader, Writer) {
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let reader = PacketReader::new(stream.try_clone().unwrap());
+        let mut writer = PacketWriter::new(stream);
+        writer
+            .send(&handshake::Handshake {
+                protocol_version: protocol,
+                server_address: "localhost".to_owned(),
+                server_port: port,
+                intent,
+            })
+            .unwrap();
+        (reader, writer)
+    }
+
+    /// Sends the handshake and hello of `name`, returning the first answer.
+    fn hello(
+        port: u16,
+        name: &str,
+        protocol: i32,
+        intent: handshake::Intent,
+    ) -> (Reader, Writer, Packet) {
+        let (mut reader, mut writer) = handshake(port, protocol, intent);
+        writer
+            .send(&login::Hello {
+                name: name.to_owned(),
+                uuid: codec::Uuid::null(),
+            })
+            .unwrap();
+        let packet = reader.receive().unwrap();
+        (reader, writer, packet)
+    }
+
+    fn server_status(port: u16) -> json::JsonValue {
+        let (mut reader, mut writer) = handshake(
+            port,
+            ProtocolVersion::LATEST.protocol(),
+            handshake::Intent::Status,
+        );
+        writer.send(&status::StatusRequest {}).unwrap();
+        expect::<status::StatusResponse>(&mut reader).status
+    }
+
+    /// Name of the next play packet, with the packet.
+    fn receive_play(reader: &mut Reader) -> (&'static str, Packet) {
+        let packet = reader.receive().unwrap();
+        let name = ProtocolVersion::LATEST
+            .packet_name(State::Play, Direction::Clientbound, packet.id)
+            .unwrap();
+        (name, packet)
+    }
+
+    fn chat(client: &mut Client) -> String {
+        match wait_for(client, |event| matches!(event, Event::Chat { .. })) {
+            Event::Chat {
+                message, ..
+            } => message,
+            _ => unreachable!("only chat events are waited for"),
+        }
+    }
+
+    #[test]
+    fn answers_status_and_ping() {
+        let (_, port) = start(ServerConfig {
+            motd: "Hello".to_owned(),
+            ..ServerConfig::default()
+        });
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let mut reader = PacketReader::new(stream.try_clone().unwrap());
+        let mut writer = PacketWriter::new(stream);
+        writer
+            .send(&handshake::Handshake {
+                protocol_version: ProtocolVersion::LATEST.protocol(),
+                server_address: "localhost".to_owned(),
+                server_port: port,
+                intent: handshake::Intent::Status,
+            })
+            .unwrap();
+        writer.send(&status::StatusRequest {}).unwrap();
+        let response = status::StatusResponse::from_packet(&reader.receive().unwrap()).unwrap();
+        assert_eq!(response.status["description"]["text"], "Hello");
+        assert_eq!(
+            response.status["version"]["protocol"],
+            ProtocolVersion::LATEST.protocol()
+        );
+        assert_eq!(response.status["players"]["online"], 0);
+
+        writer
+            .send(&status::PingRequest {
+                time: 1234,
+            })
+            .unwrap();
+        let pong = status::PongResponse::from_packet(&reader.receive().unwrap()).unwrap();
+        assert_eq!(pong.time, 1234, "the ping time should be echoed");
+    }
+
+    #[test]
+    fn players_chat_and_get_transferred() {
+        let (handle, port) = start(ServerConfig {
+            encryption: true,
+            compression_threshold: Some(16),
+            ..ServerConfig::default()
+        });
+        let mut alice = join(port, "alice");
+        let mut bob = join(port, "bob");
+        assert_eq!(handle.players(), ["alice", "bob"]);
+        assert_eq!(
+            alice.uuid().to_string(),
+            "40f5db53-a47a-33ee-b1f6-db0e20deded4",
+            "offline players should get vanilla's uuid"
+        );
+
+        alice.chat("hello").unwrap();
+        assert_eq!(chat(&mut bob), "chat.type.text [alice, hello]");
+        assert_eq!(chat(&mut alice), "chat.type.text [alice, hello]");
+        handle.broadcast("Welcome");
+        assert_eq!(chat(&mut bob), "Welcome");
+
+        assert!(handle.transfer("bob", "example.com", 25566));
+        assert!(!handle.transfer("carol", "example.com", 25566));
+        let transfer = wait_for(&mut bob, |event| matches!(event, Event::Transfer { .. }));
+        assert!(matches!(
+            transfer,
+            Event::Transfer { host, port: 25566 } if host == "example.com"
+        ));
+    }
+
+    #[test]
+    fn refuses_duplicate_names() {
+        let (handle, port) = start(ServerConfig::default());
+        let _alice = join(port, "alice");
+        let err = Client::connect("127.0.0.1", port, ClientOptions::new("alice"))
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, client::Error::Disconnected(ref reason) if reason.contains("already connected")),
+            "unexpected error: {err}"
+        );
+        assert_eq!(handle.players(), ["alice"]);
+    }
+
+    #[test]
+    fn status_counts_players() {
+        let (handle, port) = start(ServerConfig {
+            max_players: 5,
+            ..ServerConfig::default()
+        });
+        let alice = join(port, "alice");
+        let status = server_status(port);
+        assert_eq!(status["players"]["online"], 1);
+        assert_eq!(status["players"]["max"], 5);
+        assert_eq!(
+            status["version"]["name"],
+            ProtocolVersion::LATEST.releases()
+        );
+
+        drop(alice);
+        let left = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            handle.players().is_empty()
+        });
+        assert!(left, "players should leave the list once disconnected");
+        assert_eq!(server_status(port)["players"]["online"], 0);
+    }
+
+    /// Logs steve in from the compression packet on, expecting a threshold
+    /// of 64 bytes, then finishes the configuration.
+    fn login_and_configure(
+        reader: &mut Reader,
+        writer: &mut Writer,
+        compression: &Packet,
+    ) {
+        let compression = login::LoginCompression::from_packet(compression).unwrap();
+        assert_eq!(compression.size, 64);
+        reader.set_compressed(true);
+        writer.set_compression(Some(64));
+        let finished: login::LoginFinished = expect(reader);
+        assert_eq!(finished.username, "steve");
+        assert_eq!(
+            finished.uuid,
+            session::offline_uuid("steve"),
+            "offline players should get vanilla's uuid"
+        );
+        writer.send(&login::LoginAcknowledged {}).unwrap();
+
+        let offered: configuration::SelectKnownPacks = expect(reader);
+        assert_eq!(offered.packs, registries::core_packs());
+        writer
+            .send(&configuration::KnownPacks {
+                packs: offered.packs,
+            })
+            .unwrap();
+        let mut registries = 0;
+        loop {
+            match ConfigurationClientbound::from_packet(&reader.receive().unwrap()).unwrap() {
+                ConfigurationClientbound::RegistryData(_) => registries += 1,
+                ConfigurationClientbound::FinishConfiguration(_) => break,
+                packet => panic!("unexpected configuration packet {packet:?}"),
+            }
+        }
+        assert_eq!(
+            registries,
+            registries::registry_data().len(),
+            "every registry should be sent"
+        );
+        writer
+            .send(&configuration::AcknowledgeFinishConfiguration {})
+            .unwrap();
+    }
+
+    #[test]
+    fn offline_login_to_play() {
+        let (_, port) = start(ServerConfig {
+            compression_threshold: Some(64),
+            game_mode: 1,
+            view_distance: 1,
+            ..ServerConfig::default()
+        });
+        let (mut reader, mut writer, compression) = hello(
+            port,
+            "steve",
+            ProtocolVersion::LATEST.protocol(),
+            handshake::Intent::Login,
+        );
+        login_and_configure(&mut reader, &mut writer, &compression);
+
+        let (name, packet) = receive_play(&mut reader);
+        assert_eq!(name, "login");
+        let joined = play::Login::from_packet(&packet).unwrap();
+        assert_eq!(joined.spawn.game_mode, 1);
+        let mut names = Vec::new();
+        let position = loop {
+            match receive_play(&mut reader) {
+                ("player_position", packet) => {
+                    break play::PlayerPosition::from_packet(&packet).unwrap();
+                }
+                (name, _) => names.push(name),
+            }
+        };
+        assert_eq!(position.y.to_bits(), 64.0_f64.to_bits());
+        assert_eq!(names[..2], ["game_event", "set_chunk_cache_center"]);
+        assert_eq!(
+            names[2..],
+            ["level_chunk_with_light"; 9],
+            "the chunks within the view distance should be sent"
+        );
+
+        writer
+            .send(&play::ChatCommand {
+                command: "gamemode survival".to_owned(),
+            })
+            .unwrap();
+        let (name, packet) = receive_play(&mut reader);
+        assert_eq!(name, "system_chat");
+        let answer = play::SystemChat::from_packet(&packet).unwrap();
+        assert_eq!(
+            plain_text(&answer.content),
+            "Commands are not available here"
+        );
+    }
+
+    #[test]
+    fn refuses_other_versions_and_full_servers() {
+        let (handle, port) = start(ServerConfig {
+            max_players: 1,
+            ..ServerConfig::default()
+        });
+        let (_, _, refused) = hello(port, "old", 769, handshake::Intent::Login);
+        let refused = login::LoginDisconnect::from_packet(&refused).unwrap();
+        assert_eq!(
+            refused.reason["text"].as_str(),
+            Some(
+                format!(
+                    "Unsupported version, please use {}",
+                    ProtocolVersion::LATEST.releases()
+                )
+                .as_str()
+            )
+        );
+
+        let _alice = join(port, "alice");
+        let err = Client::connect("127.0.0.1", port, ClientOptions::new("bob"))
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, client::Error::Disconnected(ref reason) if reason == "The server is full"),
+            "unexpected error: {err}"
+        );
+        assert_eq!(handle.players(), ["alice"]);
+    }
+
+    #[test]
+    fn refuses_clients_without_the_core_pack() {
+        let (handle, port) = start(ServerConfig {
+            compression_threshold: None,
+            ..ServerConfig::default()
+        });
+        let (mut reader, mut writer, finished) = hello(
+            port,
+            "steve",
+            ProtocolVersion::LATEST.protocol(),
+            handshake::Intent::Login,
+        );
+        login::LoginFinished::from_packet(&finished).unwrap();
+        writer.send(&login::LoginAcknowledged {}).unwrap();
+        let _: configuration::SelectKnownPacks = expect(&mut reader);
+        writer
+            .send(&configuration::KnownPacks {
+                packs: Vec::new(),
+            })
+            .unwrap();
+        let disconnect: configuration::Disconnect = expect(&mut reader);
+        assert!(
+            plain_text(&disconnect.reason).starts_with("Unsupported version"),
+            "clients without the registries should be told to update"
+        );
+        let left = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            handle.players().is_empty()
+        });
+        assert!(left, "refused players should leave the list");
+    }
+
+    #[test]
+    fn transferred_players_log_in() {
+        let (handle, port) = start(ServerConfig {
+            compression_threshold: None,
+            ..ServerConfig::default()
+        });
+        // kept open, so that steve stays in the player list
+        let (_reader, _writer, finished) = hello(
+            port,
+            "steve",
+            ProtocolVersion::LATEST.protocol(),
+            handshake::Intent::Transfer,
+        );
+        let finished = login::LoginFinished::from_packet(&finished).unwrap();
+        assert_eq!(finished.username, "steve");
+        assert_eq!(handle.players(), ["steve"]);
+
+        let mut alice = join(port, "alice");
+        assert!(handle.transfer("alice", "localhost", 25566));
+        let transfer = wait_for(&mut alice, |event| matches!(event, Event::Transfer { .. }));
+        assert!(matches!(
+            transfer,
+            Event::Transfer { host, port: 25566 } if host == "localhost"
+        ));
+        // a transferred player is only told where to go, it leaves by itself
+        assert_eq!(handle.players(), ["alice", "steve"]);
+        assert!(
+            !handle.transfer("carol", "localhost", 25566),
+            "unknown players cannot be transferred"
+        );
+    }
+}
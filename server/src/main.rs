@@ -0,0 +1,52 @@
+#![allow(
+    clippy::multiple_crate_versions,
+    reason = "the workspace lockfile holds the syn of the proxy dependencies too"
+)]
+
+use clap::Parser;
+use server::{
+    Server,
+    ServerConfig,
+};
+
+#[derive(Parser, Debug)]
+#[command(about, version, author)]
+struct Cli {
+    #[arg(long, env, default_value = "0.0.0.0")]
+    host: String,
+    #[arg(long, env, default_value = "25565")]
+    port: u16,
+    #[arg(long, env, default_value = "A limbo server")]
+    motd: String,
+    #[arg(long, env, default_value = "100")]
+    max_players: usize,
+    /// Encrypts connections. Players are still not authenticated.
+    #[arg(long, env)]
+    encryption: bool,
+    /// Size from which packets are compressed. Negative disables
+    /// compression.
+    #[arg(long, env, default_value = "256", allow_negative_numbers = true)]
+    compression_threshold: i32,
+    /// 0 for survival, 1 for creative, 2 for adventure, 3 for spectator.
+    #[arg(long, env, default_value = "3")]
+    game_mode: u8,
+    #[arg(long, env, default_value = "2")]
+    view_distance: i32,
+}
+
+fn main() {
+    env_logger::init();
+
+    let args = Cli::parse();
+    let config = ServerConfig {
+        motd: args.motd,
+        max_players: args.max_players,
+        encryption: args.encryption,
+        compression_threshold: usize::try_from(args.compression_threshold).ok(),
+        game_mode: args.game_mode,
+        view_distance: args.view_distance,
+    };
+    let server =
+        Server::bind((args.host.as_str(), args.port), config).expect("Failed to bind the server");
+    server.run();
+}
@@ -0,0 +1,128 @@
+//! Registries sent during configuration.
+//!
+//! Entries go by name only, their data coming from the vanilla
+//! `minecraft:core` pack the client must know. Only what the client needs to
+//! join is sent: every damage and chat type, the dimension and biome of the
+//! limbo, and one entry of each registry that may not be empty.
+
+use codec::PrefixedOption;
+use data::identifier::Identifier;
+use data::model::configuration::{
+    KnownPack,
+    RegistryData,
+    RegistryEntry,
+};
+
+/// Entries in id order, by registry.
+const REGISTRIES: &[(&str, &[&str])] = &[
+    ("dimension_type", &["overworld"]),
+    ("worldgen/biome", &["plains"]),
+    ("chat_type", &[
+        "chat",
+        "emote_command",
+        "msg_command_incoming",
+        "msg_command_outgoing",
+        "say_command",
+        "team_msg_command_incoming",
+        "team_msg_command_outgoing",
+    ]),
+    ("damage_type", &[
+        "arrow",
+        "bad_respawn_point",
+        "cactus",
+        "campfire",
+        "cramming",
+        "dragon_breath",
+        "drown",
+        "dry_out",
+        "ender_pearl",
+        "explosion",
+        "fall",
+        "falling_anvil",
+        "falling_block",
+        "falling_stalactite",
+        "fireball",
+        "fireworks",
+        "fly_into_wall",
+        "freeze",
+        "generic",
+        "generic_kill",
+        "hot_floor",
+        "in_fire",
+        "in_wall",
+        "indirect_magic",
+        "lava",
+        "lightning_bolt",
+        "mace_smash",
+        "magic",
+        "mob_attack",
+        "mob_attack_no_aggro",
+        "mob_projectile",
+        "on_fire",
+        "out_of_world",
+        "outside_border",
+        "player_attack",
+        "player_explosion",
+        "sonic_boom",
+        "spit",
+        "stalagmite",
+        "starve",
+        "sting",
+        "sweet_berry_bush",
+        "thorns",
+        "thrown",
+        "trident",
+        "unattributed_fireball",
+        "wind_charge",
+        "wither",
+        "wither_skull",
+    ]),
+    ("cat_variant", &["tabby"]),
+    ("chicken_variant", &["temperate"]),
+    ("cow_variant", &["temperate"]),
+    ("frog_variant", &["temperate"]),
+    ("painting_variant", &["kebab"]),
+    ("pig_variant", &["temperate"]),
+    ("wolf_sound_variant", &["classic"]),
+    ("wolf_variant", &["pale"]),
+];
+
+/// Releases of protocol 772, whose core packs hold the same entries.
+const CORE_VERSIONS: [&str; 2] = ["1.21.7", "1.21.8"];
+
+/// The vanilla packs offered to the client, one per release.
+pub fn core_packs() -> Vec<KnownPack> {
+    CORE_VERSIONS
+        .iter()
+        .map(|version| KnownPack {
+            namespace: "minecraft".to_owned(),
+            id: "core".to_owned(),
+            version: (*version).to_owned(),
+        })
+        .collect()
+}
+
+/// Whether the client knows one of the [`core_packs`].
+pub fn knows_core(packs: &[KnownPack]) -> bool {
+    packs.iter().any(|pack| core_packs().contains(pack))
+}
+
+pub fn registry_data() -> Vec<RegistryData> {
+    REGISTRIES
+        .iter()
+        .map(|(registry, entries)| RegistryData {
+            registry: minecraft(registry),
+            entries: entries
+                .iter()
+                .map(|entry| RegistryEntry {
+                    id: minecraft(entry),
+                    data: PrefixedOption::None,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn minecraft(path: &str) -> Identifier {
+    Identifier::minecraft(path).expect("registry names should be valid identifiers")
+}
@@ -0,0 +1,506 @@
+//! One connection, from the handshake to the player leaving.
+
+use core::time::Duration;
+use std::net::{
+    Shutdown,
+    TcpStream,
+};
+use std::sync::mpsc::{
+    self,
+    Receiver,
+    RecvTimeoutError,
+    Sender,
+};
+use std::thread;
+use std::time::Instant;
+
+use codec::Uuid;
+use codec::dec::{
+    Decode as _,
+    DecodeError,
+};
+use data::identifier::Identifier;
+use data::model::{
+    configuration,
+    handshake,
+    login,
+    play,
+    status,
+};
+use data::packet::Packet;
+use data::protocol::{
+    ConfigurationServerbound,
+    Direction,
+    HandshakeServerbound,
+    LoginServerbound,
+    ProtocolPacket as _,
+    State,
+    StatusServerbound,
+};
+use data::registry::BlockState;
+use data::text::json_to_nbt;
+use data::version::ProtocolVersion;
+use data::world::{
+    ChunkData,
+    LightData,
+    Section,
+};
+use log::{
+    debug,
+    info,
+};
+use md5::{
+    Digest as _,
+    Md5,
+};
+use rsa::Pkcs1v15Encrypt;
+
+use crate::connection::{
+    PacketReader,
+    PacketWriter,
+};
+use crate::error::Error;
+use crate::{
+    Message,
+    Shared,
+    registries,
+};
+
+/// How long the client has to answer before it is dropped.
+const TIMEOUT: Duration = Duration::from_secs(30);
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// Sections of the overworld, from y = -64 to 320.
+const SECTIONS: usize = 24;
+const SPAWN_Y: f64 = 64.0;
+
+type Reader = PacketReader<TcpStream>;
+type Writer = PacketWriter<TcpStream>;
+
+/// A logged in player, removed from the player list when dropped.
+struct Player<'shared> {
+    shared: &'shared Shared,
+    name: String,
+    uuid: Uuid,
+    /// Messages for the player, the sender being in the player list.
+    sender: Sender<Message>,
+    messages: Receiver<Message>,
+}
+
+impl Drop for Player<'_> {
+    fn drop(&mut self) {
+        self.shared.players().remove(&self.name);
+        info!("{} left", self.name);
+    }
+}
+
+/// Serves a connection until it closes.
+pub fn serve(
+    stream: &TcpStream,
+    shared: &Shared,
+) -> Result<(), Error> {
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = PacketReader::new(stream.try_clone()?);
+    let mut writer = PacketWriter::new(stream.try_clone()?);
+
+    let HandshakeServerbound::Intention(handshake) =
+        HandshakeServerbound::from_packet(&reader.receive()?)?;
+    let result = match handshake.intent {
+        handshake::Intent::Status => status(&mut reader, &mut writer, shared),
+        handshake::Intent::Login | handshake::Intent::Transfer => {
+            let Some(player) = login(&handshake, &mut reader, &mut writer, shared)? else {
+                return Ok(());
+            };
+            configure(&mut reader, &mut writer).and_then(|joined| {
+                if joined {
+                    play(reader, &mut writer, shared, &player)
+                } else {
+                    Ok(())
+                }
+            })
+        }
+    };
+    // also ends the reader thread of a player
+    let _ = stream.shutdown(Shutdown::Both);
+    result
+}
+
+/// Answers a status request and the ping that follows.
+fn status(
+    reader: &mut Reader,
+    writer: &mut Writer,
+    shared: &Shared,
+) -> Result<(), Error> {
+    loop {
+        match StatusServerbound::from_packet(&reader.receive()?)? {
+            StatusServerbound::StatusRequest(_) => {
+                let version = ProtocolVersion::LATEST;
+                writer.send(&status::StatusResponse {
+                    status: json::object! {
+                        version: {
+                            name: version.releases(),
+                            protocol: version.protocol(),
+                        },
+                        players: {
+                            max: shared.config.max_players,
+                            online: shared.players().len(),
+                        },
+                        description: {
+                            text: shared.config.motd.as_str(),
+                        },
+                    },
+                })?;
+            }
+            StatusServerbound::PingRequest(ping) => {
+                writer.send(&status::PongResponse {
+                    time: ping.time,
+                })?;
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Logs a player in, returning `None` if refused.
+fn login<'shared>(
+    handshake: &handshake::Handshake,
+    reader: &mut Reader,
+    writer: &mut Writer,
+    shared: &'shared Shared,
+) -> Result<Option<Player<'shared>>, Error> {
+    let LoginServerbound::Hello(hello) = LoginServerbound::from_packet(&reader.receive()?)? else {
+        return Err(DecodeError::UnknownPacketId(login::Hello::ID).into());
+    };
+    if handshake.protocol_version != ProtocolVersion::LATEST.protocol() {
+        let reason = format!(
+            "Unsupported version, please use {}",
+            ProtocolVersion::LATEST.releases()
+        );
+        return refuse(writer, &reason);
+    }
+
+    let (sender, messages) = mpsc::channel();
+    if let Err(reason) = register(shared, &hello.name, sender.clone()) {
+        return refuse(writer, reason);
+    }
+    let player = Player {
+        shared,
+        uuid: offline_uuid(&hello.name),
+        name: hello.name,
+        sender,
+        messages,
+    };
+
+    if let Some((key, public_key)) = &shared.key {
+        let verify_token: [u8; 4] = rand::random();
+        writer.send(&login::EncryptionRequest {
+            server_id: String::new(),
+            public_key: public_key.clone(),
+            verify_token: verify_token.to_vec(),
+            should_authenticate: false,
+        })?;
+        let LoginServerbound::Key(reply) = LoginServerbound::from_packet(&reader.receive()?)?
+        else {
+            return Err(DecodeError::UnknownPacketId(login::Key::ID).into());
+        };
+        if key.decrypt(Pkcs1v15Encrypt, &reply.verify_token)? != verify_token {
+            return Err(Error::InvalidVerifyToken);
+        }
+        let secret: [u8; 16] = key
+            .decrypt(Pkcs1v15Encrypt, &reply.shared_secret)?
+            .try_into()
+            .map_err(|_| rsa::Error::Decryption)?;
+        reader.enable_encryption(&secret);
+        writer.enable_encryption(&secret);
+    }
+
+    if let Some(threshold) = shared.config.compression_threshold {
+        writer.send(&login::LoginCompression {
+            size: i32::try_from(threshold).unwrap_or(i32::MAX),
+        })?;
+        writer.set_compression(Some(threshold));
+        reader.set_compressed(true);
+    }
+
+    writer.send(&login::LoginFinished {
+        uuid: player.uuid.clone(),
+        username: player.name.clone(),
+        properties: Vec::new(),
+    })?;
+    loop {
+        match LoginServerbound::from_packet(&reader.receive()?)? {
+            LoginServerbound::LoginAcknowledged(_) => break,
+            packet => debug!("Ignoring {packet:?} during login"),
+        }
+    }
+    info!("{} joined", player.name);
+    Ok(Some(player))
+}
+
+/// Adds a player to the player list, or tells why it cannot be.
+fn register(
+    shared: &Shared,
+    name: &str,
+    sender: Sender<Message>,
+) -> Result<(), &'static str> {
+    let mut players = shared.players();
+    if players.contains_key(name) {
+        return Err("A player with this name is already connected");
+    }
+    if players.len() >= shared.config.max_players {
+        return Err("The server is full");
+    }
+    players.insert(name.to_owned(), sender);
+    Ok(())
+}
+
+fn refuse<T>(
+    writer: &mut Writer,
+    reason: &str,
+) -> Result<Option<T>, Error> {
+    writer.send(&login::LoginDisconnect {
+        reason: json::object! {
+            text: reason,
+        },
+    })?;
+    Ok(None)
+}
+
+/// The uuid vanilla gives a player in offline mode: a version 3 uuid of
+/// `OfflinePlayer:<name>`.
+pub fn offline_uuid(name: &str) -> Uuid {
+    let mut bytes: [u8; 16] = Md5::digest(format!("OfflinePlayer:{name}")).into();
+    bytes[6] = bytes[6] & 0x0F | 0x30;
+    bytes[8] = bytes[8] & 0x3F | 0x80;
+    Uuid::from_u128(u128::from_be_bytes(bytes))
+}
+
+/// Sends the registries, by name from the vanilla core pack. Returns
+/// whether the client can join, which it cannot without that pack.
+fn configure(
+    reader: &mut Reader,
+    writer: &mut Writer,
+) -> Result<bool, Error> {
+    writer.send(&configuration::SelectKnownPacks {
+        packs: registries::core_packs(),
+    })?;
+    let known = loop {
+        if let Some(ConfigurationServerbound::KnownPacks(known)) = receive_configuration(reader)? {
+            break known;
+        }
+    };
+    if !registries::knows_core(&known.packs) {
+        writer.send(&configuration::Disconnect {
+            reason: json_to_nbt(&json::object! {
+                text: format!("Unsupported version, please use {}", ProtocolVersion::LATEST.releases()),
+            }),
+        })?;
+        return Ok(false);
+    }
+
+    for data in registries::registry_data() {
+        writer.send(&data)?;
+    }
+    writer.send(&configuration::FinishConfiguration {})?;
+    loop {
+        if let Some(ConfigurationServerbound::AcknowledgeFinishConfiguration(_)) =
+            receive_configuration(reader)?
+        {
+            return Ok(true);
+        }
+    }
+}
+
+/// Receives a configuration packet, `None` for those without a model.
+fn receive_configuration(reader: &mut Reader) -> Result<Option<ConfigurationServerbound>, Error> {
+    match ConfigurationServerbound::from_packet(&reader.receive()?) {
+        Ok(packet) => Ok(Some(packet)),
+        Err(DecodeError::UnknownPacketId(id)) => {
+            debug!("Ignoring configuration packet {id:#04X}");
+            Ok(None)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Spawns the player in the empty world, then relays chat and keeps the
+/// connection alive until it closes.
+fn play(
+    mut reader: Reader,
+    writer: &mut Writer,
+    shared: &Shared,
+    player: &Player<'_>,
+) -> Result<(), Error> {
+    spawn(writer, shared)?;
+
+    let sender = player.sender.clone();
+    thread::spawn(move || {
+        while let Ok(packet) = reader.receive() {
+            if sender.send(Message::Packet(packet)).is_err() {
+                return;
+            }
+        }
+        let _ = sender.send(Message::Closed);
+    });
+
+    let mut keep_alive: Option<i64> = None;
+    let mut next_keep_alive = Instant::now() + KEEP_ALIVE_INTERVAL;
+    loop {
+        let message = player
+            .messages
+            .recv_timeout(next_keep_alive.saturating_duration_since(Instant::now()));
+        match message {
+            Ok(Message::Packet(packet)) => {
+                handle(writer, shared, player, &packet, &mut keep_alive)?;
+            }
+            Ok(Message::Closed) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            Ok(Message::Chat(content)) => {
+                writer.send(&play::SystemChat {
+                    content,
+                    overlay: false,
+                })?;
+            }
+            Ok(Message::Transfer {
+                host,
+                port,
+            }) => {
+                info!("Transferring {} to {host}:{port}", player.name);
+                writer.send(&play::Transfer {
+                    host,
+                    port,
+                })?;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if keep_alive.is_some() {
+                    return Err(Error::TimedOut);
+                }
+                let id = rand::random();
+                writer.send(&play::KeepAlive {
+                    id,
+                })?;
+                keep_alive = Some(id);
+                next_keep_alive = Instant::now() + KEEP_ALIVE_INTERVAL;
+            }
+        }
+    }
+}
+
+/// Joins the player to the world, with empty chunks around the spawn.
+fn spawn(
+    writer: &mut Writer,
+    shared: &Shared,
+) -> Result<(), Error> {
+    let config = &shared.config;
+    let overworld = Identifier::minecraft("overworld").expect("overworld should be valid");
+    writer.send(&play::Login {
+        entity_id: 1,
+        hardcore: false,
+        dimensions: vec![overworld.clone()],
+        max_players: i32::try_from(config.max_players).unwrap_or(i32::MAX),
+        view_distance: config.view_distance,
+        simulation_distance: config.view_distance,
+        reduced_debug_info: false,
+        show_death_screen: true,
+        do_limited_crafting: false,
+        spawn: play::SpawnInfo {
+            dimension_type: 0,
+            dimension: overworld,
+            hashed_seed: 0,
+            game_mode: config.game_mode,
+            previous_game_mode: -1,
+            debug: false,
+            flat: true,
+            death_location: codec::PrefixedOption::None,
+            portal_cooldown: 0,
+            sea_level: 63,
+        },
+        enforces_secure_chat: false,
+    })?;
+    writer.send(&play::GameEvent {
+        event: play::game_event::LEVEL_CHUNKS_LOAD_START,
+        value: 0.0,
+    })?;
+    writer.send(&play::SetChunkCacheCenter {
+        x: 0,
+        z: 0,
+    })?;
+    for x in -config.view_distance..=config.view_distance {
+        for z in -config.view_distance..=config.view_distance {
+            writer.send(&empty_chunk(x, z))?;
+        }
+    }
+    writer.send(&play::PlayerPosition {
+        teleport_id: 0,
+        x: 0.5,
+        y: SPAWN_Y,
+        z: 0.5,
+        velocity_x: 0.0,
+        velocity_y: 0.0,
+        velocity_z: 0.0,
+        yaw: 0.0,
+        pitch: 0.0,
+        relatives: 0,
+    })
+}
+
+fn empty_chunk(
+    x: i32,
+    z: i32,
+) -> play::LevelChunkWithLight {
+    play::LevelChunkWithLight {
+        x,
+        z,
+        chunk: ChunkData {
+            heightmaps: Vec::new(),
+            sections: vec![Section::filled(BlockState::AIR, 0); SECTIONS],
+            block_entities: Vec::new(),
+        },
+        light: LightData {
+            sky_y_mask: Vec::new(),
+            block_y_mask: Vec::new(),
+            empty_sky_y_mask: Vec::new(),
+            empty_block_y_mask: Vec::new(),
+            sky_updates: Vec::new(),
+            block_updates: Vec::new(),
+        },
+    }
+}
+
+/// Handles a play packet from the player.
+fn handle(
+    writer: &mut Writer,
+    shared: &Shared,
+    player: &Player<'_>,
+    packet: &Packet,
+    keep_alive: &mut Option<i64>,
+) -> Result<(), Error> {
+    let name = ProtocolVersion::LATEST.packet_name(State::Play, Direction::Serverbound, packet.id);
+    let data = &mut packet.data.as_ref();
+    match name {
+        Some("keep_alive") => {
+            let response = play::KeepAliveResponse::decode(data)?;
+            if *keep_alive == Some(response.id) {
+                *keep_alive = None;
+            }
+        }
+        Some("chat") => {
+            let chat = play::Chat::decode(data)?;
+            info!("<{}> {}", player.name, chat.message);
+            shared.broadcast(&json_to_nbt(&json::object! {
+                translate: "chat.type.text",
+                with: [player.name.as_str(), chat.message],
+            }));
+        }
+        Some("chat_command" | "chat_command_signed") => {
+            writer.send(&play::SystemChat {
+                content: json_to_nbt(&json::object! {
+                    text: "Commands are not available here",
+                    color: "red",
+                }),
+                overlay: false,
+            })?;
+        }
+        _ => {}
+    }
+    Ok(())
+}